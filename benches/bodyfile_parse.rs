@@ -0,0 +1,159 @@
+//! benchmarks of the bodyfile parser
+//!
+//! The benchmarks cover three kinds of lines, which are representative for
+//! the bodyfiles we usually process:
+//!
+//!  - `short`: a typical line created by `fls` or `mft2bodyfile`
+//!  - `pipes`: a long name which contains several pipe symbols
+//!  - `json`: a large JSON document in the name column, as created by `evtx2bodyfile`
+//!
+//! Additionally, `reader` measures the throughput of [`BodyfileReader`] for a
+//! whole file, and `split` compares the former splitting of lines into a
+//! `Vec<&str>` with locating the column boundaries using `memchr`, which is
+//! what the parser does now.
+//!
+//! # Baseline
+//!
+//! Before changing the parser, record a baseline and compare later runs
+//! against it:
+//!
+//! ```shell
+//! cargo bench --bench bodyfile_parse -- --save-baseline parser-baseline
+//! # ... change the parser ...
+//! cargo bench --bench bodyfile_parse -- --baseline parser-baseline
+//! ```
+//!
+//! The baseline is stored in `target/criterion/*/parser-baseline`. Numbers
+//! depend heavily on the machine, so always compare runs made on the same
+//! machine instead of absolute values.
+//!
+//! For reference, these are the numbers of `TryFrom<&str>` (`parse/owned`)
+//! and of the reader when the benchmarks were added, before the parser was
+//! optimized. They were measured on a virtual machine with a single core of
+//! an Intel Xeon, using rustc 1.95; the results of different runs on this
+//! machine vary by up to 30%:
+//!
+//! | case     | time per line | throughput           |
+//! |----------|---------------|----------------------|
+//! | `short`  | 518 ns        | 262 MiB/s            |
+//! | `pipes`  | 454 ns        | 317 MiB/s            |
+//! | `json`   | 520 ns        | 2.13 GiB/s           |
+//! | `reader` | 626 ns        | 1.60 million lines/s |
+//!
+//! On the same machine, `split` showed the following speedup of locating
+//! the column boundaries using `memchr`, as median of two runs:
+//!
+//! | case    | `vec`  | `memchr` | speedup |
+//! |---------|--------|----------|---------|
+//! | `short` | 372 ns | 77 ns    | 4.8x    |
+//! | `pipes` | 392 ns | 77 ns    | 5.1x    |
+//! | `json`  | 422 ns | 57 ns    | 7.4x    |
+//!
+//! Measured before and after the parser started to use `memchr`,
+//! `parse/borrowed` became 1.5 to 1.8 times faster. `parse/owned` and
+//! `reader` became 1.2 to 1.4 times faster on average, which is within the
+//! variance between runs, because their time is dominated by allocating the
+//! columns and by reading the lines.
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, BodyfileReader};
+
+const SHORT_LINE: &str =
+    "4bad420da66571dac7f1ace995cc55c6|/Windows/System32/notepad.exe|87915-128-1|r/rrwxrwxrwx|0|0|126378|1600000000|1600000001|1600000002|1600000003";
+
+const PIPES_LINE: &str =
+    "0|C:/Users/jdoe/AppData/Local/Temp/command was ls -l | grep foo | wc -l > output.txt|93552-48-2|d/drwxrwxrwx|0|0|92|1577092511|1577092511|1577092511|-1";
+
+const JSON_LINE: &str = "0|{\"activity_id\":null,\"channel_name\":\"Microsoft-Windows-PowerShell/Operational\",\"custom_data\":{\"EventData\":{\"ContextInfo\":\"        Severity = Warning\\r\\n        Host Name = ConsoleHost\\r\\n        Host Version = 4.0\\r\\n        Host ID = 5635c559-63c7-4bdc-8bb6-e2aa0448e7b9\\r\\n        Host Application = powershell get-VMNetworkAdapter -ManagementOS | fl | out-file -encoding ASCII VMNetworkAdapterInstances.txt\\r\\n        Engine Version = 4.0\\r\\n        Runspace ID = d315d83a-8923-4530-9553-e63551c33cbc\\r\\n        Pipeline ID = 1\\r\\n        Command Name = \\r\\n        Command Type = Script\\r\\n        Script Name = \\r\\n        Command Path = \\r\\n        Sequence Number = 15\\r\\n        User = TEST\\\\SYSTEM\\r\\n        Shell ID = Microsoft.PowerShell\\r\\n\",\"Payload\":\"Error Message = Could not load file or assembly 'System.Data, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089' or one of its dependencies. The media is write protected. (Exception from HRESULT: 0x80070013)\\r\\nFully Qualified Error ID = System.IO.FileLoadException\\r\\n\",\"UserData\":\"\"}},\"event_id\":4100,\"event_record_id\":2424468,\"provider_name\":\"Microsoft-Windows-PowerShell\"}|0||0|0|0|-1|1645178371|-1|-1";
+
+/// number of lines in the file used by the reader benchmark
+const FILE_LINES: u64 = 100_000;
+
+fn parse_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, line) in [("short", SHORT_LINE), ("pipes", PIPES_LINE), ("json", JSON_LINE)] {
+        group.throughput(Throughput::Bytes(line.len() as u64));
+        group.bench_with_input(BenchmarkId::new("owned", name), line, |b, line| {
+            b.iter(|| Bodyfile3Line::try_from(black_box(line)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("borrowed", name), line, |b, line| {
+            b.iter(|| Bodyfile3Line::try_parse_ref(black_box(line)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// splits `line` like the parser did before it used `memchr`
+fn split_vec(line: &str) -> (&str, &str, &str) {
+    let parts: Vec<&str> = line.split('|').collect();
+    let (head, tail) = parts.split_at(parts.len() - 9);
+    let name_len = head[1..].iter().map(|p| p.len()).sum::<usize>() + head.len() - 2;
+    let name_start = head[0].len() + 1;
+    (head[0], &line[name_start..name_start + name_len], tail[8])
+}
+
+/// splits `line` like the parser does now
+fn split_memchr(line: &str) -> (&str, &str, &str) {
+    let bytes = line.as_bytes();
+    let md5_end = memchr::memchr(b'|', bytes).unwrap();
+    let mut pipes = memchr::memrchr_iter(b'|', bytes);
+    let crtime_start = pipes.next().unwrap() + 1;
+    let name_end = pipes.nth(7).unwrap();
+    (
+        &line[..md5_end],
+        &line[md5_end + 1..name_end],
+        &line[crtime_start..],
+    )
+}
+
+fn split_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("split");
+    for (name, line) in [("short", SHORT_LINE), ("pipes", PIPES_LINE), ("json", JSON_LINE)] {
+        assert_eq!(split_vec(line), split_memchr(line));
+        group.throughput(Throughput::Bytes(line.len() as u64));
+        group.bench_with_input(BenchmarkId::new("vec", name), line, |b, line| {
+            b.iter(|| split_vec(black_box(line)))
+        });
+        group.bench_with_input(BenchmarkId::new("memchr", name), line, |b, line| {
+            b.iter(|| split_memchr(black_box(line)))
+        });
+    }
+    group.finish();
+}
+
+fn sample_file() -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("bodyfile_parse_bench_{}.bodyfile", std::process::id()));
+    let mut file = fs::File::create(&path).unwrap();
+    for i in 0..FILE_LINES {
+        let line = match i % 10 {
+            0 => PIPES_LINE,
+            1 => JSON_LINE,
+            _ => SHORT_LINE,
+        };
+        writeln!(file, "{line}").unwrap();
+    }
+    path
+}
+
+fn read_file(c: &mut Criterion) {
+    let path = sample_file();
+    let mut group = c.benchmark_group("reader");
+    group.throughput(Throughput::Elements(FILE_LINES));
+    group.sample_size(10);
+    group.bench_function("from_paths", |b| {
+        b.iter(|| {
+            BodyfileReader::from_paths(std::slice::from_ref(&path))
+                .filter(|line| line.is_ok())
+                .count()
+        })
+    });
+    group.finish();
+    fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, parse_lines, split_lines, read_file);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! benchmarks of writing timeline rows into a file
+//!
+//! `unbuffered` writes every row directly into the file, which results in
+//! (at least) one `write` syscall per row, like writing into
+//! `stdout().lock()` does. `output_writer` uses [`OutputWriter`], which
+//! collects rows in a buffer of [`OUTPUT_BUFFER_SIZE`] bytes and so needs
+//! only one syscall per buffer. To count the syscalls, run the compiled
+//! benchmark with `strace -c -e trace=write`.
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use dfir_toolkit::common::{OutputWriter, OUTPUT_BUFFER_SIZE};
+
+const ROW: &str =
+    "2020-09-13T12:26:40+00:00,126378,macb,r/rrwxrwxrwx,0,0,87915-128-1,\"/Windows/System32/notepad.exe\"";
+
+/// number of rows written per iteration
+const ROWS: u64 = 10_000;
+
+fn write_rows(output: &mut impl Write) {
+    for _ in 0..ROWS {
+        writeln!(output, "{ROW}").unwrap();
+    }
+}
+
+fn target_file() -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("output_writer_bench_{}.csv", std::process::id()));
+    path
+}
+
+fn write_file(c: &mut Criterion) {
+    let path = target_file();
+    let mut group = c.benchmark_group("write");
+    group.throughput(Throughput::Elements(ROWS));
+    group.sample_size(10);
+    group.bench_function("unbuffered", |b| {
+        b.iter(|| {
+            let mut file = File::create(&path).unwrap();
+            write_rows(&mut file);
+        })
+    });
+    group.bench_function("output_writer", |b| {
+        b.iter(|| {
+            let mut output =
+                OutputWriter::with_capacity(OUTPUT_BUFFER_SIZE, File::create(&path).unwrap());
+            write_rows(&mut output);
+            output.finish().unwrap();
+        })
+    });
+    group.finish();
+    fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, write_file);
+criterion_main!(benches);
@@ -19,6 +19,9 @@ cat >README.md <<'EOF'
 - [Installation](#installation)
 - [Overview of timelining tools](#overview-of-timelining-tools)
 - [Tools](#tools)
+  - [x] [`bodyfile2sqlite`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/bodyfile2sqlite.md)
+  - [x] [`bodyfile_gen`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/bodyfile_gen.md)
+  - [x] [`bodyfile_merge`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/bodyfile_merge.md)
   - [x] [`cleanhive`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/cleanhive.md)
   - [x] [`evtx2bodyfile`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/evtx2bodyfile.md)
   - [x] [`evtxanalyze`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/avtxanalyze.md)
@@ -28,16 +31,19 @@ cat >README.md <<'EOF'
   - [x] [`es4forensics`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/es4forensics.md)
   - [x] [`hivescan`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/hivescan.md)
   - [x] [`ipgrep`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/ipgrep.md)
-  - [ ] [`lnk2bodyfile`](https://github.com/janstarke/lnk2bodyfile)
+  - [x] [`lnk2bodyfile`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/lnk2bodyfile.md)
   - [x] [`mactime2`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/mactime2.md)
   - [ ] [`mft2bodyfile`](https://github.com/janstarke/mft2bodyfile)
   - [ ] [`ntdsextract2`](https://github.com/janstarke/ntdsextract2)
+  - [x] [`pf2bodyfile`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/pf2bodyfile.md)
   - [x] [`pol_export`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/pol_export.md)
   - [ ] [`procbins`](https://github.com/janstarke/procbins)
+  - [x] [`recyclebin2bodyfile`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/recyclebin2bodyfile.md)
   - [x] [`regdump`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/regdump.md)
   - [ ] [`regls`](https://github.com/janstarke/regls)
   - [ ] [`regview`](https://github.com/janstarke/regview)
   - [x] [`ts2date`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/ts2date.md)
+  - [x] [`usnjrnl2bodyfile`](https://github.com/dfir-dd/dfir-toolkit/blob/main/doc/usnjrnl2bodyfile.md)
   - [ ] [`usnjrnl_dump`](https://github.com/janstarke/usnjrnl)
 
 # Overview of timelining tools
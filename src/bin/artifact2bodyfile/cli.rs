@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueHint};
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
+use log::LevelFilter;
+
+/// creates bodyfile from any artifact for which a converter has been
+/// registered. The converter is selected by the magic bytes or by the
+/// extension of every file. Currently, Windows evtx files are supported
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// names of the artifact files
+    #[clap(value_hint=ValueHint::FilePath, num_args=1..)]
+    pub(crate) artifact_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::artifact::ArtifactRegistry;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+
+mod cli;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+    let registry = ArtifactRegistry::with_builtin_converters();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        errors = registry.convert_files(&cli.artifact_files, output)?;
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
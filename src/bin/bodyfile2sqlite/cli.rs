@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueHint};
+use dfir_toolkit::common::HasVerboseFlag;
+use log::LevelFilter;
+
+/// imports a bodyfile into a SQLite database
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// path of the SQLite database; the table `bodyfile` is created if it does not exist.
+    /// All lines are imported in a single transaction, so a failed import leaves
+    /// the database unchanged
+    #[clap(value_hint=ValueHint::FilePath, display_order(100))]
+    pub(crate) database: PathBuf,
+
+    /// path to input file or '-' for stdin. Can be specified multiple times;
+    /// glob patterns like *.bodyfile and alternatives like {a,b} are expanded
+    #[clap(short('b'), value_hint=ValueHint::FilePath, default_value="-", display_order(200))]
+    pub(crate) input_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use anyhow::Result;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use rusqlite::{params, Connection};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS bodyfile (
+    md5     TEXT,
+    name    TEXT,
+    inode   TEXT,
+    mode    TEXT,
+    uid     INTEGER,
+    gid     INTEGER,
+    size    INTEGER,
+    atime   INTEGER,
+    mtime   INTEGER,
+    ctime   INTEGER,
+    crtime  INTEGER
+);
+CREATE INDEX IF NOT EXISTS bodyfile_atime ON bodyfile(atime);
+CREATE INDEX IF NOT EXISTS bodyfile_mtime ON bodyfile(mtime);
+CREATE INDEX IF NOT EXISTS bodyfile_ctime ON bodyfile(ctime);
+CREATE INDEX IF NOT EXISTS bodyfile_crtime ON bodyfile(crtime);
+";
+
+pub(crate) struct BodyfileDatabase {
+    connection: Connection,
+}
+
+impl BodyfileDatabase {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// inserts all lines inside of a single transaction and returns the
+    /// number of inserted lines. Unset timestamps are stored as `NULL`.
+    pub fn insert_lines<I>(&mut self, lines: I) -> Result<usize>
+    where
+        I: IntoIterator<Item = Bodyfile3Line>,
+    {
+        let transaction = self.connection.transaction()?;
+        let mut count = 0;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT INTO bodyfile (md5, name, inode, mode, uid, gid, size, atime, mtime, ctime, crtime)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            )?;
+            for line in lines {
+                statement.execute(params![
+                    line.get_md5(),
+                    line.get_name(),
+                    line.get_inode(),
+                    line.get_mode_as_string(),
+                    line.get_uid(),
+                    line.get_gid(),
+                    line.get_size(),
+                    nullable(*line.get_atime()),
+                    nullable(*line.get_mtime()),
+                    nullable(*line.get_ctime()),
+                    nullable(*line.get_crtime()),
+                ])?;
+                count += 1;
+            }
+        }
+        transaction.commit()?;
+        Ok(count)
+    }
+
+    #[cfg(test)]
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+fn nullable(timestamp: i64) -> Option<i64> {
+    if timestamp == -1 {
+        None
+    } else {
+        Some(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::BodyfileDatabase;
+
+    #[test]
+    fn insert_and_query() {
+        let mut database = BodyfileDatabase::open_in_memory().unwrap();
+        let lines = [
+            "4bad420da66571dac7f1ace995cc55c6|/tmp/sample.txt|87915-128-1|r/rrwxrwxrwx|1003|500|126378|12341|12342|-1|-1",
+            "0|/tmp/other.txt|87916-128-1|r/rrwxrwxrwx|0|0|0|-1|-1|12343|-1",
+        ]
+        .into_iter()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap());
+        assert_eq!(database.insert_lines(lines).unwrap(), 2);
+
+        let (name, size, atime, ctime): (String, u64, Option<i64>, Option<i64>) = database
+            .connection()
+            .query_row(
+                "SELECT name, size, atime, ctime FROM bodyfile WHERE mtime IS NOT NULL",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(name, "/tmp/sample.txt");
+        assert_eq!(size, 126378);
+        assert_eq!(atime, Some(12341));
+        assert_eq!(ctime, None);
+
+        let count: usize = database
+            .connection()
+            .query_row("SELECT COUNT(*) FROM bodyfile WHERE crtime IS NULL", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use cli::Cli;
+use database::BodyfileDatabase;
+use dfir_toolkit::common::bodyfile::BodyfileReader;
+use dfir_toolkit::common::{expand_glob_paths, ExitCode, FancyParser};
+
+mod cli;
+mod database;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+    let input_files = expand_glob_paths(&cli.input_files)?;
+    let mut database = BodyfileDatabase::open(&cli.database)?;
+
+    let mut errors = 0;
+    let lines = BodyfileReader::from_paths(&input_files).filter_map(|line| match line {
+        Ok(bf_line) => Some(bf_line),
+        Err(why) => {
+            log::warn!("{why}");
+            errors += 1;
+            None
+        }
+    });
+
+    let count = database.insert_lines(lines)?;
+    log::info!("imported {count} lines into '{}'", cli.database.display());
+    Ok(ExitCode::with_errors(errors))
+}
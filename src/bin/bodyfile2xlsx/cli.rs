@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueHint};
+use dfir_toolkit::common::HasVerboseFlag;
+use log::LevelFilter;
+
+/// exports a bodyfile into an Excel workbook, whose timestamp columns contain
+/// real dates (in UTC). If the bodyfile has more lines than fit into a single
+/// worksheet, additional worksheets are created
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// path of the xlsx file. An existing file is replaced only after the
+    /// workbook has been written completely
+    #[clap(value_hint=ValueHint::FilePath, display_order(100))]
+    pub(crate) workbook: PathBuf,
+
+    /// path to input file or '-' for stdin. Can be specified multiple times;
+    /// glob patterns like *.bodyfile and alternatives like {a,b} are expanded
+    #[clap(short('b'), value_hint=ValueHint::FilePath, default_value="-", display_order(200))]
+    pub(crate) input_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
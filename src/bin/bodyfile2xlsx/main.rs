@@ -0,0 +1,33 @@
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::bodyfile::BodyfileReader;
+use dfir_toolkit::common::{expand_glob_paths, ExitCode, FancyParser};
+use workbook::TimelineWorkbook;
+
+mod cli;
+mod workbook;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+    let input_files = expand_glob_paths(&cli.input_files)?;
+    let mut workbook = TimelineWorkbook::default();
+
+    let mut errors = 0;
+    for line in BodyfileReader::from_paths(&input_files) {
+        match line {
+            Ok(bf_line) => workbook.add_line(&bf_line)?,
+            Err(why) => {
+                log::warn!("{why}");
+                errors += 1;
+            }
+        }
+    }
+
+    let count = workbook.save(&cli.workbook)?;
+    log::info!("exported {count} lines into '{}'", cli.workbook.display());
+    Ok(ExitCode::with_errors(errors))
+}
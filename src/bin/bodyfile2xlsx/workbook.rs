@@ -0,0 +1,167 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::AtomicOutput;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet};
+
+/// maximum number of rows of an Excel worksheet, including the header row
+const EXCEL_MAX_ROWS: u32 = 1_048_576;
+
+const HEADER: [&str; 11] = [
+    "MD5", "Name", "Inode", "Mode", "UID", "GID", "Size", "Atime", "Mtime", "Ctime", "Crtime",
+];
+
+/// column of the first timestamp, which is followed by the other timestamps
+const FIRST_TIMESTAMP_COLUMN: u16 = 7;
+
+const TIMESTAMP_KINDS: [TimestampKind; 4] = [
+    TimestampKind::Atime,
+    TimestampKind::Mtime,
+    TimestampKind::Ctime,
+    TimestampKind::Crtime,
+];
+
+/// Excel workbook which contains one row per bodyfile line. Timestamps are
+/// stored as dates (in UTC), so that Excel is able to sort and filter them.
+/// Unset timestamps result in empty cells.
+pub(crate) struct TimelineWorkbook {
+    workbook: Workbook,
+    worksheet: Option<Worksheet>,
+    header_format: Format,
+    date_format: Format,
+    rows_per_sheet: u32,
+    next_row: u32,
+    sheets: usize,
+    lines: usize,
+}
+
+impl Default for TimelineWorkbook {
+    fn default() -> Self {
+        Self {
+            workbook: Workbook::new(),
+            worksheet: None,
+            header_format: Format::new().set_bold(),
+            date_format: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+            rows_per_sheet: EXCEL_MAX_ROWS,
+            next_row: 0,
+            sheets: 0,
+            lines: 0,
+        }
+    }
+}
+
+impl TimelineWorkbook {
+    /// limits the number of rows (including the header row) per worksheet
+    #[cfg(test)]
+    pub fn with_rows_per_sheet(mut self, rows_per_sheet: u32) -> Self {
+        self.rows_per_sheet = rows_per_sheet;
+        self
+    }
+
+    pub fn add_line(&mut self, line: &Bodyfile3Line) -> Result<()> {
+        if self.worksheet.is_none() || self.next_row == self.rows_per_sheet {
+            self.start_worksheet()?;
+        }
+        let row = self.next_row;
+        let date_format = &self.date_format;
+        let worksheet = self.worksheet.as_mut().unwrap();
+
+        worksheet.write_string(row, 0, line.get_md5())?;
+        worksheet.write_string(row, 1, line.get_name())?;
+        worksheet.write_string(row, 2, line.get_inode())?;
+        worksheet.write_string(row, 3, line.get_mode_as_string())?;
+        worksheet.write_number(row, 4, *line.get_uid() as f64)?;
+        worksheet.write_number(row, 5, *line.get_gid() as f64)?;
+        worksheet.write_number(row, 6, *line.get_size() as f64)?;
+
+        for (column, kind) in (FIRST_TIMESTAMP_COLUMN..).zip(TIMESTAMP_KINDS) {
+            let timestamp = line.get_timestamp(kind);
+            if timestamp == -1 {
+                continue;
+            }
+            match ExcelDateTime::from_timestamp(timestamp) {
+                Ok(datetime) => {
+                    worksheet.write_datetime_with_format(row, column, &datetime, date_format)?;
+                }
+                Err(why) => {
+                    // Excel does not support dates before 1900
+                    log::warn!("unable to export timestamp {timestamp} as date: {why}");
+                    worksheet.write_number(row, column, timestamp as f64)?;
+                }
+            }
+        }
+
+        self.next_row += 1;
+        self.lines += 1;
+        Ok(())
+    }
+
+    /// saves the workbook and returns the number of exported lines. The file
+    /// is replaced only after the workbook has been written completely
+    pub fn save(mut self, path: &Path) -> Result<usize> {
+        if self.worksheet.is_none() {
+            self.start_worksheet()?;
+        }
+        if let Some(worksheet) = self.worksheet.take() {
+            self.workbook.push_worksheet(worksheet);
+        }
+        let mut output = AtomicOutput::create(path)?;
+        output.write_all(&self.workbook.save_to_buffer()?)?;
+        output.commit()?;
+        Ok(self.lines)
+    }
+
+    /// adds the current worksheet to the workbook, and creates a new
+    /// worksheet with a header row
+    fn start_worksheet(&mut self) -> Result<()> {
+        if let Some(worksheet) = self.worksheet.take() {
+            self.workbook.push_worksheet(worksheet);
+        }
+        self.sheets += 1;
+
+        let mut worksheet = Worksheet::new();
+        match self.sheets {
+            1 => worksheet.set_name("Timeline")?,
+            n => worksheet.set_name(format!("Timeline {n}"))?,
+        };
+        for (column, title) in (0..).zip(HEADER) {
+            worksheet.write_string_with_format(0, column, title, &self.header_format)?;
+        }
+        for column in FIRST_TIMESTAMP_COLUMN..FIRST_TIMESTAMP_COLUMN + 4 {
+            worksheet.set_column_width(column, 20)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+
+        self.worksheet = Some(worksheet);
+        self.next_row = 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{open_workbook, Reader, Xlsx};
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::TimelineWorkbook;
+
+    #[test]
+    fn split_into_sheets() {
+        let path = std::env::temp_dir().join(format!("bodyfile2xlsx_{}.xlsx", std::process::id()));
+        let mut workbook = TimelineWorkbook::default().with_rows_per_sheet(3);
+        for idx in 0..5 {
+            let line = Bodyfile3Line::new().with_owned_name(format!("/tmp/{idx}"));
+            workbook.add_line(&line).unwrap();
+        }
+        assert_eq!(workbook.save(&path).unwrap(), 5);
+
+        let xlsx: Xlsx<_> = open_workbook(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            xlsx.sheet_names(),
+            vec!["Timeline", "Timeline 2", "Timeline 3"]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use clap::Parser;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs, Rfc3339Datetime};
+use log::LevelFilter;
+
+/// generates synthetic bodyfiles for testing and demonstration purposes
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// number of lines to generate
+    #[clap(short('n'), long("lines"), default_value_t = 1000)]
+    pub(crate) lines: u64,
+
+    /// seed of the random number generator. Using the same seed yields the same output.
+    /// If no seed is specified, a random seed is used
+    #[clap(long("seed"))]
+    pub(crate) seed: Option<u64>,
+
+    /// earliest timestamp to generate (hint: use RFC 3339 syntax)
+    #[clap(long("from"), default_value = "2020-01-01")]
+    pub(crate) from: Rfc3339Datetime,
+
+    /// latest timestamp to generate (hint: use RFC 3339 syntax)
+    #[clap(long("to"), default_value = "2023-12-31T23:59:59")]
+    pub(crate) to: Rfc3339Datetime,
+
+    /// fraction of timestamps (between 0.0 and 1.0) which are left unset (`-1`)
+    #[clap(long("unset-ratio"), default_value_t = 0.1, value_parser = parse_ratio)]
+    pub(crate) unset_ratio: f64,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+fn parse_ratio(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(ratio) if (0.0..=1.0).contains(&ratio) => Ok(ratio),
+        Ok(_) => Err(format!("'{value}' is not between 0.0 and 1.0")),
+        Err(why) => Err(why.to_string()),
+    }
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
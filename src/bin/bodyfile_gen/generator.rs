@@ -0,0 +1,166 @@
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const WINDOWS_ROOTS: &[&str] = &[
+    "C:/Windows/System32",
+    "C:/Windows/SysWOW64",
+    "C:/Windows/Temp",
+    "C:/Program Files",
+    "C:/Program Files (x86)",
+    "C:/ProgramData",
+    "C:/Users/{user}/AppData/Local",
+    "C:/Users/{user}/AppData/Roaming",
+    "C:/Users/{user}/Desktop",
+    "C:/Users/{user}/Documents",
+    "C:/Users/{user}/Downloads",
+];
+
+const LINUX_ROOTS: &[&str] = &[
+    "/etc",
+    "/usr/bin",
+    "/usr/lib",
+    "/usr/share",
+    "/var/log",
+    "/var/lib",
+    "/tmp",
+    "/opt",
+    "/home/{user}",
+    "/home/{user}/.config",
+    "/home/{user}/Documents",
+];
+
+const USERS: &[&str] = &["jdoe", "alice", "bob", "admin", "svc_backup"];
+
+const DIRECTORIES: &[&str] = &[
+    "cache", "config", "data", "drivers", "logs", "Microsoft", "Mozilla", "old", "projects",
+    "reports", "temp", "tools", "updates", "Google", "backup",
+];
+
+const BASENAMES: &[&str] = &[
+    "setup", "report", "notes", "invoice", "update", "install", "config", "readme", "backup",
+    "svchost", "payload", "index", "data", "output", "session", "history", "settings",
+];
+
+const EXTENSIONS: &[&str] = &[
+    "exe", "dll", "sys", "ps1", "bat", "docx", "xlsx", "pdf", "txt", "log", "zip", "7z", "jpg",
+    "png", "ini", "xml", "json", "sh", "py", "conf",
+];
+
+/// infinite iterator over randomly generated, but plausible bodyfile lines.
+/// The same seed always yields the same sequence of lines.
+pub(crate) struct BodyfileGenerator {
+    rng: StdRng,
+    from: i64,
+    to: i64,
+    unset_ratio: f64,
+    next_inode: u64,
+}
+
+impl BodyfileGenerator {
+    pub fn new(seed: u64, from: i64, to: i64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            from,
+            to,
+            unset_ratio: 0.0,
+            next_inode: 16,
+        }
+    }
+
+    /// fraction of timestamps which are set to `-1`
+    pub fn with_unset_ratio(mut self, unset_ratio: f64) -> Self {
+        self.unset_ratio = unset_ratio;
+        self
+    }
+
+    fn choose(&mut self, values: &[&'static str]) -> &'static str {
+        values.choose(&mut self.rng).unwrap()
+    }
+
+    fn generate_path(&mut self, windows: bool, is_dir: bool) -> String {
+        let user = self.choose(USERS);
+        let root = self
+            .choose(if windows { WINDOWS_ROOTS } else { LINUX_ROOTS })
+            .replace("{user}", user);
+
+        let mut path = root;
+        for _ in 0..self.rng.gen_range(0..4) {
+            path.push('/');
+            path.push_str(self.choose(DIRECTORIES));
+        }
+
+        path.push('/');
+        path.push_str(self.choose(BASENAMES));
+        if !is_dir {
+            path.push('.');
+            path.push_str(self.choose(EXTENSIONS));
+        }
+        path
+    }
+
+    /// returns a timestamp between `min` and `self.to`, or `-1`
+    fn generate_timestamp(&mut self, min: i64) -> i64 {
+        if self.rng.gen_bool(self.unset_ratio) {
+            -1
+        } else if self.rng.gen_bool(0.3) {
+            // many timestamps of a file are identical
+            min
+        } else {
+            self.rng.gen_range(min..=self.to)
+        }
+    }
+}
+
+impl Iterator for BodyfileGenerator {
+    type Item = Bodyfile3Line;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let windows = self.rng.gen_bool(0.7);
+        let is_dir = self.rng.gen_bool(0.1);
+        let name = self.generate_path(windows, is_dir);
+
+        self.next_inode += self.rng.gen_range(1..100);
+        let (inode, uid, gid) = if windows {
+            let sequence = self.rng.gen_range(1..10);
+            (format!("{}-128-{sequence}", self.next_inode), 0, 0)
+        } else {
+            let uid = if self.rng.gen_bool(0.5) {
+                0
+            } else {
+                self.rng.gen_range(1000..1005)
+            };
+            (self.next_inode.to_string(), uid, uid)
+        };
+
+        let (mode, size) = if is_dir {
+            ("d/drwxr-xr-x", 4096)
+        } else {
+            // file sizes are roughly log-uniformly distributed
+            let exponent = self.rng.gen_range(0.0..9.0);
+            ("r/rrw-r--r--", 10f64.powf(exponent) as u64)
+        };
+
+        let crtime = self.generate_timestamp(self.from);
+        let base = if crtime == -1 { self.from } else { crtime };
+        let mtime = self.generate_timestamp(base);
+        let base = if mtime == -1 { base } else { mtime };
+        let atime = self.generate_timestamp(base);
+        let ctime = self.generate_timestamp(base);
+
+        Some(
+            Bodyfile3Line::new()
+                .with_owned_name(name)
+                .with_owned_inode(inode)
+                .with_mode(mode)
+                .with_uid(uid)
+                .with_gid(gid)
+                .with_size(size)
+                .with_atime(atime)
+                .with_mtime(mtime)
+                .with_ctime(ctime)
+                .with_crtime(crtime),
+        )
+    }
+}
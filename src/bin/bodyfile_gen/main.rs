@@ -0,0 +1,29 @@
+use anyhow::{bail, Result};
+use cli::Cli;
+use dfir_toolkit::common::FancyParser;
+use generator::BodyfileGenerator;
+
+mod cli;
+mod generator;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse_cli();
+
+    let from = cli.from.timestamp();
+    let to = cli.to.timestamp();
+    if from > to {
+        bail!("invalid time range: --from is after --to");
+    }
+
+    let seed = cli.seed.unwrap_or_else(rand::random);
+    log::info!("using seed {seed}");
+
+    let generator = BodyfileGenerator::new(seed, from, to).with_unset_ratio(cli.unset_ratio);
+    let lines = usize::try_from(cli.lines)?;
+    cli.output.write_with(|output| {
+        for line in generator.take(lines) {
+            writeln!(output, "{line}")?;
+        }
+        Ok(())
+    })
+}
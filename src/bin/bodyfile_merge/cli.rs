@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueHint};
+use dfir_toolkit::common::HasVerboseFlag;
+use log::LevelFilter;
+
+/// merges multiple bodyfiles into one, and splits merged bodyfiles
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub(crate) command: Command,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    /// merge bodyfiles, ordered by the earliest timestamp of every line.
+    /// The order of the lines of every single bodyfile is preserved.
+    Merge {
+        /// bodyfiles to be merged. Glob patterns like `*.bodyfile` are expanded, if
+        /// the shell has not already done this
+        #[clap(value_hint=ValueHint::FilePath, num_args=1..)]
+        inputs: Vec<PathBuf>,
+
+        /// name of the file to write (default to stdout). The file is only created if the merge succeeds
+        #[clap(short('o'), long("output"), value_hint=ValueHint::FilePath)]
+        output: Option<PathBuf>,
+
+        /// store the origin of every line in this file, which is needed by `split`
+        #[clap(long("origins"), value_hint=ValueHint::FilePath)]
+        origins: Option<PathBuf>,
+
+        /// write the inode column in its canonical form (e.g. `87915-128-1`
+        /// instead of `087915-128-01:$DATA`), so that merged bodyfiles of
+        /// different tools can be compared and deduplicated
+        #[clap(long("canonical-inode"))]
+        canonical_inode: bool,
+
+        /// write a report of all files (identified by their inode and name) whose
+        /// hash differs between the bodyfiles into this file. Every line contains
+        /// the identity of the file, one of its hashes and the bodyfile it has been
+        /// found in
+        #[clap(long("report-hash-conflicts"), value_name = "REPORT", value_hint=ValueHint::FilePath)]
+        report_hash_conflicts: Option<PathBuf>,
+
+        /// append a checksum column to every line, so that lines which are
+        /// altered after the merge can be detected using `verify`. The column
+        /// is ignored when the bodyfile is read again
+        #[clap(long("checksums"))]
+        checksums: bool,
+    },
+
+    /// split a merged bodyfile back into the original bodyfiles
+    Split {
+        /// merged bodyfile
+        #[clap(value_hint=ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// origins file which has been created during the merge
+        #[clap(long("origins"), value_hint=ValueHint::FilePath)]
+        origins: PathBuf,
+
+        /// directory where the original bodyfiles will be written to
+        #[clap(short('d'), long("output-dir"), value_hint=ValueHint::DirPath, default_value = ".")]
+        output_dir: PathBuf,
+    },
+
+    /// verify the checksums of a bodyfile which has been merged using
+    /// `--checksums`, and report all lines which have been altered
+    Verify {
+        /// merged bodyfile
+        #[clap(value_hint=ValueHint::FilePath)]
+        input: PathBuf,
+    },
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::merge::parse_line;
+
+/// collects the hashes of every file (identified by its inode and name), to
+/// find files whose hash differs between the merged bodyfiles. This might
+/// indicate that a file has been changed between two collections.
+#[derive(Default)]
+pub(crate) struct HashConflicts {
+    /// distinct hashes (in lowercase) of every file, together with the
+    /// index of the source they have been found in first
+    hashes: BTreeMap<String, Vec<(String, usize)>>,
+}
+
+impl HashConflicts {
+    /// adds a raw line of the source `source`. Lines which cannot be parsed
+    /// and lines without a hash are ignored.
+    pub fn add(&mut self, source: usize, line: &str) {
+        let Ok(bf_line) = parse_line(line) else {
+            return;
+        };
+        let md5 = bf_line.get_md5().to_lowercase();
+        if md5.is_empty() || md5 == "0" {
+            return;
+        }
+        let hashes = self.hashes.entry(bf_line.identity_key()).or_default();
+        if !hashes.iter().any(|(h, _)| *h == md5) {
+            hashes.push((md5, source));
+        }
+    }
+
+    /// iterates over all files which have more than one hash
+    fn conflicts(&self) -> impl Iterator<Item = (&String, &Vec<(String, usize)>)> {
+        self.hashes.iter().filter(|(_, hashes)| hashes.len() > 1)
+    }
+
+    /// writes one line `<identity>\t<hash>\t<source>` for every hash of every
+    /// conflicting file, and returns the number of conflicting files
+    pub fn write_report(&self, path: &Path, sources: &[String]) -> Result<usize> {
+        let mut report = BufWriter::new(File::create(path)?);
+        writeln!(report, "# identity\thash\tsource")?;
+        let mut count = 0;
+        for (identity, hashes) in self.conflicts() {
+            for (md5, source) in hashes {
+                writeln!(report, "{identity}\t{md5}\t{}", sources[*source])?;
+            }
+            count += 1;
+        }
+        report.flush()?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashConflicts;
+
+    #[test]
+    fn ignore_missing_hashes() {
+        let mut conflicts = HashConflicts::default();
+        conflicts.add(0, "0|/a.txt|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n");
+        conflicts.add(
+            1,
+            "b1946ac92492d2347c6235b4d2611184|/a.txt|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n",
+        );
+        conflicts.add(
+            2,
+            "B1946AC92492D2347C6235B4D2611184|/a.txt|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n",
+        );
+        assert_eq!(conflicts.conflicts().count(), 0);
+
+        conflicts.add(
+            3,
+            "d41d8cd98f00b204e9800998ecf8427e|/a.txt|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n",
+        );
+        assert_eq!(conflicts.conflicts().count(), 1);
+    }
+}
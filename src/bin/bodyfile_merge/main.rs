@@ -0,0 +1,38 @@
+use anyhow::Result;
+use cli::{Cli, Command};
+use dfir_toolkit::common::{expand_glob_paths, FancyParser};
+
+mod cli;
+mod conflicts;
+mod merge;
+mod origins;
+mod split;
+mod verify;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse_cli();
+
+    match cli.command {
+        Command::Merge {
+            inputs,
+            output,
+            origins,
+            canonical_inode,
+            report_hash_conflicts,
+            checksums,
+        } => merge::merge(
+            &expand_glob_paths(&inputs)?,
+            output.as_deref(),
+            origins.as_deref(),
+            canonical_inode,
+            report_hash_conflicts.as_deref(),
+            checksums,
+        ),
+        Command::Split {
+            input,
+            origins,
+            output_dir,
+        } => split::split(&input, &origins, &output_dir),
+        Command::Verify { input } => verify::verify(&input),
+    }
+}
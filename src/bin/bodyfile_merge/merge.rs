@@ -0,0 +1,142 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use dfir_toolkit::common::bodyfile::{
+    append_checksum, strip_checksum, Bodyfile3Line, Bodyfile3ParserError, Bodyfile3ParserOptions,
+    TimestampKind,
+};
+use dfir_toolkit::common::{AtomicOutput, FinishableWrite, OutputWriter};
+
+use crate::conflicts::HashConflicts;
+use crate::origins::OriginsWriter;
+
+struct Source {
+    name: String,
+    reader: BufReader<File>,
+}
+
+impl Source {
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            name: path.to_string_lossy().to_string(),
+            reader: BufReader::new(File::open(path)?),
+        })
+    }
+
+    /// reads the next raw line, including its line break
+    fn next_line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(line))
+        }
+    }
+}
+
+/// parses a raw line, including its line break. A checksum column, which has
+/// been appended by an earlier merge, is ignored.
+pub(crate) fn parse_line(line: &str) -> Result<Bodyfile3Line, Bodyfile3ParserError> {
+    let options = Bodyfile3ParserOptions::default().with_checksum_stripping(true);
+    Bodyfile3Line::parse(line.trim_end_matches(['\r', '\n']), &options)
+}
+
+/// lines are merged by their earliest timestamp. Lines without timestamps and
+/// lines which cannot be parsed (e.g. comments) are put first.
+fn sort_key(line: &str) -> i64 {
+    match parse_line(line) {
+        Ok(bf_line) => TimestampKind::ALL
+            .iter()
+            .map(|kind| bf_line.get_timestamp(*kind))
+            .filter(|ts| *ts != -1)
+            .min()
+            .unwrap_or(i64::MIN),
+        Err(_) => i64::MIN,
+    }
+}
+
+/// replaces the inode column of a raw line by its canonical form. Lines which
+/// cannot be parsed (e.g. comments) are returned unchanged.
+fn canonicalize_inode(line: String) -> String {
+    match parse_line(&line) {
+        Ok(bf_line) => {
+            let inode = bf_line.canonical_inode();
+            format!("{}\n", bf_line.with_owned_inode(inode))
+        }
+        Err(_) => line,
+    }
+}
+
+/// appends the checksum column to a raw line, replacing an existing one.
+/// Comments and empty lines are returned unchanged.
+fn add_checksum(line: String) -> String {
+    let content = line.trim_end_matches(['\r', '\n']);
+    if content.is_empty() || content.starts_with('#') {
+        line
+    } else {
+        format!("{}\n", append_checksum(strip_checksum(content)))
+    }
+}
+
+pub(crate) fn merge(
+    inputs: &[PathBuf],
+    output: Option<&Path>,
+    origins: Option<&Path>,
+    canonical_inode: bool,
+    report_hash_conflicts: Option<&Path>,
+    checksums: bool,
+) -> Result<()> {
+    let mut sources = inputs.iter().map(|path| Source::open(path)).collect::<Result<Vec<_>>>()?;
+    let mut origins = origins.map(OriginsWriter::create).transpose()?;
+    let mut output: Box<dyn FinishableWrite> = match output {
+        Some(path) => Box::new(AtomicOutput::create(path)?),
+        None => Box::new(OutputWriter::stdout()),
+    };
+    let mut conflicts = report_hash_conflicts.map(|_| HashConflicts::default());
+
+    // the heap only contains the next line of every source, so the order of
+    // lines from the same source is never changed
+    let mut heads = BinaryHeap::new();
+    for (idx, source) in sources.iter_mut().enumerate() {
+        if let Some(line) = source.next_line()? {
+            heads.push(Reverse((sort_key(&line), idx, line)));
+        }
+    }
+
+    while let Some(Reverse((_, idx, mut line))) = heads.pop() {
+        if canonical_inode {
+            line = canonicalize_inode(line);
+        }
+        if checksums {
+            line = add_checksum(line);
+        }
+        if !line.ends_with('\n') {
+            line.push('\n');
+        }
+        output.write_all(line.as_bytes())?;
+        if let Some(origins) = origins.as_mut() {
+            origins.add(&sources[idx].name, &line)?;
+        }
+        if let Some(conflicts) = conflicts.as_mut() {
+            conflicts.add(idx, &line);
+        }
+
+        if let Some(line) = sources[idx].next_line()? {
+            heads.push(Reverse((sort_key(&line), idx, line)));
+        }
+    }
+    output.finish()?;
+
+    if let (Some(path), Some(conflicts)) = (report_hash_conflicts, conflicts) {
+        let names: Vec<_> = sources.into_iter().map(|source| source.name).collect();
+        let count = conflicts.write_report(path, &names)?;
+        if count > 0 {
+            log::warn!("found {count} files with conflicting hashes");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::merge::parse_line;
+
+/// returns the identity of a raw line (including its line break). Lines
+/// which cannot be parsed (e.g. comments) are identified by their content.
+pub(crate) fn identity_of(line: &str) -> String {
+    match parse_line(line) {
+        Ok(bf_line) => bf_line.identity_key(),
+        Err(_) => line.trim_end_matches(['\r', '\n']).to_owned(),
+    }
+}
+
+/// side table, which stores the source of every line of a merged bodyfile.
+///
+/// The file contains one line `<source>\t<identity>` per merged line, in the
+/// order of the merged bodyfile. Identical identities (the same file in
+/// multiple sources) are resolved by that order.
+pub(crate) struct OriginsWriter {
+    file: File,
+}
+
+impl OriginsWriter {
+    pub fn create(path: &Path) -> Result<Self> {
+        Ok(Self {
+            file: File::create(path)?,
+        })
+    }
+
+    pub fn add(&mut self, source: &str, line: &str) -> Result<()> {
+        if source.contains('\t') {
+            bail!("source name '{source}' must not contain a tab");
+        }
+        writeln!(self.file, "{source}\t{}", identity_of(line))?;
+        Ok(())
+    }
+}
+
+pub(crate) struct Origins {
+    sources: Vec<String>,
+    by_identity: HashMap<String, VecDeque<usize>>,
+}
+
+impl Origins {
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut sources: Vec<String> = Vec::new();
+        let mut by_identity: HashMap<String, VecDeque<usize>> = HashMap::new();
+
+        for (idx, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            let (source, identity) = line
+                .split_once('\t')
+                .ok_or_else(|| anyhow!("invalid entry in line {} of the origins file", idx + 1))?;
+
+            let source_id = match sources.iter().position(|s| s == source) {
+                Some(id) => id,
+                None => {
+                    sources.push(source.to_owned());
+                    sources.len() - 1
+                }
+            };
+            by_identity
+                .entry(identity.to_owned())
+                .or_default()
+                .push_back(source_id);
+        }
+        Ok(Self {
+            sources,
+            by_identity,
+        })
+    }
+
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// returns the index of the source of the next line with the identity of `line`
+    pub fn take_source_of(&mut self, line: &str) -> Option<usize> {
+        self.by_identity
+            .get_mut(&identity_of(line))
+            .and_then(VecDeque::pop_front)
+    }
+}
@@ -0,0 +1,45 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+use crate::origins::Origins;
+
+/// creates a file name from the name of a source, which may contain path separators
+fn output_name(source: &str) -> String {
+    source.replace(['/', '\\', ':'], "_")
+}
+
+pub(crate) fn split(input: &Path, origins: &Path, output_dir: &Path) -> Result<()> {
+    let mut origins = Origins::load(origins)?;
+    let mut outputs = origins
+        .sources()
+        .iter()
+        .map(|source| {
+            let path = output_dir.join(output_name(source));
+            log::info!("writing lines of '{source}' to '{}'", path.display());
+            Ok(BufWriter::new(File::create(path)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut line_nr = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_nr += 1;
+
+        let source = origins
+            .take_source_of(&line)
+            .ok_or_else(|| anyhow!("unknown origin of line {line_nr}"))?;
+        outputs[source].write_all(line.as_bytes())?;
+    }
+
+    for output in outputs.iter_mut() {
+        output.flush()?;
+    }
+    Ok(())
+}
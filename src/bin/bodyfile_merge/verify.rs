@@ -0,0 +1,44 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use dfir_toolkit::common::bodyfile::{line_checksum, split_checksum};
+
+/// recomputes the checksums of all lines of a bodyfile, which has been
+/// written with `merge --checksums`, and reports every altered line
+pub(crate) fn verify(input: &Path) -> Result<()> {
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut line_nr = 0;
+    let mut altered = 0;
+    let mut missing = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_nr += 1;
+
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match split_checksum(line) {
+            (content, Some(checksum)) if checksum.eq_ignore_ascii_case(&line_checksum(content)) => (),
+            (_, Some(_)) => {
+                log::error!("line {line_nr} has been altered: {line}");
+                altered += 1;
+            }
+            (_, None) => {
+                log::error!("line {line_nr} has no checksum: {line}");
+                missing += 1;
+            }
+        }
+    }
+
+    if altered > 0 || missing > 0 {
+        bail!("found {altered} altered lines and {missing} lines without checksum");
+    }
+    log::info!("verified the checksums of {line_nr} lines");
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueHint};
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
+use log::LevelFilter;
+
+/// creates bodyfile from the browser history of Chrome (`History`) and
+/// Firefox (`places.sqlite`). Every visit of a URL results in one line
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// names of the history databases
+    #[clap(value_hint=ValueHint::FilePath, num_args=1..)]
+    pub(crate) history_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
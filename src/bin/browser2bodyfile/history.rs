@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use rusqlite::{Connection, OpenFlags};
+
+/// seconds between 1601-01-01 (the epoch used by Chrome) and 1970-01-01
+const WEBKIT_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+const CHROME_VISITS: &str = "
+SELECT urls.url, urls.title, visits.visit_time
+FROM visits JOIN urls ON urls.id = visits.url
+ORDER BY visits.visit_time";
+
+const FIREFOX_VISITS: &str = "
+SELECT moz_places.url, moz_places.title, moz_historyvisits.visit_date
+FROM moz_historyvisits JOIN moz_places ON moz_places.id = moz_historyvisits.place_id
+ORDER BY moz_historyvisits.visit_date";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Browser {
+    /// Chrome and other Chromium based browsers, which store microseconds
+    /// since 1601-01-01
+    Chrome,
+
+    /// Firefox, which stores microseconds since 1970-01-01
+    Firefox,
+}
+
+impl Browser {
+    /// converts a visit time of this browser into a unix timestamp. Missing
+    /// values (`0`) are converted into `-1`.
+    pub fn to_unix_timestamp(self, visit_time: i64) -> i64 {
+        if visit_time <= 0 {
+            return -1;
+        }
+        let seconds = visit_time.div_euclid(1_000_000);
+        match self {
+            Self::Chrome => (seconds - WEBKIT_EPOCH_OFFSET).max(-1),
+            Self::Firefox => seconds,
+        }
+    }
+}
+
+/// a history database of Chrome or Firefox, which is opened read-only
+pub(crate) struct BrowserHistory {
+    connection: Connection,
+    browser: Browser,
+}
+
+impl BrowserHistory {
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let browser = if Self::has_table(&connection, "moz_historyvisits")? {
+            Browser::Firefox
+        } else if Self::has_table(&connection, "visits")? {
+            Browser::Chrome
+        } else {
+            bail!("'{}' is no browser history database", path.display());
+        };
+        Ok(Self {
+            connection,
+            browser,
+        })
+    }
+
+    pub fn browser(&self) -> Browser {
+        self.browser
+    }
+
+    /// returns one bodyfile line per visit. The name consists of the URL and
+    /// the title of the page, and the time of the visit is stored as `atime`.
+    pub fn visits(&self) -> Result<Vec<Bodyfile3Line>> {
+        let query = match self.browser {
+            Browser::Chrome => CHROME_VISITS,
+            Browser::Firefox => FIREFOX_VISITS,
+        };
+        let mut statement = self.connection.prepare(query)?;
+        let lines = statement
+            .query_map([], |row| {
+                let url: String = row.get(0)?;
+                let title: Option<String> = row.get(1)?;
+                let visit_time: i64 = row.get(2)?;
+                Ok((url, title, visit_time))
+            })?
+            .map(|row| {
+                let (url, title, visit_time) = row?;
+                let name = match title {
+                    Some(title) if !title.is_empty() => format!("{url} ({title})"),
+                    _ => url,
+                };
+                Ok(Bodyfile3Line::new()
+                    .with_owned_name(name)
+                    .with_atime(self.browser.to_unix_timestamp(visit_time)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(lines)
+    }
+
+    fn has_table(connection: &Connection, name: &str) -> Result<bool> {
+        let count: i64 = connection.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Browser;
+
+    #[test]
+    fn convert_epochs() {
+        // 2021-02-18T00:00:00.123456+00:00
+        assert_eq!(Browser::Firefox.to_unix_timestamp(1_613_606_400_123_456), 1613606400);
+        assert_eq!(
+            Browser::Chrome.to_unix_timestamp(13_258_080_000_123_456),
+            1613606400
+        );
+        assert_eq!(Browser::Chrome.to_unix_timestamp(0), -1);
+        assert_eq!(Browser::Firefox.to_unix_timestamp(0), -1);
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use history::BrowserHistory;
+
+mod cli;
+mod history;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for path in cli.history_files.iter() {
+            let visits = BrowserHistory::open(path).and_then(|history| {
+                log::info!("reading {:?} history from '{}'", history.browser(), path.display());
+                history.visits()
+            });
+            match visits {
+                Ok(visits) => {
+                    for line in visits {
+                        writeln!(output, "{line}")?;
+                    }
+                }
+                Err(why) => {
+                    log::error!("unable to read '{}': {why}", path.display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
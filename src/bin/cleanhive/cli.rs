@@ -1,11 +1,12 @@
 use clap::{Parser, ValueHint};
 use clio::*;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 /// merges logfiles into a hive file
 #[derive(Parser)]
 #[clap(name=env!("CARGO_BIN_NAME"), author, version)]
+#[clap(mut_arg("output", |arg| arg.short('O')))]
 pub(crate) struct Cli {
     /// name of the file to dump
     #[clap(value_parser, value_hint=ValueHint::FilePath)]
@@ -18,9 +19,8 @@ pub(crate) struct Cli {
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 
-    /// name of the file to which the cleaned hive will be written.
-    #[clap(short('O'), long("output"), default_value="-", value_hint=ValueHint::FilePath, value_parser)]
-    pub(crate) dst_hive: Output,
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
 }
 
 impl HasVerboseFlag for Cli {
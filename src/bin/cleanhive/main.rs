@@ -42,6 +42,8 @@ pub fn main() -> Result<()> {
         }
     };
 
-    std::io::copy(&mut clean_hive, &mut cli.dst_hive)?;
-    Ok(())
+    cli.output.write_with(|output| {
+        std::io::copy(&mut clean_hive, output)?;
+        Ok(())
+    })
 }
@@ -0,0 +1,52 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use dfir_toolkit::common::AtomicOutput;
+
+/// file which stores the number of input lines which have been imported
+/// successfully, so that an interrupted import can be resumed
+pub(crate) struct Checkpoint {
+    path: PathBuf,
+}
+
+impl Checkpoint {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// returns the number of lines which have already been imported, or `0`
+    /// if there is no checkpoint file
+    pub fn load(&self) -> Result<usize> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => content.trim().parse().map_err(|why| {
+                anyhow!("invalid checkpoint file '{}': {why}", self.path.display())
+            }),
+            Err(why) if why.kind() == ErrorKind::NotFound => {
+                log::warn!(
+                    "checkpoint file '{}' does not exist, starting at the beginning",
+                    self.path.display()
+                );
+                Ok(0)
+            }
+            Err(why) => Err(why.into()),
+        }
+    }
+
+    /// stores the number of lines which have been imported. The file is
+    /// replaced atomically, so that it never contains a partial value.
+    pub fn store(&self, lines: usize) -> Result<()> {
+        let mut output = AtomicOutput::create(&self.path)?;
+        writeln!(output, "{lines}")?;
+        output.commit()
+    }
+
+    /// removes the checkpoint file after the import has been finished
+    pub fn remove(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(why) if why.kind() != ErrorKind::NotFound => Err(why.into()),
+            _ => Ok(()),
+        }
+    }
+}
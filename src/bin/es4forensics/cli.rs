@@ -3,6 +3,8 @@ use clap::{Parser, ValueHint};
 use clio::Input;
 use dfir_toolkit::common::HasVerboseFlag;
 use log::LevelFilter;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[cfg(feature = "gzip")]
 const INPUTFILE_HELP: &str =
@@ -21,11 +23,57 @@ pub(crate) enum Action {
         input_file: Input,
 
         /// number of timeline entries to combine in one bulk operation
-        #[clap(long("bulk-size"), default_value_t = 1000)]
-        bulk_size: usize,
+        #[clap(long("batch-size"), alias("bulk-size"), default_value_t = 1000)]
+        batch_size: usize,
+
+        /// maximum number of bulk operations which are sent to elasticsearch
+        /// at the same time
+        #[clap(long("max-in-flight"), default_value_t = 1, value_parser=clap::value_parser!(u16).range(1..))]
+        max_in_flight: u16,
+
+        /// send a bulk operation after this number of milliseconds, even if
+        /// it does not contain `batch-size` entries yet
+        #[clap(long("flush-interval"), value_name = "MILLISECONDS")]
+        flush_interval: Option<u64>,
+
+        /// delete the index (if it exists) and create it again before importing
+        #[clap(long("recreate-index"))]
+        recreate_index: bool,
+
+        /// do not derive document ids from the imported data, which creates
+        /// duplicate documents if the same data is imported twice
+        #[clap(long("no-dedup"))]
+        no_dedup: bool,
+
+        /// after every successful bulk operation, store the number of imported
+        /// lines in this file. The file is removed after the import has finished
+        #[clap(long("checkpoint"), value_hint=ValueHint::FilePath)]
+        checkpoint: Option<PathBuf>,
+
+        /// skip all lines which have already been imported according to the checkpoint file
+        #[clap(long("resume"), requires("checkpoint"))]
+        resume: bool,
+
+        /// do not connect to elasticsearch, but print the bulk operations which
+        /// would be sent as NDJSON. The name of the index and the number of
+        /// documents are printed to stderr
+        #[clap(long("dry-run"), conflicts_with_all(["recreate_index", "checkpoint"]))]
+        dry_run: bool,
+
+        /// print only the bulk operations of the first N documents during a dry
+        /// run. All documents are counted nevertheless
+        #[clap(long("dry-run-sample"), value_name = "N", requires("dry_run"))]
+        dry_run_sample: Option<usize>,
     },
 }
 
+pub(crate) struct ImportOptions {
+    pub(crate) batch_size: usize,
+    pub(crate) max_in_flight: u16,
+    pub(crate) flush_interval: Option<Duration>,
+    pub(crate) dedup: bool,
+}
+
 /// This crates provides structs and functions to insert timeline data into an elasticsearch index.
 #[derive(Parser)]
 #[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
@@ -75,6 +123,20 @@ pub struct Cli {
     #[clap(short('W'), long("password"), display_order = 860)]
     pub(crate) password: String,
 
+    /// number of times a request is sent again after a network error, a
+    /// server error or `429 Too Many Requests`
+    #[clap(long("retries"), display_order = 870, default_value_t = 8)]
+    pub(crate) max_retries: u32,
+
+    /// maximum time to wait until elasticsearch accepts a connection and
+    /// starts to respond
+    #[clap(long("connect-timeout"), value_name = "SECONDS", display_order = 880)]
+    pub(crate) connect_timeout: Option<u64>,
+
+    /// maximum time to wait for the body of a response
+    #[clap(long("read-timeout"), value_name = "SECONDS", display_order = 890)]
+    pub(crate) read_timeout: Option<u64>,
+
     #[clap(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
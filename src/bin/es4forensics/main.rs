@@ -1,13 +1,21 @@
+mod checkpoint;
 mod cli;
 
 
-use std::io::BufRead;
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+use std::time::Duration;
 use anyhow::{Result, anyhow};
+use serde_json::Value;
 
-use cli::{Cli, Action};
+use checkpoint::Checkpoint;
+use clio::Input;
+use cli::{Cli, Action, ImportOptions};
 use elasticsearch::auth::Credentials;
 use dfir_toolkit::es4forensics::*;
 use dfir_toolkit::common::{FancyParser, FileInput};
+use dfir_toolkit::common::interrupt::INTERRUPTED_EXIT_CODE;
+use tokio::time::Instant;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -27,6 +35,9 @@ struct Es4Forensics {
     omit_certificate_validation: bool,
     username: String,
     password: String,
+    max_retries: u32,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
 }
 
 impl Es4Forensics {
@@ -42,37 +53,174 @@ impl Es4Forensics {
                 builder.create_index().await?;
                 Ok(())
             }
-            Action::Import{input_file, bulk_size} => {
-                self.import(builder, input_file.into(), bulk_size).await
+            Action::Import{input_file, no_dedup, dry_run: true, dry_run_sample, ..} => {
+                self.dry_run(input_file, !no_dedup, dry_run_sample)
+            }
+            Action::Import{input_file, batch_size, max_in_flight, flush_interval, recreate_index, no_dedup, checkpoint, resume, ..} => {
+                if recreate_index {
+                    builder.delete_index().await?;
+                }
+                let checkpoint = checkpoint.map(Checkpoint::new);
+                let skip_lines = match &checkpoint {
+                    Some(checkpoint) if resume => checkpoint.load()?,
+                    _ => 0,
+                };
+                let options = ImportOptions {
+                    batch_size,
+                    max_in_flight,
+                    flush_interval: flush_interval.map(Duration::from_millis),
+                    dedup: !no_dedup,
+                };
+                self.import(builder, input_file, options, checkpoint.as_ref(), skip_lines).await
             }
         }
     }
 
-    async fn import(&self, builder: IndexBuilder, reader: FileInput, bulk_size: usize) -> Result<()> {
-        let mut index = builder.connect().await?;
-        index.set_cache_size(bulk_size).await?;
+    async fn import(&self, builder: IndexBuilder, input: Input, options: ImportOptions, checkpoint: Option<&Checkpoint>, skip_lines: usize) -> Result<()> {
+        // creates the index with the appropriate mapping, if it does not exist yet
+        let mut index = builder.create_index().await?;
+        index.set_cache_size(options.batch_size).await?;
+        index.set_max_in_flight(options.max_in_flight.into());
+        index.set_flush_interval(options.flush_interval);
+        index.set_dedup(options.dedup);
 
-        for line in reader.lines() {
-            let line = line?;
-            let value = match serde_json::from_str(&line) {
-                Ok(v) => v,
-                Err(why) => {
-                    if self.strict_mode {
-                        return Err(anyhow!(why))
-                    } else {
-                        ::log::error!("error while parsing: {}", why);
-                        ::log::error!("failed JSON was:     {}", line);
-                        continue;
+        if skip_lines > 0 {
+            ::log::info!("skipping {skip_lines} lines which have already been imported");
+        }
+
+        // the input is read in a separate thread, so that pending documents
+        // can be sent after the flush interval even if no new line arrives
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(options.batch_size.max(1));
+        let reader_task = tokio::task::spawn_blocking(move || {
+            let reader = FileInput::from(input);
+            for line in reader.lines() {
+                if sender.blocking_send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // number of documents sent so far, together with the number of lines
+        // which contained these documents
+        let mut sent_batches = VecDeque::new();
+        let mut added_documents = 0;
+        let mut read_lines = 0;
+
+        // on SIGINT, the documents which have been read so far are imported
+        let interrupted = tokio::signal::ctrl_c();
+        tokio::pin!(interrupted);
+        let mut is_interrupted = false;
+
+        loop {
+            let line = tokio::select! {
+                line = receiver.recv() => line,
+                _ = wait_until(index.flush_deadline()) => {
+                    index.send_batch().await?;
+                    if checkpoint.is_some() {
+                        sent_batches.push_back((added_documents, read_lines));
                     }
+                    continue;
+                }
+                _ = &mut interrupted => {
+                    is_interrupted = true;
+                    break;
                 }
             };
+            let Some(line) = line else { break };
+            let line = line?;
+            let idx = read_lines;
+            read_lines += 1;
+
+            if idx < skip_lines {
+                continue;
+            }
+            let Some(value) = self.parse_document(&line)? else { continue };
 
             index.add_bulk_document(value).await?;
+            added_documents += 1;
+
+            if let Some(checkpoint) = checkpoint {
+                if index.pending_documents() == 0 {
+                    sent_batches.push_back((added_documents, read_lines));
+                }
+
+                // all documents up to this line have been acknowledged by elasticsearch
+                let mut acknowledged_lines = None;
+                while let Some((documents, lines)) = sent_batches.front() {
+                    if *documents > index.acknowledged_documents() {
+                        break;
+                    }
+                    acknowledged_lines = Some(*lines);
+                    sent_batches.pop_front();
+                }
+                if let Some(lines) = acknowledged_lines {
+                    checkpoint.store(lines)?;
+                }
+            }
+        }
+        if is_interrupted {
+            // a second SIGINT terminates immediately
+            tokio::spawn(async {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    std::process::exit(INTERRUPTED_EXIT_CODE);
+                }
+            });
+
+            // the reader is not awaited, because it might be blocked by its input
+            index.flush().await?;
+            if let Some(checkpoint) = checkpoint {
+                checkpoint.store(read_lines)?;
+            }
+            eprintln!("interrupted after importing {added_documents} documents from {read_lines} lines");
+            std::process::exit(INTERRUPTED_EXIT_CODE);
         }
+
+        reader_task.await?;
         index.flush().await?;
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.remove()?;
+        }
         Ok(())
     }    
 
+    /// parses the input like `import` does, but prints the bulk operations
+    /// instead of sending them
+    fn dry_run(&self, input: Input, dedup: bool, sample: Option<usize>) -> Result<()> {
+        let sample = sample.unwrap_or(usize::MAX);
+        let mut stdout = std::io::stdout().lock();
+        let mut documents = 0;
+        for line in FileInput::from(input).lines() {
+            let line = line?;
+            let Some(value) = self.parse_document(&line)? else { continue };
+            if documents < sample {
+                let (action, document) = bulk_entry(value, dedup);
+                writeln!(stdout, "{action}")?;
+                writeln!(stdout, "{document}")?;
+            }
+            documents += 1;
+        }
+        stdout.flush()?;
+        eprintln!("index: {}", self.index_name);
+        eprintln!("documents: {documents}");
+        Ok(())
+    }
+
+    /// returns `None` if the line is no valid JSON and strict mode is disabled
+    fn parse_document(&self, line: &str) -> Result<Option<Value>> {
+        match serde_json::from_str(line) {
+            Ok(v) => Ok(Some(v)),
+            Err(why) => {
+                if self.strict_mode {
+                    Err(anyhow!(why))
+                } else {
+                    ::log::error!("error while parsing: {}", why);
+                    ::log::error!("failed JSON was:     {}", line);
+                    Ok(None)
+                }
+            }
+        }
+    }
+
     fn create_index_builder(&self) -> Result<IndexBuilder> {
         let mut builder = IndexBuilder::with_name(self.index_name.clone())
             .with_host(self.host.clone())
@@ -81,7 +229,15 @@ impl Es4Forensics {
                 self.username.clone(),
                 self.password.clone(),
             ))
-            .with_protocol(self.protocol.clone());
+            .with_protocol(self.protocol.clone())
+            .with_max_retries(self.max_retries);
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.with_connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            builder = builder.with_read_timeout(read_timeout);
+        }
 
         if self.omit_certificate_validation {
             ::log::warn!("disabling certificate validation");
@@ -92,6 +248,14 @@ impl Es4Forensics {
     }
 }
 
+/// waits until `deadline`, or forever if there is no deadline
+async fn wait_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 impl From<Cli> for Es4Forensics {
     fn from(cli: Cli) -> Self {
         Self {
@@ -103,6 +267,9 @@ impl From<Cli> for Es4Forensics {
             index_name: cli.index_name.clone(),
             protocol: cli.protocol.clone(),
             omit_certificate_validation: cli.omit_certificate_validation,
+            max_retries: cli.max_retries,
+            connect_timeout: cli.connect_timeout.map(Duration::from_secs),
+            read_timeout: cli.read_timeout.map(Duration::from_secs),
         }
     }
 }
\ No newline at end of file
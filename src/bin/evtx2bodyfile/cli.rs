@@ -1,7 +1,7 @@
 use crate::output_format::OutputFormat;
 use clap::{Parser, ValueHint};
 use clio::Input;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use getset::Getters;
 use log::LevelFilter;
 
@@ -22,6 +22,16 @@ pub(crate) struct Cli {
     #[clap(short('S'), long("strict"))]
     strict: bool,
 
+    /// use human readable descriptions of well-known events (e.g.
+    /// `[Sysmon 1] Process Create: <image>`) as names, instead of the JSON
+    /// representation of the events. Unknown events are named by their
+    /// channel and numeric event id
+    #[clap(short('D'), long("descriptive-names"))]
+    descriptive_names: bool,
+
+    #[clap(flatten)]
+    output: OutputArgs,
+
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 }
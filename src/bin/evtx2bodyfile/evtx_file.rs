@@ -1,3 +1,5 @@
+use std::io::Write;
+
 use anyhow::Result;
 use clio::Input;
 use evtx::{EvtxParser, SerializedEvtxRecord};
@@ -58,14 +60,19 @@ impl From<&Input> for EvtxFile {
 }
 
 impl EvtxFile {
-    pub(crate) fn print_records<F>(self, formatter: F, treat_errors_as_warnings: bool) -> Result<()>
+    pub(crate) fn print_records<F>(
+        self,
+        output: &mut dyn Write,
+        formatter: F,
+        treat_errors_as_warnings: bool,
+    ) -> Result<()>
     where
         F: OutputFormatter,
     {
         let bar = self.create_progress_bar().unwrap();
         for value in self.into_iter() {
             match formatter.record_to_string(&value) {
-                Ok(s) => println!("{s}"),
+                Ok(s) => writeln!(output, "{s}")?,
                 Err(why) => {
                     if treat_errors_as_warnings {
                         log::warn!("Error while reading record: {why}");
@@ -82,6 +89,7 @@ impl EvtxFile {
         Ok(())
     }
 
+    #[allow(clippy::result_large_err)]
     pub(crate) fn count_records(&self) -> Result<usize> {
         let mut parser = EvtxParser::from_read_seek(self.0.clone())?;
         Ok(parser.serialized_records(|r| r.and(Ok(()))).count())
@@ -4,13 +4,10 @@ use dfir_toolkit::common::FancyParser;
 use evtx_file::EvtxFile;
 use output_formatter::{BodyfileOutputFormatter, JsonOutputFormatter};
 
-mod bf_data;
 mod cli;
 mod evtx_file;
 mod output_format;
 mod output_formatter;
-#[macro_use]
-mod macros;
 
 fn main() -> Result<()> {
     let cli = Cli::parse_cli();
@@ -22,16 +19,21 @@ fn main() -> Result<()> {
         );
     }
 
-    for input in cli.evtx_files().iter() {
-        let file = EvtxFile::from(input);
-        match cli.format() {
-            output_format::OutputFormat::Json => {
-                file.print_records(JsonOutputFormatter, !cli.strict())?
-            }
-            output_format::OutputFormat::Bodyfile => {
-                file.print_records(BodyfileOutputFormatter, !cli.strict())?
+    cli.output().write_with(|output| {
+        for input in cli.evtx_files().iter() {
+            let file = EvtxFile::from(input);
+            match cli.format() {
+                output_format::OutputFormat::Json => {
+                    file.print_records(output, JsonOutputFormatter, !cli.strict())?
+                }
+                output_format::OutputFormat::Bodyfile => file.print_records(
+                    output,
+                    BodyfileOutputFormatter::default()
+                        .with_descriptive_names(*cli.descriptive_names()),
+                    !cli.strict(),
+                )?,
             }
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
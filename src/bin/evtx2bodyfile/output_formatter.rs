@@ -1,12 +1,21 @@
+use dfir_toolkit::evtx::BfData;
 use evtx::SerializedEvtxRecord;
 use serde_json::Value;
 
-use crate::bf_data::BfData;
-
 #[derive(Default)]
 pub(crate) struct JsonOutputFormatter;
 #[derive(Default)]
-pub(crate) struct BodyfileOutputFormatter;
+pub(crate) struct BodyfileOutputFormatter {
+    descriptive_names: bool,
+}
+
+impl BodyfileOutputFormatter {
+    /// uses human readable descriptions as names of the bodyfile lines
+    pub fn with_descriptive_names(mut self, descriptive_names: bool) -> Self {
+        self.descriptive_names = descriptive_names;
+        self
+    }
+}
 
 pub(crate) trait OutputFormatter {
     fn record_to_string(&self, record: &SerializedEvtxRecord<Value>) -> anyhow::Result<String>;
@@ -21,7 +30,8 @@ impl OutputFormatter for JsonOutputFormatter {
 
 impl OutputFormatter for BodyfileOutputFormatter {
     fn record_to_string(&self, record: &SerializedEvtxRecord<Value>) -> anyhow::Result<String> {
-        let bf_data = BfData::try_from(record)?;
+        let mut bf_data = BfData::try_from(record)?;
+        bf_data.set_descriptive_name(self.descriptive_names);
         bf_data.try_into_mactime()
     }
 }
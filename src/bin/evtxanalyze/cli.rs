@@ -1,7 +1,7 @@
-use std::{io::stdout, path::PathBuf};
+use std::{io::Write, path::PathBuf};
 
 use clap::{Parser, Subcommand, ValueEnum};
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 use super::sessions::SessionStore;
@@ -64,12 +64,15 @@ pub(crate) struct Cli {
     #[command(subcommand)]
     pub(crate) command: Command,
 
+    #[command(flatten)]
+    pub(crate) output: OutputArgs,
+
     #[command(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
 
 impl Cli {
-    pub fn display_single_session(&self) -> anyhow::Result<()> {
+    pub fn display_single_session(&self, output: &mut dyn Write) -> anyhow::Result<()> {
         match &self.command {
             Command::Session {
                 evtx_files_dir,
@@ -79,7 +82,7 @@ impl Cli {
                 match sessions.find_session(session_id) {
                     None => log::error!("no value found for session id {session_id}"),
                     Some(session) => {
-                        let mut csv_writer = csv::Writer::from_writer(stdout());
+                        let mut csv_writer = csv::Writer::from_writer(output);
                         for event in session.iter_events() {
                             event.to_csv(&mut csv_writer)?;
                         }
@@ -92,7 +95,7 @@ impl Cli {
             _ => unreachable!(),
         }
     }
-    pub fn display_sessions(&self) -> anyhow::Result<()> {
+    pub fn display_sessions(&self, output: &mut dyn Write) -> anyhow::Result<()> {
         match &self.command {
             Command::Sessions {
                 evtx_files_dir,
@@ -100,7 +103,7 @@ impl Cli {
             } => {
                 let sessions = SessionStore::import(evtx_files_dir, *include_anonymous)?;
 
-                let mut csv_writer = csv::Writer::from_writer(stdout());
+                let mut csv_writer = csv::Writer::from_writer(output);
                 for session in sessions {
                     session.into_csv(&mut csv_writer)?;
                 }
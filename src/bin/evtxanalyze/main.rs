@@ -9,10 +9,10 @@ mod sessions;
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse_cli();
 
-    match &cli.command {
+    cli.output.write_with(|output| match &cli.command {
         //TODO: move `display_pstree` into `impl Cli`
-        Command::PsTree { .. } => display_pstree(&cli),
-        Command::Sessions { .. } => cli.display_sessions(),
-        Command::Session { .. } => cli.display_single_session(),
-    }
+        Command::PsTree { .. } => display_pstree(&cli, output),
+        Command::Sessions { .. } => cli.display_sessions(output),
+        Command::Session { .. } => cli.display_single_session(output),
+    })
 }
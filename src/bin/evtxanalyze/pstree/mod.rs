@@ -3,6 +3,7 @@ pub mod unique_pid;
 use std::{
     cell::RefCell,
     collections::{BTreeMap, HashMap, HashSet},
+    io::Write,
     rc::{Rc, Weak},
 };
 
@@ -16,7 +17,7 @@ use crate::{cli::{Command, Format}, pstree::unique_pid::UniquePid};
 
 use super::Cli;
 
-pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
+pub(crate) fn display_pstree(cli: &Cli, output: &mut dyn Write) -> anyhow::Result<()> {
     match &cli.command {
         Command::PsTree {
             username,
@@ -107,7 +108,7 @@ pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
                         .collect();
 
                     let procs_as_json = json!(root_processes);
-                    println!("{}", serde_json::to_string_pretty(&procs_as_json)?);
+                    writeln!(output, "{}", serde_json::to_string_pretty(&procs_as_json)?)?;
                 }
 
                 Format::Csv => unimplemented!(),
@@ -122,7 +123,7 @@ pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
                             (timestamp, proc)
                         })
                         .collect();
-                    display_markdown(&root_processes, 0);
+                    display_markdown(&root_processes, 0, output)?;
                 }
 
                 Format::LaTeX => {
@@ -135,7 +136,7 @@ pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
                             (timestamp, proc)
                         })
                         .collect();
-                    display_latex(&root_processes);
+                    display_latex(&root_processes, output)?;
                 }
 
                 Format::Dot => {
@@ -148,10 +149,10 @@ pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
                             (timestamp, proc)
                         })
                         .collect();
-                    println!("digraph {{");
-                    println!("rankdir=\"LR\";");
-                    display_dot(&root_processes);
-                    println!("}}");
+                    writeln!(output, "digraph {{")?;
+                    writeln!(output, "rankdir=\"LR\";")?;
+                    display_dot(&root_processes, output)?;
+                    writeln!(output, "}}")?;
                 }
             }
 
@@ -161,18 +162,26 @@ pub(crate) fn display_pstree(cli: &Cli) -> anyhow::Result<()> {
     }
 }
 
-fn display_markdown(procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>, indent: usize) {
+fn display_markdown(
+    procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>,
+    indent: usize,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
     for proc in procs.values() {
         if let Some(proc) = proc.upgrade() {
-            println!("{}- {}", " ".repeat(indent), proc.borrow());
-            display_markdown(&proc.borrow().children, indent + 2);
+            writeln!(output, "{}- {}", " ".repeat(indent), proc.borrow())?;
+            display_markdown(&proc.borrow().children, indent + 2, output)?;
         }
     }
+    Ok(())
 }
 
-fn display_latex(procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>) {
+fn display_latex(
+    procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
     if !procs.is_empty() {
-        println!("\\begin{{enumerate}}");
+        writeln!(output, "\\begin{{enumerate}}")?;
         for proc in procs.values() {
             if let Some(proc) = proc.upgrade() {
                 let p = proc.borrow();
@@ -180,34 +189,41 @@ fn display_latex(procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>) {
                 let filename = &p.new_process_name;
                 let timestamp = p.timestamp.format("%FT%T");
                 let user = p.subject_user_name.replace('_', "\\_").replace('$', "\\$");
-                println!("\\item[\\texttt{{{pid}}}] \\filename{{{filename}}}, gestartet: \\ts{{{timestamp}}}, Benutzer: \\username{{{user}}}",);
-                display_latex(&proc.borrow().children);
+                writeln!(output, "\\item[\\texttt{{{pid}}}] \\filename{{{filename}}}, gestartet: \\ts{{{timestamp}}}, Benutzer: \\username{{{user}}}",)?;
+                display_latex(&proc.borrow().children, output)?;
             }
         }
-        println!("\\end{{enumerate}}");
+        writeln!(output, "\\end{{enumerate}}")?;
     }
+    Ok(())
 }
 
-fn display_dot(procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>) {
+fn display_dot(
+    procs: &BTreeMap<DateTime<Utc>, Weak<RefCell<Process>>>,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
     for proc in procs.values() {
         if let Some(proc) = proc.upgrade() {
             let p = proc.borrow();
-            dot_display_process(&p);
-            println!(
+            dot_display_process(&p, output)?;
+            writeln!(
+                output,
                 "p{} -> p{} [label=\"{}\"]",
                 p.process_id,
                 p.new_process_id,
                 p.timestamp.format("%FT%T")
-            );
-            display_dot(&proc.borrow().children);
+            )?;
+            display_dot(&proc.borrow().children, output)?;
         }
     }
+    Ok(())
 }
 
-fn dot_display_process(process: &Process) {
-    println!(
+fn dot_display_process(process: &Process, output: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        output,
         "p{} [label=<<FONT FACE=\"Courier\">{}</FONT>>, shape=\"box\"];",
         process.new_process_id,
         process.new_process_name.replace('\\', "\\\\")
-    );
+    )
 }
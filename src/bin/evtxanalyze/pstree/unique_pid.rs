@@ -36,6 +36,8 @@ impl Ord for UniquePid {
         self.timestamp.cmp(&other.timestamp)
     }
 }
+// processes with different pids are not comparable
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for UniquePid {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         if self.pid != other.pid {
@@ -1,5 +1,5 @@
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 use crate::output_format::OutputFormat;
@@ -31,6 +31,9 @@ pub (crate) struct Cli {
     #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Xml)]
     pub (crate) format: OutputFormat,
 
+    #[clap(flatten)]
+    pub (crate) output: OutputArgs,
+
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 }
@@ -19,11 +19,11 @@ use record_list_formatter::RecordListFormatter;
 fn main() -> Result<()> {
     let cli = Cli::parse_cli();
 
-    let path = PathBuf::try_from(&cli.evtx_file)?;
+    let path = PathBuf::from(&cli.evtx_file);
 
     let parser = EvtxParser::from_path(path)?;
 
-    match cli.format {
+    cli.output.write_with(|output| match cli.format {
         OutputFormat::Json => {
             let (record_ids, records) = if let Some(filter_id) = cli.id {
                 serde_json::Value::filter_by_id(parser, filter_id)
@@ -32,7 +32,7 @@ fn main() -> Result<()> {
                 let max = cli.max.unwrap_or(u64::MAX);
                 serde_json::Value::filter_by_range(parser, min, max)
             };
-            serde_json::Value::display_results(record_ids, records, &cli);
+            serde_json::Value::display_results(record_ids, records, &cli, output)
         }
         OutputFormat::Xml => {
             let (record_ids, records) = if let Some(filter_id) = cli.id {
@@ -42,8 +42,7 @@ fn main() -> Result<()> {
                 let max = cli.max.unwrap_or(u64::MAX);
                 String::filter_by_range(parser, min, max)
             };
-            String::display_results(record_ids, records, &cli);
+            String::display_results(record_ids, records, &cli, output)
         }
-    }
-    Ok(())
+    })
 }
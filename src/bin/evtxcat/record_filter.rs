@@ -8,7 +8,7 @@ use crate::unfiltered::Unfiltered;
 pub (crate) trait RecordFilter: Sized {
     type ReaderType: Read + Seek;
 
-    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<Self>;
+    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<'_, Self>;
 
     fn filter_by_id(
         mut parser: EvtxParser<Self::ReaderType>,
@@ -57,7 +57,7 @@ pub (crate) trait RecordFilter: Sized {
 impl RecordFilter for serde_json::Value {
     type ReaderType = File;
 
-    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<Self> {
+    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<'_, Self> {
         Unfiltered {
             inner: Box::new(parser.records_json_value()),
         }
@@ -67,7 +67,7 @@ impl RecordFilter for serde_json::Value {
 impl RecordFilter for String {
     type ReaderType = File;
 
-    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<Self> {
+    fn unfiltered(parser: &mut EvtxParser<Self::ReaderType>) -> Unfiltered<'_, Self> {
         Unfiltered {
             inner: Box::new(parser.records()),
         }
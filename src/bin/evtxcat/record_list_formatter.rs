@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::io::Write;
 
+use anyhow::Result;
 use colored_json::to_colored_json_auto;
 use evtx::SerializedEvtxRecord;
 use term_table::{row::Row, table_cell::TableCell};
@@ -7,17 +9,21 @@ use term_table::{row::Row, table_cell::TableCell};
 use crate::cli::Cli;
 
 pub (crate) trait RecordListFormatter: Sized {
-    fn format(record: &SerializedEvtxRecord<Self>) -> String;
+    /// formats a record; colors are only used if `colored` is `true`
+    fn format(record: &SerializedEvtxRecord<Self>, colored: bool) -> String;
 
     fn display_results(
         record_ids: Vec<u64>,
         records: HashMap<u64, SerializedEvtxRecord<Self>>,
         cli: &Cli,
-    ) {
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        // colors are not written into files
+        let colored = cli.output.output.is_none();
         if !cli.show_table {
             for id in record_ids.into_iter() {
                 let record = &records[&id];
-                println!("{}", Self::format(record));
+                writeln!(output, "{}", Self::format(record, colored))?;
             }
         } else {
             let mut table = term_table::Table::new();
@@ -29,22 +35,27 @@ pub (crate) trait RecordListFormatter: Sized {
                 let record = &records[&id];
                 table.add_row(Row::new(vec![
                     TableCell::new(id),
-                    TableCell::new(Self::format(record)),
+                    TableCell::new(Self::format(record, colored)),
                 ]));
             }
-            println!("{}", table.render());
+            writeln!(output, "{}", table.render())?;
         }
+        Ok(())
     }
 }
 
 impl RecordListFormatter for String {
-    fn format(record: &SerializedEvtxRecord<Self>) -> String {
+    fn format(record: &SerializedEvtxRecord<Self>, _colored: bool) -> String {
         record.data.clone()
     }
 }
 
 impl RecordListFormatter for serde_json::Value {
-    fn format(record: &SerializedEvtxRecord<Self>) -> String {
-        to_colored_json_auto(&record.data).unwrap()
+    fn format(record: &SerializedEvtxRecord<Self>, colored: bool) -> String {
+        if colored {
+            to_colored_json_auto(&record.data).unwrap()
+        } else {
+            serde_json::to_string_pretty(&record.data).unwrap()
+        }
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use clap::{Parser, ValueEnum};
 
-use dfir_toolkit::common::{Rfc3339Datetime, HasVerboseFlag};
+use dfir_toolkit::common::{Rfc3339Datetime, HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 use regex::Regex;
 
@@ -85,6 +85,9 @@ pub(crate) struct Cli {
     #[clap(short('B'), long("hide-base-fields"), default_value_t=false)]
     pub (crate) hide_base_fields: bool,
 
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 }
@@ -3,7 +3,7 @@ mod highlighted_string;
 mod system_field;
 
 use std::{
-    io::{Read, Seek},
+    io::{Read, Seek, Write},
     path::PathBuf,
 };
 
@@ -34,37 +34,44 @@ impl EvtxLs {
     }
 
     fn run(self) -> Result<()> {
-        let mut records = Vec::new();
+        self.cli.output.write_with(|output| {
+            let mut records = Vec::new();
 
-        for f_name in self.cli.evtx_files.iter() {
-            let path = PathBuf::try_from(&f_name)?;
+            for f_name in self.cli.evtx_files.iter() {
+                let path = PathBuf::from(&f_name);
 
-            let settings = ParserSettings::default().num_threads(0);
-            let parser = EvtxParser::from_path(path)?.with_configuration(settings);
+                let settings = ParserSettings::default().num_threads(0);
+                let parser = EvtxParser::from_path(path)?.with_configuration(settings);
 
-            records.extend(self.read_records(parser)?);
-        }
+                records.extend(self.read_records(parser, output)?);
+            }
 
-        match self.cli.sort_order {
-            SortOrder::Storage => assert!(records.is_empty()),
-            SortOrder::RecordId => {
-                records.sort_by(|a, b| a.event_record_id.cmp(&b.event_record_id))
+            match self.cli.sort_order {
+                SortOrder::Storage => assert!(records.is_empty()),
+                SortOrder::RecordId => records.sort_by_key(|r| r.event_record_id),
+                SortOrder::Time => records.sort_by_key(|r| r.timestamp),
             }
-            SortOrder::Time => records.sort_by(|a, b| a.timestamp.cmp(&b.timestamp)),
-        }
 
-        if !records.is_empty() {
-            for record in records.into_iter() {
-                self.display_record(&record)?;
+            if !records.is_empty() {
+                for record in records.into_iter() {
+                    self.display_record(&record, output)?;
+                }
             }
-        }
 
-        Ok(())
+            Ok(())
+        })
     }
 
-    fn read_records<T: Read + Seek>(&self, mut parser: EvtxParser<T>) -> Result<Vec<SerializedEvtxRecord<Value>>> {
+    fn read_records<T: Read + Seek>(
+        &self,
+        mut parser: EvtxParser<T>,
+        output: &mut dyn Write,
+    ) -> Result<Vec<SerializedEvtxRecord<Value>>> {
         if self.cli.display_colors {
             SHOULD_COLORIZE.set_override(true);
+        } else if self.cli.output.output.is_some() {
+            // colors are not written into files, unless they are requested explicitly
+            SHOULD_COLORIZE.set_override(false);
         }
 
         let mut records = Vec::new();
@@ -100,7 +107,7 @@ impl EvtxLs {
                     }
 
                     if matches!(self.cli.sort_order, SortOrder::Storage) {
-                        self.display_record(&record)?
+                        self.display_record(&record, output)?
                     } else {
                         records.push(record);
                     }
@@ -111,7 +118,11 @@ impl EvtxLs {
         Ok(records)
     }
 
-    fn display_record(&self, record: &SerializedEvtxRecord<Value>) -> Result<()> {
+    fn display_record(
+        &self,
+        record: &SerializedEvtxRecord<Value>,
+        output: &mut dyn Write,
+    ) -> Result<()> {
         let system_fields = if self.cli.hide_base_fields {
             "".to_owned()
         } else {
@@ -145,7 +156,7 @@ impl EvtxLs {
             .unwrap_or_else(|| "".to_owned())
             .replace("\\u001b", "\u{001b}");
 
-        let output = match self.cli.delimiter {
+        let line = match self.cli.delimiter {
             None => format!(
                 "{} {system_fields}{event_data}",
                 record.timestamp.format("%FT%T%.3f")
@@ -157,7 +168,7 @@ impl EvtxLs {
         }
         .normal();
 
-        println!("{output}");
+        writeln!(output, "{line}")?;
 
         Ok(())
     }
@@ -1,5 +1,5 @@
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 
@@ -18,6 +18,9 @@ pub (crate) struct Cli {
     #[clap(short = 'N', long, default_value_t = 5)]
     pub (crate) negative_tolerance: u32,
 
+    #[clap(flatten)]
+    pub (crate) output: OutputArgs,
+
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
 }
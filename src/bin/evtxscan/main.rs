@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, io::Write, path::PathBuf};
 
 use anyhow::Result;
 use chrono::Duration;
@@ -16,7 +16,7 @@ fn main() -> Result<()> {
     let mut record_ids: Vec<EventId> = Vec::new();
     let mut records: HashMap<EventId, SerializedEvtxRecord<serde_json::Value>> = HashMap::new();
 
-    let path = PathBuf::try_from(&cli.evtx_file)?;
+    let path = PathBuf::from(&cli.evtx_file);
 
     let mut parser = EvtxParser::from_path(path)?;
     for record in parser.records_json_value() {
@@ -51,16 +51,26 @@ fn main() -> Result<()> {
         ranges.push(range)
     }
     ranges.sort();
-    print_ranges(&ranges, &records, &cli);
-    Ok(())
+    cli.output
+        .write_with(|output| print_ranges(&ranges, &records, &cli, output))
 }
 
 fn print_ranges(
     ranges: &[Range],
     records: &HashMap<EventId, SerializedEvtxRecord<serde_json::Value>>,
     cli: &Cli,
-) {
+    output: &mut dyn Write,
+) -> Result<()> {
     let allowed_bias = Duration::seconds(cli.negative_tolerance.into());
+
+    // colors are not written into files
+    let to_json = |value: &serde_json::Value| {
+        if cli.output.output.is_none() {
+            to_colored_json_auto(value).unwrap()
+        } else {
+            serde_json::to_string_pretty(value).unwrap()
+        }
+    };
     if cli.show_records {
         for range in ranges.iter() {
             let mut table = term_table::Table::new();
@@ -87,11 +97,11 @@ fn print_ranges(
             let first_record = &records[range.begin()];
             let last_record = &records[range.end()];
             table.add_row(Row::new(vec![
-                TableCell::new(to_colored_json_auto(&first_record.data).unwrap()),
-                TableCell::new(to_colored_json_auto(&last_record.data).unwrap()),
+                TableCell::new(to_json(&first_record.data)),
+                TableCell::new(to_json(&last_record.data)),
             ]));
 
-            println!("{}", table.render());
+            writeln!(output, "{}", table.render())?;
 
             let mut table = term_table::Table::new();
             if let Some(size) = termsize::get() {
@@ -123,43 +133,46 @@ fn print_ranges(
                         let record2 = &records[current_event];
                         table.add_row(Row::new(vec![
                             TableCell::new(""),
-                            TableCell::new(to_colored_json_auto(&record1.data).unwrap()),
-                            TableCell::new(to_colored_json_auto(&record2.data).unwrap()),
+                            TableCell::new(to_json(&record1.data)),
+                            TableCell::new(to_json(&record2.data)),
                         ]));
                     }
                 }
                 last_event = Some(current_event);
             }
-            println!("{}", table.render());
+            writeln!(output, "{}", table.render())?;
         }
     } else {
         for range in ranges.iter() {
-            println!("RANGE: {}", range);
-            println!("  {} events", range.len());
+            writeln!(output, "RANGE: {}", range)?;
+            writeln!(output, "  {} events", range.len())?;
 
             let mut last_event: Option<&EventId> = None;
             for current_event in range.events() {
                 if let Some(event) = last_event {
                     if *current_event.timestamp() + allowed_bias < *event.timestamp() {
-                        println!("  time skew detected:");
-                        println!(
+                        writeln!(output, "  time skew detected:")?;
+                        writeln!(
+                            output,
                             "    last event            {} occurred at {},",
                             event.event_record_id(),
                             event.timestamp().format("%FT%T")
-                        );
-                        println!(
+                        )?;
+                        writeln!(
+                            output,
                             "    but the current event {} occurred at {}",
                             current_event.event_record_id(),
                             current_event.timestamp().format("%FT%T")
-                        );
+                        )?;
 
                         let duration = *current_event.timestamp() - *event.timestamp();
-                        println!("    this is a duration of {}", duration);
-                        println!();
+                        writeln!(output, "    this is a duration of {}", duration)?;
+                        writeln!(output)?;
                     }
                 }
                 last_event = Some(current_event);
             }
         }
     }
+    Ok(())
 }
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 
@@ -23,6 +23,9 @@ pub (crate) struct Cli {
     /// output as bodyfile format
     #[clap(short('b'))]
     pub (crate) print_bodyfile: bool,
+
+    #[clap(flatten)]
+    pub (crate) output: OutputArgs,
 }
 
 impl HasVerboseFlag for Cli {
@@ -5,6 +5,7 @@ use dfir_toolkit::common::bodyfile::Bodyfile3Line;
 use indicatif::{ProgressBar, ProgressStyle};
 use nt_hive2::*;
 use std::fs::File;
+use std::io::Write;
 use std::time::Duration;
 
 use crate::regtreebuilder::RegTreeBuilder;
@@ -41,21 +42,29 @@ impl HiveScanApplication {
 
         assert!(self.hive.is_none());
 
-        for node in builder.root_nodes() {
-            if node.borrow().offset() == &self.root_offset {
-                // this is the root entry, which we don't print by itself
-                for grandchild in node.borrow().children() {
-                    self.print_entry("", &grandchild, false);
+        self.cli.output.write_with(|output| {
+            for node in builder.root_nodes() {
+                if node.borrow().offset() == &self.root_offset {
+                    // this is the root entry, which we don't print by itself
+                    for grandchild in node.borrow().children() {
+                        self.print_entry("", &grandchild, false, output)?;
+                    }
+                } else {
+                    let parent = format!("/$Orphaned/{:x}", node.borrow().nk().parent.0);
+                    self.print_entry(&parent, &node.borrow(), false, output)?;
                 }
-            } else {
-                let parent = format!("/$Orphaned/{:x}", node.borrow().nk().parent.0);
-                self.print_entry(&parent, &node.borrow(), false);
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 
-    fn print_entry(&self, path: &str, entry: &RegTreeEntry, force_print: bool) {
+    fn print_entry(
+        &self,
+        path: &str,
+        entry: &RegTreeEntry,
+        force_print: bool,
+        output: &mut dyn Write,
+    ) -> Result<()> {
         let path = format!("{}/{}", path, entry.nk().name());
 
         if self.cli.print_bodyfile {
@@ -69,26 +78,29 @@ impl HiveScanApplication {
                 .with_owned_name(bf_name)
                 .with_inode(&format!("{:x}", entry.offset().0))
                 .with_ctime(entry.nk().timestamp().timestamp());
-            println!("{}", bf_line);
+            writeln!(output, "{}", bf_line)?;
         } else if entry.is_deleted() || force_print {
-            println!(
+            writeln!(
+                output,
                 "[{}]; last change at {}, found at offset 0x{:x}",
                 path,
                 entry.nk().timestamp().to_rfc3339(),
                 entry.offset().0 + BASEBLOCK_SIZE as u32
-            );
-            self.print_values_of(entry);
-            println!();
+            )?;
+            self.print_values_of(entry, output)?;
+            writeln!(output)?;
         }
 
         for child in entry.children() {
-            self.print_entry(&path, &child, entry.is_deleted());
+            self.print_entry(&path, &child, entry.is_deleted(), output)?;
         }
+        Ok(())
     }
 
-    fn print_values_of(&self, entry: &RegTreeEntry) {
+    fn print_values_of(&self, entry: &RegTreeEntry, output: &mut dyn Write) -> Result<()> {
         for value in entry.nk().values() {
-            println!("\"{}\" = {}", value.name(), value.value());
+            writeln!(output, "\"{}\" = {}", value.name(), value.value())?;
         }
+        Ok(())
     }
 }
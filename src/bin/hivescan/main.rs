@@ -38,7 +38,7 @@ fn main() -> Result<()> {
             app.run()
         }
         Err(why) => {
-            eprintln!("unable to open '{}': {}", cli.hive_file, why);
+            log::error!("unable to open '{}': {}", cli.hive_file, why);
             std::process::exit(-1);
         }
     }
@@ -71,7 +71,7 @@ impl RegTreeBuilder {
         me
     }
 
-    pub fn root_nodes(&self) -> RootNodes {
+    pub fn root_nodes(&self) -> RootNodes<'_> {
         RootNodes {
             values: self.subtrees.values(),
         }
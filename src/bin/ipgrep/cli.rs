@@ -1,7 +1,7 @@
-use std::{net::Ipv4Addr, path::PathBuf, io::BufRead};
+use std::{net::Ipv4Addr, path::PathBuf, io::{BufRead, Write}};
 
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 use crate::{ip_filter::IpFilter, format_ipv4};
@@ -49,6 +49,9 @@ pub(crate) struct Cli {
     #[clap(short('c'), long("colors"))]
     pub(crate) display_colors: bool,
 
+    #[command(flatten)]
+    pub(crate) output: OutputArgs,
+
     #[command(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
@@ -60,7 +63,7 @@ impl HasVerboseFlag for Cli {
 }
 
 impl Cli {
-    pub (crate) fn ipgrep<R: BufRead>(&self, mut reader: R) -> anyhow::Result<()> {
+    pub (crate) fn ipgrep<R: BufRead>(&self, mut reader: R, output: &mut dyn Write) -> anyhow::Result<()> {
         let mut line = String::new();
         loop {
             line.clear();
@@ -74,7 +77,7 @@ impl Cli {
                         &self.ignore_ips[..],
                         &line,
                     ) {
-                        print!("{hline}");
+                        write!(output, "{hline}")?;
                     }
                 }
             }
@@ -87,7 +87,7 @@ pub fn format_ipv4(
         }
     }
 
-    if last_end < line.as_bytes().len() {
+    if last_end < line.len() {
         result.push_str(&line[last_end..]);
     }
 
@@ -1,10 +1,9 @@
 use anyhow::Result;
 use cli::Cli;
 use colored::control::SHOULD_COLORIZE;
-use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
 use std::{fs::File, io::BufReader};
 
-use dfir_toolkit::common::FancyParser;
+use dfir_toolkit::common::{expand_glob_paths, FancyParser};
 
 mod cli;
 mod format_ipv4;
@@ -15,25 +14,19 @@ use crate::format_ipv4::format_ipv4;
 fn main() -> Result<()> {
     let app = Cli::parse_cli();
 
-    TermLogger::init(
-        app.verbose.log_level_filter(),
-        Config::default(),
-        TerminalMode::Stderr,
-        ColorChoice::Auto,
-    )?;
-
     if app.display_colors {
         SHOULD_COLORIZE.set_override(true);
     }
 
-    if app.file.is_empty() {
-        app.ipgrep(std::io::stdin().lock())?;
-    } else {
-        for file in app.file.iter() {
-            let f = File::open(file)?;
-            app.ipgrep(BufReader::new(f))?;
+    app.output.write_with(|output| {
+        if app.file.is_empty() {
+            app.ipgrep(std::io::stdin().lock(), output)?;
+        } else {
+            for file in expand_glob_paths(&app.file)? {
+                let f = File::open(file)?;
+                app.ipgrep(BufReader::new(f), output)?;
+            }
         }
-    }
-
-    Ok(())
+        Ok(())
+    })
 }
@@ -0,0 +1,25 @@
+use clap::{Parser, ValueHint};
+use clio::Input;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
+use log::LevelFilter;
+
+/// creates bodyfile from Windows .lnk files
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// names of the lnk files
+    #[clap(value_hint=ValueHint::FilePath, num_args=1..)]
+    pub(crate) lnk_files: Vec<Input>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
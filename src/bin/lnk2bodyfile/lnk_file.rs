@@ -0,0 +1,198 @@
+use std::fmt::Display;
+use std::io::{Read, Seek, SeekFrom};
+
+use anyhow::Result;
+use binread::{BinRead, BinReaderExt};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::unix_from_filetime;
+
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const IS_UNICODE: u32 = 0x0000_0080;
+
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x0000_0001;
+const COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX: u32 = 0x0000_0002;
+
+/// <https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink/c3376b21-0931-45e4-b2fc-a48ac0e60d15>
+#[derive(BinRead)]
+#[br(little, magic = 0x4cu32)]
+struct ShellLinkHeader {
+    #[br(assert(_link_clsid == LINK_CLSID))]
+    _link_clsid: [u8; 16],
+    link_flags: u32,
+    _file_attributes: u32,
+    creation_time: u64,
+    access_time: u64,
+    write_time: u64,
+    file_size: u32,
+    _icon_index: i32,
+    _show_command: u32,
+    _hot_key: u16,
+    _reserved: [u8; 10],
+}
+
+#[derive(BinRead)]
+#[br(little)]
+struct LinkInfoHeader {
+    link_info_size: u32,
+    _link_info_header_size: u32,
+    link_info_flags: u32,
+    volume_id_offset: u32,
+    local_base_path_offset: u32,
+    common_network_relative_link_offset: u32,
+    common_path_suffix_offset: u32,
+}
+
+#[derive(BinRead)]
+#[br(little)]
+struct VolumeId {
+    _volume_id_size: u32,
+    drive_type: u32,
+    drive_serial_number: u32,
+}
+
+struct DriveType(u32);
+
+impl Display for DriveType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            1 => write!(f, "no root dir"),
+            2 => write!(f, "removable"),
+            3 => write!(f, "fixed"),
+            4 => write!(f, "remote"),
+            5 => write!(f, "cdrom"),
+            6 => write!(f, "ramdisk"),
+            _ => write!(f, "unknown"),
+        }
+    }
+}
+
+pub(crate) struct LnkFile {
+    header: ShellLinkHeader,
+    volume_id: Option<VolumeId>,
+    target: Option<String>,
+    arguments: Option<String>,
+}
+
+impl LnkFile {
+    pub fn parse<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let header: ShellLinkHeader = reader.read_le()?;
+
+        if header.link_flags & HAS_LINK_TARGET_ID_LIST != 0 {
+            let id_list_size: u16 = reader.read_le()?;
+            reader.seek(SeekFrom::Current(id_list_size.into()))?;
+        }
+
+        let mut volume_id = None;
+        let mut target = None;
+        if header.link_flags & HAS_LINK_INFO != 0 {
+            let start = reader.stream_position()?;
+            let link_info: LinkInfoHeader = reader.read_le()?;
+
+            let suffix = if link_info.common_path_suffix_offset != 0 {
+                read_cstring(reader, start + u64::from(link_info.common_path_suffix_offset))?
+            } else {
+                String::new()
+            };
+
+            if link_info.link_info_flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+                reader.seek(SeekFrom::Start(start + u64::from(link_info.volume_id_offset)))?;
+                volume_id = Some(reader.read_le()?);
+                let base_path =
+                    read_cstring(reader, start + u64::from(link_info.local_base_path_offset))?;
+                target = Some(format!("{base_path}{suffix}"));
+            } else if link_info.link_info_flags & COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX != 0 {
+                let cnrl_start = start + u64::from(link_info.common_network_relative_link_offset);
+                reader.seek(SeekFrom::Start(cnrl_start + 8))?;
+                let net_name_offset: u32 = reader.read_le()?;
+                let net_name = read_cstring(reader, cnrl_start + u64::from(net_name_offset))?;
+                target = Some(format!("{net_name}\\{suffix}"));
+            }
+
+            reader.seek(SeekFrom::Start(start + u64::from(link_info.link_info_size)))?;
+        }
+
+        let is_unicode = header.link_flags & IS_UNICODE != 0;
+        let mut relative_path = None;
+        let mut arguments = None;
+        for flag in [HAS_NAME, HAS_RELATIVE_PATH, HAS_WORKING_DIR, HAS_ARGUMENTS] {
+            if header.link_flags & flag != 0 {
+                let value = read_string_data(reader, is_unicode)?;
+                match flag {
+                    HAS_RELATIVE_PATH => relative_path = Some(value),
+                    HAS_ARGUMENTS => arguments = Some(value),
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(Self {
+            header,
+            volume_id,
+            target: target.or(relative_path),
+            arguments,
+        })
+    }
+
+    pub fn to_bodyfile(&self, lnk_path: &str) -> Bodyfile3Line {
+        let mut name = format!(
+            "{lnk_path} -> {}",
+            self.target.as_deref().unwrap_or("<unknown target>")
+        );
+        if let Some(arguments) = self.arguments.as_ref().filter(|a| !a.is_empty()) {
+            name.push(' ');
+            name.push_str(arguments);
+        }
+        if let Some(volume_id) = self.volume_id.as_ref() {
+            name.push_str(&format!(
+                " (volume serial: {:04X}-{:04X}, drive type: {})",
+                volume_id.drive_serial_number >> 16,
+                volume_id.drive_serial_number & 0xffff,
+                DriveType(volume_id.drive_type)
+            ));
+        }
+
+        Bodyfile3Line::new()
+            .with_owned_name(name)
+            .with_size(self.header.file_size.into())
+            .with_atime(unix_from_filetime(self.header.access_time))
+            .with_mtime(unix_from_filetime(self.header.write_time))
+            .with_crtime(unix_from_filetime(self.header.creation_time))
+    }
+}
+
+fn read_cstring<R: Read + Seek>(reader: &mut R, offset: u64) -> Result<String> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::new();
+    loop {
+        let b: u8 = reader.read_le()?;
+        if b == 0 {
+            break;
+        }
+        bytes.push(b);
+    }
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn read_string_data<R: Read + Seek>(reader: &mut R, is_unicode: bool) -> Result<String> {
+    let count: u16 = reader.read_le()?;
+    if is_unicode {
+        let mut chars = vec![0u16; count.into()];
+        for c in chars.iter_mut() {
+            *c = reader.read_le()?;
+        }
+        Ok(String::from_utf16_lossy(&chars))
+    } else {
+        let mut bytes = vec![0u8; count.into()];
+        reader.read_exact(&mut bytes)?;
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
@@ -0,0 +1,39 @@
+use std::io::{Cursor, Read};
+
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use lnk_file::LnkFile;
+
+mod cli;
+mod lnk_file;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for mut input in cli.lnk_files.into_iter() {
+            let mut buffer = Vec::new();
+            input.read_to_end(&mut buffer)?;
+
+            match LnkFile::parse(&mut Cursor::new(buffer)) {
+                Ok(lnk) => writeln!(
+                    output,
+                    "{}",
+                    lnk.to_bodyfile(&input.path().to_string_lossy())
+                )?,
+                Err(why) => {
+                    log::error!("unable to parse '{}': {why}", input.path().display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
@@ -1,14 +1,60 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use chrono_tz::Tz;
+use anyhow::{bail, Context};
 use clap::ValueEnum;
 use clio::Input;
+use dfir_toolkit::common::bodyfile::{
+    strip_checksum_column, Bodyfile3Line, EntryType, Filesystem, MacbFlags, OnUnparseable,
+    TimestampKind,
+};
+use dfir_toolkit::common::{ExitCode, OutputArgs, TimeRange};
+use regex::Regex;
 use strum_macros::Display;
 
-use super::bodyfile::{BodyfileDecoder, BodyfileReader, BodyfileSorter};
-use super::cli::Cli;
+use super::bodyfile::{
+    AccessStorm, BodyfileDecoder, BodyfileReader, BodyfileSorter, CacheFile, Mactime2Writer,
+    ParseErrorCounts, SessionGrouper, SourceFingerprint,
+};
+use super::cli::{Cli, Mactime2Command};
 use super::error::MactimeError;
-use super::filter::{Consumer, Joinable, Provider, RunOptions, Sorter};
-use super::output::{CsvOutput, JsonSorter, TxtOutput};
-use super::stream::StreamReader;
+use super::filter::{
+    AdsFilter, CategoryFilter, Consumer, DeletedFilter, EventFilter, Joinable, PerFileEvent,
+    Provider, RunOptions, Sorter,
+};
+use super::business_hours::OutsideHoursFilter;
+use super::case_uco::CaseUcoExport;
+use super::expression::Expression;
+use super::gaps::Gaps;
+use super::correlate::{Correlation, CorrelationKey, HostBodyfile};
+use super::hashes::HashManifest;
+use super::high_water_mark::HighWaterMark;
+use super::histogram::Histogram;
+use super::stats::BodyfileStats;
+use super::summary::DirectorySummary;
+use super::profile::UserProfiles;
+use super::directory_graph::DirectoryGraph;
+use super::largest::LargestFiles;
+use super::registry_timezone::timezone_from_registry;
+use super::sample::Sample;
+use super::verify::HashVerifier;
+use super::watch::IntakeDirectory;
+use super::output::{
+    CountOutput, CsvDialect, CsvOutput, GeoJsonOutput, HtmlOutput, JsonPerFileOutput, JsonSorter,
+    NullDate, OutputField, SplitBucket, PrettyOutput, SplitOutput, TimesketchOutput, TxtOutput,
+};
+use super::stream::{InputLocation, StreamReader, StreamSource};
+
+/// time to wait before checking again for lines which have been appended
+/// to a file which is being followed
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// environment variable which contains the key of `--anonymize-hashes`, if
+/// `--hash-key-file` has not been specified
+const HASH_KEY_VARIABLE: &str = "MACTIME2_HASH_KEY";
 
 #[derive(ValueEnum, Clone, Display)]
 enum InputFormat {
@@ -31,21 +77,94 @@ pub (crate) enum OutputFormat {
     #[strum(serialize = "json")]
     Json,
 
+    #[strum(serialize = "timesketch")]
+    Timesketch,
+
+    #[strum(serialize = "geojson")]
+    Geojson,
+
     #[cfg(feature = "elastic")]
     #[strum(serialize = "elastic")]
     Elastic,
 }
 
+impl OutputFormat {
+    /// extension of files which contain output in this format
+    fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Csv | Self::Timesketch => "csv",
+            Self::Txt => "txt",
+            Self::Json => "json",
+            Self::Geojson => "geojson",
+            #[cfg(feature = "elastic")]
+            Self::Elastic => "json",
+        }
+    }
+}
+
 //#[derive(Builder)]
 pub struct Mactime2Application {
+    command: Option<Mactime2Command>,
     format: OutputFormat,
-    bodyfile: Input,
+    bodyfile: InputLocation,
     src_zone: Tz,
     dst_zone: Tz,
     strict_mode: bool,
+    strip_prefix: Option<String>,
+    normalize_paths: bool,
+    reconstruct_paths: bool,
+    pseudonymize: bool,
+    hash_secret: Option<String>,
+    allow_multiline_names: bool,
+    ignore_crtime: bool,
+    max_errors: usize,
+    on_unparseable: OnUnparseable,
+    strict_utf8: bool,
+    fields: Option<Vec<OutputField>>,
+    pretty: Option<usize>,
+    time_range: TimeRange,
+    categories: Option<CategoryFilter>,
+    ads: Option<AdsFilter>,
+    deleted: Option<DeletedFilter>,
+    entry_type: Option<EntryType>,
+    expression: Option<Expression>,
+    fold_repeats: Option<u64>,
+    access_storm: Option<AccessStorm>,
+    session_gap: Option<u64>,
+    per_file: Option<PerFileEvent>,
+    only: Option<MacbFlags>,
+    outside_hours: Option<OutsideHoursFilter>,
+    sort_by: Option<TimestampKind>,
+    limit: Option<usize>,
+    sample: Option<Sample>,
+    deterministic_order: bool,
+    case_insensitive_paths: bool,
+    coordinates: Regex,
+    cache: Option<PathBuf>,
+    high_water_mark: Option<HighWaterMark>,
+    count_only: bool,
+    json_per_file: bool,
+    split_by: Option<SplitBucket>,
+    split_dir: PathBuf,
+    null_date: NullDate,
+    split_macb: bool,
+    depth_column: bool,
+    epoch_ms: bool,
+    csv_dialect: CsvDialect,
+    follow: Option<Duration>,
+    output: OutputArgs,
 }
 
 impl Mactime2Application {
+    /// selects the events which are counted by the reports
+    fn event_filter(&self) -> EventFilter {
+        EventFilter {
+            time_range: self.time_range,
+            only: self.only,
+            outside_hours: self.outside_hours,
+        }
+    }
+
     fn create_sorter(
         &self,
         decoder: &mut BodyfileDecoder,
@@ -53,43 +172,436 @@ impl Mactime2Application {
         let options = RunOptions {
             strict_mode: self.strict_mode,
             src_zone: self.src_zone,
+            strip_prefix: self.strip_prefix.clone(),
+            normalize_paths: self.normalize_paths,
+            reconstruct_paths: self.reconstruct_paths,
+            pseudonymize: self.pseudonymize,
+            hash_secret: self.hash_secret.clone(),
+            allow_multiline_names: self.allow_multiline_names,
+            ignore_crtime: self.ignore_crtime,
+            max_errors: self.max_errors,
+            on_unparseable: self.on_unparseable,
+            time_range: self.time_range,
+            categories: self.categories,
+            ads: self.ads,
+            deleted: self.deleted,
+            entry_type: self.entry_type,
+            expression: self.expression.clone(),
+            fold_repeats: self.fold_repeats,
+            access_storm: self.access_storm,
+            sessions: self
+                .session_gap
+                .map(|gap| SessionGrouper::new(gap, self.src_zone, self.dst_zone)),
+            per_file: self.per_file,
+            only: self.only,
+            outside_hours: self.outside_hours,
+            sort_by: self.sort_by,
+            limit: self.limit,
+            sample: self.sample,
+            deterministic_order: self.deterministic_order,
+            case_insensitive_paths: self.case_insensitive_paths,
+            cache: None,
+            high_water_mark: self.high_water_mark.clone(),
+            null_date: self.null_date,
+            split_macb: self.split_macb,
+            depth_column: self.depth_column,
+            follow: self.follow.is_some(),
+            output: self.output.clone(),
         };
 
-        if matches!(self.format, OutputFormat::Json) {
+        if matches!(self.command, Some(Mactime2Command::Report { .. })) {
+            let sorter = BodyfileSorter::default()
+                .with_receiver(decoder.get_receiver(), options)
+                .with_output(Box::new(HtmlOutput::new(self.src_zone, self.dst_zone)));
+            Box::new(sorter)
+        } else if self.count_only {
+            let sorter = BodyfileSorter::default()
+                .with_receiver(decoder.get_receiver(), options)
+                .with_output(Box::new(
+                    CountOutput::default().with_case_insensitive_paths(self.case_insensitive_paths),
+                ));
+            Box::new(sorter)
+        } else if self.json_per_file {
+            let sorter = BodyfileSorter::default()
+                .with_receiver(decoder.get_receiver(), options)
+                .with_output(Box::new(JsonPerFileOutput::new(self.src_zone, self.dst_zone)));
+            Box::new(sorter)
+        } else if matches!(self.format, OutputFormat::Json) {
             Box::new(JsonSorter::with_receiver(decoder.get_receiver(), options))
         } else {
             let mut sorter =
                 BodyfileSorter::default().with_receiver(decoder.get_receiver(), options);
 
-            sorter = sorter.with_output(match self.format {
-                OutputFormat::Csv => Box::new(CsvOutput::new(self.src_zone, self.dst_zone)),
-                OutputFormat::Txt => Box::new(TxtOutput::new(self.src_zone, self.dst_zone)),
+            let output: Box<dyn Mactime2Writer> = match self.format {
+                OutputFormat::Csv => {
+                    let output = CsvOutput::new(self.src_zone, self.dst_zone)
+                        .with_null_date(self.null_date)
+                        .with_split_macb(self.split_macb)
+                        .with_epoch_ms(self.epoch_ms)
+                        .with_dialect(self.csv_dialect);
+                    match &self.fields {
+                        Some(fields) => Box::new(output.with_fields(fields.clone())),
+                        None => Box::new(output),
+                    }
+                }
+                OutputFormat::Txt => match self.pretty {
+                    Some(name_width) => {
+                        let output = PrettyOutput::new(self.src_zone, self.dst_zone)
+                            .with_name_width(name_width);
+                        match &self.fields {
+                            Some(fields) => Box::new(output.with_fields(fields.clone())),
+                            None => Box::new(output),
+                        }
+                    }
+                    None => {
+                        let output = TxtOutput::new(self.src_zone, self.dst_zone);
+                        match &self.fields {
+                            Some(fields) => Box::new(output.with_fields(fields.clone())),
+                            None => Box::new(output),
+                        }
+                    }
+                },
+                OutputFormat::Timesketch => {
+                    Box::new(TimesketchOutput::new(self.src_zone, self.dst_zone))
+                }
+                OutputFormat::Geojson => Box::new(GeoJsonOutput::new(
+                    self.src_zone,
+                    self.dst_zone,
+                    self.coordinates.clone(),
+                )),
                 _ => panic!("invalid execution path"),
+            };
+            sorter = sorter.with_output(match self.split_by {
+                Some(bucket) => Box::new(SplitOutput::new(
+                    output,
+                    bucket,
+                    self.split_dir.clone(),
+                    self.format.file_extension(),
+                    self.src_zone,
+                    self.dst_zone,
+                )),
+                None => output,
             });
             Box::new(sorter)
         }
     }
 
-    pub fn run(&self) -> anyhow::Result<()> {
+    /// creates the timeline or runs the subcommand. Returns
+    /// [`ExitCode::PartialSuccess`] if some lines could not be parsed.
+    pub fn run(&self) -> anyhow::Result<ExitCode> {
+        match &self.command {
+            Some(Mactime2Command::Check { fs }) => self.check(*fs),
+            Some(Mactime2Command::Watch {
+                dir,
+                processed_dir,
+                failed_dir,
+                settle_time,
+                max_files,
+            }) => {
+                let intake = IntakeDirectory::new(dir.clone())
+                    .with_processed_dir(processed_dir.clone())
+                    .with_failed_dir(failed_dir.clone())
+                    .with_settle_time(Duration::from_secs(*settle_time))
+                    .with_max_files(*max_files);
+                self.watch(intake)
+            }
+            _ => self.run_on(&self.bodyfile),
+        }
+    }
+
+    /// creates a timeline of `bodyfile`, or runs the analysis of the
+    /// subcommand on it
+    fn run_on(&self, bodyfile: &InputLocation) -> anyhow::Result<ExitCode> {
+
         let options = RunOptions {
             strict_mode: self.strict_mode,
             src_zone: self.src_zone,
+            strip_prefix: self.strip_prefix.clone(),
+            normalize_paths: self.normalize_paths,
+            reconstruct_paths: self.reconstruct_paths,
+            pseudonymize: self.pseudonymize,
+            hash_secret: self.hash_secret.clone(),
+            allow_multiline_names: self.allow_multiline_names,
+            ignore_crtime: self.ignore_crtime,
+            max_errors: self.max_errors,
+            on_unparseable: self.on_unparseable,
+            time_range: self.time_range,
+            categories: self.categories,
+            ads: self.ads,
+            deleted: self.deleted,
+            entry_type: self.entry_type,
+            expression: self.expression.clone(),
+            fold_repeats: self.fold_repeats,
+            access_storm: self.access_storm,
+            sessions: self
+                .session_gap
+                .map(|gap| SessionGrouper::new(gap, self.src_zone, self.dst_zone)),
+            per_file: self.per_file,
+            only: self.only,
+            outside_hours: self.outside_hours,
+            sort_by: self.sort_by,
+            limit: self.limit,
+            sample: self.sample,
+            deterministic_order: self.deterministic_order,
+            case_insensitive_paths: self.case_insensitive_paths,
+            cache: None,
+            high_water_mark: self.high_water_mark.clone(),
+            null_date: self.null_date,
+            split_macb: self.split_macb,
+            depth_column: self.depth_column,
+            follow: self.follow.is_some(),
+            output: self.output.clone(),
         };
 
-        let mut reader = <BodyfileReader as StreamReader<String, ()>>::from(self.bodyfile.clone())?;
-        let mut decoder = BodyfileDecoder::with_receiver(reader.get_receiver(), options);
-        let mut sorter = self.create_sorter(&mut decoder);
-        sorter.run();
+        if let Some(Mactime2Command::Correlate { hosts, key, window }) = &self.command {
+            return self.correlate(hosts, *key, *window, options);
+        }
+
+        let cache = self
+            .cache
+            .as_ref()
+            .map(|path| CacheFile::new(path.clone(), SourceFingerprint::of(bodyfile)));
+        let timeline = cache.as_ref().and_then(CacheFile::load);
+        let (mut reader, mut decoder) = match timeline {
+            Some(timeline) => (None, BodyfileDecoder::from_timeline(timeline, options)),
+            None => {
+                let mut reader = if let Some(poll_interval) = self.follow {
+                    BodyfileReader::with_follow(bodyfile, poll_interval)?
+                } else if self.strict_utf8 {
+                    BodyfileReader::with_strict_utf8(bodyfile)?
+                } else {
+                    <BodyfileReader as StreamReader<String, ()>>::from(bodyfile)?
+                };
+                let decoder = BodyfileDecoder::with_receiver(
+                    reader.get_receiver(),
+                    RunOptions { cache, ..options },
+                );
+                (Some(reader), decoder)
+            }
+        };
+
+        let errors;
+        match &self.command {
+            // every file of the intake directory is turned into a timeline
+            None | Some(Mactime2Command::Report { .. } | Mactime2Command::Watch { .. }) => {
+                let mut sorter = self.create_sorter(&mut decoder);
+                sorter.run();
+
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                sorter.join().unwrap()?;
+            }
+            Some(Mactime2Command::Histogram { bucket, kinds, bar }) => {
+                let mut histogram =
+                    Histogram::new(*bucket, kinds.clone(), self.src_zone, self.dst_zone)
+                        .with_filter(self.event_filter());
+                for line in decoder.get_receiver() {
+                    histogram.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output
+                    .write_with(|out| Ok(histogram.print(*bar, out)?))?;
+            }
+            Some(Mactime2Command::Gaps { min }) => {
+                let mut gaps = Gaps::new(*min, self.src_zone, self.dst_zone)
+                    .with_filter(self.event_filter());
+                for line in decoder.get_receiver() {
+                    gaps.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output.write_with(|out| Ok(gaps.print(out)?))?;
+            }
+            Some(Mactime2Command::Summary { by_dir: _, top }) => {
+                let mut summary = DirectorySummary::default().with_filter(self.event_filter());
+                for line in decoder.get_receiver() {
+                    summary.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output
+                    .write_with(|out| Ok(summary.print(*top, out)?))?;
+            }
+            Some(Mactime2Command::Profile { by_user: _, top }) => {
+                let mut profiles = UserProfiles::new(self.src_zone, self.dst_zone)
+                    .with_filter(self.event_filter());
+                for line in decoder.get_receiver() {
+                    profiles.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output
+                    .write_with(|out| Ok(profiles.print(*top, out)?))?;
+            }
+            Some(Mactime2Command::Dot { max_depth }) => {
+                let mut graph = DirectoryGraph::default()
+                    .with_max_depth(*max_depth)
+                    .with_time_range(self.time_range);
+                for line in decoder.get_receiver() {
+                    graph.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output.write_with(|out| Ok(graph.print(out)?))?;
+            }
+            Some(Mactime2Command::Largest { top }) => {
+                let mut largest = LargestFiles::new(*top, self.src_zone, self.dst_zone);
+                for line in decoder.get_receiver() {
+                    largest.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output.write_with(|out| Ok(largest.print(out)?))?;
+            }
+            Some(Mactime2Command::Stats) => {
+                let mut stats = BodyfileStats::new(self.src_zone, self.dst_zone);
+                for line in decoder.get_receiver() {
+                    stats.add(&line);
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                self.output.write_with(|out| Ok(stats.print(out)?))?;
+            }
+            Some(Mactime2Command::Verify { root }) => {
+                let mut verifier = HashVerifier::new(root.clone());
+                let mut out = self.output.open()?;
+                for line in decoder.get_receiver() {
+                    verifier.add(&line, &mut out)?;
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                out.finish()?;
+                verifier.print_summary();
+            }
+            Some(Mactime2Command::Hashes { style }) => {
+                let mut manifest = HashManifest::new(*style);
+                let mut out = self.output.open()?;
+                if let Some(header) = manifest.header() {
+                    writeln!(out, "{header}")?;
+                }
+                for line in decoder.get_receiver() {
+                    manifest.add(&line, &mut out)?;
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                out.finish()?;
+            }
+            Some(Mactime2Command::CaseUco) => {
+                let mut export = CaseUcoExport::new(self.src_zone, self.dst_zone);
+                let mut out = self.output.open()?;
+                for line in decoder.get_receiver() {
+                    export.add(&line, &mut out)?;
+                }
+                errors = Self::join_input(reader.as_mut(), &mut decoder)?;
+                export.finish(&mut out)?;
+                out.finish()?;
+            }
+            Some(Mactime2Command::Check { .. } | Mactime2Command::Correlate { .. }) => {
+                unreachable!("has already been handled")
+            }
+        }
+        Ok(ExitCode::with_errors(errors))
+    }
+
+    /// creates a timeline of every bodyfile which is dropped into the intake
+    /// directory, until the process is interrupted or the maximum number of
+    /// files has been processed. A file which cannot be processed is moved
+    /// into the failed directory, to not process it again
+    fn watch(&self, intake: IntakeDirectory) -> anyhow::Result<ExitCode> {
+        let mut errors = 0;
+        intake.watch(|path| {
+            log::info!("processing {}", path.display());
+            let result = Input::new(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|input| self.run_on(&InputLocation::Local(input)));
+            if !matches!(result, Ok(ExitCode::Success)) {
+                errors += 1;
+            }
+            result.map(|_| ())
+        })?;
+        Ok(ExitCode::with_errors(errors))
+    }
+
+    /// reads the bodyfiles of all hosts and prints the files which appear on
+    /// more than one of them
+    fn correlate(
+        &self,
+        hosts: &[HostBodyfile],
+        key: CorrelationKey,
+        window: Option<u64>,
+        options: RunOptions,
+    ) -> anyhow::Result<ExitCode> {
+        let mut correlation = Correlation::new(key, self.src_zone, self.dst_zone)
+            .with_window(window)
+            .with_time_range(self.time_range);
+        let mut errors = 0;
+        for host in hosts {
+            let mut reader = <BodyfileReader as StreamReader<String, ()>>::from(&host.bodyfile)?;
+            let mut decoder = BodyfileDecoder::with_receiver(reader.get_receiver(), options.clone());
+            for line in decoder.get_receiver() {
+                correlation.add(&host.label, &line);
+            }
+            errors += Self::join_input(Some(&mut reader), &mut decoder)?;
+        }
+        self.output.write_with(|out| Ok(correlation.print(out)?))?;
+        Ok(ExitCode::with_errors(errors))
+    }
+
+    /// prints all timestamps which violate the constraints of `fs`. Line
+    /// numbers refer to the physical lines of the input, including comments.
+    fn check(&self, fs: Filesystem) -> anyhow::Result<ExitCode> {
+        let reader = BufReader::new(StreamSource::open(&self.bodyfile)?);
+        let mut violations = 0;
+        let mut errors = 0;
+        let mut error_counts = ParseErrorCounts::default();
+        let mut out = self.output.open()?;
+        for (idx, line) in reader.split(b'\n').enumerate() {
+            let line_nr = idx + 1;
+            let line = String::from_utf8_lossy(&line?).into_owned();
+            let line = line.trim_end_matches('\r');
+            if line.is_empty()
+                || line.starts_with('#')
+                || (line_nr == 1 && Bodyfile3Line::is_header(line))
+            {
+                continue;
+            }
+
+            match Bodyfile3Line::try_from(strip_checksum_column(line)) {
+                Ok(bf_line) => {
+                    for violation in fs.check(&bf_line) {
+                        writeln!(out, "line {line_nr}: '{}': {violation}", bf_line.get_name())?;
+                        violations += 1;
+                    }
+                    for warning in bf_line.warnings() {
+                        log::warn!("line {line_nr}: '{}': {warning}", bf_line.get_name());
+                    }
+                }
+                Err(why) => {
+                    log::warn!("unable to parse line {line_nr}: {why}");
+                    error_counts.add(&why);
+                    errors += 1;
+                }
+            }
+        }
+        out.finish()?;
+        log::info!("found {violations} invalid timestamps");
+        error_counts.log_summary();
+        Ok(ExitCode::with_errors(errors))
+    }
 
-        let _ = reader.join();
-        let _ = decoder.join();
-        sorter.join().unwrap()?;
-        Ok(())
+    /// waits for the input to be processed completely, and returns the
+    /// number of lines which could not be parsed
+    fn join_input(
+        reader: Option<&mut BodyfileReader>,
+        decoder: &mut BodyfileDecoder,
+    ) -> anyhow::Result<usize> {
+        let mut invalid_lines = 0;
+        if let Some(reader) = reader {
+            let _ = reader.join();
+            invalid_lines = reader.invalid_lines();
+        }
+        match decoder.join() {
+            Ok(result) => Ok(result? + invalid_lines),
+            Err(_) => Ok(invalid_lines),
+        }
     }
 }
 
-impl From<Cli> for Mactime2Application {
-    fn from(cli: Cli) -> Self {
+impl TryFrom<Cli> for Mactime2Application {
+    type Error = anyhow::Error;
+
+    fn try_from(cli: Cli) -> Result<Self, Self::Error> {
         let format = match cli.output_format {
             Some(f) => f,
             None => {
@@ -103,12 +615,206 @@ impl From<Cli> for Mactime2Application {
             }
         };
 
-        Self {
+        if cli.split_by.is_some()
+            && !matches!(format, OutputFormat::Csv | OutputFormat::Txt | OutputFormat::Timesketch)
+        {
+            bail!("--split-by supports only the formats csv, txt and timesketch");
+        }
+
+        if cli.pretty && !matches!(format, OutputFormat::Txt) {
+            bail!("--pretty supports only the format txt");
+        }
+
+        if cli.split_macb && !matches!(format, OutputFormat::Csv | OutputFormat::Json) {
+            bail!("--split-macb supports only the formats csv and json");
+        }
+
+        if cli.epoch_ms && !matches!(format, OutputFormat::Csv | OutputFormat::Json) {
+            bail!("--epoch-ms supports only the formats csv and json");
+        }
+
+        if cli.depth_column
+            && !matches!(format, OutputFormat::Csv | OutputFormat::Txt | OutputFormat::Json)
+        {
+            bail!("--depth-column supports only the formats csv, txt and json");
+        }
+
+        if cli.csv_dialect.is_some() && !matches!(format, OutputFormat::Csv) {
+            bail!("--csv-dialect supports only the format csv");
+        }
+
+        if let Some(Mactime2Command::Correlate { hosts, .. }) = &cli.command {
+            let labels: HashSet<_> = hosts.iter().map(|host| &host.label).collect();
+            if hosts.len() < 2 || labels.len() != hosts.len() {
+                bail!("correlate requires at least two hosts with distinct labels");
+            }
+        }
+
+        if cli.follow && (cli.command.is_some() || matches!(format, OutputFormat::Json)) {
+            bail!("--follow cannot be used with subcommands or with the format json");
+        }
+
+        // the output file is created only after the input has been read
+        // completely, which never happens while following or watching
+        if cli.output.output.is_some()
+            && (cli.follow || matches!(cli.command, Some(Mactime2Command::Watch { .. })))
+        {
+            bail!("--output cannot be used with --follow or with the watch subcommand");
+        }
+
+        let hash_secret = if cli.anonymize_hashes {
+            Some(read_hash_key(cli.hash_key_file.as_deref())?)
+        } else {
+            None
+        };
+
+        let coordinates = Regex::new(&cli.coordinates)?;
+        for group in ["lat", "lon"] {
+            if !coordinates.capture_names().any(|name| name == Some(group)) {
+                bail!("the coordinates pattern has no capture group named '{group}'");
+            }
+        }
+
+        // events at or before the latest event of the previous run are skipped
+        let high_water_mark = cli.newer_than_file.map(HighWaterMark::new);
+        let mut time_range = cli.time_range.resolve()?;
+        if let Some(previous) = high_water_mark.as_ref().map(HighWaterMark::load).transpose()?.flatten() {
+            let from = time_range.from().map_or(previous + 1, |from| from.max(previous + 1));
+            time_range = TimeRange::new(Some(from), time_range.to());
+        }
+
+        let src_zone = cli.src_zone.into_tz().unwrap();
+        let dst_zone = match &cli.tz_from_registry {
+            Some(hive) => timezone_from_registry(hive)?,
+            None => cli.dst_zone.into_tz().unwrap(),
+        };
+
+        Ok(Self {
+            time_range,
+            command: cli.command,
             format,
-            bodyfile: cli.input_file,
-            src_zone: cli.src_zone.into_tz().unwrap(),
-            dst_zone: cli.dst_zone.into_tz().unwrap(),
+            #[cfg(feature = "http")]
+            bodyfile: InputLocation::from_args(&cli.input_files)?
+                .with_timeout(cli.timeout.map(Duration::from_secs)),
+            #[cfg(not(feature = "http"))]
+            bodyfile: InputLocation::from_args(&cli.input_files)?,
+            src_zone,
+            dst_zone,
             strict_mode: cli.strict_mode,
-        }
+            strip_prefix: cli.strip_prefix,
+            normalize_paths: cli.normalize_paths,
+            reconstruct_paths: cli.reconstruct_paths,
+            pseudonymize: cli.pseudonymize,
+            hash_secret,
+            allow_multiline_names: cli.allow_multiline_names,
+            ignore_crtime: cli.no_crtime,
+            max_errors: cli.max_errors,
+            on_unparseable: cli.on_unparseable,
+            strict_utf8: cli.strict_utf8,
+            fields: match cli.fields {
+                Some(fields) if cli.depth_column && !fields.contains(&OutputField::Depth) => {
+                    Some(fields.into_iter().chain([OutputField::Depth]).collect())
+                }
+                None if cli.depth_column => Some(
+                    OutputField::ALL
+                        .into_iter()
+                        .chain([OutputField::Depth])
+                        .collect(),
+                ),
+                fields => fields,
+            },
+            pretty: cli.pretty.then_some(cli.name_width),
+            categories: cli.categories.map(|c| c.into_iter().collect()),
+            ads: if cli.only_ads {
+                Some(AdsFilter::Only)
+            } else if cli.no_ads {
+                Some(AdsFilter::Exclude)
+            } else {
+                None
+            },
+            deleted: if cli.only_deleted {
+                Some(DeletedFilter::Only)
+            } else if cli.no_deleted {
+                Some(DeletedFilter::Exclude)
+            } else {
+                None
+            },
+            entry_type: if cli.only_dirs {
+                Some(EntryType::Directory)
+            } else if cli.only_files {
+                Some(EntryType::File)
+            } else {
+                None
+            },
+            expression: match cli.expression {
+                Some(expression) if cli.case_insensitive_paths => {
+                    Some(expression.with_case_insensitive_paths()?)
+                }
+                expression => expression,
+            },
+            fold_repeats: cli.fold_repeats,
+            access_storm: cli
+                .collapse_access_storm
+                .map(|threshold| AccessStorm::new(threshold, cli.access_storm_window)),
+            session_gap: cli.session_gap,
+            // every file is displayed only once, at its first event
+            per_file: if cli.first_per_file || cli.json_per_file {
+                Some(PerFileEvent::First)
+            } else if cli.last_per_file {
+                Some(PerFileEvent::Last)
+            } else {
+                None
+            },
+            only: cli.only.map(|kinds| {
+                kinds
+                    .into_iter()
+                    .fold(MacbFlags::NONE, |flags, kind| flags | MacbFlags::from(kind))
+            }),
+            outside_hours: cli.outside_hours.map(|hours| {
+                OutsideHoursFilter::new(hours, src_zone, dst_zone)
+                    .with_weekdays_only(cli.weekdays_only)
+            }),
+            sort_by: cli.sort_by,
+            limit: cli.limit,
+            sample: cli.sample.map(|size| Sample::new(size, cli.seed)),
+            deterministic_order: cli.deterministic_order,
+            case_insensitive_paths: cli.case_insensitive_paths,
+            coordinates,
+            cache: cli.cache,
+            high_water_mark,
+            count_only: cli.count_only,
+            json_per_file: cli.json_per_file,
+            split_by: cli.split_by,
+            split_dir: cli.split_dir,
+            null_date: cli.null_date,
+            split_macb: cli.split_macb,
+            depth_column: cli.depth_column,
+            epoch_ms: cli.epoch_ms,
+            csv_dialect: cli.csv_dialect.unwrap_or_default(),
+            follow: cli.follow.then_some(FOLLOW_POLL_INTERVAL),
+            output: cli.output,
+        })
+    }
+}
+
+/// reads the key of `--anonymize-hashes` from `key_file`, or from the
+/// environment if no file has been specified. The key is not accepted on the
+/// command line, where it would be visible to other users
+fn read_hash_key(key_file: Option<&Path>) -> anyhow::Result<String> {
+    let key = match key_file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read the hash key from '{}'", path.display()))?
+            .trim_end_matches(['\r', '\n'])
+            .to_owned(),
+        None => match std::env::var(HASH_KEY_VARIABLE) {
+            Ok(key) => key,
+            Err(_) => bail!(
+                "--anonymize-hashes requires --hash-key-file or the environment variable {HASH_KEY_VARIABLE}"
+            ),
+        },
+    };
+    if key.is_empty() {
+        bail!("the key of --anonymize-hashes must not be empty");
     }
+    Ok(key)
 }
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+use super::{ListEntry, MacbFlags};
+
+/// detects bursts of atime-only events (e.g. caused by an antivirus scan,
+/// which reads every file) and collapses every burst into a single summary
+/// row. A burst consists of at least `threshold` atime-only events within
+/// `window` seconds after its first event. Events which also change other
+/// timestamps are never part of a burst.
+#[derive(Clone, Copy, Debug)]
+pub struct AccessStorm {
+    threshold: usize,
+    window: i64,
+}
+
+impl AccessStorm {
+    pub fn new(threshold: usize, window: u64) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            window: i64::try_from(window).unwrap_or(i64::MAX),
+        }
+    }
+
+    /// replaces all bursts in `entries` by summary rows, which are placed at
+    /// the timestamp of the first event of the burst. Returns the number of
+    /// bursts which have been found.
+    pub fn collapse(&self, entries: &mut BTreeMap<i64, Vec<ListEntry>>) -> usize {
+        // position of every atime-only event, in chronological order
+        let accesses: Vec<(i64, usize)> = entries
+            .iter()
+            .flat_map(|(ts, entries_at_ts)| {
+                entries_at_ts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| entry.flags == MacbFlags::A)
+                    .map(|(idx, _)| (*ts, idx))
+            })
+            .collect();
+
+        let mut collapsed = HashSet::new();
+        let mut summaries = Vec::new();
+        let mut start = 0;
+        while start < accesses.len() {
+            let first_ts = accesses[start].0;
+            let end = start
+                + accesses[start..]
+                    .iter()
+                    .take_while(|(ts, _)| ts - first_ts <= self.window)
+                    .count();
+            if end - start < self.threshold {
+                start += 1;
+                continue;
+            }
+
+            let last_ts = accesses[end - 1].0;
+            collapsed.extend(accesses[start..end].iter().copied());
+            summaries.push((first_ts, Self::summary(first_ts, last_ts, end - start)));
+            start = end;
+        }
+
+        for (ts, entries_at_ts) in entries.iter_mut() {
+            let mut idx = 0;
+            entries_at_ts.retain(|_| {
+                idx += 1;
+                !collapsed.contains(&(*ts, idx - 1))
+            });
+        }
+        entries.retain(|_, entries_at_ts| !entries_at_ts.is_empty());
+
+        let count = summaries.len();
+        for (ts, summary) in summaries {
+            entries.entry(ts).or_default().insert(0, summary);
+        }
+        count
+    }
+
+    fn summary(first_ts: i64, last_ts: i64, count: usize) -> ListEntry {
+        let name = format!(
+            "access storm: {count} atime-only events within {} seconds",
+            last_ts - first_ts
+        );
+        ListEntry {
+            flags: MacbFlags::A,
+            line: Arc::new(
+                Bodyfile3Line::new()
+                    .with_owned_name(name)
+                    .with_atime(first_ts),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::AccessStorm;
+    use crate::bodyfile::{ListEntry, MacbFlags};
+
+    fn timeline(events: &[(i64, MacbFlags, &str)]) -> BTreeMap<i64, Vec<ListEntry>> {
+        let mut entries: BTreeMap<i64, Vec<ListEntry>> = BTreeMap::new();
+        for (ts, flags, name) in events {
+            entries.entry(*ts).or_default().push(ListEntry {
+                flags: *flags,
+                line: Arc::new(Bodyfile3Line::new().with_name(name)),
+            });
+        }
+        entries
+    }
+
+    fn names(entries: &BTreeMap<i64, Vec<ListEntry>>) -> Vec<(i64, String)> {
+        entries
+            .iter()
+            .flat_map(|(ts, entries_at_ts)| {
+                entries_at_ts
+                    .iter()
+                    .map(|entry| (*ts, entry.line.get_name().to_owned()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn burst_is_collapsed() {
+        let mut entries = timeline(&[
+            (90, MacbFlags::A, "/before"),
+            (100, MacbFlags::A, "/a"),
+            (101, MacbFlags::A, "/b"),
+            (101, MacbFlags::M | MacbFlags::A, "/changed"),
+            (103, MacbFlags::A, "/c"),
+            (200, MacbFlags::A, "/after"),
+        ]);
+        assert_eq!(AccessStorm::new(3, 5).collapse(&mut entries), 1);
+        assert_eq!(
+            names(&entries),
+            vec![
+                (90, "/before".to_owned()),
+                (
+                    100,
+                    "access storm: 3 atime-only events within 3 seconds".to_owned()
+                ),
+                (101, "/changed".to_owned()),
+                (200, "/after".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn below_threshold() {
+        let mut entries = timeline(&[
+            (100, MacbFlags::A, "/a"),
+            (101, MacbFlags::A, "/b"),
+            (110, MacbFlags::A, "/c"),
+        ]);
+        assert_eq!(AccessStorm::new(3, 5).collapse(&mut entries), 0);
+        assert_eq!(names(&entries).len(), 3);
+    }
+}
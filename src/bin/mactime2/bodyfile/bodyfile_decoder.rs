@@ -1,16 +1,34 @@
+use crate::error::MactimeError;
 use crate::filter::{Consumer, Filter, Joinable, Provider, RunOptions};
-use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use super::{MultilineAssembler, ParseErrorCounts, Timeline};
+use dfir_toolkit::common::bodyfile::{
+    strip_checksum_column, Bodyfile3Line, Bodyfile3ParserError, OnUnparseable, PathResolver,
+};
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread::JoinHandle;
 
+/// parses the lines of a bodyfile in a separate thread. The worker returns
+/// the number of lines which could not be parsed.
 pub struct BodyfileDecoder {
-    worker: Option<JoinHandle<()>>,
+    worker: Option<JoinHandle<Result<usize, MactimeError>>>,
     rx: Option<Receiver<Bodyfile3Line>>,
 }
 
-impl Filter<String, Bodyfile3Line, ()> for BodyfileDecoder {
-    fn worker(reader: Receiver<String>, tx: Sender<Bodyfile3Line>, options: RunOptions) {
+impl Filter<String, Bodyfile3Line, Result<usize, MactimeError>> for BodyfileDecoder {
+    fn worker(
+        reader: Receiver<String>,
+        tx: Sender<Bodyfile3Line>,
+        options: RunOptions,
+    ) -> Result<usize, MactimeError> {
+        let mut decoder = DecoderState {
+            cached_lines: options.cache.as_ref().map(|_| Vec::new()),
+            pending_lines: options.reconstruct_paths.then(Vec::new),
+            ..Default::default()
+        };
+        let mut assembler = MultilineAssembler::default();
+        let mut sniffer = Some(Sniffer::default());
         loop {
             let mut line = match reader.recv() {
                 Err(_) => {
@@ -19,34 +37,292 @@ impl Filter<String, Bodyfile3Line, ()> for BodyfileDecoder {
                 Ok(l) => l,
             };
 
-            if line.starts_with('#') {
+            if line.starts_with('#') && !assembler.is_pending() {
                 continue;
             }
             Self::trim_newline(&mut line);
 
-            let bf_line = match Bodyfile3Line::try_from(line.as_ref()) {
-                Err(e) => {
-                    if options.strict_mode {
-                        log::warn!("bodyfile parser error: {}", e);
-                        panic!("failed while parsing: {:?}", line);
-                    } else {
-                        log::warn!("bodyfile parser error: {}", e);
-                        #[cfg(debug_assertions)]
-                        log::warn!("failed line was: {:?}", line);
-                    }
+            if let Some(sniffer) = sniffer.as_mut() {
+                if !line.contains('|') {
+                    sniffer.hold(line)?;
                     continue;
                 }
-                Ok(l) => l,
-            };
+            }
+            if let Some(sniffer) = sniffer.take() {
+                for line in sniffer.held_lines {
+                    if !decoder.process(line, &mut assembler, &tx, &options)? {
+                        return Ok(decoder.error_count);
+                    }
+                }
+            }
 
-            if tx.send(bf_line).is_err() {
-                break;
+            if !decoder.process(line, &mut assembler, &tx, &options)? {
+                return Ok(decoder.error_count);
             }
         }
+
+        if let Some(sniffer) = sniffer {
+            sniffer.finish()?;
+            for line in sniffer.held_lines {
+                if !decoder.process(line, &mut assembler, &tx, &options)? {
+                    return Ok(decoder.error_count);
+                }
+            }
+        }
+
+        if let Some(line) = assembler.finish() {
+            decoder.decode(Err(line), &tx, &options)?;
+        }
+        decoder.send_pending_lines(&tx, &options)?;
+        decoder.report_skipped_lines();
+        log::info!(
+            "parsed {} lines with {} errors",
+            decoder.line_count,
+            decoder.error_count
+        );
+        decoder.error_counts.log_summary();
+        if let (Some(cache), Some(lines)) = (&options.cache, decoder.cached_lines) {
+            cache.store(lines);
+        }
+        Ok(decoder.error_count)
+    }
+}
+
+/// number of non-empty lines which are inspected before the input is
+/// rejected as not being a bodyfile
+const SNIFF_LINES: usize = 10;
+
+/// holds back the first lines of the input until a line containing a pipe
+/// has been found. If none of the first [`SNIFF_LINES`] non-empty lines
+/// contains a pipe, the input cannot be a bodyfile, and parsing is aborted
+/// with a single error instead of one parser error per line.
+#[derive(Default)]
+struct Sniffer {
+    held_lines: Vec<String>,
+    non_empty_lines: usize,
+}
+
+impl Sniffer {
+    fn hold(&mut self, line: String) -> Result<(), MactimeError> {
+        if !line.is_empty() {
+            self.non_empty_lines += 1;
+        }
+        self.held_lines.push(line);
+        if self.non_empty_lines >= SNIFF_LINES {
+            return Err(MactimeError::NotABodyfile);
+        }
+        Ok(())
+    }
+
+    /// is called at the end of the input, which was too short to be checked
+    /// completely
+    fn finish(&self) -> Result<(), MactimeError> {
+        if self.non_empty_lines > 0 {
+            return Err(MactimeError::NotABodyfile);
+        }
+        Ok(())
     }
 }
 
-impl Provider<Bodyfile3Line, ()> for BodyfileDecoder {
+#[derive(Default)]
+struct DecoderState {
+    line_count: usize,
+    error_count: usize,
+
+    /// number of lines which could not be parsed, but have not been
+    /// counted as errors
+    skipped_count: usize,
+
+    /// number of unparseable lines per kind of parser error, including the
+    /// skipped lines
+    error_counts: ParseErrorCounts,
+
+    /// skipped lines which will be logged for review
+    collected_lines: Vec<String>,
+    user_mapping: HashMap<String, String>,
+
+    /// parsed lines which will be written into the timeline cache
+    cached_lines: Option<Vec<Bodyfile3Line>>,
+
+    /// parsed lines which are held back until all lines are known, because
+    /// their paths need to be reconstructed
+    pending_lines: Option<Vec<Bodyfile3Line>>,
+}
+
+impl DecoderState {
+    /// passes a line to the multiline assembler, if multiline names are
+    /// allowed, and decodes the resulting lines. Returns `false` if the
+    /// receiver has been closed.
+    fn process(
+        &mut self,
+        line: String,
+        assembler: &mut MultilineAssembler,
+        tx: &Sender<Bodyfile3Line>,
+        options: &RunOptions,
+    ) -> Result<bool, MactimeError> {
+        if !options.allow_multiline_names {
+            return self.decode(Ok(line), tx, options);
+        }
+
+        for line in assembler.push(line) {
+            if !self.decode(line, tx, options)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// parses and filters a line and sends it to `tx`. `Err` contains lines
+    /// which are known to be incomplete. Returns `false` if the receiver has
+    /// been closed.
+    fn decode(
+        &mut self,
+        line: Result<String, String>,
+        tx: &Sender<Bodyfile3Line>,
+        options: &RunOptions,
+    ) -> Result<bool, MactimeError> {
+        self.line_count += 1;
+        let result = match &line {
+            // files written by `bodyfile_merge merge --checksums` can be read again
+            Ok(line) => Bodyfile3Line::try_from(strip_checksum_column(line)),
+            Err(_) => Err(Bodyfile3ParserError::IncompleteMultilineName),
+        };
+
+        let bf_line = match result {
+            Err(e) => {
+                self.error_counts.add(&e);
+                let line = line.unwrap_or_else(|line| line);
+                match options.on_unparseable {
+                    OnUnparseable::Error => (),
+                    OnUnparseable::Skip => {
+                        log::debug!("skipping unparseable line {:?}: {}", line, e);
+                        self.skipped_count += 1;
+                        return Ok(true);
+                    }
+                    OnUnparseable::Collect => {
+                        self.skipped_count += 1;
+                        self.collected_lines.push(line);
+                        return Ok(true);
+                    }
+                }
+                log::warn!("bodyfile parser error: {}", e);
+                if options.strict_mode {
+                    panic!("failed while parsing: {:?}", line);
+                }
+                log::debug!("failed line was: {:?}", line);
+
+                self.error_count += 1;
+                if options.max_errors != 0 && self.error_count >= options.max_errors {
+                    return Err(MactimeError::TooManyErrors(self.error_count));
+                }
+                return Ok(true);
+            }
+            Ok(l) => l,
+        };
+
+        if let Some(cached_lines) = self.cached_lines.as_mut() {
+            cached_lines.push(bf_line.clone());
+        }
+        if let Some(pending_lines) = self.pending_lines.as_mut() {
+            pending_lines.push(bf_line);
+            return Ok(true);
+        }
+        self.apply_options(bf_line, tx, options)
+    }
+
+    /// warns about the number of lines which have been skipped, and logs the
+    /// lines themselves if they have been collected for review
+    fn report_skipped_lines(&self) {
+        if self.skipped_count > 0 {
+            log::warn!("skipped {} unparseable lines", self.skipped_count);
+        }
+        for line in self.collected_lines.iter() {
+            log::warn!("unparseable line: {:?}", line);
+        }
+    }
+
+    /// reconstructs the paths of all lines which have been held back, and
+    /// sends them to `tx`. Returns `false` if the receiver has been closed.
+    fn send_pending_lines(
+        &mut self,
+        tx: &Sender<Bodyfile3Line>,
+        options: &RunOptions,
+    ) -> Result<bool, MactimeError> {
+        if let Some(mut lines) = self.pending_lines.take() {
+            PathResolver::reconstruct_paths(&mut lines);
+            for bf_line in lines {
+                if !self.apply_options(bf_line, tx, options)? {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// changes and filters a parsed line as requested by `options`, and sends
+    /// it to `tx`. Returns `false` if the receiver has been closed.
+    fn apply_options(
+        &mut self,
+        mut bf_line: Bodyfile3Line,
+        tx: &Sender<Bodyfile3Line>,
+        options: &RunOptions,
+    ) -> Result<bool, MactimeError> {
+        if options.ignore_crtime {
+            bf_line.set_crtime(-1);
+        }
+
+        if let Some(prefix) = &options.strip_prefix {
+            bf_line.strip_name_prefix(prefix);
+        }
+
+        if options.normalize_paths {
+            let name = bf_line.normalized_name();
+            bf_line.set_name(name);
+        }
+
+        if let Some(categories) = options.categories {
+            if !categories.contains(bf_line.category()) {
+                return Ok(true);
+            }
+        }
+
+        if let Some(ads) = options.ads {
+            if !ads.matches(&bf_line) {
+                return Ok(true);
+            }
+        }
+
+        if let Some(deleted) = options.deleted {
+            if !deleted.matches(&bf_line) {
+                return Ok(true);
+            }
+        }
+
+        if let Some(entry_type) = options.entry_type {
+            if bf_line.entry_type() != entry_type {
+                return Ok(true);
+            }
+        }
+
+        if let Some(expression) = &options.expression {
+            if !expression.evaluate(&bf_line) {
+                return Ok(true);
+            }
+        }
+
+        if options.pseudonymize {
+            bf_line.redact_users(&mut self.user_mapping);
+        }
+
+        if let Some(secret) = &options.hash_secret {
+            bf_line.anonymize_hashes(secret.as_bytes());
+        }
+
+        Ok(tx.send(bf_line).is_ok())
+    }
+}
+
+impl Provider<Bodyfile3Line, Result<usize, MactimeError>> for BodyfileDecoder {
     fn get_receiver(&mut self) -> Receiver<Bodyfile3Line> {
         self.rx.take().unwrap()
     }
@@ -65,6 +341,27 @@ impl Consumer<String> for BodyfileDecoder {
 }
 
 impl BodyfileDecoder {
+    /// provides the lines of a cached timeline instead of parsing a bodyfile
+    pub fn from_timeline(timeline: Timeline, options: RunOptions) -> Self {
+        let (tx, rx): (Sender<Bodyfile3Line>, Receiver<Bodyfile3Line>) = mpsc::channel();
+        Self {
+            worker: Some(std::thread::spawn(move || {
+                let mut decoder = DecoderState::default();
+                let mut lines = timeline.into_lines();
+                if options.reconstruct_paths {
+                    PathResolver::reconstruct_paths(&mut lines);
+                }
+                for bf_line in lines {
+                    if !decoder.apply_options(bf_line, &tx, &options)? {
+                        break;
+                    }
+                }
+                Ok(0)
+            })),
+            rx: Some(rx),
+        }
+    }
+
     fn trim_newline(s: &mut String) {
         if s.ends_with('\n') {
             s.pop();
@@ -75,8 +372,8 @@ impl BodyfileDecoder {
     }
 }
 
-impl Joinable<()> for BodyfileDecoder {
-    fn join(&mut self) -> std::thread::Result<()> {
+impl Joinable<Result<usize, MactimeError>> for BodyfileDecoder {
+    fn join(&mut self) -> std::thread::Result<Result<usize, MactimeError>> {
         self.worker.take().unwrap().join()
     }
 }
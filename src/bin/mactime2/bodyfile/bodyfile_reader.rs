@@ -1,14 +1,30 @@
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::interrupt;
 use encoding_rs_io::DecodeReaderBytesBuilder;
 use std::io::{BufRead, BufReader, Read};
-use std::sync::mpsc::{Receiver, Sender};
-use std::thread::JoinHandle;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
 
 use crate::filter::{Joinable, Provider};
-use crate::stream::{StreamReader, StreamWorker};
+use crate::stream::{InputLocation, StreamReader, StreamSource, StreamWorker};
 
+/// reads lines from a bodyfile in a separate thread.
+///
+/// The reader can be moved to another thread (it is `Send`), but it
+/// cannot be shared between threads, because it owns the receiving end of
+/// a channel, which is not `Sync`.
 pub struct BodyfileReader {
     worker: Option<JoinHandle<()>>,
     rx: Option<Receiver<String>>,
+
+    /// number of lines which have been dropped because they were not valid
+    /// UTF-8 (only if strict UTF-8 decoding has been requested)
+    invalid_lines: Arc<AtomicUsize>,
 }
 
 impl Provider<String, ()> for BodyfileReader {
@@ -19,21 +35,95 @@ impl Provider<String, ()> for BodyfileReader {
 
 impl StreamWorker<String> for BodyfileReader {
     fn worker<R: Read + Send>(input: R, tx: Sender<String>) {
+        Self::read_lines(input, tx, interrupt::is_interrupted, None)
+    }
+}
+
+impl BodyfileReader {
+    /// like `StreamReader::from`, but lines which are not valid UTF-8 are
+    /// dropped instead of replacing the invalid sequences. The number of
+    /// dropped lines is returned by [`Self::invalid_lines`]
+    pub fn with_strict_utf8(input: &InputLocation) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let invalid_lines = Arc::new(AtomicUsize::new(0));
+
+        let source = StreamSource::open(input)?;
+        let counter = Arc::clone(&invalid_lines);
+        let worker = thread::spawn(move || {
+            Self::read_lines(source, tx, interrupt::is_interrupted, Some(&counter));
+        });
+
+        Ok(Self {
+            worker: Some(worker),
+            rx: Some(rx),
+            invalid_lines,
+        })
+    }
+
+    /// like `StreamReader::from`, but the reader does not stop at the end
+    /// of the file. Instead, it waits for lines which are appended to the
+    /// file, and checks for new data every `poll_interval`. Reading stops
+    /// when `SIGINT` has been received.
+    pub fn with_follow(input: &InputLocation, poll_interval: Duration) -> Result<Self> {
+        let source = match input {
+            InputLocation::Local(input) if input.can_seek() => input.clone(),
+            _ => bail!("--follow requires a local file, but the input is '{input}'"),
+        };
+        if input.is_gzipped() || input.is_zstd_compressed() {
+            bail!("--follow cannot be used with compressed files");
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            Self::follow_lines(source, tx, interrupt::is_interrupted, poll_interval);
+        });
+
+        Ok(Self {
+            worker: Some(worker),
+            rx: Some(rx),
+            invalid_lines: Arc::default(),
+        })
+    }
+
+    /// number of lines which have been dropped because they were not valid UTF-8
+    pub fn invalid_lines(&self) -> usize {
+        self.invalid_lines.load(Ordering::Relaxed)
+    }
+
+    /// sends all lines of `input`, until `is_interrupted` returns `true`. If
+    /// `invalid_lines` is specified, lines which are not valid UTF-8 are not
+    /// sent, but counted
+    fn read_lines<R: Read + Send>(
+        input: R,
+        tx: Sender<String>,
+        is_interrupted: fn() -> bool,
+        invalid_lines: Option<&AtomicUsize>,
+    ) {
         let mut line_ctr = 1;
 
+        // without a BOM, the bytes are passed through unchanged. An explicit
+        // UTF-8 encoding would replace invalid sequences, so that they could
+        // neither be reported nor skipped
         let drb = DecodeReaderBytesBuilder::new()
-            .encoding(Some(encoding_rs::UTF_8))
-            .utf8_passthru(true)
+            .encoding(None)
             .build(input);
         let mut reader = BufReader::new(drb);
 
         loop {
-            let mut line = String::new();
-            let size = reader.read_line(&mut line);
+            if is_interrupted() {
+                eprintln!(
+                    "interrupted after reading {} lines, the output contains only the events of these lines",
+                    line_ctr - 1
+                );
+                break;
+            }
+
+            let mut buffer = Vec::new();
+            let size = reader.read_until(b'\n', &mut buffer);
 
             match size {
                 Err(why) => {
-                    eprintln!("IO Error in line {}: {:?}", line_ctr, why);
+                    log::error!("IO Error in line {}: {:?}", line_ctr, why);
                     break;
                 }
                 Ok(s) => {
@@ -41,6 +131,26 @@ impl StreamWorker<String> for BodyfileReader {
                         break;
                     }
 
+                    let line = match invalid_lines {
+                        None => Self::decode_line(buffer, line_ctr),
+                        Some(invalid_lines) => match String::from_utf8(buffer) {
+                            Ok(line) => line,
+                            Err(_) => {
+                                log::warn!("invalid UTF-8 in line {line_ctr}, skipping line");
+                                invalid_lines.fetch_add(1, Ordering::Relaxed);
+                                line_ctr += 1;
+                                continue;
+                            }
+                        },
+                    };
+
+                    // some generators prepend a line which contains the column names
+                    if line_ctr == 1 && Bodyfile3Line::is_header(&line) {
+                        log::debug!("skipping bodyfile header: {}", line.trim_end());
+                        line_ctr += 1;
+                        continue;
+                    }
+
                     if tx.send(line).is_err() {
                         break;
                     }
@@ -49,6 +159,61 @@ impl StreamWorker<String> for BodyfileReader {
             line_ctr += 1;
         }
     }
+
+    /// sends all complete lines of `input`, and waits for more lines at the
+    /// end of the input, until `is_interrupted` returns `true`. An
+    /// incomplete line at the end of the input is kept until its newline
+    /// character has been appended.
+    fn follow_lines<R: Read + Send>(
+        input: R,
+        tx: Sender<String>,
+        is_interrupted: fn() -> bool,
+        poll_interval: Duration,
+    ) {
+        let mut line_ctr = 1;
+        let mut reader = BufReader::new(input);
+        let mut buffer = Vec::new();
+
+        while !is_interrupted() {
+            match reader.read_until(b'\n', &mut buffer) {
+                Err(why) => {
+                    log::error!("IO Error in line {}: {:?}", line_ctr, why);
+                    break;
+                }
+                Ok(_) if !buffer.ends_with(b"\n") => thread::sleep(poll_interval),
+                Ok(_) => {
+                    let mut line = Self::decode_line(std::mem::take(&mut buffer), line_ctr);
+                    if line_ctr == 1 {
+                        if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                            line = stripped.to_owned();
+                        }
+                        if Bodyfile3Line::is_header(&line) {
+                            log::debug!("skipping bodyfile header: {}", line.trim_end());
+                            line_ctr += 1;
+                            continue;
+                        }
+                    }
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                    line_ctr += 1;
+                }
+            }
+        }
+    }
+
+    /// converts the bytes of a line into a `String` without copying them.
+    /// Invalid UTF-8 sequences are replaced by U+FFFD, so that the line can
+    /// still be parsed.
+    fn decode_line(buffer: Vec<u8>, line_ctr: usize) -> String {
+        match String::from_utf8(buffer) {
+            Ok(line) => line,
+            Err(why) => {
+                log::warn!("invalid UTF-8 in line {line_ctr}, replacing invalid characters");
+                String::from_utf8_lossy(why.as_bytes()).into_owned()
+            }
+        }
+    }
 }
 
 impl StreamReader<String, ()> for BodyfileReader {
@@ -56,6 +221,7 @@ impl StreamReader<String, ()> for BodyfileReader {
         Self {
             worker: Some(worker),
             rx: Some(rx),
+            invalid_lines: Arc::default(),
         }
     }
 }
@@ -65,3 +231,89 @@ impl Joinable<()> for BodyfileReader {
         self.worker.take().unwrap().join()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::BodyfileReader;
+
+    fn _assert_send<T: Send>() {}
+
+    #[test]
+    fn reader_is_send() {
+        _assert_send::<BodyfileReader>();
+    }
+
+    #[test]
+    fn stop_when_interrupted() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        BodyfileReader::read_lines("a\nb\n".as_bytes(), tx, || false, None);
+        assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["a\n", "b\n"]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        BodyfileReader::read_lines("a\nb\n".as_bytes(), tx, || true, None);
+        assert_eq!(rx.iter().count(), 0);
+    }
+
+    #[test]
+    fn drop_invalid_utf8() {
+        let invalid_lines = AtomicUsize::new(0);
+        let (tx, rx) = std::sync::mpsc::channel();
+        BodyfileReader::read_lines(&b"a\nb\xff\nc\n"[..], tx, || false, Some(&invalid_lines));
+        assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["a\n", "c\n"]);
+        assert_eq!(invalid_lines.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn decode_invalid_utf8() {
+        assert_eq!(
+            BodyfileReader::decode_line(b"a\xffb\n".to_vec(), 1),
+            "a\u{fffd}b\n"
+        );
+        assert_eq!(
+            BodyfileReader::decode_line("\u{e4}\n".as_bytes().to_vec(), 1),
+            "\u{e4}\n"
+        );
+    }
+
+    static STOP_FOLLOWING: AtomicBool = AtomicBool::new(false);
+
+    #[test]
+    fn follow_appended_lines() {
+        let path = std::env::temp_dir().join(format!("mactime2_follow_{}", std::process::id()));
+        fs::write(&path, "a\n").unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let input = fs::File::open(&path).unwrap();
+        let worker = thread::spawn(move || {
+            BodyfileReader::follow_lines(
+                input,
+                tx,
+                || STOP_FOLLOWING.load(Ordering::SeqCst),
+                Duration::from_millis(10),
+            )
+        });
+        let timeout = Duration::from_secs(5);
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "a\n");
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"b\nc").unwrap();
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "b\n");
+
+        // the incomplete line must not be sent until it has been completed
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        file.write_all(b"d\ne\n").unwrap();
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "cd\n");
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "e\n");
+
+        STOP_FOLLOWING.store(true, Ordering::SeqCst);
+        worker.join().unwrap();
+        assert!(rx.recv().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
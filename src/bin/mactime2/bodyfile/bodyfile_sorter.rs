@@ -1,19 +1,41 @@
-use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::{OutputArgs, TimeRange};
 use std::borrow::Borrow;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
 use std::sync::mpsc::Receiver;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
+use crate::business_hours::OutsideHoursFilter;
 use crate::error::MactimeError;
-use crate::filter::{Joinable, RunOptions, Runnable, Sorter};
+use crate::filter::{Joinable, PerFileEvent, RunOptions, Runnable, Sorter};
+use crate::high_water_mark::HighWaterMark;
+use crate::sample::Sample;
 
-use super::MACBFlags;
+use super::{AccessStorm, MacbFlags, RepeatFolder, SessionGrouper};
 
 pub trait Mactime2Writer: Send {
-    fn write(&self, timestamp: &i64, entry: &ListEntry) {
-        println!("{}", self.fmt(timestamp, entry));
+    /// optional line which is printed before the first entry
+    fn header(&self) -> Option<String> {
+        None
+    }
+    /// optional line which is printed after the last entry
+    fn footer(&self) -> Option<String> {
+        None
+    }
+    /// string which terminates every line, including the header and the footer
+    fn line_terminator(&self) -> &'static str {
+        "\n"
+    }
+    fn write(
+        &self,
+        out: &mut dyn Write,
+        timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        write!(out, "{}{}", self.fmt(timestamp, entry), self.line_terminator())
     }
     fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String;
 }
@@ -23,11 +45,26 @@ pub struct BodyfileSorter {
     worker: Option<JoinHandle<Result<(), MactimeError>>>,
     receiver: Option<Receiver<Bodyfile3Line>>,
     output: Option<Box<dyn Mactime2Writer>>,
+    time_range: TimeRange,
+    fold_repeats: Option<u64>,
+    access_storm: Option<AccessStorm>,
+    sessions: Option<SessionGrouper>,
+    per_file: Option<PerFileEvent>,
+    only: Option<MacbFlags>,
+    outside_hours: Option<OutsideHoursFilter>,
+    sort_by: Option<TimestampKind>,
+    limit: Option<usize>,
+    sample: Option<Sample>,
+    deterministic_order: bool,
+    case_insensitive_paths: bool,
+    high_water_mark: Option<HighWaterMark>,
+    follow: bool,
+    destination: OutputArgs,
 }
 
 #[derive(Debug)]
 pub struct ListEntry {
-    pub flags: MACBFlags,
+    pub flags: MacbFlags,
     pub line: Arc<Bodyfile3Line>,
 }
 
@@ -53,23 +90,24 @@ impl Ord for ListEntry {
     }
 }
 
+/// identifies the file of a line by its inode and name. Names which only
+/// differ in their case refer to the same file if `case_insensitive_paths`
+/// is set
+pub(crate) fn file_key(line: &Bodyfile3Line, case_insensitive_paths: bool) -> String {
+    if case_insensitive_paths {
+        line.case_insensitive_identity_key()
+    } else {
+        line.identity_key()
+    }
+}
+
+/// adds an event of `line` at `timestamp`, which is annotated with `flag`
 fn insert_timestamp(
     entries: &mut BTreeMap<i64, Vec<ListEntry>>,
-    flag: MACBFlags,
+    timestamp: i64,
+    flag: MacbFlags,
     line: Arc<Bodyfile3Line>,
 ) {
-    let timestamp = if flag.contains(MACBFlags::M) {
-        *line.get_mtime()
-    } else if flag.contains(MACBFlags::A) {
-        *line.get_atime()
-    } else if flag.contains(MACBFlags::C) {
-        *line.get_ctime()
-    } else if flag.contains(MACBFlags::B) {
-        *line.get_crtime()
-    } else {
-        -1
-    };
-
     match entries.get_mut(&timestamp) {
         None => {
             let mut entries_at_ts = Vec::new();
@@ -95,13 +133,75 @@ impl Runnable for BodyfileSorter {
             .output
             .take()
             .expect("no output provided; please call with_output()");
-        self.worker = Some(std::thread::spawn(move || Self::worker(receiver, output)));
+        let time_range = self.time_range;
+        let fold_repeats = self.fold_repeats;
+        let access_storm = self.access_storm;
+        let sessions = self.sessions.take();
+        let per_file = self.per_file;
+        let only = self.only;
+        let outside_hours = self.outside_hours;
+        let sort_by = self.sort_by;
+        let limit = self.limit;
+        let sample = self.sample;
+        let deterministic_order = self.deterministic_order;
+        let case_insensitive_paths = self.case_insensitive_paths;
+        let high_water_mark = self.high_water_mark.take();
+        let destination = self.destination.clone();
+        if self.follow {
+            self.worker = Some(std::thread::spawn(move || {
+                Self::follow_worker(
+                    receiver,
+                    output,
+                    destination,
+                    time_range,
+                    only,
+                    outside_hours,
+                    sort_by,
+                )
+            }));
+            return;
+        }
+        self.worker = Some(std::thread::spawn(move || {
+            Self::worker(
+                receiver,
+                output,
+                destination,
+                time_range,
+                fold_repeats,
+                access_storm,
+                sessions,
+                per_file,
+                only,
+                outside_hours,
+                sort_by,
+                limit,
+                sample,
+                deterministic_order,
+                case_insensitive_paths,
+                high_water_mark,
+            )
+        }));
     }
 }
 
 impl BodyfileSorter {
-    pub fn with_receiver(mut self, decoder: Receiver<Bodyfile3Line>, _: RunOptions) -> Self {
+    pub fn with_receiver(mut self, decoder: Receiver<Bodyfile3Line>, options: RunOptions) -> Self {
         self.receiver = Some(decoder);
+        self.time_range = options.time_range;
+        self.fold_repeats = options.fold_repeats;
+        self.access_storm = options.access_storm;
+        self.sessions = options.sessions;
+        self.per_file = options.per_file;
+        self.only = options.only;
+        self.outside_hours = options.outside_hours;
+        self.sort_by = options.sort_by;
+        self.limit = options.limit;
+        self.sample = options.sample;
+        self.deterministic_order = options.deterministic_order;
+        self.case_insensitive_paths = options.case_insensitive_paths;
+        self.high_water_mark = options.high_water_mark;
+        self.follow = options.follow;
+        self.destination = options.output;
         self
     }
 
@@ -110,12 +210,28 @@ impl BodyfileSorter {
         self
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn worker(
         decoder: Receiver<Bodyfile3Line>,
         output: Box<dyn Mactime2Writer>,
+        destination: OutputArgs,
+        time_range: TimeRange,
+        fold_repeats: Option<u64>,
+        access_storm: Option<AccessStorm>,
+        mut sessions: Option<SessionGrouper>,
+        per_file: Option<PerFileEvent>,
+        only: Option<MacbFlags>,
+        outside_hours: Option<OutsideHoursFilter>,
+        sort_by: Option<TimestampKind>,
+        limit: Option<usize>,
+        sample: Option<Sample>,
+        deterministic_order: bool,
+        case_insensitive_paths: bool,
+        high_water_mark: Option<HighWaterMark>,
     ) -> Result<(), MactimeError> {
         let mut entries: BTreeMap<i64, Vec<ListEntry>> = BTreeMap::new();
-        let mut names: HashSet<(String, String)> = HashSet::new();
+        let mut names: HashSet<String> = HashSet::new();
+        let mut reservoir = sample.map(|sample| sample.reservoir());
 
         loop {
             let line = Arc::new(match decoder.recv() {
@@ -125,74 +241,262 @@ impl BodyfileSorter {
                 Ok(l) => l,
             });
 
+            // only the sampled events are kept, so that the memory usage does
+            // not depend on the size of the input
+            if let Some(reservoir) = reservoir.as_mut() {
+                let mut events = BTreeMap::new();
+                Self::insert_line(&mut events, line, only, outside_hours, sort_by);
+                for (ts, entries_at_ts) in events
+                    .into_iter()
+                    .filter(|(ts, _)| time_range.contains(*ts))
+                {
+                    for entry in entries_at_ts {
+                        reservoir.push((ts, entry));
+                    }
+                }
+                continue;
+            }
+
             // each name && inode SHOULD occur only once
             {
                 let bf: &Bodyfile3Line = line.borrow();
-                if names.contains(&(bf.get_inode().to_owned(), bf.get_name().to_owned())) {
+                if !names.insert(file_key(bf, case_insensitive_paths)) {
                     log::warn!(
                         "ambigious file name: '{}' and inode '{}'",
                         bf.get_name(),
                         bf.get_inode()
                     );
                 }
-                names.insert((bf.get_inode().to_owned(), bf.get_name().to_owned()));
             } // delete the borrow to line
 
-            // we need *some* value in mactimes!
-            if *line.get_mtime() == -1
-                && *line.get_atime() == -1
-                && *line.get_ctime() == -1
-                && *line.get_crtime() == -1
-            {
-                insert_timestamp(&mut entries, MACBFlags::NONE, Arc::clone(&line));
-                continue;
+            Self::insert_line(&mut entries, line, only, outside_hours, sort_by);
+        }
+
+        if let Some(reservoir) = reservoir {
+            let seen = reservoir.seen();
+            let events = reservoir.into_items();
+            log::info!("sampled {} out of {seen} events", events.len());
+            for (ts, entry) in events {
+                entries.entry(ts).or_default().push(entry);
             }
+        }
 
-            let mut flags: [MACBFlags; 4] = [MACBFlags::NONE; 4];
+        if deterministic_order {
+            for entries_at_ts in entries.values_mut() {
+                entries_at_ts.sort_by(|e1, e2| {
+                    e1.cmp(e2)
+                        .then_with(|| e1.flags.bits().cmp(&e2.flags.bits()))
+                });
+            }
+        }
 
-            if *line.get_mtime() != -1 {
-                flags[0] |= MACBFlags::M;
+        if let Some(access_storm) = access_storm {
+            let storms = access_storm.collapse(&mut entries);
+            if storms > 0 {
+                log::info!("collapsed {storms} access storms");
             }
-            if *line.get_atime() != -1 {
-                if line.get_mtime() == line.get_atime() {
-                    flags[0] |= MACBFlags::A;
-                } else {
-                    flags[1] |= MACBFlags::A;
+        }
+
+        let mut out = destination.open()?;
+        if let Some(header) = output.header() {
+            write!(out, "{header}{}", output.line_terminator())?;
+        }
+        let mut selected = per_file.map(|per_file| {
+            Self::select_per_file(&entries, time_range, per_file, case_insensitive_paths)
+        });
+        let mut folder = fold_repeats.map(RepeatFolder::new);
+        let mut latest = None;
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut written = 0;
+        'events: for (ts, entries_at_ts) in entries
+            .iter()
+            .filter(|(ts, _)| time_range.contains(**ts))
+        {
+            for line in entries_at_ts {
+                if written >= limit {
+                    break 'events;
                 }
-            }
-            if *line.get_ctime() != -1 {
-                if line.get_mtime() == line.get_ctime() {
-                    flags[0] |= MACBFlags::C;
-                } else if line.get_atime() == line.get_ctime() {
-                    flags[1] |= MACBFlags::C;
-                } else {
-                    flags[2] |= MACBFlags::C;
+                if let Some(selected) = selected.as_mut() {
+                    // every file is displayed only once, even if multiple lines
+                    // describe it
+                    let key = file_key(&line.line, case_insensitive_paths);
+                    if selected.get(&key) != Some(ts) {
+                        continue;
+                    }
+                    selected.remove(&key);
+                }
+                latest = Some(*ts);
+                match folder.as_mut() {
+                    None => {
+                        Self::write_entry(output.as_ref(), &mut out, sessions.as_mut(), ts, line)?;
+                        written += 1;
+                    }
+                    Some(folder) => {
+                        if let Some((ts, line)) = folder.push(*ts, line) {
+                            Self::write_entry(
+                                output.as_ref(),
+                                &mut out,
+                                sessions.as_mut(),
+                                &ts,
+                                &line,
+                            )?;
+                            written += 1;
+                        }
+                    }
                 }
             }
-            if *line.get_crtime() != -1 {
-                if line.get_mtime() == line.get_crtime() {
-                    flags[0] |= MACBFlags::B;
-                } else if line.get_atime() == line.get_crtime() {
-                    flags[1] |= MACBFlags::B;
-                } else if line.get_ctime() == line.get_crtime() {
-                    flags[2] |= MACBFlags::B;
-                } else {
-                    flags[3] |= MACBFlags::B;
+        }
+        if written < limit {
+            if let Some((ts, line)) = folder.as_mut().and_then(RepeatFolder::finish) {
+                Self::write_entry(output.as_ref(), &mut out, sessions.as_mut(), &ts, &line)?;
+            }
+        }
+        if let Some(footer) = output.footer() {
+            write!(out, "{footer}{}", output.line_terminator())?;
+        }
+        out.finish()?;
+        if let Some(sessions) = sessions {
+            sessions.print_summaries();
+        }
+        if let (Some(high_water_mark), Some(latest)) = (high_water_mark, latest) {
+            high_water_mark.store(latest);
+        }
+        Ok(())
+    }
+
+    /// writes a row of the timeline, which is annotated with its session id
+    /// if events are grouped into sessions
+    fn write_entry(
+        output: &dyn Mactime2Writer,
+        out: &mut dyn Write,
+        sessions: Option<&mut SessionGrouper>,
+        ts: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        match sessions {
+            Some(sessions) => output.write(out, ts, &sessions.push(*ts, entry)),
+            None => output.write(out, ts, entry),
+        }
+    }
+
+    /// adds the events of `line` to `entries`
+    fn insert_line(
+        entries: &mut BTreeMap<i64, Vec<ListEntry>>,
+        line: Arc<Bodyfile3Line>,
+        only: Option<MacbFlags>,
+        outside_hours: Option<OutsideHoursFilter>,
+        sort_by: Option<TimestampKind>,
+    ) {
+        let is_selected =
+            |ts: i64| ts != -1 && outside_hours.iter().all(|filter| filter.matches(ts));
+
+        // every file results in exactly one entry at the selected timestamp
+        if let Some(kind) = sort_by {
+            let ts = line.get_timestamp(kind);
+            if is_selected(ts) {
+                let flag = MacbFlags::from_line_at_time(&line, ts);
+                insert_timestamp(entries, ts, flag, Arc::clone(&line));
+            }
+            return;
+        }
+
+        // we need *some* value in mactimes!
+        if *line.get_mtime() == -1
+            && *line.get_atime() == -1
+            && *line.get_ctime() == -1
+            && *line.get_crtime() == -1
+        {
+            if only.is_none() && outside_hours.is_none() {
+                insert_timestamp(entries, -1, MacbFlags::NONE, Arc::clone(&line));
+            }
+            return;
+        }
+
+        // every distinct timestamp results in one entry, which is
+        // annotated with all timestamps that share this value
+        let mut seen = Vec::with_capacity(4);
+        for ts in [
+            *line.get_mtime(),
+            *line.get_atime(),
+            *line.get_ctime(),
+            *line.get_crtime(),
+        ] {
+            if !is_selected(ts) || seen.contains(&ts) {
+                continue;
+            }
+            seen.push(ts);
+            let mut flag = MacbFlags::from_line_at_time(&line, ts);
+            if let Some(only) = only {
+                flag &= only;
+                if flag.is_empty() {
+                    continue;
                 }
             }
-            for flag in flags.iter() {
-                if flag != &MACBFlags::NONE {
-                    insert_timestamp(&mut entries, *flag, Arc::clone(&line));
+            insert_timestamp(entries, ts, flag, Arc::clone(&line));
+        }
+    }
+
+    /// writes the events of every line as soon as it has been received.
+    /// The events are not sorted, apart from the events of a single line
+    fn follow_worker(
+        decoder: Receiver<Bodyfile3Line>,
+        output: Box<dyn Mactime2Writer>,
+        destination: OutputArgs,
+        time_range: TimeRange,
+        only: Option<MacbFlags>,
+        outside_hours: Option<OutsideHoursFilter>,
+        sort_by: Option<TimestampKind>,
+    ) -> Result<(), MactimeError> {
+        let mut out = destination.open()?;
+        if let Some(header) = output.header() {
+            write!(out, "{header}{}", output.line_terminator())?;
+        }
+        for line in decoder {
+            let mut entries = BTreeMap::new();
+            Self::insert_line(&mut entries, Arc::new(line), only, outside_hours, sort_by);
+            for (ts, entries_at_ts) in entries
+                .iter()
+                .filter(|(ts, _)| time_range.contains(**ts))
+            {
+                for line in entries_at_ts {
+                    output.write(&mut out, ts, line)?;
                 }
             }
+            // new events must be visible immediately
+            out.flush()?;
         }
+        if let Some(footer) = output.footer() {
+            write!(out, "{footer}{}", output.line_terminator())?;
+        }
+        Ok(out.finish()?)
+    }
 
-        for (ts, entries_at_ts) in entries.iter() {
-            for line in entries_at_ts {
-                output.write(ts, line);
+    /// returns the timestamp of the first or last event of every file, which
+    /// is identified by its name and inode (see [`file_key`])
+    fn select_per_file(
+        entries: &BTreeMap<i64, Vec<ListEntry>>,
+        time_range: TimeRange,
+        per_file: PerFileEvent,
+        case_insensitive_paths: bool,
+    ) -> HashMap<String, i64> {
+        let mut selected = HashMap::new();
+        for (ts, entries_at_ts) in entries
+            .iter()
+            .filter(|(ts, _)| time_range.contains(**ts))
+        {
+            for entry in entries_at_ts {
+                let key = file_key(&entry.line, case_insensitive_paths);
+                match per_file {
+                    PerFileEvent::First => {
+                        selected.entry(key).or_insert(*ts);
+                    }
+                    PerFileEvent::Last => {
+                        selected.insert(key, *ts);
+                    }
+                }
             }
         }
-        Ok(())
+        selected
     }
 }
 
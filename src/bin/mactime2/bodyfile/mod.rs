@@ -1,9 +1,21 @@
+mod access_storm;
 mod bodyfile_decoder;
 mod bodyfile_reader;
 mod bodyfile_sorter;
-mod macb_flags;
+mod multiline;
+mod parse_error_counts;
+mod repeat_folder;
+mod session_grouper;
+mod timeline_cache;
 
+pub use access_storm::*;
 pub use bodyfile_decoder::*;
 pub use bodyfile_reader::*;
 pub use bodyfile_sorter::*;
-pub use macb_flags::*;
\ No newline at end of file
+pub use multiline::*;
+pub use parse_error_counts::*;
+pub use repeat_folder::*;
+pub use session_grouper::*;
+pub use timeline_cache::*;
+
+pub use dfir_toolkit::common::bodyfile::MacbFlags;
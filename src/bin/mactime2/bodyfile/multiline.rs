@@ -0,0 +1,103 @@
+/// number of columns of a bodyfile line
+const COLUMNS: usize = 11;
+
+/// reassembles bodyfile lines whose name contains line breaks, and which
+/// have thus been split into multiple physical lines.
+///
+/// # Heuristic
+///
+/// A physical line with less than 11 columns is not parsed immediately, but
+/// joined with the following lines (using `\n`) as long as the joined line has
+/// less than 11 columns. If it has exactly 11 columns, it is returned as one
+/// line. If it would have more than 11 columns, the incomplete line is
+/// returned as error, and the current line is handled on its own.
+///
+/// This has some limitations:
+///  - names which contain both a line break and a pipe symbol cannot be
+///    reassembled, because the joined line would have too many columns
+///  - a truncated line, which is followed by another truncated line, may be
+///    joined with it, if both lines together happen to have 11 columns
+///  - comments cannot be detected inside of a multiline name
+#[derive(Default)]
+pub struct MultilineAssembler {
+    pending: Option<String>,
+}
+
+impl MultilineAssembler {
+    /// adds the next physical line (without its line break) and returns all
+    /// lines which are ready to be parsed. Lines which could not be completed
+    /// are returned as `Err`.
+    pub fn push(&mut self, line: String) -> Vec<Result<String, String>> {
+        let mut result = Vec::new();
+        if let Some(mut pending) = self.pending.take() {
+            let columns = Self::columns(&pending) + Self::columns(&line) - 1;
+            if columns <= COLUMNS {
+                pending.push('\n');
+                pending.push_str(&line);
+                if columns == COLUMNS {
+                    result.push(Ok(pending));
+                } else {
+                    self.pending = Some(pending);
+                }
+                return result;
+            }
+            result.push(Err(pending));
+        }
+
+        if Self::columns(&line) < COLUMNS {
+            self.pending = Some(line);
+        } else {
+            result.push(Ok(line));
+        }
+        result
+    }
+
+    /// returns `true` if the last line was incomplete
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// returns the incomplete line at the end of the input, if any
+    pub fn finish(&mut self) -> Option<String> {
+        self.pending.take()
+    }
+
+    fn columns(line: &str) -> usize {
+        line.matches('|').count() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultilineAssembler;
+
+    #[test]
+    fn reassemble_name_with_newline() {
+        let mut assembler = MultilineAssembler::default();
+        assert!(assembler.push("0|/tmp/first".to_owned()).is_empty());
+        assert_eq!(
+            assembler.push("second|12|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned()),
+            vec![Ok("0|/tmp/first\nsecond|12|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned())]
+        );
+        assert_eq!(
+            assembler.push("0|/tmp/a|13|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned()),
+            vec![Ok("0|/tmp/a|13|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned())]
+        );
+        assert_eq!(assembler.finish(), None);
+    }
+
+    #[test]
+    fn truncated_line() {
+        let mut assembler = MultilineAssembler::default();
+        assert!(assembler.push("0|/tmp/truncated|12".to_owned()).is_empty());
+        assert_eq!(
+            assembler.push("0|/tmp/a|13|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned()),
+            vec![
+                Err("0|/tmp/truncated|12".to_owned()),
+                Ok("0|/tmp/a|13|r/rrw-r--r--|0|0|0|-1|1|-1|-1".to_owned())
+            ]
+        );
+        assert!(assembler.push("0|/tmp/b".to_owned()).is_empty());
+        assert_eq!(assembler.finish(), Some("0|/tmp/b".to_owned()));
+    }
+}
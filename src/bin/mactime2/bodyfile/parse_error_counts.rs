@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use dfir_toolkit::common::bodyfile::Bodyfile3ParserError;
+
+/// number of unparseable lines per kind of parser error (e.g.
+/// `IllegalUid`), which helps to find systematic bugs of a collector
+#[derive(Default)]
+pub struct ParseErrorCounts(BTreeMap<&'static str, usize>);
+
+impl ParseErrorCounts {
+    pub fn add(&mut self, error: &Bodyfile3ParserError) {
+        *self.0.entry(error.kind()).or_insert(0) += 1;
+    }
+
+    /// returns the number of errors of every kind, beginning with the most
+    /// frequent kind
+    pub fn breakdown(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<_> = self.0.iter().map(|(kind, count)| (*kind, *count)).collect();
+        counts.sort_by(|(kind1, count1), (kind2, count2)| {
+            count2.cmp(count1).then_with(|| kind1.cmp(kind2))
+        });
+        counts
+    }
+
+    pub fn log_summary(&self) {
+        if self.0.is_empty() {
+            return;
+        }
+        let breakdown: Vec<_> = self
+            .breakdown()
+            .into_iter()
+            .map(|(kind, count)| format!("{kind}: {count}"))
+            .collect();
+        log::info!("unparseable lines per error kind: {}", breakdown.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError};
+
+    use super::ParseErrorCounts;
+
+    #[test]
+    fn counts_per_kind() {
+        let mut counts = ParseErrorCounts::default();
+        for line in [
+            "0|/a|1||x|0|0|-1|-1|-1|-1",
+            "0|/b|1||0|0|0|a|-1|-1|-1",
+            "0|/c|1||y|0|0|-1|-1|-1|-1",
+            "0|/d",
+        ] {
+            counts.add(&Bodyfile3Line::try_from(line).unwrap_err());
+        }
+        counts.add(&Bodyfile3ParserError::IllegalATime);
+
+        assert_eq!(
+            counts.breakdown(),
+            vec![("IllegalATime", 2), ("IllegalUid", 2), ("TooFewColumns", 1)]
+        );
+    }
+}
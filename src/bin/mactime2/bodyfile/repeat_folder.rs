@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+use super::{ListEntry, MacbFlags};
+
+struct PendingRepeat {
+    first_ts: i64,
+    last_ts: i64,
+    flags: MacbFlags,
+    line: Arc<Bodyfile3Line>,
+    count: usize,
+}
+
+/// collapses consecutive timeline rows which refer to the same file name and
+/// the same MACB flags into a single row, as long as all of these rows lie
+/// within `window` seconds after the first row.
+///
+/// The name of a folded row is annotated with the number of rows and the
+/// time span which they cover.
+pub struct RepeatFolder {
+    window: i64,
+    pending: Option<PendingRepeat>,
+}
+
+impl RepeatFolder {
+    pub fn new(window: u64) -> Self {
+        Self {
+            window: i64::try_from(window).unwrap_or(i64::MAX),
+            pending: None,
+        }
+    }
+
+    /// adds the next row of the timeline and returns the previous row, if it
+    /// cannot be folded with the current row
+    pub fn push(&mut self, timestamp: i64, entry: &ListEntry) -> Option<(i64, ListEntry)> {
+        if let Some(pending) = self.pending.as_mut() {
+            if pending.flags == entry.flags
+                && pending.line.get_name() == entry.line.get_name()
+                && timestamp - pending.first_ts <= self.window
+            {
+                pending.last_ts = timestamp;
+                pending.count += 1;
+                return None;
+            }
+        }
+
+        let previous = self.finish();
+        self.pending = Some(PendingRepeat {
+            first_ts: timestamp,
+            last_ts: timestamp,
+            flags: entry.flags,
+            line: Arc::clone(&entry.line),
+            count: 1,
+        });
+        previous
+    }
+
+    /// returns the last row, which has not been returned by [`Self::push`]
+    pub fn finish(&mut self) -> Option<(i64, ListEntry)> {
+        self.pending.take().map(|pending| {
+            let line = if pending.count == 1 {
+                pending.line
+            } else {
+                let mut fields = pending.line.fields();
+                fields.name = format!(
+                    "{} (repeated {} times within {} seconds)",
+                    fields.name,
+                    pending.count,
+                    pending.last_ts - pending.first_ts
+                );
+                Arc::new(Bodyfile3Line::from(fields))
+            };
+            (
+                pending.first_ts,
+                ListEntry {
+                    flags: pending.flags,
+                    line,
+                },
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::RepeatFolder;
+    use crate::bodyfile::{ListEntry, MacbFlags};
+
+    fn fold(window: u64, rows: &[(i64, &str)]) -> Vec<(i64, String)> {
+        let mut folder = RepeatFolder::new(window);
+        let mut result = Vec::new();
+        for (ts, name) in rows {
+            let entry = ListEntry {
+                flags: MacbFlags::M,
+                line: Arc::new(Bodyfile3Line::new().with_name(name).with_mtime(*ts)),
+            };
+            result.extend(folder.push(*ts, &entry));
+        }
+        result.extend(folder.finish());
+        result
+            .into_iter()
+            .map(|(ts, entry)| (ts, entry.line.get_name().to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn repeats_within_window_are_folded() {
+        let rows = [
+            (100, "/var/log/syslog"),
+            (101, "/var/log/syslog"),
+            (101, "/var/log/syslog"),
+            (103, "/var/log/syslog"),
+            (104, "/var/log/syslog"),
+        ];
+        assert_eq!(
+            fold(5, &rows),
+            vec![(
+                100,
+                "/var/log/syslog (repeated 5 times within 4 seconds)".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn repeats_outside_of_window_are_kept() {
+        let rows = [
+            (100, "/var/log/syslog"),
+            (105, "/var/log/syslog"),
+            (106, "/var/log/syslog"),
+        ];
+        assert_eq!(
+            fold(5, &rows),
+            vec![
+                (
+                    100,
+                    "/var/log/syslog (repeated 2 times within 5 seconds)".to_owned()
+                ),
+                (106, "/var/log/syslog".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_files_interrupt_folding() {
+        let rows = [(100, "a"), (101, "b"), (102, "a")];
+        assert_eq!(
+            fold(5, &rows),
+            vec![
+                (100, "a".to_owned()),
+                (101, "b".to_owned()),
+                (102, "a".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use super::ListEntry;
+
+/// consecutive events without a longer period of inactivity
+#[derive(Clone, Copy)]
+struct Session {
+    start: i64,
+    end: i64,
+    events: usize,
+}
+
+/// groups the rows of a timeline into sessions of activity. A new session
+/// starts whenever the gap between two consecutive rows is at least `gap`
+/// seconds. The name of every row is annotated with the id of its session,
+/// and the sessions are summarized after the timeline has been written.
+#[derive(Clone)]
+pub struct SessionGrouper {
+    gap: i64,
+    src_zone: Tz,
+    dst_zone: Tz,
+    sessions: Vec<Session>,
+}
+
+impl SessionGrouper {
+    pub fn new(gap: u64, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            gap: i64::try_from(gap).unwrap_or(i64::MAX),
+            src_zone,
+            dst_zone,
+            sessions: Vec::new(),
+        }
+    }
+
+    /// adds the next row of the timeline, which must not be earlier than
+    /// the previous row, and returns the row annotated with its session id
+    pub fn push(&mut self, timestamp: i64, entry: &ListEntry) -> ListEntry {
+        match self.sessions.last_mut() {
+            Some(session) if timestamp.saturating_sub(session.end) < self.gap => {
+                session.end = timestamp;
+                session.events += 1;
+            }
+            _ => self.sessions.push(Session {
+                start: timestamp,
+                end: timestamp,
+                events: 1,
+            }),
+        }
+
+        let mut fields = entry.line.fields();
+        fields.name = format!("{} (session {})", fields.name, self.sessions.len());
+        ListEntry {
+            flags: entry.flags,
+            line: Arc::new(Bodyfile3Line::from(fields)),
+        }
+    }
+
+    /// returns the start, the end and the number of events of every session
+    pub fn summaries(&self) -> Vec<(usize, i64, i64, usize)> {
+        self.sessions
+            .iter()
+            .enumerate()
+            .map(|(idx, session)| (idx + 1, session.start, session.end, session.events))
+            .collect()
+    }
+
+    /// prints the summary of every session to stderr, so that the timeline
+    /// on stdout is not changed
+    pub fn print_summaries(&self) {
+        for (id, start, end, events) in self.summaries() {
+            eprintln!(
+                "session {id}: {} - {} ({events} events)",
+                ForensicsTimestamp::new(start, self.src_zone, self.dst_zone),
+                ForensicsTimestamp::new(end, self.src_zone, self.dst_zone)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::SessionGrouper;
+    use crate::bodyfile::{ListEntry, MacbFlags};
+
+    #[test]
+    fn gap_starts_new_session() {
+        let mut grouper = SessionGrouper::new(3600, Tz::UTC, Tz::UTC);
+        let names: Vec<_> = [(100, "/a"), (200, "/b"), (3799, "/c"), (7399, "/d")]
+            .into_iter()
+            .map(|(ts, name)| {
+                let entry = ListEntry {
+                    flags: MacbFlags::M,
+                    line: Arc::new(Bodyfile3Line::new().with_name(name)),
+                };
+                grouper.push(ts, &entry).line.get_name().to_owned()
+            })
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "/a (session 1)",
+                "/b (session 1)",
+                "/c (session 1)",
+                "/d (session 2)"
+            ]
+        );
+        assert_eq!(
+            grouper.summaries(),
+            vec![(1, 100, 3799, 3), (2, 7399, 7399, 1)]
+        );
+    }
+}
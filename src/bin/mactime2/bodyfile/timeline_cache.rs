@@ -0,0 +1,303 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::AtomicOutput;
+use thiserror::Error;
+
+use crate::stream::InputLocation;
+
+const CACHE_MAGIC: &[u8; 8] = b"MT2CACHE";
+
+/// must be incremented whenever the layout of the cache changes
+pub const CACHE_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+
+    #[error("the file is no timeline cache")]
+    InvalidMagic,
+
+    #[error("unsupported cache version {found} (expected {expected})")]
+    VersionMismatch { found: u32, expected: u32 },
+
+    #[error("invalid cache content: {0}")]
+    InvalidData(String),
+}
+
+/// identifies the bodyfile a cache has been created from, so that a cache
+/// is not used after the bodyfile has been changed. Bodyfiles which are read
+/// from stdin, from a URL or from multiple files cannot be identified and
+/// have an empty fingerprint.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceFingerprint {
+    size: u64,
+    modified: i64,
+}
+
+impl SourceFingerprint {
+    pub fn of(input: &InputLocation) -> Self {
+        let input = match input {
+            InputLocation::Local(input) => input,
+            InputLocation::Parts(_) => return Self::default(),
+            #[cfg(feature = "http")]
+            InputLocation::Url { .. } => return Self::default(),
+        };
+        if input.path().is_std() {
+            return Self::default();
+        }
+        match fs::metadata(input.path().to_path_buf()) {
+            Ok(metadata) => Self {
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs() as i64)
+                    .unwrap_or(-1),
+            },
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// all lines of a bodyfile which have been parsed successfully, in the
+/// order of the input. Options which change or filter lines are not applied,
+/// so that a cache can be used with any combination of options.
+pub struct Timeline {
+    source: SourceFingerprint,
+    lines: Vec<Bodyfile3Line>,
+}
+
+impl Timeline {
+    pub fn new(source: SourceFingerprint, lines: Vec<Bodyfile3Line>) -> Self {
+        Self { source, lines }
+    }
+
+    pub fn source(&self) -> SourceFingerprint {
+        self.source
+    }
+
+    pub fn into_lines(self) -> Vec<Bodyfile3Line> {
+        self.lines
+    }
+
+    /// stores the timeline in a binary file. The file is replaced
+    /// atomically, so that an interrupted run does not leave a partial cache.
+    pub fn save_cache(&self, path: &Path) -> anyhow::Result<()> {
+        let mut output = AtomicOutput::create(path)?;
+        {
+            let mut writer = BufWriter::new(&mut output);
+            self.write_to(&mut writer)?;
+            writer.flush()?;
+        }
+        output.commit()
+    }
+
+    /// reads a timeline which has been stored with [`Timeline::save_cache`]
+    pub fn load_cache(path: &Path) -> Result<Self, CacheError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(CACHE_MAGIC)?;
+        w.write_all(&CACHE_VERSION.to_le_bytes())?;
+        w.write_all(&self.source.size.to_le_bytes())?;
+        w.write_all(&self.source.modified.to_le_bytes())?;
+        w.write_all(&(self.lines.len() as u64).to_le_bytes())?;
+        for line in &self.lines {
+            for value in [
+                line.get_md5(),
+                line.get_name(),
+                line.get_inode(),
+                line.get_mode_as_string(),
+            ] {
+                w.write_all(&(value.len() as u32).to_le_bytes())?;
+                w.write_all(value.as_bytes())?;
+            }
+            for value in [line.get_uid(), line.get_gid(), line.get_size()] {
+                w.write_all(&value.to_le_bytes())?;
+            }
+            for value in [
+                line.get_atime(),
+                line.get_mtime(),
+                line.get_ctime(),
+                line.get_crtime(),
+            ] {
+                w.write_all(&value.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(r: &mut R) -> Result<Self, CacheError> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != CACHE_MAGIC {
+            return Err(CacheError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(read_bytes(r)?);
+        if version != CACHE_VERSION {
+            return Err(CacheError::VersionMismatch {
+                found: version,
+                expected: CACHE_VERSION,
+            });
+        }
+        let source = SourceFingerprint {
+            size: u64::from_le_bytes(read_bytes(r)?),
+            modified: i64::from_le_bytes(read_bytes(r)?),
+        };
+
+        let count = u64::from_le_bytes(read_bytes(r)?);
+        let mut lines = Vec::new();
+        for _ in 0..count {
+            let line = Bodyfile3Line::new()
+                .with_owned_md5(read_string(r)?)
+                .with_owned_name(read_string(r)?)
+                .with_owned_inode(read_string(r)?)
+                .with_owned_mode(read_string(r)?)
+                .with_uid(u64::from_le_bytes(read_bytes(r)?))
+                .with_gid(u64::from_le_bytes(read_bytes(r)?))
+                .with_size(u64::from_le_bytes(read_bytes(r)?))
+                .with_atime(i64::from_le_bytes(read_bytes(r)?))
+                .with_mtime(i64::from_le_bytes(read_bytes(r)?))
+                .with_ctime(i64::from_le_bytes(read_bytes(r)?))
+                .with_crtime(i64::from_le_bytes(read_bytes(r)?));
+            lines.push(line);
+        }
+        Ok(Self { source, lines })
+    }
+}
+
+fn read_bytes<R: Read, const N: usize>(r: &mut R) -> Result<[u8; N], CacheError> {
+    let mut buffer = [0; N];
+    r.read_exact(&mut buffer).map_err(|why| match why.kind() {
+        ErrorKind::UnexpectedEof => CacheError::InvalidData("the cache is truncated".into()),
+        _ => why.into(),
+    })?;
+    Ok(buffer)
+}
+
+fn read_string<R: Read>(r: &mut R) -> Result<String, CacheError> {
+    let len = u32::from_le_bytes(read_bytes(r)?) as u64;
+    let mut buffer = Vec::new();
+    r.take(len).read_to_end(&mut buffer)?;
+    if buffer.len() as u64 != len {
+        return Err(CacheError::InvalidData("the cache is truncated".into()));
+    }
+    String::from_utf8(buffer).map_err(|why| CacheError::InvalidData(why.to_string()))
+}
+
+/// location of a timeline cache, together with the fingerprint of the
+/// bodyfile which is currently being read
+#[derive(Clone, Debug)]
+pub struct CacheFile {
+    path: PathBuf,
+    source: SourceFingerprint,
+}
+
+impl CacheFile {
+    pub fn new(path: PathBuf, source: SourceFingerprint) -> Self {
+        Self { path, source }
+    }
+
+    /// returns the cached timeline, or `None` if the input must be parsed
+    /// again because there is no usable cache
+    pub fn load(&self) -> Option<Timeline> {
+        match Timeline::load_cache(&self.path) {
+            Ok(timeline) if timeline.source() == self.source => {
+                log::info!("reading timeline from cache '{}'", self.path.display());
+                Some(timeline)
+            }
+            Ok(_) => {
+                log::info!(
+                    "cache '{}' belongs to a different bodyfile, parsing the input again",
+                    self.path.display()
+                );
+                None
+            }
+            Err(CacheError::Io(why)) if why.kind() == ErrorKind::NotFound => None,
+            Err(why) => {
+                log::warn!(
+                    "unable to use cache '{}': {why}, parsing the input again",
+                    self.path.display()
+                );
+                None
+            }
+        }
+    }
+
+    pub fn store(&self, lines: Vec<Bodyfile3Line>) {
+        if let Err(why) = Timeline::new(self.source, lines).save_cache(&self.path) {
+            log::warn!("unable to write cache '{}': {why}", self.path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::{CacheError, SourceFingerprint, Timeline, CACHE_VERSION};
+
+    fn sample() -> Timeline {
+        Timeline::new(
+            SourceFingerprint {
+                size: 1234,
+                modified: 1609459200,
+            },
+            vec![
+                Bodyfile3Line::try_from(
+                    "0|/Users/jdoe/report.pages|1|r/rrw-r--r--|501|20|4096|1609459300|1609459200|1609459200|1577836800",
+                )
+                .unwrap(),
+                Bodyfile3Line::new().with_name("C:/Windows/ü|pipe"),
+            ],
+        )
+    }
+
+    #[test]
+    fn roundtrip() {
+        let timeline = sample();
+        let mut buffer = Vec::new();
+        timeline.write_to(&mut buffer).unwrap();
+
+        let loaded = Timeline::read_from(&mut buffer.as_slice()).unwrap();
+        assert_eq!(loaded.source(), timeline.source());
+        assert_eq!(
+            loaded.into_lines().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            timeline.into_lines().iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let mut buffer = Vec::new();
+        sample().write_to(&mut buffer).unwrap();
+        buffer[8..12].copy_from_slice(&(CACHE_VERSION + 1).to_le_bytes());
+
+        match Timeline::read_from(&mut buffer.as_slice()) {
+            Err(CacheError::VersionMismatch { found, expected }) => {
+                assert_eq!(found, CACHE_VERSION + 1);
+                assert_eq!(expected, CACHE_VERSION);
+            }
+            _ => panic!("a cache with another version must be rejected"),
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        let mut buffer = Vec::new();
+        sample().write_to(&mut buffer).unwrap();
+        buffer.truncate(buffer.len() - 3);
+
+        assert!(matches!(
+            Timeline::read_from(&mut buffer.as_slice()),
+            Err(CacheError::InvalidData(_))
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use chrono::{Datelike, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+/// window of the day (e.g. `09:00-18:00`) which is considered as normal
+/// working time. The start is inclusive, the end is exclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BusinessHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+pub(crate) const DEFAULT_BUSINESS_HOURS: &str = "09:00-18:00";
+
+impl FromStr for BusinessHours {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        match s
+            .split_once('-')
+            .map(|(start, end)| (parse(start), parse(end)))
+        {
+            Some((Some(start), Some(end))) if start < end => Ok(Self { start, end }),
+            _ => bail!("expected a window like '{DEFAULT_BUSINESS_HOURS}', but found '{s}'"),
+        }
+    }
+}
+
+impl BusinessHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// selects events which happened outside of the business hours, which are
+/// compared with the time of day in the destination timezone
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OutsideHoursFilter {
+    hours: BusinessHours,
+    weekdays_only: bool,
+    src_zone: Tz,
+    dst_zone: Tz,
+}
+
+impl OutsideHoursFilter {
+    pub fn new(hours: BusinessHours, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            hours,
+            weekdays_only: false,
+            src_zone,
+            dst_zone,
+        }
+    }
+
+    /// considers Saturday and Sunday as completely outside of the business
+    /// hours
+    pub fn with_weekdays_only(mut self, weekdays_only: bool) -> Self {
+        self.weekdays_only = weekdays_only;
+        self
+    }
+
+    /// returns `true` if `unix_ts`, which has been read from a bodyfile, is
+    /// outside of the business hours. Unset timestamps never match
+    pub fn matches(&self, unix_ts: i64) -> bool {
+        self.matches_timestamp(ForensicsTimestamp::new(
+            unix_ts,
+            self.src_zone,
+            self.dst_zone,
+        ))
+    }
+
+    /// like [`Self::matches`], but for a timestamp which has already been
+    /// converted from the source timezone to UTC
+    pub fn matches_utc(&self, unix_ts: i64) -> bool {
+        self.matches_timestamp(ForensicsTimestamp::new(unix_ts, Tz::UTC, self.dst_zone))
+    }
+
+    fn matches_timestamp(&self, timestamp: ForensicsTimestamp) -> bool {
+        match timestamp.as_datetime() {
+            Some(datetime) => {
+                let is_weekend = matches!(datetime.weekday(), Weekday::Sat | Weekday::Sun);
+                (self.weekdays_only && is_weekend) || !self.hours.contains(datetime.time())
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono_tz::Tz;
+
+    use super::{BusinessHours, OutsideHoursFilter, DEFAULT_BUSINESS_HOURS};
+
+    /// 2021-01-04 (a Monday) 00:00:00 UTC
+    const MONDAY: i64 = 1609718400;
+
+    /// 2021-01-09 (a Saturday) 00:00:00 UTC
+    const SATURDAY: i64 = 1610150400;
+
+    const HOUR: i64 = 3600;
+
+    fn filter() -> OutsideHoursFilter {
+        let hours = BusinessHours::from_str(DEFAULT_BUSINESS_HOURS).unwrap();
+        OutsideHoursFilter::new(hours, Tz::UTC, Tz::UTC)
+    }
+
+    #[test]
+    fn parse() {
+        assert!(BusinessHours::from_str("08:30-17:00").is_ok());
+        assert!(BusinessHours::from_str("18:00-09:00").is_err());
+        assert!(BusinessHours::from_str("09:00").is_err());
+        assert!(BusinessHours::from_str("9 to 5").is_err());
+    }
+
+    #[test]
+    fn outside_hours() {
+        let filter = filter();
+        assert!(filter.matches(MONDAY + 3 * HOUR));
+        assert!(!filter.matches(MONDAY + 14 * HOUR));
+        assert!(!filter.matches(MONDAY + 9 * HOUR));
+        assert!(filter.matches(MONDAY + 18 * HOUR));
+        assert!(!filter.matches(-1));
+    }
+
+    #[test]
+    fn weekdays_only() {
+        assert!(!filter().matches(SATURDAY + 14 * HOUR));
+        assert!(filter()
+            .with_weekdays_only(true)
+            .matches(SATURDAY + 14 * HOUR));
+        assert!(!filter()
+            .with_weekdays_only(true)
+            .matches(MONDAY + 14 * HOUR));
+    }
+
+    #[test]
+    fn destination_timezone() {
+        // 14:00 UTC is 23:00 in Tokyo
+        let hours = BusinessHours::from_str(DEFAULT_BUSINESS_HOURS).unwrap();
+        let filter = OutsideHoursFilter::new(hours, Tz::UTC, Tz::Asia__Tokyo);
+        assert!(filter.matches(MONDAY + 14 * HOUR));
+    }
+
+    #[test]
+    fn source_timezone() {
+        // the bodyfile contains 14:00 Tokyo time, which is 05:00 UTC
+        let hours = BusinessHours::from_str(DEFAULT_BUSINESS_HOURS).unwrap();
+        let filter = OutsideHoursFilter::new(hours, Tz::Asia__Tokyo, Tz::UTC);
+        assert!(filter.matches(MONDAY + 14 * HOUR));
+        assert!(!filter.matches_utc(MONDAY + 14 * HOUR));
+    }
+}
@@ -0,0 +1,122 @@
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::ForensicsTimestamp;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+/// exports every file as an `uco-observable:File` object of the
+/// [CASE/UCO](https://caseontology.org/) ontology. The graph is written
+/// while the files are added, so that the files need not be kept in memory.
+pub(crate) struct CaseUcoExport {
+    src_zone: Tz,
+    dst_zone: Tz,
+    objects: usize,
+}
+
+impl CaseUcoExport {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            objects: 0,
+        }
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line, out: &mut dyn Write) -> std::io::Result<()> {
+        if self.objects == 0 {
+            Self::print_header(out)?;
+        } else {
+            writeln!(out, ",")?;
+        }
+        write!(out, "{}", self.file_object(line))?;
+        self.objects += 1;
+        Ok(())
+    }
+
+    /// terminates the graph, which must be done after all files have been
+    /// added
+    pub fn finish(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        if self.objects == 0 {
+            Self::print_header(out)?;
+        } else {
+            writeln!(out)?;
+        }
+        writeln!(out, "]}}")
+    }
+
+    fn print_header(out: &mut dyn Write) -> std::io::Result<()> {
+        let context = json!({
+            "kb": "http://example.org/kb/",
+            "uco-core": "https://ontology.unifiedcyberontology.org/uco/core/",
+            "uco-observable": "https://ontology.unifiedcyberontology.org/uco/observable/",
+            "xsd": "http://www.w3.org/2001/XMLSchema#",
+        });
+        writeln!(out, "{{\"@context\":{context},\"@graph\":[")
+    }
+
+    fn file_object(&self, line: &Bodyfile3Line) -> Value {
+        // identical lines result in identical ids
+        let id = Self::uuid_of(&line.to_string());
+        let name = line.get_name();
+        let file_name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+
+        let mut timestamps = Map::new();
+        timestamps.insert(
+            "@id".into(),
+            json!(format!("kb:file-system-object-facet-{id}")),
+        );
+        timestamps.insert(
+            "@type".into(),
+            json!("uco-observable:FileSystemObjectFacet"),
+        );
+        for (key, ts) in [
+            ("uco-observable:accessedTime", line.get_atime()),
+            ("uco-observable:modifiedTime", line.get_mtime()),
+            ("uco-observable:metadataChangeTime", line.get_ctime()),
+            ("uco-observable:observableCreatedTime", line.get_crtime()),
+        ] {
+            if let Some(datetime) =
+                ForensicsTimestamp::new(*ts, self.src_zone, self.dst_zone).as_datetime()
+            {
+                timestamps.insert(
+                    key.into(),
+                    json!({ "@type": "xsd:dateTime", "@value": datetime.to_rfc3339() }),
+                );
+            }
+        }
+
+        json!({
+            "@id": format!("kb:file-{id}"),
+            "@type": "uco-observable:File",
+            "uco-core:hasFacet": [
+                {
+                    "@id": format!("kb:file-facet-{id}"),
+                    "@type": "uco-observable:FileFacet",
+                    "uco-observable:fileName": file_name,
+                    "uco-observable:filePath": name,
+                    "uco-observable:sizeInBytes": {
+                        "@type": "xsd:integer",
+                        "@value": line.get_size(),
+                    },
+                },
+                timestamps,
+            ],
+        })
+    }
+
+    /// formats the first 128 bits of the SHA-256 hash of `value` like a UUID
+    fn uuid_of(value: &str) -> String {
+        let hash = Sha256::digest(value.as_bytes());
+        let hex: String = hash[..16].iter().map(|b| format!("{b:02x}")).collect();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
@@ -1,25 +1,54 @@
-use clap::{Parser, ValueHint};
-use clio::Input;
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
 use log::LevelFilter;
 use chrono_tz::Tz;
 
-use dfir_toolkit::common::{HasVerboseFlag,TzArgument};
+use dfir_toolkit::common::bodyfile::{FileCategory, Filesystem, OnUnparseable, TimestampKind};
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs, TimeRangeArgs, TzArgument};
 
 use super::OutputFormat;
+use crate::business_hours::{BusinessHours, DEFAULT_BUSINESS_HOURS};
+use crate::hashes::HashManifestFormat;
+use crate::correlate::{CorrelationKey, HostBodyfile};
+use crate::gaps::parse_duration;
+use crate::histogram::HistogramBucket;
+use crate::expression::Expression;
+use crate::output::{CsvDialect, NullDate, OutputField, SplitBucket, DEFAULT_COORDINATES_PATTERN, DEFAULT_NAME_WIDTH};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-#[cfg(feature = "gzip")]
+#[cfg(all(feature = "gzip", feature = "http"))]
 const BODYFILE_HELP: &str =
-    "path to input file or '-' for stdin (files ending with .gz will be treated as being gzipped)";
-#[cfg(not(feature = "gzip"))]
-const BODYFILE_HELP: &str = "path to input file or '-' for stdin";
+    "path to input file, '-' for stdin or a http(s) URL (files ending with .gz or .zst will be decompressed with gzip or zstd). Can be specified multiple times; glob patterns like *.bodyfile and alternatives like {a,b} are expanded";
+#[cfg(all(feature = "gzip", not(feature = "http")))]
+const BODYFILE_HELP: &str =
+    "path to input file or '-' for stdin (files ending with .gz or .zst will be decompressed with gzip or zstd). Can be specified multiple times; glob patterns like *.bodyfile and alternatives like {a,b} are expanded";
+#[cfg(all(not(feature = "gzip"), feature = "http"))]
+const BODYFILE_HELP: &str = "path to input file, '-' for stdin or a http(s) URL. Can be specified multiple times; glob patterns like *.bodyfile and alternatives like {a,b} are expanded";
+#[cfg(not(any(feature = "gzip", feature = "http")))]
+const BODYFILE_HELP: &str = "path to input file or '-' for stdin. Can be specified multiple times; glob patterns like *.bodyfile and alternatives like {a,b} are expanded";
 
 /// replacement for `mactime`
 #[derive(Parser)]
 #[clap(name="mactime2", author, version, long_about = None)]
-
 pub struct Cli {
-    #[clap(short('b'), value_parser, value_hint=ValueHint::FilePath, default_value="-", help=BODYFILE_HELP, display_order(100))]
-    pub(crate) input_file: Input,
+    #[command(subcommand)]
+    pub(crate) command: Option<Mactime2Command>,
+
+    #[clap(short('b'), value_hint=ValueHint::FilePath, default_value="-", help=BODYFILE_HELP, display_order(100))]
+    pub(crate) input_files: Vec<String>,
+
+    /// after the bodyfile has been read, wait for lines which are appended to it (like
+    /// `tail -f`), and display their events immediately. Events are displayed in the
+    /// order of the lines instead of being sorted. Press Ctrl-C to stop
+    #[clap(long("follow"), conflicts_with_all(["first_per_file", "last_per_file", "fold_repeats", "collapse_access_storm", "session_gap", "deterministic_order", "limit", "cache", "newer_than_file", "count_only", "reconstruct_paths", "allow_multiline_names", "strict_utf8", "sample"]), display_order(105))]
+    pub(crate) follow: bool,
+
+    /// maximum time to wait until the connection to the server has been
+    /// established, if the bodyfile is read from a URL
+    #[cfg(feature = "http")]
+    #[clap(long("timeout"), value_name = "SECONDS", display_order(110))]
+    pub(crate) timeout: Option<u64>,
 
     /// output format, if not specified, default value is 'txt'
     #[clap(
@@ -40,6 +69,213 @@ pub struct Cli {
     #[clap(short('j'), display_order(620))]
     pub(crate) json_format: bool,
 
+    /// output one JSON object per file instead of one line per event. Every object
+    /// contains the metadata of the file and all its timestamps in a nested
+    /// `timestamps` object, where unset timestamps are `null`
+    #[clap(long("json-per-file"), conflicts_with_all(["output_format", "csv_format", "json_format", "fields", "pretty", "split_by", "count_only", "first_per_file", "last_per_file", "fold_repeats", "follow"]), display_order(625))]
+    pub(crate) json_per_file: bool,
+
+    /// comma separated list of columns to be printed (txt and csv only). If specified,
+    /// the csv output starts with a header line
+    #[clap(long("fields"), value_enum, value_delimiter = ',', display_order(630))]
+    pub(crate) fields: Option<Vec<OutputField>>,
+
+    /// render the txt output as an aligned table with a header. The column widths are
+    /// computed from the data, so that the events are displayed after all of them have
+    /// been read
+    #[clap(long("pretty"), conflicts_with("split_by"), display_order(631))]
+    pub(crate) pretty: bool,
+
+    /// maximum width of the file name column of the `--pretty` output. Longer names are
+    /// truncated with an ellipsis
+    #[clap(long("name-width"), value_name = "WIDTH", default_value_t = DEFAULT_NAME_WIDTH, requires("pretty"), display_order(632))]
+    pub(crate) name_width: usize,
+
+    /// emit the MACB flags as the boolean columns `accessed`, `modified`, `changed`
+    /// and `created` (csv and json only). In csv output, they replace the `Type`
+    /// column, and the output starts with a header line
+    #[clap(long("split-macb"), display_order(633))]
+    pub(crate) split_macb: bool,
+
+    /// add the depth of every file in the directory tree (the number of path separators
+    /// in its name) as last column (txt and csv) or as `file.depth` (json)
+    #[clap(long("depth-column"), conflicts_with("json_per_file"), display_order(634))]
+    pub(crate) depth_column: bool,
+
+    /// how timestamps which are not set are rendered in csv and json output: `skip`
+    /// leaves them out, `epoch` uses `1970-01-01T00:00:00+00:00`, and an ISO 8601 date
+    /// (e.g. `2099-12-31T00:00:00+00:00`) is used as it is
+    #[clap(long("null-date"), value_name = "POLICY", value_parser = NullDate::from_str, default_value = "skip", display_order(635))]
+    pub(crate) null_date: NullDate,
+
+    /// render timestamps as milliseconds since the unix epoch instead of ISO 8601
+    /// strings (csv and json only). The json output always uses milliseconds
+    #[clap(long("epoch-ms"), display_order(636))]
+    pub(crate) epoch_ms: bool,
+
+    /// delimiter, quoting and line terminator of the csv output: `rfc4180` (default)
+    /// uses commas and `\r\n`, `excel` uses semicolons and `\r\n`, `unix` uses commas
+    /// and `\n` and quotes every field, and `legacy` uses commas and `\n` and quotes
+    /// only the file name, like mactime2 did before the dialects were added
+    #[clap(long("csv-dialect"), value_enum, value_name = "DIALECT", display_order(637))]
+    pub(crate) csv_dialect: Option<CsvDialect>,
+
+    /// comma separated list of file categories to be displayed (e.g. `executable,script`).
+    /// The category of a file is derived from the extension of its name
+    #[clap(long("category"), value_enum, value_delimiter = ',', display_order(640))]
+    pub(crate) categories: Option<Vec<FileCategory>>,
+
+    /// display only alternate data streams (e.g. `setup.exe:Zone.Identifier`),
+    /// which may contain hidden data or the origin of downloaded files
+    #[clap(long("only-ads"), conflicts_with("no_ads"), display_order(645))]
+    pub(crate) only_ads: bool,
+
+    /// do not display alternate data streams
+    #[clap(long("no-ads"), display_order(646))]
+    pub(crate) no_ads: bool,
+
+    /// display only deleted entries, i.e. entries whose name is marked with
+    /// `(deleted)` or `* ` by TSK
+    #[clap(long("only-deleted"), conflicts_with("no_deleted"), display_order(649))]
+    pub(crate) only_deleted: bool,
+
+    /// do not display deleted entries
+    #[clap(long("no-deleted"), display_order(651))]
+    pub(crate) no_deleted: bool,
+
+    /// display only directories. The type is taken from the mode column (e.g.
+    /// `d/drwxr-xr-x`), or from a type marker at the beginning of the name (e.g.
+    /// `d//etc`) if the mode column does not contain a type
+    #[clap(long("only-dirs"), conflicts_with("only_files"), display_order(647))]
+    pub(crate) only_dirs: bool,
+
+    /// display only regular files (see `--only-dirs`)
+    #[clap(long("only-files"), display_order(648))]
+    pub(crate) only_files: bool,
+
+    /// only display lines which match this expression, e.g.
+    /// `size > 1000000 && name ~ "\\.exe$" && mtime > 2021-01-01`.
+    /// Supported operators are `==`, `!=`, `<`, `<=`, `>`, `>=`, `~` (regular expression),
+    /// `&&`, `||` and `!`
+    #[clap(long("where"), value_parser = Expression::from_str, display_order(650))]
+    pub(crate) expression: Option<Expression>,
+
+    /// do not display the events, but only the number of events and the number of
+    /// distinct files, after all filters have been applied
+    #[clap(long("count-only"), display_order(655))]
+    pub(crate) count_only: bool,
+
+    /// display only the first N events, after all filters have been applied and
+    /// the events have been sorted
+    #[clap(long("limit"), value_name = "N", display_order(656))]
+    pub(crate) limit: Option<usize>,
+
+    /// display a random subset of N events, which are selected uniformly from
+    /// the whole timeline after all filters have been applied. In contrast to
+    /// `--limit`, the subset is not biased to the beginning of the timeline.
+    /// The events are still displayed in chronological order
+    #[clap(long("sample"), value_name = "N", conflicts_with_all(["limit", "first_per_file", "last_per_file", "fold_repeats"]), display_order(658))]
+    pub(crate) sample: Option<usize>,
+
+    /// seed of the random number generator used by `--sample`. Using the same
+    /// seed on the same input always selects the same events
+    #[clap(long("seed"), value_name = "S", requires("sample"), display_order(659))]
+    pub(crate) seed: Option<u64>,
+
+    /// write the events into one file per day, week or month (e.g.
+    /// `timeline-2021-02-18.csv`) instead of printing them (csv, txt and timesketch only).
+    /// The buckets use the destination timezone
+    #[clap(long("split-by"), value_enum, value_name = "BUCKET", conflicts_with("output"), display_order(653))]
+    pub(crate) split_by: Option<SplitBucket>,
+
+    /// directory in which the files created by `--split-by` are stored
+    #[clap(long("split-dir"), value_name = "DIR", value_hint = ValueHint::DirPath, default_value = ".", requires("split_by"), display_order(654))]
+    pub(crate) split_dir: PathBuf,
+
+    /// colorize the MACB column of the txt output, and highlight rows of files which
+    /// seem to have been timestomped (i.e. whose mtime or crtime is later than their
+    /// ctime). `auto` uses colors only if the output is written to a terminal and
+    /// not into an `--output` file
+    #[clap(long("color"), value_enum, default_value_t = ColorMode::Auto, display_order(660))]
+    pub(crate) color: ColorMode,
+
+    /// collapse consecutive rows for the same file name and MACB flags, which occur
+    /// within this number of seconds, into a single row annotated with the number of repeats
+    #[clap(long("fold-repeats"), value_name = "SECONDS", display_order(670))]
+    pub(crate) fold_repeats: Option<u64>,
+
+    /// collapse bursts of at least this number of atime-only events (e.g. caused
+    /// by an antivirus scan), which occur within `--access-storm-window` seconds,
+    /// into a single summary row. Events which change other timestamps are kept
+    #[clap(long("collapse-access-storm"), value_name = "THRESHOLD", display_order(671))]
+    pub(crate) collapse_access_storm: Option<usize>,
+
+    /// maximum duration of an access storm in seconds
+    #[clap(long("access-storm-window"), value_name = "SECONDS", default_value_t = 60, requires("collapse_access_storm"), display_order(672))]
+    pub(crate) access_storm_window: u64,
+
+    /// group the events into sessions of activity, which are separated by
+    /// periods of inactivity of at least this duration (e.g. `30m` or `1h`; a
+    /// number without unit is interpreted as seconds). The name of every event
+    /// is annotated with the id of its session, and the start, end and number of
+    /// events of every session are printed to stderr
+    #[clap(long("session-gap"), value_name = "DURATION", value_parser = parse_duration, display_order(673))]
+    pub(crate) session_gap: Option<u64>,
+
+    /// regular expression which finds coordinates in file names (geojson only).
+    /// It must contain the named capture groups `lat` and `lon`
+    #[clap(long("coordinates"), value_name = "REGEX", default_value = DEFAULT_COORDINATES_PATTERN, display_order(675))]
+    pub(crate) coordinates: String,
+
+    /// display only the chronologically first event of every file (identified by
+    /// its name and inode)
+    #[clap(long("first-per-file"), conflicts_with("last_per_file"), display_order(680))]
+    pub(crate) first_per_file: bool,
+
+    /// display only the chronologically last event of every file (identified by
+    /// its name and inode)
+    #[clap(long("last-per-file"), display_order(690))]
+    pub(crate) last_per_file: bool,
+
+    /// display only events of these timestamp kinds (e.g. `b` for the creation
+    /// time). Flags of other timestamps with the same value are hidden. The
+    /// histogram, gaps, summary and profile reports count only these events
+    #[clap(long("only"), value_enum, value_delimiter = ',', value_name = "KINDS", display_order(692))]
+    pub(crate) only: Option<Vec<TimestampKind>>,
+
+    /// display only events outside of the business hours (default: 09:00-18:00),
+    /// which are compared with the time of day in the destination timezone. The
+    /// histogram, gaps, summary and profile reports count only these events
+    #[clap(long("outside-hours"), value_name = "WINDOW", value_parser = BusinessHours::from_str, num_args = 0..=1, default_missing_value = DEFAULT_BUSINESS_HOURS, display_order(696))]
+    pub(crate) outside_hours: Option<BusinessHours>,
+
+    /// treat all events on Saturday and Sunday as outside of the business hours
+    #[clap(long("weekdays-only"), requires("outside_hours"), display_order(698))]
+    pub(crate) weekdays_only: bool,
+
+    /// do not display one event per timestamp, but every file once, ordered by this
+    /// timestamp (e.g. `crtime` for the creation order). Files whose selected
+    /// timestamp is not set are skipped
+    #[clap(long("sort-by"), value_enum, value_name = "KIND", conflicts_with_all(["only", "first_per_file", "last_per_file"]), display_order(693))]
+    pub(crate) sort_by: Option<TimestampKind>,
+
+    /// order events with the same timestamp by the name and inode of their file and by
+    /// their MACB flags, instead of by their position in the bodyfile. Identical lines
+    /// result in identical output, even if the order of the lines has changed
+    #[clap(long("deterministic-order"), display_order(694))]
+    pub(crate) deterministic_order: bool,
+
+    /// store the parsed bodyfile in this binary cache file, and read it from there
+    /// on subsequent runs, as long as the bodyfile has not been changed
+    #[clap(long("cache"), value_name = "FILE", value_hint = ValueHint::FilePath, display_order(695))]
+    pub(crate) cache: Option<PathBuf>,
+
+    /// display only events after the timestamp stored in this state file, and store the
+    /// timestamp of the latest displayed event in it. This allows repeated runs to display
+    /// only new events. Subcommands use the stored timestamp, but do not update it
+    #[clap(long("newer-than-file"), value_name = "STATE", value_hint = ValueHint::FilePath, display_order(697))]
+    pub(crate) newer_than_file: Option<PathBuf>,
+
     /// name of offset of source timezone (or 'list' to display all possible values
     #[clap(short('f'), long("from-timezone"), display_order(300), default_value_t=TzArgument::Tz(Tz::UTC))]
     pub src_zone: TzArgument,
@@ -48,6 +284,11 @@ pub struct Cli {
     #[clap(short('t'), long("to-timezone"), display_order(400), default_value_t=TzArgument::Tz(Tz::UTC))]
     pub dst_zone: TzArgument,
 
+    /// read the destination timezone from the `TimeZoneInformation` key of a Windows
+    /// SYSTEM hive (falls back to UTC if the key is missing)
+    #[clap(long("tz-from-registry"), value_name = "SYSTEM", value_hint = ValueHint::FilePath, conflicts_with("dst_zone"), display_order(410))]
+    pub(crate) tz_from_registry: Option<PathBuf>,
+
     // /// convert only, but do not sort
     // #[clap(short('c'), long("convert-only"), display_order(450))]
     // pub(crate) dont_sort: bool,
@@ -55,10 +296,258 @@ pub struct Cli {
     #[clap(long("strict"), display_order(500))]
     pub(crate) strict_mode: bool,
 
+    /// remove this leading path (e.g. the mount point used with `fls -m`) from all file names
+    #[clap(long("strip-prefix"), value_name = "PREFIX", display_order(505))]
+    pub(crate) strip_prefix: Option<String>,
+
+    /// convert backslashes to slashes and uppercase drive letters in file names
+    #[clap(long("normalize-paths"), display_order(510))]
+    pub(crate) normalize_paths: bool,
+
+    /// treat names which only differ in their case (e.g. `C:/Windows` and `C:/windows`)
+    /// as the same file when identifying files (e.g. for `--first-per-file` and
+    /// `--count-only`) and when matching names with `--where`. The displayed names
+    /// are not changed
+    #[clap(long("case-insensitive-paths"), display_order(511))]
+    pub(crate) case_insensitive_paths: bool,
+
+    /// reconstruct full paths from names which are relative to the MFT entry of their
+    /// parent directory (e.g. `1234/report.docx`). This requires the whole bodyfile to be
+    /// read before the first event can be displayed
+    #[clap(long("reconstruct-paths"), display_order(512))]
+    pub(crate) reconstruct_paths: bool,
+
+    /// replace user names in profile paths (e.g. C:/Users/jdoe) by pseudonyms like user001
+    #[clap(long("pseudonymize"), display_order(515))]
+    pub(crate) pseudonymize: bool,
+
+    /// replace every hash by its HMAC-SHA256. Equal hashes result in equal values, so that
+    /// hashes can be correlated without revealing them. The key is read from the file specified
+    /// by `--hash-key-file`, or from the environment variable MACTIME2_HASH_KEY
+    #[clap(long("anonymize-hashes"), display_order(516))]
+    pub(crate) anonymize_hashes: bool,
+
+    /// file which contains the key of `--anonymize-hashes`. A trailing line break is ignored
+    #[clap(long("hash-key-file"), value_name = "FILE", value_hint = ValueHint::FilePath, requires("anonymize_hashes"), display_order(517))]
+    pub(crate) hash_key_file: Option<PathBuf>,
+
+    /// join lines with too few columns with the following lines, to read file names which
+    /// contain line breaks. Names which contain both line breaks and pipe symbols are not supported
+    #[clap(long("allow-multiline-names"), display_order(518))]
+    pub(crate) allow_multiline_names: bool,
+
+    /// ignore the creation time (crtime), e.g. for Windows-only workflows which do
+    /// not use the `b` column
+    #[clap(long("no-crtime"), display_order(519))]
+    pub(crate) no_crtime: bool,
+
+    /// abort after this number of lines could not be parsed (0 means unlimited)
+    #[clap(long("max-errors"), display_order(520), default_value_t = 100)]
+    pub(crate) max_errors: usize,
+
+    /// how lines which cannot be parsed (e.g. banners like `=== Volume C: ===`, which
+    /// were injected by a collector) are handled: `error` reports them as errors, `skip`
+    /// skips them and only warns about their number, and `collect` additionally logs
+    /// them after the bodyfile has been read. Skipped lines do not count as errors
+    #[clap(long("on-unparseable"), value_name = "POLICY", value_enum, default_value_t = OnUnparseable::Error, display_order(521))]
+    pub(crate) on_unparseable: OnUnparseable,
+
+    /// report lines which are not valid UTF-8 as errors and skip them, instead of
+    /// replacing the invalid characters by U+FFFD
+    #[clap(long("strict-utf8"), display_order(522))]
+    pub(crate) strict_utf8: bool,
+
+    #[clap(flatten)]
+    pub(crate) time_range: TimeRangeArgs,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
     #[clap(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
 
+#[derive(ValueEnum, Clone, Copy)]
+pub(crate) enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// enables or disables colored output for the whole process
+    pub fn apply(&self, output: &OutputArgs) {
+        let colorize = match self {
+            Self::Auto => output.output.is_none() && std::io::stdout().is_terminal(),
+            Self::Always => true,
+            Self::Never => false,
+        };
+        colored::control::set_override(colorize);
+    }
+}
+
+#[derive(Subcommand, Clone)]
+pub(crate) enum Mactime2Command {
+    /// count the number of events per hour or per day
+    Histogram {
+        /// size of the time buckets
+        #[clap(long("bucket"), value_enum, default_value_t = HistogramBucket::Hour)]
+        bucket: HistogramBucket,
+
+        /// timestamp kinds to be counted
+        #[clap(long("kind"), value_enum, value_delimiter = ',', default_value = "a,m,c,b")]
+        kinds: Vec<TimestampKind>,
+
+        /// display an ASCII bar for every bucket
+        #[clap(long("bar"))]
+        bar: bool,
+    },
+
+    /// display the periods of the timeline without any event, together with
+    /// their start, end and duration
+    Gaps {
+        /// minimum length of a reported period, e.g. `30m`, `3h` or `1d`. A
+        /// number without unit is interpreted as seconds
+        #[clap(long("min"), value_name = "DURATION", value_parser = parse_duration)]
+        min: u64,
+    },
+
+    /// display the directories with the most events
+    Summary {
+        /// count the events per parent directory
+        #[clap(long("by-dir"), required = true)]
+        by_dir: bool,
+
+        /// number of directories to display
+        #[clap(long("top"), default_value_t = 10)]
+        top: usize,
+    },
+
+    /// summarize the activity of every user: the number of events, the first
+    /// and the last event, and the directories with the most events. The user
+    /// is taken from the profile directory (e.g. `C:/Users/<name>/...` or
+    /// `/home/<name>/...`), or from the uid column for all other files
+    Profile {
+        /// create a profile per user
+        #[clap(long("by-user"), required = true)]
+        by_user: bool,
+
+        /// number of directories to display for every user
+        #[clap(long("top"), default_value_t = 3)]
+        top: usize,
+    },
+
+    /// export the directory tree as a Graphviz graph (DOT language), whose
+    /// nodes are labeled with the number of events in every directory and
+    /// its subdirectories. More active directories are drawn larger and darker
+    Dot {
+        /// merge all directories below this depth into their ancestor
+        #[clap(long("max-depth"), value_name = "DEPTH")]
+        max_depth: Option<usize>,
+    },
+
+    /// display the largest files together with their timestamps. Files of the
+    /// same size are ordered by their modification time
+    Largest {
+        /// number of files to display
+        #[clap(long("top"), default_value_t = 10)]
+        top: usize,
+    },
+
+    /// report timestamps which cannot have been stored by the specified filesystem,
+    /// e.g. odd modification times on FAT
+    Check {
+        /// filesystem whose timestamp constraints are checked
+        #[clap(long("fs"), value_enum)]
+        fs: Filesystem,
+    },
+
+    /// display per-column statistics, e.g. the number of distinct UIDs or the
+    /// earliest and latest timestamps
+    Stats,
+
+    /// compare the md5 column with the hashes of the referenced files. Besides
+    /// MD5, SHA-1 and SHA-256 hashes are detected by their length. Lines without
+    /// hash are skipped
+    Verify {
+        /// directory which contains the files referenced by the bodyfile, e.g.
+        /// the mount point of the analyzed filesystem
+        #[clap(long("root"), value_hint=ValueHint::DirPath)]
+        root: PathBuf,
+    },
+
+    /// print the hashes and names of all files, e.g. to look them up in a
+    /// hash database. Lines with the placeholder `0` are skipped, and duplicates
+    /// are printed only once
+    Hashes {
+        /// output format of the hash manifest
+        #[clap(long("style"), value_enum, default_value_t = HashManifestFormat::Tsv)]
+        style: HashManifestFormat,
+    },
+
+    /// export all files as a JSON-LD graph of the CASE/UCO ontology
+    /// (<https://caseontology.org/>), which can be imported by standards-based
+    /// forensic platforms. Every file becomes an `uco-observable:File` object
+    CaseUco,
+
+    /// find files which appear on several hosts, e.g. tools which have been
+    /// copied during lateral movement. For every file, the time when it first
+    /// appeared on each host is displayed. The input file (`-b`) is not read
+    Correlate {
+        /// bodyfile of a host, together with a label which identifies the host.
+        /// Specify this option for every host, at least twice
+        #[clap(long("host"), value_name = "LABEL=FILE", value_parser = HostBodyfile::from_str, required = true)]
+        hosts: Vec<HostBodyfile>,
+
+        /// property which identifies a file on different hosts
+        #[clap(long("by"), value_enum, default_value_t = CorrelationKey::Md5)]
+        key: CorrelationKey,
+
+        /// report only files which appeared on all hosts within this duration,
+        /// e.g. `30m`, `3h` or `1d`
+        #[clap(long("window"), value_name = "DURATION", value_parser = parse_duration)]
+        window: Option<u64>,
+    },
+
+    /// render the timeline as a self-contained report, which can be shared
+    /// with people who do not use a terminal
+    Report {
+        /// create an HTML page with a sortable and filterable table and a chart
+        /// of the number of events per day
+        #[clap(long("html"), required = true)]
+        html: bool,
+    },
+
+    /// watch a directory for new bodyfiles (e.g. the intake directory of a
+    /// collection pipeline), and create a timeline of every file as soon as
+    /// its size has not changed for some time. Processed files are moved into
+    /// another directory. The input file (`-b`) is not read
+    Watch {
+        /// directory to watch; subdirectories and hidden files are ignored
+        #[clap(value_hint = ValueHint::DirPath)]
+        dir: PathBuf,
+
+        /// directory into which processed files are moved [default: <DIR>/processed]
+        #[clap(long("processed-dir"), value_name = "DIR", value_hint = ValueHint::DirPath)]
+        processed_dir: Option<PathBuf>,
+
+        /// directory into which files are moved which could not be processed
+        /// [default: <DIR>/failed]
+        #[clap(long("failed-dir"), value_name = "DIR", value_hint = ValueHint::DirPath)]
+        failed_dir: Option<PathBuf>,
+
+        /// time during which the size of a file must not change before it is
+        /// processed, e.g. `10s` or `1m`. A number without unit is interpreted
+        /// as seconds
+        #[clap(long("settle-time"), value_name = "DURATION", value_parser = parse_duration, default_value = "2")]
+        settle_time: u64,
+
+        /// exit after this number of files has been processed
+        #[clap(long("max-files"), value_name = "COUNT")]
+        max_files: Option<usize>,
+    },
+}
+
 impl HasVerboseFlag for Cli {
     fn log_level_filter(&self) -> LevelFilter {
         self.verbose.log_level_filter()
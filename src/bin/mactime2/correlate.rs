@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::str::FromStr;
+
+use anyhow::bail;
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, HashAlgo, TimestampKind};
+use dfir_toolkit::common::{ForensicsTimestamp, TimeRange};
+
+use crate::gaps::format_duration;
+use crate::stream::InputLocation;
+
+/// bodyfile of a single host, specified as `LABEL=FILE`
+#[derive(Clone, Debug)]
+pub(crate) struct HostBodyfile {
+    pub label: String,
+    pub bodyfile: InputLocation,
+}
+
+impl FromStr for HostBodyfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((label, bodyfile)) if !label.is_empty() => Ok(Self {
+                label: label.to_owned(),
+                bodyfile: InputLocation::from_str(bodyfile)?,
+            }),
+            _ => bail!("expected LABEL=FILE, but found '{s}'"),
+        }
+    }
+}
+
+/// property of a file which is compared between hosts
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub(crate) enum CorrelationKey {
+    /// the hash in the md5 column. Lines without hash are skipped
+    #[default]
+    Md5,
+
+    /// the inode and the name of the file
+    Identity,
+}
+
+/// first appearance of a file on a single host
+struct Sighting {
+    first_seen: i64,
+    name: String,
+}
+
+/// finds files which appear on more than one host
+pub(crate) struct Correlation {
+    key: CorrelationKey,
+    src_zone: Tz,
+    dst_zone: Tz,
+    window: Option<i64>,
+    time_range: TimeRange,
+    sightings: HashMap<String, BTreeMap<String, Sighting>>,
+}
+
+impl Correlation {
+    pub fn new(key: CorrelationKey, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            key,
+            src_zone,
+            dst_zone,
+            window: None,
+            time_range: TimeRange::default(),
+            sightings: HashMap::new(),
+        }
+    }
+
+    /// reports only files whose first appearances on the different hosts are
+    /// at most `window` seconds apart
+    pub fn with_window(mut self, window: Option<u64>) -> Self {
+        self.window = window.map(|window| i64::try_from(window).unwrap_or(i64::MAX));
+        self
+    }
+
+    /// ignores all timestamps outside of `time_range`
+    pub fn with_time_range(mut self, time_range: TimeRange) -> Self {
+        self.time_range = time_range;
+        self
+    }
+
+    fn key_of(&self, line: &Bodyfile3Line) -> Option<String> {
+        match self.key {
+            CorrelationKey::Md5 => match line.hash_algorithm() {
+                HashAlgo::None => None,
+                _ => Some(line.get_md5().to_lowercase()),
+            },
+            CorrelationKey::Identity => Some(line.identity_key()),
+        }
+    }
+
+    pub fn add(&mut self, host: &str, line: &Bodyfile3Line) {
+        let Some(key) = self.key_of(line) else {
+            return;
+        };
+        let Some(first_seen) = TimestampKind::ALL
+            .iter()
+            .map(|kind| line.get_timestamp(*kind))
+            .filter(|ts| *ts != -1 && self.time_range.contains(*ts))
+            .min()
+        else {
+            return;
+        };
+
+        let sightings = self.sightings.entry(key).or_default();
+        match sightings.get_mut(host) {
+            Some(sighting) if sighting.first_seen <= first_seen => (),
+            Some(sighting) => {
+                sighting.first_seen = first_seen;
+                sighting.name = line.get_name().to_owned();
+            }
+            None => {
+                sightings.insert(
+                    host.to_owned(),
+                    Sighting {
+                        first_seen,
+                        name: line.get_name().to_owned(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// returns the files which appear on at least two hosts, ordered by
+    /// their first appearance. The hosts of every file are ordered by the
+    /// time when the file appeared on them
+    fn common_files(&self) -> Vec<(&String, Vec<(&String, &Sighting)>)> {
+        let mut files: Vec<_> = self
+            .sightings
+            .iter()
+            .filter(|(_, sightings)| sightings.len() >= 2)
+            .map(|(key, sightings)| {
+                let mut hosts: Vec<_> = sightings.iter().collect();
+                hosts.sort_by_key(|(host, sighting)| (sighting.first_seen, *host));
+                (key, hosts)
+            })
+            .filter(|(_, hosts)| match self.window {
+                Some(window) => Self::spread(hosts) <= window,
+                None => true,
+            })
+            .collect();
+        files.sort_by_key(|(key, hosts)| (hosts[0].1.first_seen, *key));
+        files
+    }
+
+    fn spread(hosts: &[(&String, &Sighting)]) -> i64 {
+        hosts[hosts.len() - 1].1.first_seen - hosts[0].1.first_seen
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        for (key, hosts) in self.common_files() {
+            writeln!(
+                out,
+                "{key}\t{} hosts\tspread {}",
+                hosts.len(),
+                format_duration(Self::spread(&hosts))
+            )?;
+            for (host, sighting) in hosts {
+                writeln!(
+                    out,
+                    "\t{host}\t{}\t{}",
+                    ForensicsTimestamp::new(sighting.first_seen, self.src_zone, self.dst_zone),
+                    sighting.name
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::{Correlation, CorrelationKey};
+
+    const HASH: &str = "b1946ac92492d2347c6235b4d2611184";
+
+    #[test]
+    fn files_on_multiple_hosts() {
+        let mut correlation = Correlation::new(CorrelationKey::Md5, Tz::UTC, Tz::UTC);
+        correlation.add("ws01", &Bodyfile3Line::new().with_md5(HASH).with_mtime(200));
+        correlation.add(
+            "ws01",
+            &Bodyfile3Line::new().with_md5(HASH).with_crtime(150),
+        );
+        correlation.add("dc01", &Bodyfile3Line::new().with_md5(HASH).with_mtime(100));
+        correlation.add("dc01", &Bodyfile3Line::new().with_md5("0").with_mtime(100));
+
+        let files = correlation.common_files();
+        assert_eq!(files.len(), 1);
+        let hosts: Vec<_> = files[0]
+            .1
+            .iter()
+            .map(|(host, sighting)| (host.as_str(), sighting.first_seen))
+            .collect();
+        assert_eq!(hosts, vec![("dc01", 100), ("ws01", 150)]);
+
+        let correlation = correlation.with_window(Some(49));
+        assert!(correlation.common_files().is_empty());
+    }
+}
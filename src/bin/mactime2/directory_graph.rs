@@ -0,0 +1,147 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::TimeRange;
+
+use crate::summary::DirectorySummary;
+
+const MIN_FONT_SIZE: usize = 10;
+const MAX_FONT_SIZE: usize = 30;
+
+/// number of colors of the `reds9` color scheme of Graphviz
+const COLORS: usize = 9;
+
+/// builds a directory tree from the names of a bodyfile, and renders it as
+/// a Graphviz graph. Every directory is labeled with the number of events
+/// of the files inside of it and of all its subdirectories
+#[derive(Default)]
+pub(crate) struct DirectoryGraph {
+    counts: BTreeMap<String, usize>,
+    edges: BTreeSet<(String, String)>,
+    max_depth: Option<usize>,
+    time_range: TimeRange,
+}
+
+impl DirectoryGraph {
+    /// directories below this depth are merged into their ancestor at
+    /// `max_depth`
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// ignores all timestamps outside of `time_range`
+    pub fn with_time_range(mut self, time_range: TimeRange) -> Self {
+        self.time_range = time_range;
+        self
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        // identical timestamps of a file are merged into one event
+        let events = TimestampKind::ALL
+            .iter()
+            .map(|kind| line.get_timestamp(*kind))
+            .filter(|ts| *ts != -1 && self.time_range.contains(*ts))
+            .collect::<BTreeSet<_>>()
+            .len();
+        if events == 0 {
+            return;
+        }
+
+        let mut ancestors = Self::ancestors_of(DirectorySummary::parent_of(line.get_name()));
+        if let Some(max_depth) = self.max_depth {
+            ancestors.truncate(max_depth.max(1));
+        }
+        for dir in ancestors.iter() {
+            *self.counts.entry(dir.to_string()).or_insert(0) += events;
+        }
+        for (parent, child) in ancestors.iter().zip(ancestors.iter().skip(1)) {
+            self.edges.insert((parent.to_string(), child.to_string()));
+        }
+    }
+
+    /// returns `dir` and all of its ancestors, beginning with the root
+    fn ancestors_of(dir: &str) -> Vec<&str> {
+        let mut ancestors: Vec<_> = dir
+            .match_indices(['/', '\\'])
+            .map(|(idx, _)| if idx == 0 { &dir[..1] } else { &dir[..idx] })
+            .collect();
+        ancestors.push(dir);
+        ancestors.dedup();
+        ancestors
+    }
+
+    /// the last component of `dir`, or `dir` itself if it is a root
+    fn label_of(dir: &str) -> &str {
+        match dir.rfind(['/', '\\']) {
+            Some(idx) if idx + 1 < dir.len() => &dir[idx + 1..],
+            _ => dir,
+        }
+    }
+
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    /// renders the graph in the DOT language. More active directories are
+    /// drawn with a larger font and a darker color
+    pub fn to_dot(&self) -> String {
+        let max_count = self.counts.values().max().copied().unwrap_or(1);
+        let mut lines = vec![
+            "digraph timeline {".to_owned(),
+            "    rankdir=LR;".to_owned(),
+            "    node [shape=box, style=filled, colorscheme=reds9];".to_owned(),
+        ];
+        for (dir, count) in self.counts.iter() {
+            let font_size = MIN_FONT_SIZE + (count * (MAX_FONT_SIZE - MIN_FONT_SIZE)) / max_count;
+            let color = 1 + (count * (COLORS - 1)) / max_count;
+            lines.push(format!(
+                "    {} [label={}, fontsize={font_size}, fillcolor={color}];",
+                Self::quote(dir),
+                Self::quote(&format!("{}\n{count} events", Self::label_of(dir)))
+                    .replace('\n', "\\n"),
+            ));
+        }
+        for (parent, child) in self.edges.iter() {
+            lines.push(format!(
+                "    {} -> {};",
+                Self::quote(parent),
+                Self::quote(child)
+            ));
+        }
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        writeln!(out, "{}", self.to_dot())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DirectoryGraph;
+
+    #[test]
+    fn ancestors_of() {
+        assert_eq!(
+            DirectoryGraph::ancestors_of("/etc/ssh"),
+            vec!["/", "/etc", "/etc/ssh"]
+        );
+        assert_eq!(DirectoryGraph::ancestors_of("/"), vec!["/"]);
+        assert_eq!(
+            DirectoryGraph::ancestors_of("C:\\Windows\\System32"),
+            vec!["C:", "C:\\Windows", "C:\\Windows\\System32"]
+        );
+        assert_eq!(DirectoryGraph::ancestors_of("."), vec!["."]);
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(DirectoryGraph::label_of("/etc/ssh"), "ssh");
+        assert_eq!(DirectoryGraph::label_of("/"), "/");
+        assert_eq!(DirectoryGraph::label_of("C:\\Windows"), "Windows");
+    }
+}
@@ -2,4 +2,15 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum MactimeError {
-}
\ No newline at end of file
+    #[error("aborting after {0} parser errors; the input does not seem to be a bodyfile (use `--max-errors 0` to disable this check)")]
+    TooManyErrors(usize),
+
+    #[error("input does not appear to be a TSK v3 bodyfile")]
+    NotABodyfile,
+
+    #[error("unable to write the output: {0}")]
+    Output(#[from] std::io::Error),
+
+    #[error("unable to write the output file: {0}")]
+    OutputFile(#[from] anyhow::Error),
+}
@@ -0,0 +1,429 @@
+//! boolean expressions over the fields of a bodyfile line, which are used
+//! by `--where`, e.g. `size > 1000000 && name ~ "\\.exe$" && mtime > 2021-01-01`
+//!
+//! ```text
+//! expression := and ( "||" and )*
+//! and        := unary ( "&&" unary )*
+//! unary      := "!" unary | "(" expression ")" | field operator value
+//! operator   := "==" | "!=" | "<" | "<=" | ">" | ">=" | "~"
+//! ```
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use regex::{Regex, RegexBuilder};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Field {
+    Md5,
+    Name,
+    Inode,
+    Mode,
+    Uid,
+    Gid,
+    Size,
+    Atime,
+    Mtime,
+    Ctime,
+    Crtime,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "md5" => Self::Md5,
+            "name" => Self::Name,
+            "inode" => Self::Inode,
+            "mode" => Self::Mode,
+            "uid" => Self::Uid,
+            "gid" => Self::Gid,
+            "size" => Self::Size,
+            "atime" => Self::Atime,
+            "mtime" => Self::Mtime,
+            "ctime" => Self::Ctime,
+            "crtime" => Self::Crtime,
+            _ => return None,
+        })
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self, Self::Md5 | Self::Name | Self::Inode | Self::Mode)
+    }
+
+    fn is_timestamp(&self) -> bool {
+        matches!(self, Self::Atime | Self::Mtime | Self::Ctime | Self::Crtime)
+    }
+
+    fn text<'l>(&self, line: &'l Bodyfile3Line) -> &'l str {
+        match self {
+            Self::Md5 => line.get_md5(),
+            Self::Name => line.get_name(),
+            Self::Inode => line.get_inode(),
+            Self::Mode => line.get_mode_as_string(),
+            _ => unreachable!("{self:?} is not a text field"),
+        }
+    }
+
+    fn number(&self, line: &Bodyfile3Line) -> i128 {
+        match self {
+            Self::Uid => *line.get_uid() as i128,
+            Self::Gid => *line.get_gid() as i128,
+            Self::Size => *line.get_size() as i128,
+            Self::Atime => *line.get_atime() as i128,
+            Self::Mtime => *line.get_mtime() as i128,
+            Self::Ctime => *line.get_ctime() as i128,
+            Self::Crtime => *line.get_crtime() as i128,
+            _ => unreachable!("{self:?} is not a numeric field"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+}
+
+impl Operator {
+    fn compare<T: PartialOrd>(&self, lhs: T, rhs: T) -> bool {
+        match self {
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Match => unreachable!("regular expressions cannot be compared"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Expression {
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Text(Field, Operator, String),
+    TextIgnoringCase(Field, Operator, String),
+    Number(Field, Operator, i128),
+    Match(Field, Regex),
+}
+
+impl Expression {
+    /// checks if `line` matches this expression
+    pub fn evaluate(&self, line: &Bodyfile3Line) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.evaluate(line) && rhs.evaluate(line),
+            Self::Or(lhs, rhs) => lhs.evaluate(line) || rhs.evaluate(line),
+            Self::Not(expr) => !expr.evaluate(line),
+            // hashes are written in upper or lower case by different tools
+            Self::Text(Field::Md5, op, value) => {
+                line.get_md5().eq_ignore_ascii_case(value) == (*op == Operator::Eq)
+            }
+            Self::Text(field, op, value) => op.compare(field.text(line), value.as_str()),
+            Self::TextIgnoringCase(field, op, value) => {
+                op.compare(field.text(line).to_lowercase().as_str(), value.as_str())
+            }
+            Self::Number(field, op, value) => op.compare(field.number(line), *value),
+            Self::Match(field, regex) => regex.is_match(field.text(line)),
+        }
+    }
+
+    /// compares and matches names case-insensitively
+    pub fn with_case_insensitive_paths(self) -> Result<Self> {
+        Ok(match self {
+            Self::And(lhs, rhs) => Self::And(
+                Box::new(lhs.with_case_insensitive_paths()?),
+                Box::new(rhs.with_case_insensitive_paths()?),
+            ),
+            Self::Or(lhs, rhs) => Self::Or(
+                Box::new(lhs.with_case_insensitive_paths()?),
+                Box::new(rhs.with_case_insensitive_paths()?),
+            ),
+            Self::Not(expr) => Self::Not(Box::new(expr.with_case_insensitive_paths()?)),
+            Self::Text(Field::Name, op, value) => {
+                Self::TextIgnoringCase(Field::Name, op, value.to_lowercase())
+            }
+            Self::Match(Field::Name, regex) => Self::Match(
+                Field::Name,
+                RegexBuilder::new(regex.as_str())
+                    .case_insensitive(true)
+                    .build()?,
+            ),
+            other => other,
+        })
+    }
+}
+
+impl FromStr for Expression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expression),
+            Some(token) => bail!("unexpected token {token:?}"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '~' => Token::Operator(Operator::Match),
+            '&' | '|' => {
+                if chars.next() != Some(c) {
+                    bail!("expected '{c}{c}'");
+                }
+                if c == '&' {
+                    Token::And
+                } else {
+                    Token::Or
+                }
+            }
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ne),
+            '!' => Token::Not,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Eq),
+            '=' => bail!("expected '=='"),
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Le),
+            '<' => Token::Operator(Operator::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ge),
+            '>' => Token::Operator(Operator::Gt),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        None => bail!("unterminated string"),
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\')) => text.push(escaped),
+                            Some(other) => {
+                                text.push('\\');
+                                text.push(other);
+                            }
+                            None => bail!("unterminated string"),
+                        },
+                        Some(other) => text.push(other),
+                    }
+                }
+                Token::Text(text)
+            }
+            _ => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"()~&|!=<>\"".contains(*c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_if(&mut self, expected: Token) -> bool {
+        if self.tokens.get(self.pos) == Some(&expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Expression> {
+        let mut expression = self.and()?;
+        while self.next_if(Token::Or) {
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expression> {
+        let mut expression = self.unary()?;
+        while self.next_if(Token::And) {
+            expression = Expression::And(Box::new(expression), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expression> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expression::Not(Box::new(self.unary()?))),
+            Some(Token::LeftParen) => {
+                let expression = self.or()?;
+                if !self.next_if(Token::RightParen) {
+                    bail!("missing ')'");
+                }
+                Ok(expression)
+            }
+            Some(Token::Word(name)) => match Field::from_name(&name) {
+                Some(field) => self.comparison(field),
+                None => bail!("unknown field '{name}'"),
+            },
+            Some(token) => bail!("unexpected token {token:?}"),
+            None => bail!("unexpected end of expression"),
+        }
+    }
+
+    fn comparison(&mut self, field: Field) -> Result<Expression> {
+        let op = match self.next() {
+            Some(Token::Operator(op)) => op,
+            _ => bail!("expected an operator after {field:?}"),
+        };
+        let value = match self.next() {
+            Some(Token::Word(value)) | Some(Token::Text(value)) => value,
+            _ => bail!("expected a value after {op:?}"),
+        };
+
+        if op == Operator::Match {
+            if !field.is_text() {
+                bail!("'~' cannot be applied to {field:?}");
+            }
+            Ok(Expression::Match(field, Regex::new(&value)?))
+        } else if field.is_text() {
+            if !matches!(op, Operator::Eq | Operator::Ne) {
+                bail!("{op:?} cannot be applied to {field:?}");
+            }
+            Ok(Expression::Text(field, op, value))
+        } else if let Ok(number) = value.parse::<i128>() {
+            Ok(Expression::Number(field, op, number))
+        } else if field.is_timestamp() {
+            Ok(Expression::Number(field, op, parse_date(&value)?.into()))
+        } else {
+            bail!("invalid value for {field:?}: '{value}'")
+        }
+    }
+}
+
+/// parses a date, which is interpreted as UTC if it does not contain a timezone
+fn parse_date(value: &str) -> Result<i64> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.timestamp());
+    }
+    if let Ok(ts) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(ts.timestamp());
+    }
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date.and_hms_opt(0, 0, 0).unwrap().timestamp()),
+        Err(_) => bail!("invalid date: '{value}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::Expression;
+
+    fn sample_line() -> Bodyfile3Line {
+        Bodyfile3Line::new()
+            .with_name("C:/Windows/Temp/evil.exe")
+            .with_size(2000000)
+            .with_mtime(1625097600) // 2021-07-01
+    }
+
+    fn eval(expression: &str) -> bool {
+        Expression::from_str(expression).unwrap().evaluate(&sample_line())
+    }
+
+    #[test]
+    fn compound_match() {
+        assert!(eval(r#"size > 1000000 && name ~ "\\.exe$" && mtime > 2021-01-01"#));
+        assert!(eval(r#"size < 1000 || !(name ~ "\\.dll$")"#));
+        assert!(eval(r#"name == "C:/Windows/Temp/evil.exe" && crtime == -1"#));
+    }
+
+    #[test]
+    fn case_insensitive_paths() {
+        let line = sample_line();
+        for expression in [
+            r#"name == "c:/windows/temp/EVIL.EXE""#,
+            r#"name ~ "windows/temp""#,
+            r#"name != "C:/Windows/Temp/other.exe""#,
+        ] {
+            let expression = Expression::from_str(expression).unwrap();
+            assert!(expression.clone().with_case_insensitive_paths().unwrap().evaluate(&line));
+        }
+        let expression = Expression::from_str(r#"name == "c:/windows/temp/EVIL.EXE""#).unwrap();
+        assert!(!expression.evaluate(&line));
+    }
+
+    #[test]
+    fn compound_no_match() {
+        assert!(!eval(r#"size > 1000000 && name ~ "\\.exe$" && mtime > 2022-01-01"#));
+        assert!(!eval(r#"size <= 1000 || name ~ "\\.dll$" || mtime < 2021-01-01T00:00:00"#));
+        assert!(!eval(r#"!(name ~ "(?i)EVIL")"#));
+    }
+
+    #[test]
+    fn case_insensitive_hashes() {
+        let line = Bodyfile3Line::new().with_md5("B1946AC92492D2347C6235B4D2611184");
+        let denied = Expression::from_str(r#"md5 == "b1946ac92492d2347c6235b4d2611184""#).unwrap();
+        let allowed = Expression::from_str(r#"md5 != "b1946ac92492d2347c6235b4d2611184""#).unwrap();
+        assert!(denied.evaluate(&line));
+        assert!(!allowed.evaluate(&line));
+
+        let other = Bodyfile3Line::new().with_md5("d41d8cd98f00b204e9800998ecf8427e");
+        assert!(!denied.evaluate(&other));
+        assert!(allowed.evaluate(&other));
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for expression in [
+            "",
+            "size >",
+            "size > 1 &&",
+            "unknown == 1",
+            "size ~ \"1\"",
+            "name < \"a\"",
+            "size > large",
+            "(size > 1",
+            "size > 1)",
+            "name ~ \"(\"",
+            "name = \"a\"",
+            "size > 1 & size < 2",
+        ] {
+            assert!(Expression::from_str(expression).is_err(), "{expression:?} should be invalid");
+        }
+    }
+}
@@ -1,11 +1,157 @@
+use std::collections::BTreeSet;
 use std::sync::mpsc::{Sender, Receiver};
 
 use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::{
+    Bodyfile3Line, EntryType, FileCategory, MacbFlags, OnUnparseable, TimestampKind,
+};
+use dfir_toolkit::common::{OutputArgs, TimeRange};
 
-#[derive(Copy, Clone)]
+use crate::bodyfile::{AccessStorm, CacheFile, SessionGrouper};
+use crate::business_hours::OutsideHoursFilter;
+use crate::expression::Expression;
+use crate::high_water_mark::HighWaterMark;
+use crate::output::NullDate;
+use crate::sample::Sample;
+
+#[derive(Clone)]
 pub struct RunOptions {
     pub strict_mode: bool,
-    pub src_zone: Tz
+    pub src_zone: Tz,
+    pub strip_prefix: Option<String>,
+    pub normalize_paths: bool,
+    pub reconstruct_paths: bool,
+    pub pseudonymize: bool,
+    pub hash_secret: Option<String>,
+    pub allow_multiline_names: bool,
+    pub ignore_crtime: bool,
+    pub max_errors: usize,
+    pub on_unparseable: OnUnparseable,
+    pub time_range: TimeRange,
+    pub categories: Option<CategoryFilter>,
+    pub ads: Option<AdsFilter>,
+    pub deleted: Option<DeletedFilter>,
+    pub entry_type: Option<EntryType>,
+    pub expression: Option<Expression>,
+    pub fold_repeats: Option<u64>,
+    pub access_storm: Option<AccessStorm>,
+    pub sessions: Option<SessionGrouper>,
+    pub per_file: Option<PerFileEvent>,
+    pub only: Option<MacbFlags>,
+    pub outside_hours: Option<OutsideHoursFilter>,
+    pub sort_by: Option<TimestampKind>,
+    pub limit: Option<usize>,
+    pub sample: Option<Sample>,
+    pub deterministic_order: bool,
+    pub case_insensitive_paths: bool,
+    pub cache: Option<CacheFile>,
+    pub high_water_mark: Option<HighWaterMark>,
+    pub null_date: NullDate,
+    pub split_macb: bool,
+    pub depth_column: bool,
+    pub follow: bool,
+    pub output: OutputArgs,
+}
+
+/// selects a single event of every file
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PerFileEvent {
+    /// the chronologically first event
+    First,
+
+    /// the chronologically last event
+    Last,
+}
+
+/// selects lines by whether they reference an NTFS alternate data stream
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AdsFilter {
+    /// only alternate data streams
+    Only,
+
+    /// everything but alternate data streams
+    Exclude,
+}
+
+impl AdsFilter {
+    pub fn matches(&self, line: &Bodyfile3Line) -> bool {
+        let is_ads = line.alternate_data_stream().is_some();
+        match self {
+            Self::Only => is_ads,
+            Self::Exclude => !is_ads,
+        }
+    }
+}
+
+/// selects lines by whether the name carries a deletion marker
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeletedFilter {
+    /// only deleted entries
+    Only,
+
+    /// everything but deleted entries
+    Exclude,
+}
+
+impl DeletedFilter {
+    pub fn matches(&self, line: &Bodyfile3Line) -> bool {
+        match self {
+            Self::Only => line.is_deleted(),
+            Self::Exclude => !line.is_deleted(),
+        }
+    }
+}
+
+/// set of file categories which should be displayed
+#[derive(Copy, Clone, Default)]
+pub struct CategoryFilter(u32);
+
+impl CategoryFilter {
+    pub fn contains(&self, category: FileCategory) -> bool {
+        self.0 & Self::bit(category) != 0
+    }
+
+    fn bit(category: FileCategory) -> u32 {
+        1 << category as u32
+    }
+}
+
+impl FromIterator<FileCategory> for CategoryFilter {
+    fn from_iter<T: IntoIterator<Item = FileCategory>>(iter: T) -> Self {
+        Self(iter.into_iter().fold(0, |bits, c| bits | Self::bit(c)))
+    }
+}
+
+/// selects the timestamps which are counted by the reports (`histogram`,
+/// `gaps`, `summary` and `profile`), in the same way as the timeline selects
+/// its events using the time range, `--only` and `--outside-hours`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EventFilter {
+    pub time_range: TimeRange,
+    pub only: Option<MacbFlags>,
+    pub outside_hours: Option<OutsideHoursFilter>,
+}
+
+impl EventFilter {
+    /// returns `true` if the timestamp of `kind` is set in `line` and
+    /// passes all filters
+    pub fn matches(&self, line: &Bodyfile3Line, kind: TimestampKind) -> bool {
+        let ts = line.get_timestamp(kind);
+        ts != -1
+            && self.time_range.contains(ts)
+            && self.only.is_none_or(|only| only.contains(kind.into()))
+            && self.outside_hours.iter().all(|filter| filter.matches(ts))
+    }
+
+    /// returns the distinct selected timestamps of `line`. Identical
+    /// timestamps of a file are merged into one event, like in the timeline
+    pub fn events(&self, line: &Bodyfile3Line) -> BTreeSet<i64> {
+        TimestampKind::ALL
+            .iter()
+            .filter(|kind| self.matches(line, **kind))
+            .map(|kind| line.get_timestamp(*kind))
+            .collect()
+    }
 }
 
 pub trait Provider<To, R>: Joinable<R> {
@@ -17,7 +163,7 @@ pub trait Consumer<From> {
 }
 
 pub trait Filter<From, To, R> : Consumer<From> + Provider<To, R> {
-    fn worker(reader: Receiver<From>, tx: Sender<To>, options: RunOptions);
+    fn worker(reader: Receiver<From>, tx: Sender<To>, options: RunOptions) -> R;
 }
 
 pub trait Joinable<R> {
@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::filter::EventFilter;
+
+/// parses a duration like `90`, `30s`, `15m`, `3h` or `2d` into seconds. A
+/// number without unit is interpreted as seconds
+pub(crate) fn parse_duration(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, factor) = match value.char_indices().last() {
+        Some((idx, 's')) => (&value[..idx], 1),
+        Some((idx, 'm')) => (&value[..idx], 60),
+        Some((idx, 'h')) => (&value[..idx], 60 * 60),
+        Some((idx, 'd')) => (&value[..idx], 24 * 60 * 60),
+        _ => (value, 1),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: '{value}' (expected e.g. '30m', '3h' or '1d')"))?;
+    number
+        .checked_mul(factor)
+        .ok_or_else(|| format!("duration is too large: '{value}'"))
+}
+
+/// formats a number of seconds like `1d 3h 0m 5s`, leaving out leading units
+/// which are zero
+pub(crate) fn format_duration(seconds: i64) -> String {
+    let units = [
+        (seconds / 86400, "d"),
+        (seconds % 86400 / 3600, "h"),
+        (seconds % 3600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let first = units
+        .iter()
+        .position(|(value, _)| *value != 0)
+        .unwrap_or(units.len() - 1);
+    units[first..]
+        .iter()
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// finds the periods of the timeline which do not contain any event
+pub(crate) struct Gaps {
+    min_duration: i64,
+    src_zone: Tz,
+    dst_zone: Tz,
+    timestamps: BTreeSet<i64>,
+    filter: EventFilter,
+}
+
+impl Gaps {
+    pub fn new(min_duration: u64, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            min_duration: i64::try_from(min_duration).unwrap_or(i64::MAX),
+            src_zone,
+            dst_zone,
+            timestamps: BTreeSet::new(),
+            filter: EventFilter::default(),
+        }
+    }
+
+    /// ignores all timestamps which are not selected by `filter`
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        self.timestamps.extend(
+            self.filter
+                .events(line)
+                .into_iter()
+                .filter(|unix_ts| *unix_ts >= 0),
+        );
+    }
+
+    /// returns the start and the end of every period without events which
+    /// is longer than the minimum duration
+    pub fn gaps(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.timestamps
+            .iter()
+            .zip(self.timestamps.iter().skip(1))
+            .filter(|(start, end)| *end - *start > self.min_duration)
+            .map(|(start, end)| (*start, *end))
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        for (start, end) in self.gaps() {
+            writeln!(
+                out,
+                "{}\t{}\t{}",
+                ForensicsTimestamp::new(start, self.src_zone, self.dst_zone),
+                ForensicsTimestamp::new(end, self.src_zone, self.dst_zone),
+                format_duration(end - start)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::{format_duration, parse_duration, Gaps};
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("30s"), Ok(30));
+        assert_eq!(parse_duration("15m"), Ok(900));
+        assert_eq!(parse_duration("3h"), Ok(10800));
+        assert_eq!(parse_duration("2d"), Ok(172800));
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("3w").is_err());
+        assert!(parse_duration("-1h").is_err());
+
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(10800), "3h 0m 0s");
+        assert_eq!(format_duration(90061), "1d 1h 1m 1s");
+    }
+
+    #[test]
+    fn gaps_between_all_timestamps() {
+        let mut gaps = Gaps::new(60, Tz::UTC, Tz::UTC);
+        gaps.add(&Bodyfile3Line::new().with_mtime(1000).with_atime(1030));
+        gaps.add(&Bodyfile3Line::new().with_crtime(1200));
+        gaps.add(&Bodyfile3Line::new().with_ctime(1260));
+        assert_eq!(gaps.gaps().collect::<Vec<_>>(), vec![(1030, 1200)]);
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use clap::ValueEnum;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, HashAlgo};
+
+/// output format of a hash manifest
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub(crate) enum HashManifestFormat {
+    /// `md5<TAB>name`
+    #[default]
+    Tsv,
+
+    /// `md5,"name"` with a header line
+    Csv,
+
+    /// `md5  name`, which can be checked with `md5sum -c`. SHA-1 and SHA-256
+    /// hashes are written as `SHA256 (name) = hash`, which can be checked
+    /// with `cksum -c`
+    Md5sum,
+}
+
+/// prints the hashes and names of all files, skipping lines which have
+/// only the placeholder `0` (or no valid hash at all) in the md5 column. Every combination of hash and name
+/// is printed only once.
+pub(crate) struct HashManifest {
+    format: HashManifestFormat,
+    seen: HashSet<(String, String)>,
+}
+
+impl HashManifest {
+    pub fn new(format: HashManifestFormat) -> Self {
+        Self {
+            format,
+            seen: HashSet::new(),
+        }
+    }
+
+    pub fn header(&self) -> Option<&'static str> {
+        match self.format {
+            HashManifestFormat::Csv => Some("md5,name"),
+            _ => None,
+        }
+    }
+
+    /// prints the line, if it has a real md5 hash and has not been printed before
+    pub fn add(&mut self, line: &Bodyfile3Line, out: &mut dyn Write) -> std::io::Result<()> {
+        if let Some(entry) = self.format_line(line) {
+            writeln!(out, "{entry}")?;
+        }
+        Ok(())
+    }
+
+    fn format_line(&mut self, line: &Bodyfile3Line) -> Option<String> {
+        let algorithm = line.hash_algorithm();
+        if algorithm == HashAlgo::None {
+            return None;
+        }
+        let md5 = line.get_md5();
+        let name = line.get_name();
+        if !self.seen.insert((md5.to_owned(), name.to_owned())) {
+            return None;
+        }
+
+        Some(match self.format {
+            HashManifestFormat::Tsv => format!("{md5}\t{name}"),
+            HashManifestFormat::Csv => format!("{md5},\"{}\"", name.replace('"', "\"\"")),
+            HashManifestFormat::Md5sum if algorithm == HashAlgo::Md5 => format!("{md5}  {name}"),
+            HashManifestFormat::Md5sum => format!("{algorithm} ({name}) = {md5}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::{HashManifest, HashManifestFormat};
+
+    const HELLO_MD5: &str = "b1946ac92492d2347c6235b4d2611184";
+
+    #[test]
+    fn skip_placeholder_and_duplicates() {
+        let mut manifest = HashManifest::new(HashManifestFormat::Tsv);
+        let line = Bodyfile3Line::new().with_md5(HELLO_MD5).with_name("/etc/hosts");
+        assert_eq!(
+            manifest.format_line(&line),
+            Some(format!("{HELLO_MD5}\t/etc/hosts"))
+        );
+        assert_eq!(manifest.format_line(&line), None);
+        assert_eq!(
+            manifest.format_line(&Bodyfile3Line::new().with_name("/etc/passwd")),
+            None
+        );
+    }
+
+    #[test]
+    fn other_algorithms() {
+        const HELLO_SHA256: &str =
+            "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+        let mut manifest = HashManifest::new(HashManifestFormat::Md5sum);
+        let line = Bodyfile3Line::new().with_md5(HELLO_SHA256).with_name("/etc/hosts");
+        assert_eq!(
+            manifest.format_line(&line),
+            Some(format!("SHA256 (/etc/hosts) = {HELLO_SHA256}"))
+        );
+        let line = Bodyfile3Line::new().with_md5(HELLO_MD5).with_name("/etc/hosts");
+        assert_eq!(
+            manifest.format_line(&line),
+            Some(format!("{HELLO_MD5}  /etc/hosts"))
+        );
+        let line = Bodyfile3Line::new().with_md5("not-a-hash").with_name("/etc/hosts");
+        assert_eq!(manifest.format_line(&line), None);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        let mut manifest = HashManifest::new(HashManifestFormat::Csv);
+        let line = Bodyfile3Line::new().with_md5(HELLO_MD5).with_name("say \"hello\".txt");
+        assert_eq!(
+            manifest.format_line(&line),
+            Some(format!("{HELLO_MD5},\"say \"\"hello\"\".txt\""))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use dfir_toolkit::common::AtomicOutput;
+
+/// file which stores the latest timestamp that has been displayed, so that
+/// repeated runs over growing inputs display only new events
+#[derive(Clone, Debug)]
+pub struct HighWaterMark {
+    path: PathBuf,
+}
+
+impl HighWaterMark {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// returns the latest timestamp of the previous run, or `None` if there
+    /// is no state file yet
+    pub fn load(&self) -> Result<Option<i64>> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => content.trim().parse().map(Some).map_err(|why| {
+                anyhow!("invalid state file '{}': {why}", self.path.display())
+            }),
+            Err(why) if why.kind() == ErrorKind::NotFound => {
+                log::info!(
+                    "state file '{}' does not exist, displaying all events",
+                    self.path.display()
+                );
+                Ok(None)
+            }
+            Err(why) => Err(why.into()),
+        }
+    }
+
+    /// stores the latest timestamp which has been displayed. The file is
+    /// replaced atomically, so that it never contains a partial value.
+    pub fn store(&self, timestamp: i64) {
+        let result = AtomicOutput::create(&self.path).and_then(|mut output| {
+            writeln!(output, "{timestamp}")?;
+            output.commit()
+        });
+        if let Err(why) = result {
+            log::error!("unable to write state file '{}': {why}", self.path.display());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use chrono::{NaiveDateTime, Timelike};
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::filter::EventFilter;
+
+const BAR_WIDTH: usize = 60;
+
+#[derive(ValueEnum, Clone, Copy)]
+pub(crate) enum HistogramBucket {
+    Hour,
+    Day,
+}
+
+/// counts the timestamps of a bodyfile per hour or per day
+pub(crate) struct Histogram {
+    bucket: HistogramBucket,
+    kinds: Vec<TimestampKind>,
+    src_zone: Tz,
+    dst_zone: Tz,
+    counts: BTreeMap<NaiveDateTime, usize>,
+    filter: EventFilter,
+}
+
+impl Histogram {
+    pub fn new(bucket: HistogramBucket, kinds: Vec<TimestampKind>, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            bucket,
+            kinds,
+            src_zone,
+            dst_zone,
+            counts: BTreeMap::new(),
+            filter: EventFilter::default(),
+        }
+    }
+
+    /// ignores all timestamps which are not selected by `filter`
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        for kind in self.kinds.iter() {
+            if !self.filter.matches(line, *kind) {
+                continue;
+            }
+            let unix_ts = line.get_timestamp(*kind);
+            let ts = ForensicsTimestamp::new(unix_ts, self.src_zone, self.dst_zone);
+            if let Some(datetime) = ts.as_datetime() {
+                let datetime = datetime.naive_local();
+                let key = match self.bucket {
+                    HistogramBucket::Hour => datetime.date().and_hms_opt(datetime.hour(), 0, 0),
+                    HistogramBucket::Day => datetime.date().and_hms_opt(0, 0, 0),
+                }
+                .unwrap();
+                *self.counts.entry(key).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// returns the formatted bucket names together with their number of events
+    pub fn buckets(&self) -> impl Iterator<Item = (String, usize)> + '_ {
+        let format = match self.bucket {
+            HistogramBucket::Hour => "%Y-%m-%d %H:00",
+            HistogramBucket::Day => "%Y-%m-%d",
+        };
+        self.counts
+            .iter()
+            .map(move |(bucket, count)| (bucket.format(format).to_string(), *count))
+    }
+
+    pub fn print(&self, with_bar: bool, out: &mut dyn Write) -> std::io::Result<()> {
+        let max_count = self.counts.values().max().copied().unwrap_or(0);
+        for (bucket, count) in self.buckets() {
+            if with_bar {
+                let width = (count * BAR_WIDTH).div_ceil(max_count);
+                writeln!(out, "{bucket}\t{count:>8} {}", "#".repeat(width))?;
+            } else {
+                writeln!(out, "{bucket}\t{count}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::ForensicsTimestamp;
+
+/// file which is a candidate for the list of the largest files
+struct Candidate(Bodyfile3Line);
+
+/// sort key of a file. Larger files are greater. If two files have the same
+/// size, the more recently modified file is greater
+fn key_of(line: &Bodyfile3Line) -> (u64, i64, Reverse<&String>) {
+    (
+        *line.get_size(),
+        line.get_timestamp(TimestampKind::Mtime),
+        Reverse(line.get_name()),
+    )
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        key_of(&self.0).cmp(&key_of(&other.0))
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        key_of(&self.0) == key_of(&other.0)
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// finds the largest files in a single pass. Only the `count` largest files
+/// seen so far are kept in a min-heap, so that the memory usage does not
+/// depend on the size of the bodyfile
+pub(crate) struct LargestFiles {
+    src_zone: Tz,
+    dst_zone: Tz,
+    count: usize,
+    heap: BinaryHeap<Reverse<Candidate>>,
+}
+
+impl LargestFiles {
+    pub fn new(count: usize, src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            count,
+            heap: BinaryHeap::with_capacity(count + 1),
+        }
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        if self.count == 0 {
+            return;
+        }
+
+        if self.heap.len() == self.count {
+            // avoid cloning lines which would be dropped immediately
+            match self.heap.peek() {
+                Some(Reverse(smallest)) if key_of(line) > key_of(&smallest.0) => {
+                    self.heap.pop();
+                }
+                _ => return,
+            }
+        }
+        self.heap.push(Reverse(Candidate(line.clone())));
+    }
+
+    /// returns the largest files, beginning with the largest file
+    pub fn top(&self) -> Vec<&Bodyfile3Line> {
+        let mut candidates: Vec<_> = self.heap.iter().map(|Reverse(c)| c).collect();
+        candidates.sort_by(|c1, c2| c2.cmp(c1));
+        candidates.into_iter().map(|c| &c.0).collect()
+    }
+
+    fn format_timestamp(&self, ts: i64) -> String {
+        match ts {
+            -1 => "-".to_owned(),
+            ts => ForensicsTimestamp::new(ts, self.src_zone, self.dst_zone).to_string(),
+        }
+    }
+
+    /// prints the size, the timestamps (in the order m, a, c, b) and the name
+    /// of every file
+    pub fn print(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        for line in self.top() {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                line.get_size(),
+                self.format_timestamp(line.get_timestamp(TimestampKind::Mtime)),
+                self.format_timestamp(line.get_timestamp(TimestampKind::Atime)),
+                self.format_timestamp(line.get_timestamp(TimestampKind::Ctime)),
+                self.format_timestamp(line.get_timestamp(TimestampKind::Crtime)),
+                line.get_name()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::LargestFiles;
+
+    fn names(largest: &LargestFiles) -> Vec<&str> {
+        largest
+            .top()
+            .into_iter()
+            .map(|line| line.get_name().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn bounded_heap() {
+        let mut largest = LargestFiles::new(2, Tz::UTC, Tz::UTC);
+        for (name, size) in [("/a", 10), ("/b", 30), ("/c", 20), ("/d", 5), ("/e", 40)] {
+            largest.add(&Bodyfile3Line::new().with_name(name).with_size(size));
+            assert!(largest.heap.len() <= 2);
+        }
+        assert_eq!(names(&largest), vec!["/e", "/b"]);
+    }
+
+    #[test]
+    fn ties_broken_by_mtime() {
+        let mut largest = LargestFiles::new(1, Tz::UTC, Tz::UTC);
+        for (name, mtime) in [("/old", 100), ("/new", 200), ("/older", 50)] {
+            largest.add(
+                &Bodyfile3Line::new()
+                    .with_name(name)
+                    .with_size(10)
+                    .with_mtime(mtime),
+            );
+        }
+        assert_eq!(names(&largest), vec!["/new"]);
+    }
+
+    #[test]
+    fn zero_files() {
+        let mut largest = LargestFiles::new(0, Tz::UTC, Tz::UTC);
+        largest.add(&Bodyfile3Line::new().with_name("/a").with_size(10));
+        assert!(largest.top().is_empty());
+    }
+}
@@ -5,24 +5,57 @@ mod error;
 mod filter;
 mod output;
 mod cli;
+mod histogram;
+mod gaps;
+mod correlate;
+mod summary;
+mod profile;
+mod directory_graph;
+mod largest;
+mod stats;
+mod verify;
+mod hashes;
+mod case_uco;
+mod high_water_mark;
+mod registry_timezone;
+mod expression;
+mod business_hours;
+mod sample;
+mod watch;
+mod timestomp;
 
 use application::*;
 use cli::*;
 
+use std::io::Write;
+
 use anyhow::Result;
-use dfir_toolkit::common::{FancyParser, TzArgument};
+use dfir_toolkit::common::interrupt::{self, INTERRUPTED_EXIT_CODE};
+use dfir_toolkit::common::{ExitCode, FancyParser, TzArgument};
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
 
-fn main() -> Result<()> {
+fn run() -> Result<ExitCode> {
     let cli: Cli = Cli::parse_cli();
 
     if cli.src_zone.is_list() || cli.dst_zone.is_list() {
         TzArgument::display_zones();
-        return Ok(());
+        return Ok(ExitCode::Success);
     }
     debug_assert!(cli.dst_zone.is_tz());
     debug_assert!(cli.src_zone.is_tz());
 
-    let app: Mactime2Application = cli.into();
+    cli.color.apply(&cli.output);
+    let app = Mactime2Application::try_from(cli)?;
 
-    app.run()
+    interrupt::install_handler();
+    let exit_code = app.run()?;
+
+    if interrupt::is_interrupted() {
+        std::io::stdout().flush()?;
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
+    Ok(exit_code)
 }
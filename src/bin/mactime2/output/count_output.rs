@@ -0,0 +1,50 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::bodyfile::{file_key, ListEntry, Mactime2Writer};
+
+/// counts the events and the distinct files (identified by their name and
+/// inode) instead of printing them. The totals are printed after the last
+/// event.
+#[derive(Default)]
+pub(crate) struct CountOutput {
+    events: Cell<usize>,
+    files: RefCell<HashSet<String>>,
+    case_insensitive_paths: bool,
+}
+
+impl CountOutput {
+    /// count names which only differ in their case as the same file
+    pub fn with_case_insensitive_paths(mut self, case_insensitive_paths: bool) -> Self {
+        self.case_insensitive_paths = case_insensitive_paths;
+        self
+    }
+}
+
+impl Mactime2Writer for CountOutput {
+    fn footer(&self) -> Option<String> {
+        Some(format!(
+            "events: {}\nfiles: {}",
+            self.events.get(),
+            self.files.borrow().len()
+        ))
+    }
+
+    fn write(
+        &self,
+        _out: &mut dyn Write,
+        _timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        self.events.set(self.events.get() + 1);
+        self.files
+            .borrow_mut()
+            .insert(file_key(&entry.line, self.case_insensitive_paths));
+        Ok(())
+    }
+
+    fn fmt(&self, _timestamp: &i64, _entry: &ListEntry) -> String {
+        String::new()
+    }
+}
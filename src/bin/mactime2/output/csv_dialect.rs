@@ -0,0 +1,67 @@
+use clap::ValueEnum;
+
+/// delimiter, quoting and line terminator of the csv output
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum CsvDialect {
+    /// commas as delimiter and `\n` as line terminator; only the file name is
+    /// quoted, and quotes inside of it are not escaped. This is the format
+    /// which has been written before dialects could be selected, and which
+    /// must be selected explicitly now
+    Legacy,
+
+    /// semicolons as delimiter and `\r\n` as line terminator, which is what
+    /// Excel expects in most locales
+    Excel,
+
+    /// commas as delimiter and `\n` as line terminator; every field is quoted
+    Unix,
+
+    /// commas as delimiter and `\r\n` as line terminator, as specified by
+    /// RFC 4180
+    #[default]
+    Rfc4180,
+}
+
+impl CsvDialect {
+    pub fn delimiter(&self) -> char {
+        match self {
+            Self::Excel => ';',
+            Self::Legacy | Self::Unix | Self::Rfc4180 => ',',
+        }
+    }
+
+    pub fn quote_char(&self) -> char {
+        '"'
+    }
+
+    pub fn line_terminator(&self) -> &'static str {
+        match self {
+            Self::Legacy | Self::Unix => "\n",
+            Self::Excel | Self::Rfc4180 => "\r\n",
+        }
+    }
+
+    /// encloses `field` in quotes if this is required (i.e. it contains the
+    /// delimiter, a quote or a line break), if `always` is set or if the
+    /// dialect quotes every field. Quotes inside of the field are doubled.
+    /// The legacy dialect quotes only if `always` is set, and does not escape.
+    pub fn quote(&self, field: &str, always: bool) -> String {
+        let quote = self.quote_char();
+        if matches!(self, Self::Legacy) {
+            return if always {
+                format!("{quote}{field}{quote}")
+            } else {
+                field.to_owned()
+            };
+        }
+        let required = field
+            .chars()
+            .any(|c| c == self.delimiter() || c == quote || c == '\r' || c == '\n');
+        if always || required || matches!(self, Self::Unix) {
+            let escaped = field.replace(quote, &format!("{quote}{quote}"));
+            format!("{quote}{escaped}{quote}")
+        } else {
+            field.to_owned()
+        }
+    }
+}
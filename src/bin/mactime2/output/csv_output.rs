@@ -1,43 +1,153 @@
 use chrono_tz::Tz;
 use dfir_toolkit::common::ForensicsTimestamp;
 
-use crate::bodyfile::{ListEntry, Mactime2Writer};
+use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+use super::{CsvDialect, NullDate, OutputField};
 
 pub(crate) struct CsvOutput {
     src_zone: Tz,
     dst_zone: Tz,
+    fields: Vec<OutputField>,
+    with_header: bool,
+    null_date: NullDate,
+    split_macb: bool,
+    epoch_ms: bool,
+    dialect: CsvDialect,
 }
 
+/// columns which replace the MACB column if `--split-macb` is specified
+const SPLIT_MACB_COLUMNS: [(&str, MacbFlags); 4] = [
+    ("accessed", MacbFlags::A),
+    ("modified", MacbFlags::M),
+    ("changed", MacbFlags::C),
+    ("created", MacbFlags::B),
+];
+
 impl CsvOutput {
     pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
-        Self { src_zone, dst_zone }
+        Self {
+            src_zone,
+            dst_zone,
+            fields: OutputField::ALL.to_vec(),
+            with_header: false,
+            null_date: NullDate::default(),
+            split_macb: false,
+            epoch_ms: false,
+            dialect: CsvDialect::default(),
+        }
+    }
+
+    /// restricts the output to the specified columns, and prints a header line
+    pub fn with_fields(mut self, fields: Vec<OutputField>) -> Self {
+        self.fields = fields;
+        self.with_header = true;
+        self
+    }
+
+    /// specifies how timestamps which are not set are rendered
+    pub fn with_null_date(mut self, null_date: NullDate) -> Self {
+        self.null_date = null_date;
+        self
+    }
+
+    /// replaces the MACB column by one boolean column per flag, and prints
+    /// a header line
+    pub fn with_split_macb(mut self, split_macb: bool) -> Self {
+        self.split_macb = split_macb;
+        self.with_header |= split_macb;
+        self
+    }
+
+    /// renders timestamps as milliseconds since the unix epoch instead of
+    /// ISO 8601 strings
+    pub fn with_epoch_ms(mut self, epoch_ms: bool) -> Self {
+        self.epoch_ms = epoch_ms;
+        self
+    }
+
+    /// specifies the delimiter, the quoting and the line terminator
+    pub fn with_dialect(mut self, dialect: CsvDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    fn format_timestamp(&self, unix_ts: i64) -> Option<String> {
+        if self.epoch_ms {
+            self.null_date.format_epoch_ms(unix_ts, self.src_zone)
+        } else {
+            self.null_date.format(unix_ts, self.src_zone, self.dst_zone)
+        }
     }
 }
 
 impl Mactime2Writer for CsvOutput {
+    fn header(&self) -> Option<String> {
+        if self.with_header {
+            let delimiter = self.dialect.delimiter().to_string();
+            Some(
+                self.fields
+                    .iter()
+                    .flat_map(|field| match field {
+                        OutputField::Macb if self.split_macb => {
+                            SPLIT_MACB_COLUMNS.map(|(header, _)| header).to_vec()
+                        }
+                        _ => vec![field.header()],
+                    })
+                    .map(|header| self.dialect.quote(header, false))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn line_terminator(&self) -> &'static str {
+        self.dialect.line_terminator()
+    }
+
     fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
-        let timestamp = ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone);
-        format!(
-            "{},{},{},{},{},{},{},\"{}\"",
-            timestamp,
-            entry.line.get_size(),
-            entry.flags,
-            entry.line.get_mode_as_string(),
-            entry.line.get_uid(),
-            entry.line.get_gid(),
-            entry.line.get_inode(),
-            entry.line.get_name()
-        )
+        let unix_ts = *timestamp;
+        let timestamp = ForensicsTimestamp::new(unix_ts, self.src_zone, self.dst_zone);
+        let delimiter = self.dialect.delimiter().to_string();
+        self.fields
+            .iter()
+            .map(|field| match field {
+                OutputField::Macb if self.split_macb => SPLIT_MACB_COLUMNS
+                    .map(|(_, flag)| {
+                        self.dialect
+                            .quote(&entry.flags.contains(flag).to_string(), false)
+                    })
+                    .join(&delimiter),
+                _ => {
+                    let value = match field {
+                        OutputField::Date => self
+                            .format_timestamp(unix_ts)
+                            .unwrap_or_else(|| timestamp.to_string()),
+                        _ => match field.timestamp_kind() {
+                            Some(kind) => self
+                                .format_timestamp(entry.line.get_timestamp(kind))
+                                .unwrap_or_default(),
+                            None => field.value(&timestamp, entry),
+                        },
+                    };
+                    // names are always quoted, because they may contain any character
+                    self.dialect.quote(&value, *field == OutputField::Name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&delimiter)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::bodyfile::ListEntry;
-    use crate::bodyfile::MACBFlags;
+    use crate::bodyfile::MacbFlags;
     use crate::bodyfile::Mactime2Writer;
 
-    use super::CsvOutput;
+    use super::{CsvDialect, CsvOutput, OutputField};
     use chrono::DateTime;
     use chrono_tz::Tz;
     use chrono_tz::TZ_VARIANTS;
@@ -57,7 +167,7 @@ mod tests {
             let unix_ts = rand::random::<u32>() as i64;
             let bf_line = Bodyfile3Line::new().with_crtime(unix_ts);
             let entry = ListEntry {
-                flags: MACBFlags::B,
+                flags: MacbFlags::B,
                 line: Arc::new(bf_line),
             };
 
@@ -84,7 +194,7 @@ mod tests {
             let unix_ts = rand::random::<u32>() as i64;
             let bf_line = Bodyfile3Line::new().with_crtime(unix_ts);
             let entry = ListEntry {
-                flags: MACBFlags::B,
+                flags: MacbFlags::B,
                 line: Arc::new(bf_line),
             };
 
@@ -104,4 +214,135 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_default_fields() {
+        let output = CsvOutput::new(Tz::UTC, Tz::UTC);
+        let bf_line = Bodyfile3Line::new()
+            .with_name("sample.txt")
+            .with_inode("1234")
+            .with_mode("r/rrwxrwxrwx")
+            .with_size(42)
+            .with_crtime(0);
+        let entry = ListEntry {
+            flags: MacbFlags::B,
+            line: Arc::new(bf_line),
+        };
+        assert!(output.header().is_none());
+        assert_eq!(
+            output.fmt(&0, &entry),
+            "1970-01-01T00:00:00+00:00,42,...b,r/rrwxrwxrwx,0,0,1234,\"sample.txt\""
+        );
+    }
+
+    #[test]
+    fn test_split_macb() {
+        let output = CsvOutput::new(Tz::UTC, Tz::UTC)
+            .with_split_macb(true)
+            .with_fields(vec![OutputField::Macb, OutputField::Name]);
+        let bf_line = Bodyfile3Line::new()
+            .with_name("sample.txt")
+            .with_mtime(0)
+            .with_atime(0);
+        let entry = ListEntry {
+            flags: MacbFlags::M | MacbFlags::A,
+            line: Arc::new(bf_line),
+        };
+        assert_eq!(
+            output.header().unwrap(),
+            "accessed,modified,changed,created,File Name"
+        );
+        assert_eq!(
+            output.fmt(&0, &entry),
+            "true,true,false,false,\"sample.txt\""
+        );
+    }
+
+    #[test]
+    fn test_dialects() {
+        let bf_line = Bodyfile3Line::new()
+            .with_name("/tmp/a,b\nc \"d\";e")
+            .with_inode("1,2");
+        let entry = ListEntry {
+            flags: MacbFlags::M,
+            line: Arc::new(bf_line),
+        };
+
+        for (dialect, expected_header, expected_line, terminator) in [
+            (
+                CsvDialect::Legacy,
+                "Meta,File Name",
+                "1,2,\"/tmp/a,b\nc \"d\";e\"",
+                "\n",
+            ),
+            (
+                CsvDialect::Rfc4180,
+                "Meta,File Name",
+                "\"1,2\",\"/tmp/a,b\nc \"\"d\"\";e\"",
+                "\r\n",
+            ),
+            (
+                CsvDialect::Excel,
+                "Meta;File Name",
+                "1,2;\"/tmp/a,b\nc \"\"d\"\";e\"",
+                "\r\n",
+            ),
+            (
+                CsvDialect::Unix,
+                "\"Meta\",\"File Name\"",
+                "\"1,2\",\"/tmp/a,b\nc \"\"d\"\";e\"",
+                "\n",
+            ),
+        ] {
+            let output = CsvOutput::new(Tz::UTC, Tz::UTC)
+                .with_dialect(dialect)
+                .with_fields(vec![OutputField::Inode, OutputField::Name]);
+            assert_eq!(output.header().unwrap(), expected_header);
+            assert_eq!(output.fmt(&0, &entry), expected_line);
+
+            let mut out = Vec::new();
+            output.write(&mut out, &0, &entry).unwrap();
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                format!("{expected_line}{terminator}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_epoch_ms() {
+        let output = CsvOutput::new(Tz::UTC, Tz::Europe__Berlin)
+            .with_epoch_ms(true)
+            .with_fields(vec![OutputField::Date, OutputField::Name]);
+        let bf_line = Bodyfile3Line::new()
+            .with_name("sample.txt")
+            .with_mtime(1609459200);
+        let entry = ListEntry {
+            flags: MacbFlags::M,
+            line: Arc::new(bf_line),
+        };
+        assert_eq!(
+            output.fmt(&1609459200, &entry),
+            "1609459200000,\"sample.txt\""
+        );
+    }
+
+    #[test]
+    fn test_selected_fields() {
+        let output = CsvOutput::new(Tz::UTC, Tz::UTC).with_fields(vec![
+            OutputField::Name,
+            OutputField::Macb,
+            OutputField::Date,
+        ]);
+        let bf_line = Bodyfile3Line::new().with_name("sample.txt").with_crtime(0);
+        let entry = ListEntry {
+            flags: MacbFlags::B,
+            line: Arc::new(bf_line),
+        };
+        assert_eq!(output.header().unwrap(), "File Name,Type,Date");
+        assert_eq!(
+            output.fmt(&0, &entry),
+            "\"sample.txt\",...b,1970-01-01T00:00:00+00:00"
+        );
+    }
 }
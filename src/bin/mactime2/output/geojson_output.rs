@@ -0,0 +1,142 @@
+use std::cell::Cell;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::bodyfile::{ListEntry, Mactime2Writer};
+
+/// default pattern to find coordinates like `48.137,11.575` in file names
+pub(crate) const DEFAULT_COORDINATES_PATTERN: &str =
+    r"(?P<lat>-?\d{1,2}(?:\.\d+)?),\s*(?P<lon>-?\d{1,3}(?:\.\d+)?)";
+
+/// writes all events whose names contain coordinates as a GeoJSON
+/// `FeatureCollection`. Events without coordinates are skipped.
+pub(crate) struct GeoJsonOutput {
+    src_zone: Tz,
+    dst_zone: Tz,
+    pattern: Regex,
+    is_first: Cell<bool>,
+}
+
+impl GeoJsonOutput {
+    /// creates a new output. `pattern` must contain the named capture groups
+    /// `lat` and `lon`
+    pub fn new(src_zone: Tz, dst_zone: Tz, pattern: Regex) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            pattern,
+            is_first: Cell::new(true),
+        }
+    }
+
+    fn coordinates(&self, name: &str) -> Option<(f64, f64)> {
+        let captures = self.pattern.captures(name)?;
+        let lat: f64 = captures.name("lat")?.as_str().parse().ok()?;
+        let lon: f64 = captures.name("lon")?.as_str().parse().ok()?;
+        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) {
+            Some((lat, lon))
+        } else {
+            None
+        }
+    }
+
+    fn feature(&self, timestamp: &i64, entry: &ListEntry) -> Option<Value> {
+        let line = &entry.line;
+        let (lat, lon) = self.coordinates(line.get_name())?;
+        Some(json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "Point",
+                "coordinates": [lon, lat],
+            },
+            "properties": {
+                "datetime": ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone).to_string(),
+                "timestamp": timestamp,
+                "macb": entry.flags.to_string(),
+                "name": line.get_name(),
+                "inode": line.get_inode(),
+            }
+        }))
+    }
+}
+
+impl Mactime2Writer for GeoJsonOutput {
+    fn header(&self) -> Option<String> {
+        Some(r#"{"type":"FeatureCollection","features":["#.to_owned())
+    }
+
+    fn footer(&self) -> Option<String> {
+        Some("]}".to_owned())
+    }
+
+    fn write(
+        &self,
+        out: &mut dyn Write,
+        timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        if let Some(feature) = self.feature(timestamp, entry) {
+            if self.is_first.replace(false) {
+                writeln!(out, "{feature}")?;
+            } else {
+                writeln!(out, ",{feature}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
+        self.feature(timestamp, entry)
+            .map(|feature| feature.to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+    use super::{GeoJsonOutput, DEFAULT_COORDINATES_PATTERN};
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    use regex::Regex;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    fn entry(name: &str) -> ListEntry {
+        ListEntry {
+            flags: MacbFlags::M,
+            line: Arc::new(Bodyfile3Line::new().with_name(name).with_mtime(1609459200)),
+        }
+    }
+
+    #[test]
+    fn test_feature() {
+        let output = GeoJsonOutput::new(
+            Tz::UTC,
+            Tz::UTC,
+            Regex::new(DEFAULT_COORDINATES_PATTERN).unwrap(),
+        );
+        let feature: Value =
+            serde_json::from_str(&output.fmt(&1609459200, &entry("IMG_0001.JPG (48.137, 11.575)")))
+                .unwrap();
+        assert_eq!(feature["geometry"]["coordinates"][0], 11.575);
+        assert_eq!(feature["geometry"]["coordinates"][1], 48.137);
+        assert_eq!(feature["properties"]["timestamp"], 1609459200);
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        let output = GeoJsonOutput::new(
+            Tz::UTC,
+            Tz::UTC,
+            Regex::new(DEFAULT_COORDINATES_PATTERN).unwrap(),
+        );
+        assert!(output.fmt(&0, &entry("/etc/passwd")).is_empty());
+        assert!(output.fmt(&0, &entry("IMG_0001.JPG (98.1, 11.5)")).is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use std::cell::Cell;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+use serde_json::json;
+
+use crate::bodyfile::{ListEntry, Mactime2Writer};
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>mactime2 report</title>
+<style>
+body { font-family: sans-serif; margin: 1em; }
+#chart { width: 100%; height: 120px; border: 1px solid #ccc; }
+#chart rect { fill: #4a7ab5; }
+#filter { width: 40em; margin: 1em 0; }
+table { border-collapse: collapse; font-size: 0.9em; }
+th { cursor: pointer; background: #eee; text-align: left; }
+th, td { border: 1px solid #ccc; padding: 2px 6px; }
+td.name { font-family: monospace; white-space: pre-wrap; word-break: break-all; }
+</style>
+</head>
+<body>
+<h1>mactime2 report</h1>
+<svg id="chart" preserveAspectRatio="none"></svg>
+<div><input id="filter" type="search" placeholder="filter (case-insensitive substring of any column)"> <span id="count"></span></div>
+<table>
+<thead><tr><th data-col="1">Date</th><th data-col="2">Type</th><th data-col="3">File Name</th><th data-col="4">Inode</th><th data-col="5">Mode</th><th data-col="6">Size</th></tr></thead>
+<tbody id="rows"></tbody>
+</table>
+<script>
+// every row contains: timestamp, date, macb, name, inode, mode, size
+const ROWS = [
+"#;
+
+const HTML_FOOTER: &str = r#"];
+const DAY = 86400;
+let sortColumn = 0;
+let sortAscending = true;
+
+function render() {
+  const filter = document.getElementById("filter").value.toLowerCase();
+  const rows = ROWS.filter(row => filter === "" || row.some(v => String(v).toLowerCase().includes(filter)));
+  rows.sort((a, b) => {
+    const result = a[sortColumn] < b[sortColumn] ? -1 : (a[sortColumn] > b[sortColumn] ? 1 : 0);
+    return sortAscending ? result : -result;
+  });
+
+  const tbody = document.getElementById("rows");
+  tbody.replaceChildren(...rows.map(row => {
+    const tr = document.createElement("tr");
+    row.slice(1).forEach((value, idx) => {
+      const td = document.createElement("td");
+      td.textContent = value;
+      if (idx === 2) { td.className = "name"; }
+      tr.appendChild(td);
+    });
+    return tr;
+  }));
+  document.getElementById("count").textContent = rows.length + " of " + ROWS.length + " events";
+  drawChart(rows);
+}
+
+function drawChart(rows) {
+  const svg = document.getElementById("chart");
+  svg.replaceChildren();
+  if (rows.length === 0) { return; }
+  const buckets = new Map();
+  for (const row of rows) {
+    const day = Math.floor(row[0] / DAY);
+    buckets.set(day, (buckets.get(day) || 0) + 1);
+  }
+  const days = [...buckets.keys()];
+  const first = Math.min(...days);
+  const width = Math.max(...days) - first + 1;
+  const height = Math.max(...buckets.values());
+  svg.setAttribute("viewBox", "0 0 " + width + " " + height);
+  for (const [day, count] of buckets) {
+    const rect = document.createElementNS("http://www.w3.org/2000/svg", "rect");
+    rect.setAttribute("x", day - first);
+    rect.setAttribute("y", height - count);
+    rect.setAttribute("width", 1);
+    rect.setAttribute("height", count);
+    const title = document.createElementNS("http://www.w3.org/2000/svg", "title");
+    title.textContent = new Date(day * DAY * 1000).toISOString().substring(0, 10) + ": " + count;
+    rect.appendChild(title);
+    svg.appendChild(rect);
+  }
+}
+
+document.querySelectorAll("th").forEach(th => th.addEventListener("click", () => {
+  const column = Number(th.dataset.col);
+  // the date column is sorted by the numeric timestamp
+  const sortBy = column === 1 ? 0 : column;
+  sortAscending = sortColumn === sortBy ? !sortAscending : true;
+  sortColumn = sortBy;
+  render();
+}));
+document.getElementById("filter").addEventListener("input", render);
+render();
+</script>
+</body>
+</html>"#;
+
+/// writes the timeline as a self-contained HTML page, which contains the
+/// events as JSON data and displays them in a sortable and filterable table
+/// and in a chart of the number of events per day. The page does not load
+/// any external resources.
+pub(crate) struct HtmlOutput {
+    src_zone: Tz,
+    dst_zone: Tz,
+    is_first: Cell<bool>,
+}
+
+impl HtmlOutput {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            is_first: Cell::new(true),
+        }
+    }
+}
+
+impl Mactime2Writer for HtmlOutput {
+    fn header(&self) -> Option<String> {
+        Some(HTML_HEADER.trim_end().to_owned())
+    }
+
+    fn footer(&self) -> Option<String> {
+        Some(HTML_FOOTER.to_owned())
+    }
+
+    fn write(
+        &self,
+        out: &mut dyn Write,
+        timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        if self.is_first.replace(false) {
+            writeln!(out, "{}", self.fmt(timestamp, entry))
+        } else {
+            writeln!(out, ",{}", self.fmt(timestamp, entry))
+        }
+    }
+
+    fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
+        let line = &entry.line;
+        json!([
+            timestamp,
+            ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone).to_string(),
+            entry.flags.to_string(),
+            line.get_name(),
+            line.get_inode(),
+            line.get_mode_as_string(),
+            line.get_size(),
+        ])
+        .to_string()
+        // '<' can only occur in strings, where it can be escaped, so that
+        // file names cannot close the script element
+        .replace('<', "\\u003c")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+    use super::HtmlOutput;
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_escape_script() {
+        let output = HtmlOutput::new(Tz::UTC, Tz::UTC);
+        let entry = ListEntry {
+            flags: MacbFlags::M,
+            line: Arc::new(
+                Bodyfile3Line::new()
+                    .with_name("/tmp/</script><script>alert(1)")
+                    .with_mtime(1609459200),
+            ),
+        };
+        let row = output.fmt(&1609459200, &entry);
+        assert!(!row.contains('<'));
+
+        let row: Value = serde_json::from_str(&row).unwrap();
+        assert_eq!(row[0], 1609459200);
+        assert_eq!(row[2], "m...");
+        assert_eq!(row[3], "/tmp/</script><script>alert(1)");
+    }
+}
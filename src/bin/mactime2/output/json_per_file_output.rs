@@ -0,0 +1,92 @@
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+use serde_json::json;
+
+use crate::bodyfile::{ListEntry, Mactime2Writer};
+
+/// writes one JSON object per file, which contains all timestamps of the
+/// file in a nested object. Unset timestamps are written as `null`. This is
+/// the per-file counterpart to the per-event JSON format, so every file must
+/// be passed to this output only once.
+pub(crate) struct JsonPerFileOutput {
+    src_zone: Tz,
+    dst_zone: Tz,
+}
+
+impl JsonPerFileOutput {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self { src_zone, dst_zone }
+    }
+
+    fn format_timestamp(&self, unix_ts: i64) -> Option<String> {
+        ForensicsTimestamp::new(unix_ts, self.src_zone, self.dst_zone)
+            .as_datetime()
+            .map(|datetime| datetime.to_rfc3339())
+    }
+}
+
+impl Mactime2Writer for JsonPerFileOutput {
+    fn fmt(&self, _timestamp: &i64, entry: &ListEntry) -> String {
+        let line = &entry.line;
+        json!({
+            "name": line.get_name(),
+            "md5": line.get_md5(),
+            "inode": line.get_inode(),
+            "mode": line.get_mode_as_string(),
+            "uid": line.get_uid(),
+            "gid": line.get_gid(),
+            "size": line.get_size(),
+            "timestamps": {
+                "atime": self.format_timestamp(*line.get_atime()),
+                "mtime": self.format_timestamp(*line.get_mtime()),
+                "ctime": self.format_timestamp(*line.get_ctime()),
+                "crtime": self.format_timestamp(*line.get_crtime()),
+            },
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+    use super::JsonPerFileOutput;
+    use chrono::DateTime;
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_nested_timestamps() {
+        let output = JsonPerFileOutput::new(Tz::UTC, Tz::UTC);
+        let bf_line = Bodyfile3Line::new()
+            .with_name("C:/Windows/notepad.exe")
+            .with_size(42)
+            .with_mtime(1609459200)
+            .with_crtime(1609455600);
+        let entry = ListEntry {
+            flags: MacbFlags::B,
+            line: Arc::new(bf_line),
+        };
+
+        let document: Value = serde_json::from_str(&output.fmt(&1609455600, &entry)).unwrap();
+        for key in ["md5", "size", "mode", "uid", "gid", "inode"] {
+            assert!(document.get(key).is_some(), "missing key '{key}'");
+        }
+        assert_eq!(document["name"], "C:/Windows/notepad.exe");
+        assert_eq!(document["size"], 42);
+
+        let timestamps = document["timestamps"].as_object().unwrap();
+        assert_eq!(timestamps.len(), 4);
+        let mtime = DateTime::parse_from_rfc3339(timestamps["mtime"].as_str().unwrap()).unwrap();
+        assert_eq!(mtime.timestamp(), 1609459200);
+        let crtime = DateTime::parse_from_rfc3339(timestamps["crtime"].as_str().unwrap()).unwrap();
+        assert_eq!(crtime.timestamp(), 1609455600);
+
+        // -1 is used for timestamps which are not set
+        assert!(timestamps["atime"].is_null());
+        assert!(timestamps["ctime"].is_null());
+    }
+}
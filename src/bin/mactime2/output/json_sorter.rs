@@ -1,26 +1,47 @@
 use std::{
     borrow::Borrow,
     collections::{BTreeMap, BTreeSet},
+    io::Write,
     sync::{mpsc::Receiver, Arc},
     thread::JoinHandle,
 };
 
 use chrono_tz::Tz;
 use dfir_toolkit::{
-    common::bodyfile::Bodyfile3Line,
+    common::{
+        bodyfile::{Bodyfile3Line, MacbFlags},
+        OutputArgs, TimeRange,
+    },
     es4forensics::{objects::PosixFile, Timestamp, TimelineObject},
 };
+use serde_json::{json, Value};
 use std::convert::TryFrom;
 
 use crate::{
+    business_hours::OutsideHoursFilter,
     error::MactimeError,
     filter::RunOptions,
     filter::{Consumer, Joinable, Runnable, Sorter},
+    high_water_mark::HighWaterMark,
+    sample::Sample,
 };
+
+use super::NullDate;
+
 pub struct JsonSorter {
     worker: Option<JoinHandle<Result<(), MactimeError>>>,
     receiver: Option<Receiver<Bodyfile3Line>>,
     src_zone: Tz,
+    time_range: TimeRange,
+    limit: Option<usize>,
+    sample: Option<Sample>,
+    high_water_mark: Option<HighWaterMark>,
+    null_date: NullDate,
+    split_macb: bool,
+    depth_column: bool,
+    outside_hours: Option<OutsideHoursFilter>,
+    only: Option<MacbFlags>,
+    destination: OutputArgs,
 }
 
 impl Joinable<Result<(), MactimeError>> for JsonSorter {
@@ -35,6 +56,16 @@ impl Consumer<Bodyfile3Line> for JsonSorter {
             receiver: Some(previous),
             worker: None,
             src_zone: options.src_zone,
+            time_range: options.time_range,
+            limit: options.limit,
+            sample: options.sample,
+            high_water_mark: options.high_water_mark,
+            null_date: options.null_date,
+            split_macb: options.split_macb,
+            depth_column: options.depth_column,
+            outside_hours: options.outside_hours,
+            only: options.only,
+            destination: options.output,
         }
     }
 }
@@ -46,8 +77,31 @@ impl Runnable for JsonSorter {
             .take()
             .expect("no receiver provided; please call with_receiver()");
         let src_zone = self.src_zone;
+        let time_range = self.time_range;
+        let limit = self.limit;
+        let sample = self.sample;
+        let high_water_mark = self.high_water_mark.take();
+        let null_date = self.null_date;
+        let split_macb = self.split_macb;
+        let depth_column = self.depth_column;
+        let outside_hours = self.outside_hours;
+        let only = self.only;
+        let destination = self.destination.clone();
         self.worker = Some(std::thread::spawn(move || {
-            Self::json_worker(receiver, src_zone)
+            Self::json_worker(
+                receiver,
+                destination,
+                src_zone,
+                time_range,
+                limit,
+                sample,
+                high_water_mark,
+                null_date,
+                split_macb,
+                depth_column,
+                outside_hours,
+                only,
+            )
         }));
     }
 }
@@ -55,8 +109,23 @@ impl Runnable for JsonSorter {
 impl Sorter<Result<(), MactimeError>> for JsonSorter {}
 
 impl JsonSorter {
-    fn json_worker(decoder: Receiver<Bodyfile3Line>, src_zone: Tz) -> Result<(), MactimeError> {
+    #[allow(clippy::too_many_arguments)]
+    fn json_worker(
+        decoder: Receiver<Bodyfile3Line>,
+        destination: OutputArgs,
+        src_zone: Tz,
+        time_range: TimeRange,
+        limit: Option<usize>,
+        sample: Option<Sample>,
+        high_water_mark: Option<HighWaterMark>,
+        null_date: NullDate,
+        split_macb: bool,
+        depth_column: bool,
+        outside_hours: Option<OutsideHoursFilter>,
+        only: Option<MacbFlags>,
+    ) -> Result<(), MactimeError> {
         let mut entries: BTreeMap<Timestamp, BTreeSet<String>> = BTreeMap::new();
+        let mut reservoir = sample.map(|sample| sample.reservoir());
         loop {
             let line = Arc::new(match decoder.recv() {
                 Err(_) => {
@@ -70,24 +139,105 @@ impl JsonSorter {
 
             let lines: Vec<(Timestamp, String)> = pf
                 .into_tuples()
-                .map(|(t, v)| (t, serde_json::to_string(&v).unwrap()))
+                .filter(|(t, _)| {
+                    let unix_ts = t.timestamp_millis() / 1000;
+                    time_range.contains(unix_ts)
+                        && outside_hours.iter().all(|filter| filter.matches_utc(unix_ts))
+                        && only.iter().all(|only| {
+                            MacbFlags::from_line_at_time(bfline, unix_ts).intersects(*only)
+                        })
+                })
+                .map(|(t, mut v)| {
+                    if split_macb {
+                        Self::insert_macb_flags(&mut v);
+                    }
+                    if let Some(placeholder) = null_date.placeholder() {
+                        Self::insert_null_dates(&mut v, placeholder);
+                    }
+                    if depth_column {
+                        if let Some(file) = v.get_mut("file").and_then(Value::as_object_mut) {
+                            file.insert("depth".to_owned(), json!(bfline.path_depth()));
+                        }
+                    }
+                    (t, serde_json::to_string(&v).unwrap())
+                })
                 .collect();
 
             if lines.is_empty() {
                 log::warn!("file {} has no timestamp entries", line.get_name());
                 log::warn!("raw entry is {}", line.to_string());
+            } else if let Some(reservoir) = reservoir.as_mut() {
+                for event in lines {
+                    reservoir.push(event);
+                }
             } else {
                 for (ts, line) in lines {
-                    entries.entry(ts).or_insert(BTreeSet::new()).insert(line);
+                    entries.entry(ts).or_default().insert(line);
                 }
             }
         }
 
-        for lines in entries.into_values() {
-            for line in lines {
-                println!("{}", line);
+        if let Some(reservoir) = reservoir {
+            let seen = reservoir.seen();
+            let events = reservoir.into_items();
+            log::info!("sampled {} out of {seen} events", events.len());
+            for (ts, line) in events {
+                entries.entry(ts).or_default().insert(line);
             }
         }
+
+        let mut out = destination.open()?;
+        let mut latest = None;
+        let events = entries
+            .into_iter()
+            .flat_map(|(ts, lines)| {
+                let ts = ts.timestamp_millis() / 1000;
+                lines.into_iter().map(move |line| (ts, line))
+            })
+            .take(limit.unwrap_or(usize::MAX));
+        for (ts, line) in events {
+            writeln!(out, "{}", line)?;
+            latest = Some(ts);
+        }
+        out.finish()?;
+        if let (Some(high_water_mark), Some(latest)) = (high_water_mark, latest) {
+            high_water_mark.store(latest);
+        }
         Ok(())
     }
+
+    /// adds the boolean fields `accessed`, `modified`, `changed` and `created`,
+    /// which denote which timestamps of the file are equal to the timestamp
+    /// of the event
+    fn insert_macb_flags(value: &mut Value) {
+        let timestamp = value.get("@timestamp").cloned();
+        let flags: Vec<_> = [
+            ("accessed", "accessed"),
+            ("modified", "mtime"),
+            ("changed", "ctime"),
+            ("created", "created"),
+        ]
+        .into_iter()
+        .map(|(flag, key)| {
+            let is_set =
+                timestamp.is_some() && value.pointer(&format!("/file/{key}")) == timestamp.as_ref();
+            (flag, is_set)
+        })
+        .collect();
+        if let Some(document) = value.as_object_mut() {
+            for (flag, is_set) in flags {
+                document.insert(flag.to_owned(), json!(is_set));
+            }
+        }
+    }
+
+    /// adds a placeholder (a unix timestamp) for every timestamp of the file
+    /// which is not set
+    fn insert_null_dates(value: &mut Value, placeholder: i64) {
+        if let Some(file) = value.get_mut("file").and_then(Value::as_object_mut) {
+            for key in ["accessed", "mtime", "ctime", "created"] {
+                file.entry(key).or_insert_with(|| json!(placeholder * 1000));
+            }
+        }
+    }
 }
@@ -1,7 +1,27 @@
 mod csv_output;
+mod csv_dialect;
 mod txt_output;
 mod json_sorter;
+mod output_field;
+mod timesketch_output;
+mod geojson_output;
+mod html_output;
+mod count_output;
+mod split_output;
+mod null_date;
+mod pretty_output;
+mod json_per_file_output;
 
 pub (crate) use csv_output::*;
+pub (crate) use csv_dialect::*;
 pub (crate) use txt_output::*;
-pub (crate) use json_sorter::*;
\ No newline at end of file
+pub (crate) use json_sorter::*;
+pub (crate) use output_field::*;
+pub (crate) use timesketch_output::*;
+pub (crate) use geojson_output::*;
+pub (crate) use html_output::*;
+pub (crate) use count_output::*;
+pub (crate) use split_output::*;
+pub (crate) use null_date::*;
+pub (crate) use pretty_output::*;
+pub (crate) use json_per_file_output::*;
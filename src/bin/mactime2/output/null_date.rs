@@ -0,0 +1,137 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+/// specifies how timestamps which are not set (`-1`) are rendered by the csv
+/// and json outputs. The parsed values are never changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum NullDate {
+    /// leave the value empty (csv) or omit the field (json)
+    #[default]
+    Skip,
+
+    /// use `1970-01-01T00:00:00+00:00`
+    Epoch,
+
+    /// use a fixed date, stored as unix timestamp
+    Date(i64),
+}
+
+impl FromStr for NullDate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "epoch" => Ok(Self::Epoch),
+            date => {
+                if let Ok(datetime) = DateTime::parse_from_rfc3339(date) {
+                    Ok(Self::Date(datetime.timestamp()))
+                } else if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                    Ok(Self::Date(date.and_hms_opt(0, 0, 0).unwrap().timestamp()))
+                } else {
+                    bail!("expected 'skip', 'epoch' or an ISO 8601 date, but found '{date}'")
+                }
+            }
+        }
+    }
+}
+
+impl NullDate {
+    /// returns the unix timestamp which replaces an unset timestamp, or
+    /// `None` if unset timestamps are skipped
+    pub fn placeholder(&self) -> Option<i64> {
+        match self {
+            Self::Skip => None,
+            Self::Epoch => Some(0),
+            Self::Date(ts) => Some(*ts),
+        }
+    }
+
+    /// formats `unix_ts` like [`ForensicsTimestamp`] does, but renders an
+    /// unset timestamp as placeholder. Placeholders denote a fixed point in
+    /// time and are therefore not shifted by the source timezone.
+    pub fn format(&self, unix_ts: i64, src_zone: Tz, dst_zone: Tz) -> Option<String> {
+        if unix_ts != -1 {
+            Some(ForensicsTimestamp::new(unix_ts, src_zone, dst_zone).to_string())
+        } else {
+            self.placeholder()
+                .map(|ts| ForensicsTimestamp::new(ts, Tz::UTC, dst_zone).to_string())
+        }
+    }
+
+    /// like [`Self::format`], but renders the timestamp as milliseconds since
+    /// the unix epoch. Because the timestamp is absolute, no destination
+    /// timezone is needed.
+    pub fn format_epoch_ms(&self, unix_ts: i64, src_zone: Tz) -> Option<String> {
+        if unix_ts != -1 {
+            ForensicsTimestamp::new(unix_ts, src_zone, Tz::UTC)
+                .as_datetime()
+                .map(|datetime| datetime.timestamp_millis().to_string())
+        } else {
+            self.placeholder().map(|ts| (ts * 1000).to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono_tz::Tz;
+
+    use super::NullDate;
+
+    #[test]
+    fn parse() {
+        assert_eq!(NullDate::from_str("skip").unwrap(), NullDate::Skip);
+        assert_eq!(NullDate::from_str("epoch").unwrap(), NullDate::Epoch);
+        assert_eq!(
+            NullDate::from_str("2099-12-31").unwrap(),
+            NullDate::Date(4102358400)
+        );
+        assert_eq!(
+            NullDate::from_str("2099-12-31T01:00:00+01:00").unwrap(),
+            NullDate::Date(4102358400)
+        );
+        assert!(NullDate::from_str("tomorrow").is_err());
+    }
+
+    #[test]
+    fn format() {
+        let berlin = Tz::Europe__Berlin;
+        assert_eq!(NullDate::Skip.format(-1, berlin, Tz::UTC), None);
+        assert_eq!(
+            NullDate::Epoch.format(-1, berlin, Tz::UTC).unwrap(),
+            "1970-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            NullDate::Skip.format(3600, berlin, Tz::UTC).unwrap(),
+            "1970-01-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn format_epoch_ms() {
+        let berlin = Tz::Europe__Berlin;
+        assert_eq!(
+            NullDate::Skip.format_epoch_ms(1609459200, Tz::UTC).unwrap(),
+            "1609459200000"
+        );
+        assert_eq!(
+            NullDate::Skip.format_epoch_ms(1609459200, berlin).unwrap(),
+            "1609455600000"
+        );
+        assert_eq!(NullDate::Skip.format_epoch_ms(-1, berlin), None);
+        assert_eq!(NullDate::Epoch.format_epoch_ms(-1, berlin).unwrap(), "0");
+        assert_eq!(
+            NullDate::Date(4102358400)
+                .format_epoch_ms(-1, berlin)
+                .unwrap(),
+            "4102358400000"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use clap::ValueEnum;
+use dfir_toolkit::common::bodyfile::TimestampKind;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::bodyfile::ListEntry;
+
+/// columns which can be selected using `--fields`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OutputField {
+    Date,
+    Size,
+    Macb,
+    Mode,
+    Uid,
+    Gid,
+    Inode,
+    Name,
+    Category,
+    Atime,
+    Mtime,
+    Ctime,
+    Crtime,
+    Depth,
+}
+
+impl OutputField {
+    /// columns printed by `mactime`, in the same order
+    pub const ALL: [Self; 8] = [
+        Self::Date,
+        Self::Size,
+        Self::Macb,
+        Self::Mode,
+        Self::Uid,
+        Self::Gid,
+        Self::Inode,
+        Self::Name,
+    ];
+
+    /// column title, as used by `mactime`
+    pub fn header(&self) -> &'static str {
+        match self {
+            Self::Date => "Date",
+            Self::Size => "Size",
+            Self::Macb => "Type",
+            Self::Mode => "Mode",
+            Self::Uid => "UID",
+            Self::Gid => "GID",
+            Self::Inode => "Meta",
+            Self::Name => "File Name",
+            Self::Category => "Category",
+            Self::Atime => "atime",
+            Self::Mtime => "mtime",
+            Self::Ctime => "ctime",
+            Self::Crtime => "crtime",
+            Self::Depth => "Depth",
+        }
+    }
+
+    /// returns the kind of timestamp displayed in this column, independent
+    /// of the timestamp of the event
+    pub fn timestamp_kind(&self) -> Option<TimestampKind> {
+        match self {
+            Self::Atime => Some(TimestampKind::Atime),
+            Self::Mtime => Some(TimestampKind::Mtime),
+            Self::Ctime => Some(TimestampKind::Ctime),
+            Self::Crtime => Some(TimestampKind::Crtime),
+            _ => None,
+        }
+    }
+
+    /// unformatted value of this column
+    pub fn value(&self, timestamp: &ForensicsTimestamp, entry: &ListEntry) -> String {
+        match self {
+            Self::Date => timestamp.to_string(),
+            Self::Size => entry.line.get_size().to_string(),
+            Self::Macb => entry.flags.to_string(),
+            Self::Mode => entry.line.get_mode_as_string().to_owned(),
+            Self::Uid => entry.line.get_uid().to_string(),
+            Self::Gid => entry.line.get_gid().to_string(),
+            Self::Inode => entry.line.get_inode().to_owned(),
+            Self::Name => entry.line.get_name().to_owned(),
+            Self::Category => entry.line.category().to_string(),
+            Self::Depth => entry.line.path_depth().to_string(),
+            Self::Atime | Self::Mtime | Self::Ctime | Self::Crtime => entry
+                .line
+                .get_timestamp(self.timestamp_kind().unwrap())
+                .to_string(),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::cell::RefCell;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::bodyfile::{ListEntry, Mactime2Writer};
+
+use super::{NullDate, OutputField};
+
+/// default maximum width of the file name column
+pub(crate) const DEFAULT_NAME_WIDTH: usize = 80;
+
+/// renders the events as an aligned table with a header. Because the column
+/// widths are computed from the data, all rows are collected and printed
+/// after the last event.
+pub(crate) struct PrettyOutput {
+    src_zone: Tz,
+    dst_zone: Tz,
+    fields: Vec<OutputField>,
+    name_width: usize,
+    rows: RefCell<Vec<Vec<String>>>,
+}
+
+impl PrettyOutput {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            fields: OutputField::ALL.to_vec(),
+            name_width: DEFAULT_NAME_WIDTH,
+            rows: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// restricts the output to the specified columns
+    pub fn with_fields(mut self, fields: Vec<OutputField>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// file names which are longer than `name_width` characters are truncated
+    pub fn with_name_width(mut self, name_width: usize) -> Self {
+        self.name_width = name_width;
+        self
+    }
+
+    fn cells(&self, timestamp: &i64, entry: &ListEntry) -> Vec<String> {
+        let timestamp = ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone);
+        self.fields
+            .iter()
+            .map(|field| match field {
+                OutputField::Name => {
+                    Self::truncate(field.value(&timestamp, entry), self.name_width)
+                }
+                _ => match field.timestamp_kind() {
+                    Some(kind) => NullDate::Skip
+                        .format(entry.line.get_timestamp(kind), self.src_zone, self.dst_zone)
+                        .unwrap_or_default(),
+                    None => field.value(&timestamp, entry),
+                },
+            })
+            .collect()
+    }
+
+    /// shortens `value` to `width` characters, the last of which is an ellipsis
+    fn truncate(value: String, width: usize) -> String {
+        if value.chars().count() <= width {
+            value
+        } else {
+            value
+                .chars()
+                .take(width.saturating_sub(1))
+                .chain(std::iter::once('…'))
+                .collect()
+        }
+    }
+
+    /// numbers are aligned to the right, everything else to the left
+    fn is_right_aligned(field: &OutputField) -> bool {
+        matches!(
+            field,
+            OutputField::Size | OutputField::Uid | OutputField::Gid | OutputField::Depth
+        )
+    }
+
+    fn format_row(&self, cells: &[String], widths: &[usize]) -> String {
+        let last = cells.len().saturating_sub(1);
+        cells
+            .iter()
+            .zip(widths)
+            .zip(&self.fields)
+            .enumerate()
+            .map(|(idx, ((cell, width), field))| {
+                if Self::is_right_aligned(field) {
+                    format!("{cell:>width$}")
+                } else if idx == last {
+                    cell.clone()
+                } else {
+                    format!("{cell:<width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+impl Mactime2Writer for PrettyOutput {
+    fn footer(&self) -> Option<String> {
+        let header: Vec<_> = self.fields.iter().map(|f| f.header().to_owned()).collect();
+        let rows = self.rows.borrow();
+        let widths: Vec<_> = (0..self.fields.len())
+            .map(|idx| {
+                std::iter::once(&header)
+                    .chain(rows.iter())
+                    .map(|row| row[idx].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let separator: Vec<_> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        Some(
+            [&header, &separator]
+                .into_iter()
+                .chain(rows.iter())
+                .map(|row| self.format_row(row, &widths))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn write(
+        &self,
+        _out: &mut dyn Write,
+        timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        let cells = self.cells(timestamp, entry);
+        self.rows.borrow_mut().push(cells);
+        Ok(())
+    }
+
+    fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
+        self.cells(timestamp, entry).join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrettyOutput;
+
+    #[test]
+    fn truncate_names() {
+        assert_eq!(PrettyOutput::truncate("/tmp".to_owned(), 4), "/tmp");
+        assert_eq!(PrettyOutput::truncate("/tmp/a".to_owned(), 4), "/tm…");
+        assert_eq!(PrettyOutput::truncate("/tmp/ä".to_owned(), 6), "/tmp/ä");
+    }
+}
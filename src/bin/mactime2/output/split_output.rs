@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::bodyfile::{ListEntry, Mactime2Writer};
+
+/// size of the time buckets which are written into separate files
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub(crate) enum SplitBucket {
+    Day,
+
+    /// ISO 8601 week, e.g. `2021-W07`
+    Week,
+
+    Month,
+}
+
+impl SplitBucket {
+    fn format(&self) -> &'static str {
+        match self {
+            Self::Day => "%Y-%m-%d",
+            Self::Week => "%G-W%V",
+            Self::Month => "%Y-%m",
+        }
+    }
+}
+
+/// writes the events into one file per time bucket (e.g.
+/// `timeline-2021-02-18.csv`), using another writer to format them. Because
+/// the events arrive in chronological order, every file is sorted, and only
+/// one file needs to be open at a time. Events without a valid timestamp are
+/// written into `timeline-unknown`.
+pub(crate) struct SplitOutput {
+    inner: Box<dyn Mactime2Writer>,
+    bucket: SplitBucket,
+    directory: PathBuf,
+    extension: &'static str,
+    src_zone: Tz,
+    dst_zone: Tz,
+    current: RefCell<Option<(String, BufWriter<File>)>>,
+}
+
+impl SplitOutput {
+    pub fn new(
+        inner: Box<dyn Mactime2Writer>,
+        bucket: SplitBucket,
+        directory: PathBuf,
+        extension: &'static str,
+        src_zone: Tz,
+        dst_zone: Tz,
+    ) -> Self {
+        Self {
+            inner,
+            bucket,
+            directory,
+            extension,
+            src_zone,
+            dst_zone,
+            current: RefCell::new(None),
+        }
+    }
+
+    fn bucket_of(&self, timestamp: i64) -> String {
+        match ForensicsTimestamp::new(timestamp, self.src_zone, self.dst_zone).as_datetime() {
+            Some(datetime) => datetime.format(self.bucket.format()).to_string(),
+            None => "unknown".to_owned(),
+        }
+    }
+
+    fn open(&self, bucket: &str) -> std::io::Result<BufWriter<File>> {
+        let path = self
+            .directory
+            .join(format!("timeline-{bucket}.{}", self.extension));
+        log::info!("writing events to '{}'", path.display());
+        let mut file = BufWriter::new(File::create(path)?);
+        if let Some(header) = self.inner.header() {
+            write!(file, "{header}{}", self.inner.line_terminator())?;
+        }
+        Ok(file)
+    }
+
+    fn close(&self, mut file: BufWriter<File>) -> std::io::Result<()> {
+        if let Some(footer) = self.inner.footer() {
+            write!(file, "{footer}{}", self.inner.line_terminator())?;
+        }
+        file.flush()
+    }
+}
+
+impl Mactime2Writer for SplitOutput {
+    /// closes the last file; nothing is printed to stdout
+    fn footer(&self) -> Option<String> {
+        if let Some((bucket, file)) = self.current.borrow_mut().take() {
+            if let Err(why) = self.close(file) {
+                log::error!("unable to write the events of {bucket}: {why}");
+            }
+        }
+        None
+    }
+
+    /// writes into the file of the current bucket instead of `_out`
+    fn write(
+        &self,
+        _out: &mut dyn Write,
+        timestamp: &i64,
+        entry: &ListEntry,
+    ) -> std::io::Result<()> {
+        let bucket = self.bucket_of(*timestamp);
+        let mut current = self.current.borrow_mut();
+
+        if current.as_ref().map(|(b, _)| b != &bucket).unwrap_or(true) {
+            if let Some((previous, file)) = current.take() {
+                if let Err(why) = self.close(file) {
+                    log::error!("unable to write the events of {previous}: {why}");
+                }
+            }
+            match self.open(&bucket) {
+                Ok(file) => *current = Some((bucket.clone(), file)),
+                Err(why) => {
+                    log::error!("unable to create the file for {bucket}: {why}");
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Some((_, file)) = current.as_mut() {
+            if let Err(why) = self.inner.write(file, timestamp, entry) {
+                log::error!("unable to write the events of {bucket}: {why}");
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
+        self.inner.fmt(timestamp, entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono_tz::Tz;
+
+    use super::{SplitBucket, SplitOutput};
+    use crate::output::CsvOutput;
+
+    fn output(bucket: SplitBucket, dst_zone: Tz) -> SplitOutput {
+        SplitOutput::new(
+            Box::new(CsvOutput::new(Tz::UTC, dst_zone)),
+            bucket,
+            PathBuf::from("."),
+            "csv",
+            Tz::UTC,
+            dst_zone,
+        )
+    }
+
+    #[test]
+    fn buckets() {
+        // 2021-02-18T23:30:00+00:00
+        let ts = 1613691000;
+        assert_eq!(output(SplitBucket::Day, Tz::UTC).bucket_of(ts), "2021-02-18");
+        assert_eq!(output(SplitBucket::Week, Tz::UTC).bucket_of(ts), "2021-W07");
+        assert_eq!(output(SplitBucket::Month, Tz::UTC).bucket_of(ts), "2021-02");
+
+        // the buckets honor the destination timezone
+        assert_eq!(
+            output(SplitBucket::Day, Tz::Europe__Berlin).bucket_of(ts),
+            "2021-02-19"
+        );
+        assert_eq!(output(SplitBucket::Day, Tz::UTC).bucket_of(-1), "unknown");
+    }
+}
@@ -0,0 +1,104 @@
+use chrono_tz::Tz;
+use dfir_toolkit::common::ForensicsTimestamp;
+use serde_json::json;
+
+use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+/// writes one event per timestamp kind in the JSONL format which can be
+/// imported by Timesketch
+pub(crate) struct TimesketchOutput {
+    src_zone: Tz,
+    dst_zone: Tz,
+}
+
+impl TimesketchOutput {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self { src_zone, dst_zone }
+    }
+
+    const DESCRIPTIONS: [(MacbFlags, &'static str); 4] = [
+        (MacbFlags::M, "Content Modification Time"),
+        (MacbFlags::A, "Last Access Time"),
+        (MacbFlags::C, "Metadata Modification Time"),
+        (MacbFlags::B, "Creation Time"),
+    ];
+}
+
+impl Mactime2Writer for TimesketchOutput {
+    fn fmt(&self, timestamp: &i64, entry: &ListEntry) -> String {
+        let datetime = ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone).to_string();
+        let line = &entry.line;
+        Self::DESCRIPTIONS
+            .iter()
+            .filter(|(flag, _)| entry.flags.contains(*flag))
+            .map(|(_, description)| {
+                json!({
+                    "datetime": datetime,
+                    "timestamp_desc": description,
+                    "message": line.get_name(),
+                    "macb": entry.flags.to_string(),
+                    "inode": line.get_inode(),
+                    "mode": line.get_mode_as_string(),
+                    "uid": line.get_uid(),
+                    "gid": line.get_gid(),
+                    "size": line.get_size(),
+                    "md5": line.get_md5(),
+                })
+                .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+
+    use super::TimesketchOutput;
+    use chrono::DateTime;
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_timesketch_event() {
+        let output = TimesketchOutput::new(Tz::UTC, Tz::UTC);
+        let bf_line = Bodyfile3Line::new()
+            .with_name("C:/Windows/notepad.exe")
+            .with_mtime(1609459200);
+        let entry = ListEntry {
+            flags: MacbFlags::M,
+            line: Arc::new(bf_line),
+        };
+
+        let out_line = output.fmt(&1609459200, &entry);
+        let event: Value = serde_json::from_str(&out_line).unwrap();
+        for key in ["datetime", "timestamp_desc", "message"] {
+            assert!(event.get(key).is_some(), "missing key '{key}'");
+        }
+        assert_eq!(event["timestamp_desc"], "Content Modification Time");
+        assert_eq!(event["message"], "C:/Windows/notepad.exe");
+
+        let datetime = DateTime::parse_from_rfc3339(event["datetime"].as_str().unwrap()).unwrap();
+        assert_eq!(datetime.timestamp(), 1609459200);
+    }
+
+    #[test]
+    fn test_one_event_per_kind() {
+        let output = TimesketchOutput::new(Tz::UTC, Tz::UTC);
+        let bf_line = Bodyfile3Line::new().with_atime(0).with_crtime(0);
+        let entry = ListEntry {
+            flags: MacbFlags::A | MacbFlags::B,
+            line: Arc::new(bf_line),
+        };
+
+        let descriptions: Vec<_> = output
+            .fmt(&0, &entry)
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap()["timestamp_desc"].clone())
+            .collect();
+        assert_eq!(descriptions, vec!["Last Access Time", "Creation Time"]);
+    }
+}
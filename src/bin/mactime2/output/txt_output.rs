@@ -1,14 +1,19 @@
 use chrono_tz::Tz;
+use colored::{Color, Colorize};
 use dfir_toolkit::common::ForensicsTimestamp;
 use std::cell::RefCell;
 
-use crate::bodyfile::{ListEntry, Mactime2Writer};
+use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
+use crate::timestomp::is_timestomped;
+
+use super::{NullDate, OutputField};
 
 pub struct TxtOutput {
     src_zone: Tz,
     dst_zone: Tz,
     last_ts: (RefCell<i64>, RefCell<String>),
     empty_ts: RefCell<String>,
+    fields: Vec<OutputField>,
 }
 
 impl TxtOutput {
@@ -18,6 +23,45 @@ impl TxtOutput {
             dst_zone,
             last_ts: (RefCell::new(i64::MIN), RefCell::new("".to_owned())),
             empty_ts: RefCell::new("                         ".to_owned()),
+            fields: OutputField::ALL.to_vec(),
+        }
+    }
+
+    /// restricts the output to the specified columns
+    pub fn with_fields(mut self, fields: Vec<OutputField>) -> Self {
+        self.fields = fields;
+        self
+    }
+}
+
+impl TxtOutput {
+    /// uses a distinct color for every flag; colors are only added if
+    /// colored output has been enabled using `--color`
+    fn colorize_macb(flags: MacbFlags) -> String {
+        [
+            (MacbFlags::M, "m", Color::Yellow),
+            (MacbFlags::A, "a", Color::Green),
+            (MacbFlags::C, "c", Color::Blue),
+            (MacbFlags::B, "b", Color::Red),
+        ]
+        .iter()
+        .map(|(flag, name, color)| {
+            if flags.contains(*flag) {
+                name.color(*color).to_string()
+            } else {
+                ".".to_owned()
+            }
+        })
+        .collect()
+    }
+
+    /// highlights a column of a row whose timestamps seem to have been
+    /// manipulated
+    fn highlight(value: String, timestomped: bool) -> String {
+        if timestomped {
+            value.color(Color::Magenta).to_string()
+        } else {
+            value
         }
     }
 }
@@ -32,24 +76,43 @@ impl Mactime2Writer for TxtOutput {
         } else {
             self.empty_ts.borrow()
         };
-        format!(
-            "{} {:>8} {} {:<12} {:<7} {:<7} {} {}",
-            ts,
-            entry.line.get_size(),
-            entry.flags,
-            entry.line.get_mode_as_string(),
-            entry.line.get_uid(),
-            entry.line.get_gid(),
-            entry.line.get_inode(),
-            entry.line.get_name()
-        )
+        let timestamp = ForensicsTimestamp::new(*timestamp, self.src_zone, self.dst_zone);
+        let timestomped = is_timestomped(&entry.line);
+        self.fields
+            .iter()
+            .map(|field| match field {
+                OutputField::Macb => Self::colorize_macb(entry.flags),
+                _ => Self::highlight(
+                    match field {
+                        OutputField::Date => ts.clone(),
+                        OutputField::Size => format!("{:>8}", field.value(&timestamp, entry)),
+                        OutputField::Mode => format!("{:<12}", field.value(&timestamp, entry)),
+                        OutputField::Uid | OutputField::Gid => {
+                            format!("{:<7}", field.value(&timestamp, entry))
+                        }
+                        _ => match field.timestamp_kind() {
+                            Some(kind) => NullDate::Skip
+                                .format(
+                                    entry.line.get_timestamp(kind),
+                                    self.src_zone,
+                                    self.dst_zone,
+                                )
+                                .unwrap_or_default(),
+                            None => field.value(&timestamp, entry),
+                        },
+                    },
+                    timestomped,
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::TxtOutput;
-    use crate::bodyfile::{ListEntry, MACBFlags, Mactime2Writer};
+    use crate::bodyfile::{ListEntry, MacbFlags, Mactime2Writer};
     use chrono::DateTime;
     use chrono_tz::Tz;
     use chrono_tz::TZ_VARIANTS;
@@ -69,7 +132,7 @@ mod tests {
             let unix_ts = rand::random::<u32>() as i64;
             let bf_line = Bodyfile3Line::new().with_crtime(unix_ts);
             let entry = ListEntry {
-                flags: MACBFlags::B,
+                flags: MacbFlags::B,
                 line: Arc::new(bf_line),
             };
 
@@ -99,7 +162,7 @@ mod tests {
             let unix_ts = rand::random::<u32>() as i64;
             let bf_line = Bodyfile3Line::new().with_crtime(unix_ts);
             let entry = ListEntry {
-                flags: MACBFlags::B,
+                flags: MacbFlags::B,
                 line: Arc::new(bf_line),
             };
 
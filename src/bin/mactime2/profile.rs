@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::ForensicsTimestamp;
+
+use crate::filter::EventFilter;
+use crate::summary::DirectorySummary;
+
+/// activity of a single user
+struct UserActivity {
+    /// name of the user as it has been found first
+    user: String,
+    events: usize,
+    first: i64,
+    last: i64,
+    directories: HashMap<String, usize>,
+}
+
+impl UserActivity {
+    fn new(user: String) -> Self {
+        Self {
+            user,
+            events: 0,
+            first: i64::MAX,
+            last: i64::MIN,
+            directories: HashMap::new(),
+        }
+    }
+
+    /// returns the `count` directories with the most events
+    fn top_directories(&self, count: usize) -> Vec<(&str, usize)> {
+        let mut directories: Vec<_> = self
+            .directories
+            .iter()
+            .map(|(dir, count)| (dir.as_str(), *count))
+            .collect();
+        directories.sort_by(|(dir1, count1), (dir2, count2)| {
+            count2.cmp(count1).then_with(|| dir1.cmp(dir2))
+        });
+        directories.truncate(count);
+        directories
+    }
+}
+
+/// summarizes the activity of every user, i.e. the number of events, the
+/// first and last event and the directories with the most events. The user
+/// is taken from the profile directory in the name of the file (e.g.
+/// `C:/Users/jdoe/...`), or from the uid column if the file is not stored
+/// in a profile directory.
+pub(crate) struct UserProfiles {
+    src_zone: Tz,
+    dst_zone: Tz,
+    filter: EventFilter,
+    users: HashMap<String, UserActivity>,
+}
+
+impl UserProfiles {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            filter: EventFilter::default(),
+            users: HashMap::new(),
+        }
+    }
+
+    /// ignores all timestamps which are not selected by `filter`
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        // identical timestamps of a file are merged into one event
+        let events = self.filter.events(line);
+        let (first, last) = match (events.first(), events.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return,
+        };
+
+        // profile directories are compared case-insensitively, like on Windows
+        let user = match line.profile_user() {
+            Some(user) => user.to_owned(),
+            None => format!("uid {}", line.get_uid()),
+        };
+        let activity = self
+            .users
+            .entry(user.to_lowercase())
+            .or_insert_with(|| UserActivity::new(user));
+
+        activity.events += events.len();
+        activity.first = activity.first.min(first);
+        activity.last = activity.last.max(last);
+        *activity
+            .directories
+            .entry(DirectorySummary::parent_of(line.get_name()).to_owned())
+            .or_insert(0) += events.len();
+    }
+
+    /// returns the activities of all users, beginning with the most active
+    /// user
+    fn activities(&self) -> Vec<&UserActivity> {
+        let mut activities: Vec<_> = self.users.values().collect();
+        activities.sort_by(|a1, a2| {
+            a2.events
+                .cmp(&a1.events)
+                .then_with(|| a1.user.cmp(&a2.user))
+        });
+        activities
+    }
+
+    fn format_timestamp(&self, ts: i64) -> String {
+        ForensicsTimestamp::new(ts, self.src_zone, self.dst_zone).to_string()
+    }
+
+    /// prints the number of events, the first and the last event of every
+    /// user, followed by the `top` directories with the most events
+    pub fn print(&self, top: usize, out: &mut dyn Write) -> std::io::Result<()> {
+        for activity in self.activities() {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}",
+                activity.user,
+                activity.events,
+                self.format_timestamp(activity.first),
+                self.format_timestamp(activity.last)
+            )?;
+            for (dir, events) in activity.top_directories(top) {
+                writeln!(out, "\t{events}\t{dir}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::UserProfiles;
+
+    #[test]
+    fn users_are_case_insensitive() {
+        let mut profiles = UserProfiles::new(Tz::UTC, Tz::UTC);
+        for (name, mtime) in [
+            ("C:/Users/jdoe/a.txt", 200),
+            ("C:/USERS/JDOE/b.txt", 100),
+            ("/etc/passwd", 300),
+        ] {
+            profiles.add(&Bodyfile3Line::new().with_name(name).with_mtime(mtime));
+        }
+
+        let activities = profiles.activities();
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].user, "jdoe");
+        assert_eq!(activities[0].events, 2);
+        assert_eq!((activities[0].first, activities[0].last), (100, 200));
+        assert_eq!(activities[1].user, "uid 0");
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono_tz::Tz;
+use dfir_toolkit::common::tz::windows_to_iana;
+use nt_hive2::*;
+
+/// reads the configured timezone of a Windows host from its `SYSTEM` hive.
+/// Falls back to UTC if the hive contains no timezone information.
+pub(crate) fn timezone_from_registry(path: &Path) -> Result<Tz> {
+    let file = File::open(path)
+        .map_err(|why| anyhow!("unable to open '{}': {why}", path.display()))?;
+    let mut hive = Hive::new(file, HiveParseMode::NormalWithBaseBlock)?.treat_hive_as_clean();
+    let root_key = hive.root_key_node()?;
+
+    // `CurrentControlSet` is a volatile link, which is not stored in the hive
+    let current = root_key
+        .subkey("Select", &mut hive)?
+        .and_then(|select| dword_value(&select.borrow(), "Current"))
+        .unwrap_or(1);
+    let key_path = format!("ControlSet{current:03}\\Control\\TimeZoneInformation");
+
+    let (key_name, bias) = match root_key.subpath(&key_path, &mut hive)? {
+        Some(key) => {
+            let key = key.borrow();
+            (
+                string_value(&key, "TimeZoneKeyName"),
+                dword_value(&key, "Bias").map(|bias| bias as i32),
+            )
+        }
+        None => {
+            log::warn!(
+                "'{}' contains no key '{key_path}', using UTC as destination timezone",
+                path.display()
+            );
+            return Ok(Tz::UTC);
+        }
+    };
+
+    match timezone_from_values(key_name.as_deref(), bias) {
+        Some(tz) => {
+            log::info!(
+                "detected timezone '{tz}' (TimeZoneKeyName: {}, Bias: {})",
+                key_name.as_deref().unwrap_or("-"),
+                bias.map(|b| b.to_string()).unwrap_or_else(|| "-".into())
+            );
+            Ok(tz)
+        }
+        None => {
+            log::warn!("unable to detect the timezone from '{key_path}', using UTC as destination timezone");
+            Ok(Tz::UTC)
+        }
+    }
+}
+
+/// derives a timezone from the `TimeZoneKeyName` and `Bias` values. The
+/// `Bias` is the number of minutes which must be added to the local time to
+/// get UTC; it is only used for timezones which are unknown by name and
+/// which have a whole-hour offset. Daylight saving time cannot be derived
+/// from the bias.
+fn timezone_from_values(key_name: Option<&str>, bias: Option<i32>) -> Option<Tz> {
+    key_name.and_then(windows_to_iana).or_else(|| match bias {
+        // the sign of `Etc/GMT` zones is inverted, so that it matches the bias
+        Some(bias) if bias % 60 == 0 => Tz::from_str(&format!("Etc/GMT{:+}", bias / 60)).ok(),
+        _ => None,
+    })
+}
+
+fn dword_value(key: &KeyNode, name: &str) -> Option<u32> {
+    key.values()
+        .iter()
+        .find(|v| v.name() == name)
+        .and_then(|v| match v.value() {
+            RegistryValue::RegDWord(value) => Some(*value),
+            _ => None,
+        })
+}
+
+fn string_value(key: &KeyNode, name: &str) -> Option<String> {
+    key.values()
+        .iter()
+        .find(|v| v.name() == name)
+        .and_then(|v| match v.value() {
+            RegistryValue::RegSZ(value) => Some(value.trim_end_matches('\0').to_string()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use super::timezone_from_values;
+
+    #[test]
+    fn known_key_name() {
+        assert_eq!(
+            timezone_from_values(Some("W. Europe Standard Time"), Some(-60)),
+            Some(Tz::Europe__Berlin)
+        );
+        assert_eq!(
+            timezone_from_values(Some("Eastern Standard Time"), None),
+            Some(Tz::America__New_York)
+        );
+    }
+
+    #[test]
+    fn fallback_to_bias() {
+        assert_eq!(
+            timezone_from_values(Some("Unknown Standard Time"), Some(-60)),
+            Some(Tz::Etc__GMTMinus1)
+        );
+        assert_eq!(timezone_from_values(None, Some(300)), Some(Tz::Etc__GMTPlus5));
+        assert_eq!(timezone_from_values(None, Some(0)), Some(Tz::Etc__GMTPlus0));
+    }
+
+    #[test]
+    fn no_timezone() {
+        assert_eq!(timezone_from_values(None, Some(-330)), None);
+        assert_eq!(timezone_from_values(None, None), None);
+    }
+}
@@ -0,0 +1,106 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// selects a uniformly distributed random subset of `size` events of the
+/// whole timeline (see [`Reservoir`])
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Sample {
+    size: usize,
+    seed: Option<u64>,
+}
+
+impl Sample {
+    pub fn new(size: usize, seed: Option<u64>) -> Self {
+        Self { size, seed }
+    }
+
+    pub fn reservoir<T>(&self) -> Reservoir<T> {
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Reservoir {
+            capacity: self.size,
+            seen: 0,
+            items: Vec::with_capacity(self.size),
+            rng,
+        }
+    }
+}
+
+/// keeps a random subset of at most `capacity` items of a stream of unknown
+/// length, using reservoir sampling (Algorithm R). Every item has the same
+/// probability to be selected, and the memory usage does not depend on the
+/// length of the stream.
+pub(crate) struct Reservoir<T> {
+    capacity: usize,
+    seen: u64,
+    items: Vec<(u64, T)>,
+    rng: StdRng,
+}
+
+impl<T> Reservoir<T> {
+    pub fn push(&mut self, item: T) {
+        let index = self.seen;
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push((index, item));
+        } else {
+            let slot = self.rng.gen_range(0..self.seen);
+            if slot < self.capacity as u64 {
+                self.items[slot as usize] = (index, item);
+            }
+        }
+    }
+
+    /// number of items which have been pushed
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// returns the selected items in the order in which they have been pushed
+    pub fn into_items(mut self) -> Vec<T> {
+        self.items.sort_unstable_by_key(|(index, _)| *index);
+        self.items.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sample;
+
+    fn sample(size: usize, seed: u64, total: u32) -> Vec<u32> {
+        let mut reservoir = Sample::new(size, Some(seed)).reservoir();
+        for item in 0..total {
+            reservoir.push(item);
+        }
+        assert_eq!(reservoir.seen(), u64::from(total));
+        reservoir.into_items()
+    }
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(sample(10, 42, 1000), sample(10, 42, 1000));
+        assert_ne!(sample(10, 42, 1000), sample(10, 43, 1000));
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(sample(10, 42, 1000).len(), 10);
+        assert_eq!(sample(10, 42, 5), vec![0, 1, 2, 3, 4]);
+        assert!(sample(0, 42, 5).is_empty());
+    }
+
+    #[test]
+    fn ordered() {
+        let items = sample(100, 42, 10000);
+        assert!(items.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn not_biased_to_start() {
+        // the probability that all 100 items are taken from the first half
+        // of the stream is negligible
+        assert!(sample(100, 42, 10000).iter().any(|item| *item >= 5000));
+    }
+}
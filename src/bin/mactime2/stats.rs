@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use chrono_tz::Tz;
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+use dfir_toolkit::common::ForensicsTimestamp;
+
+/// minimum and maximum of a column
+#[derive(Default)]
+struct Range {
+    min: Option<i64>,
+    max: Option<i64>,
+}
+
+impl Range {
+    fn add(&mut self, value: i64) {
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+}
+
+/// aggregates of a single timestamp column
+#[derive(Default)]
+struct TimestampStats {
+    range: Range,
+    unset: usize,
+}
+
+/// collects per-column statistics of a bodyfile in a single pass
+pub(crate) struct BodyfileStats {
+    src_zone: Tz,
+    dst_zone: Tz,
+    lines: usize,
+    md5: HashSet<String>,
+    uid: HashSet<u64>,
+    gid: HashSet<u64>,
+    size: Range,
+    timestamps: [TimestampStats; 4],
+}
+
+impl BodyfileStats {
+    pub fn new(src_zone: Tz, dst_zone: Tz) -> Self {
+        Self {
+            src_zone,
+            dst_zone,
+            lines: 0,
+            md5: HashSet::new(),
+            uid: HashSet::new(),
+            gid: HashSet::new(),
+            size: Range::default(),
+            timestamps: Default::default(),
+        }
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        self.lines += 1;
+        if !self.md5.contains(line.get_md5()) {
+            self.md5.insert(line.get_md5().to_owned());
+        }
+        self.uid.insert(*line.get_uid());
+        self.gid.insert(*line.get_gid());
+        self.size.add(*line.get_size() as i64);
+
+        for (kind, stats) in TimestampKind::ALL.iter().zip(self.timestamps.iter_mut()) {
+            match line.get_timestamp(*kind) {
+                -1 => stats.unset += 1,
+                ts => stats.range.add(ts),
+            }
+        }
+    }
+
+    /// returns all statistics as (column, aggregate, value) triples
+    fn rows(&self) -> Vec<(&'static str, &'static str, String)> {
+        let mut rows = vec![
+            ("lines", "count", self.lines.to_string()),
+            ("md5", "distinct", self.md5.len().to_string()),
+            ("uid", "distinct", self.uid.len().to_string()),
+            ("gid", "distinct", self.gid.len().to_string()),
+        ];
+        if let (Some(min), Some(max)) = (self.size.min, self.size.max) {
+            rows.push(("size", "min", min.to_string()));
+            rows.push(("size", "max", max.to_string()));
+        }
+
+        for (kind, stats) in TimestampKind::ALL.iter().zip(self.timestamps.iter()) {
+            let column = match kind {
+                TimestampKind::Atime => "atime",
+                TimestampKind::Mtime => "mtime",
+                TimestampKind::Ctime => "ctime",
+                TimestampKind::Crtime => "crtime",
+            };
+            if let (Some(min), Some(max)) = (stats.range.min, stats.range.max) {
+                rows.push((column, "min", self.format_timestamp(min)));
+                rows.push((column, "max", self.format_timestamp(max)));
+            }
+            rows.push((column, "unset", stats.unset.to_string()));
+        }
+        rows
+    }
+
+    fn format_timestamp(&self, ts: i64) -> String {
+        ForensicsTimestamp::new(ts, self.src_zone, self.dst_zone).to_string()
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        for (column, aggregate, value) in self.rows() {
+            writeln!(out, "{column}\t{aggregate}\t{value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+    use super::BodyfileStats;
+
+    #[test]
+    fn empty_input() {
+        let stats = BodyfileStats::new(Tz::UTC, Tz::UTC);
+        let rows = stats.rows();
+        assert_eq!(rows[0], ("lines", "count", "0".to_owned()));
+        assert!(!rows.iter().any(|(_, aggregate, _)| *aggregate == "min"));
+    }
+
+    #[test]
+    fn size_range() {
+        let mut stats = BodyfileStats::new(Tz::UTC, Tz::UTC);
+        stats.add(&Bodyfile3Line::new().with_size(42));
+        stats.add(&Bodyfile3Line::new().with_size(7));
+        let rows = stats.rows();
+        assert!(rows.contains(&("size", "min", "7".to_owned())));
+        assert!(rows.contains(&("size", "max", "42".to_owned())));
+        assert!(rows.contains(&("mtime", "unset", "2".to_owned())));
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+#[cfg(feature = "http")]
+use std::time::Duration;
+
+use anyhow::bail;
+use clio::Input;
+use dfir_toolkit::common::expand_glob_paths;
+
+/// location of the bodyfile to read, which is either a local file (or
+/// stdin), multiple local files, or a `http://` or `https://` URL
+#[derive(Clone, Debug)]
+pub(crate) enum InputLocation {
+    Local(Input),
+
+    /// local files, which are read one after the other as if they were a
+    /// single bodyfile
+    Parts(Vec<PathBuf>),
+
+    #[cfg(feature = "http")]
+    Url {
+        url: String,
+
+        /// maximum time to wait until the connection has been established
+        timeout: Option<Duration>,
+    },
+}
+
+impl FromStr for InputLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "http")]
+        if Self::is_url(s) {
+            return Ok(Self::Url {
+                url: s.to_owned(),
+                timeout: None,
+            });
+        }
+
+        Ok(Self::Local(Input::new(s)?))
+    }
+}
+
+impl Display for InputLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local(input) => write!(f, "{}", input.path().display()),
+            Self::Parts(paths) => {
+                let paths: Vec<_> = paths.iter().map(|path| path.to_string_lossy()).collect();
+                write!(f, "{}", paths.join(", "))
+            }
+            #[cfg(feature = "http")]
+            Self::Url { url, .. } => write!(f, "{url}"),
+        }
+    }
+}
+
+impl InputLocation {
+    /// creates the location from the values of `-b`. Glob patterns and
+    /// alternatives like `{a,b}` are expanded. If this results in more than
+    /// one file, the files are read one after the other; URLs cannot be
+    /// combined with other inputs.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Self> {
+        match args {
+            [arg] if Self::is_url(arg) => return Self::from_str(arg),
+            _ if args.iter().any(|arg| Self::is_url(arg)) => {
+                bail!("a URL cannot be read together with other inputs")
+            }
+            _ => (),
+        }
+
+        let paths = expand_glob_paths(&args.iter().map(PathBuf::from).collect::<Vec<_>>())?;
+        if let [path] = &paths[..] {
+            return Self::from_str(&path.to_string_lossy());
+        }
+        if let Some(path) = paths
+            .iter()
+            .find(|path| path.as_os_str() != "-" && !path.exists())
+        {
+            bail!("'{}' does not exist", path.display());
+        }
+        Ok(Self::Parts(paths))
+    }
+
+    fn is_url(s: &str) -> bool {
+        cfg!(feature = "http") && (s.starts_with("http://") || s.starts_with("https://"))
+    }
+
+    /// sets the connection timeout for URLs; local files are not affected
+    #[cfg(feature = "http")]
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        match self {
+            Self::Url { url, .. } => Self::Url { url, timeout },
+            local => local,
+        }
+    }
+
+    /// returns `true` if the name of the file ends with `.gz`. The query
+    /// and the fragment of a URL are ignored.
+    pub fn is_gzipped(&self) -> bool {
+        self.has_suffix(".gz")
+    }
+
+    /// returns `true` if the name of the file ends with `.zst`. The query
+    /// and the fragment of a URL are ignored.
+    pub fn is_zstd_compressed(&self) -> bool {
+        self.has_suffix(".zst")
+    }
+
+    fn has_suffix(&self, suffix: &str) -> bool {
+        match self {
+            Self::Local(input) => input.path().to_string_lossy().ends_with(suffix),
+
+            // every part is decompressed separately
+            Self::Parts(_) => false,
+            #[cfg(feature = "http")]
+            Self::Url { url, .. } => url
+                .split(['?', '#'])
+                .next()
+                .is_some_and(|path| path.ends_with(suffix)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::InputLocation;
+
+    #[test]
+    fn parse_url() {
+        let location = InputLocation::from_str("https://example.com/bodyfile.gz?sig=abc").unwrap();
+        assert!(matches!(location, InputLocation::Url { .. }));
+        assert!(location.is_gzipped());
+        assert!(!location.is_zstd_compressed());
+
+        let location = InputLocation::from_str("http://example.com/bodyfile#.gz").unwrap();
+        assert!(!location.is_gzipped());
+
+        let location = InputLocation::from_str("https://example.com/bodyfile.zst?sig=abc").unwrap();
+        assert!(location.is_zstd_compressed());
+    }
+
+    #[test]
+    fn parse_stdin() {
+        let location = InputLocation::from_str("-").unwrap();
+        assert!(matches!(location, InputLocation::Local(_)));
+        assert!(!location.is_gzipped());
+        assert!(!location.is_zstd_compressed());
+    }
+}
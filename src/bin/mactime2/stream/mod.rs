@@ -1,7 +1,9 @@
+mod input_location;
 mod stream_reader;
 mod stream_source;
 mod stream_worker;
 
+pub (crate) use input_location::*;
 pub (crate) use stream_reader::*;
 pub (crate) use stream_source::*;
 pub (crate) use stream_worker::*;
\ No newline at end of file
@@ -4,7 +4,6 @@ use std::{
 };
 
 use anyhow::Result;
-use clio::Input;
 
 use crate::{
     filter::{Joinable, Provider},
@@ -16,11 +15,13 @@ pub(crate) trait StreamReader<T, R>:
 where
     T: Send + 'static,
 {
-    fn from(input: Input) -> Result<Self> {
+    fn from(input: &InputLocation) -> Result<Self> {
         let (tx, rx): (Sender<T>, Receiver<T>) = mpsc::channel();
 
+        // open the input before starting the worker, so that errors can be reported
+        let source = StreamSource::open(input)?;
         let worker = thread::spawn(move || {
-            <Self as StreamWorker<T>>::worker(StreamSource::from(input), tx);
+            <Self as StreamWorker<T>>::worker(source, tx);
         });
 
         Ok(<Self as StreamReader<T, R>>::new(worker, rx))
@@ -1,18 +1,51 @@
 use std::io::Read;
+#[cfg(feature = "http")]
+use std::time::Duration;
 
-use clio::Input;
+use anyhow::Result;
+use dfir_toolkit::common::bodyfile::{BodyfileReader, UnparsedBodyfileReader};
 #[cfg(feature = "gzip")]
 use flate2::read::GzDecoder;
+
+use super::InputLocation;
+
 pub(crate) struct StreamSource(Box<dyn Read + Send>);
 
-impl From<Input> for StreamSource {
-    fn from(input: Input) -> Self {
+impl StreamSource {
+    /// opens the bodyfile at `location`. Bodyfiles from a URL are not
+    /// downloaded completely, but are read while they are being received.
+    pub fn open(location: &InputLocation) -> Result<Self> {
+        let input: Box<dyn Read + Send> = match location {
+            InputLocation::Local(input) => Box::new(input.clone()),
+            InputLocation::Parts(paths) => return Ok(Self(Box::new(PartsSource::new(paths)))),
+            #[cfg(feature = "http")]
+            InputLocation::Url { url, timeout } => Box::new(Self::fetch(url, *timeout)?),
+        };
+
         #[cfg(feature = "gzip")]
-        if input.path().ends_with(".gz") {
-            return Self(Box::new(GzDecoder::new(input)));
+        if location.is_gzipped() {
+            return Ok(Self(Box::new(GzDecoder::new(input))));
         }
 
-        Self(Box::new(input))
+        #[cfg(feature = "zstd")]
+        if location.is_zstd_compressed() {
+            return Ok(Self(Box::new(zstd::Decoder::new(input)?)));
+        }
+
+        Ok(Self(input))
+    }
+
+    #[cfg(feature = "http")]
+    fn fetch(url: &str, timeout: Option<Duration>) -> Result<reqwest::blocking::Response> {
+        // the transfer of large bodyfiles may take a long time, so that
+        // only the time to connect is limited
+        let mut builder = reqwest::blocking::Client::builder().timeout(None);
+        if let Some(timeout) = timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        let response = builder.build()?.get(url).send()?.error_for_status()?;
+        log::info!("reading bodyfile from '{url}'");
+        Ok(response)
     }
 }
 
@@ -20,4 +53,43 @@ impl Read for StreamSource {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.0.read(buf)
     }
-}
\ No newline at end of file
+}
+
+/// reads the lines of multiple bodyfiles as one stream. Every part is
+/// decompressed separately, and its header is skipped.
+struct PartsSource {
+    lines: UnparsedBodyfileReader,
+    line: Vec<u8>,
+    pos: usize,
+}
+
+impl PartsSource {
+    fn new(paths: &[std::path::PathBuf]) -> Self {
+        Self {
+            lines: BodyfileReader::from_paths(paths).with_unparsed(),
+            line: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for PartsSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.line.len() {
+            match self.lines.next() {
+                None => return Ok(0),
+                Some(Ok(line)) => {
+                    self.line = line;
+                    self.line.push(b'\n');
+                    self.pos = 0;
+                }
+                Some(Err(why)) => return Err(std::io::Error::other(why)),
+            }
+        }
+
+        let len = buf.len().min(self.line.len() - self.pos);
+        buf[..len].copy_from_slice(&self.line[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
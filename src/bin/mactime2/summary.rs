@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+use crate::filter::EventFilter;
+
+/// counts the number of timeline events per parent directory
+#[derive(Default)]
+pub(crate) struct DirectorySummary {
+    counts: HashMap<String, usize>,
+    filter: EventFilter,
+}
+
+impl DirectorySummary {
+    /// ignores all timestamps which are not selected by `filter`
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn add(&mut self, line: &Bodyfile3Line) {
+        // identical timestamps of a file are merged into one event
+        let events = self.filter.events(line);
+
+        if !events.is_empty() {
+            *self
+                .counts
+                .entry(Self::parent_of(line.get_name()).to_owned())
+                .or_insert(0) += events.len();
+        }
+    }
+
+    /// returns the parent directory of `name`, which may use slashes or backslashes
+    pub(crate) fn parent_of(name: &str) -> &str {
+        match name.rfind(['/', '\\']) {
+            Some(0) => &name[..1],
+            Some(idx) => &name[..idx],
+            None => ".",
+        }
+    }
+
+    /// returns the `count` directories with the most events, together with
+    /// their number of events
+    pub fn top(&self, count: usize) -> Vec<(&str, usize)> {
+        let mut directories: Vec<_> = self
+            .counts
+            .iter()
+            .map(|(dir, count)| (dir.as_str(), *count))
+            .collect();
+        directories.sort_by(|(dir1, count1), (dir2, count2)| {
+            count2.cmp(count1).then_with(|| dir1.cmp(dir2))
+        });
+        directories.truncate(count);
+        directories
+    }
+
+    pub fn print(&self, count: usize, out: &mut dyn Write) -> std::io::Result<()> {
+        for (dir, events) in self.top(count) {
+            writeln!(out, "{events}\t{dir}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DirectorySummary;
+
+    #[test]
+    fn parent_of() {
+        assert_eq!(DirectorySummary::parent_of("/etc/passwd"), "/etc");
+        assert_eq!(DirectorySummary::parent_of("C:\\Windows\\notepad.exe"), "C:\\Windows");
+        assert_eq!(DirectorySummary::parent_of("/passwd"), "/");
+        assert_eq!(DirectorySummary::parent_of("passwd"), ".");
+    }
+}
@@ -0,0 +1,70 @@
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+
+/// detects files whose timestamps have probably been set explicitly
+/// ("timestomped"), e.g. using `touch -d` or `SetFileTime()`. Creating or
+/// modifying a file also updates its ctime (the time of the last change of
+/// the metadata, or of the MFT entry on NTFS), so a file cannot have been
+/// created or modified after the last change of its metadata. Unset
+/// timestamps (`-1` or `0`) are never suspicious.
+pub(crate) fn is_timestomped(line: &Bodyfile3Line) -> bool {
+    !indicators(line).is_empty()
+}
+
+/// returns the timestamps of `line` which are later than its ctime
+pub(crate) fn indicators(line: &Bodyfile3Line) -> Vec<TimestampKind> {
+    let ctime = *line.get_ctime();
+    if ctime <= 0 {
+        return Vec::new();
+    }
+    [TimestampKind::Mtime, TimestampKind::Crtime]
+        .into_iter()
+        .filter(|kind| line.get_timestamp(*kind) > ctime)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+
+    use super::{indicators, is_timestomped};
+
+    #[test]
+    fn regular_files() {
+        // created and modified at the same time
+        let line = Bodyfile3Line::new()
+            .with_mtime(1000)
+            .with_ctime(1000)
+            .with_crtime(1000);
+        assert!(!is_timestomped(&line));
+
+        // copied file, which kept its original modification time
+        let line = Bodyfile3Line::new()
+            .with_mtime(500)
+            .with_ctime(1000)
+            .with_crtime(1000);
+        assert!(!is_timestomped(&line));
+
+        // no ctime available
+        let line = Bodyfile3Line::new().with_mtime(1000).with_crtime(2000);
+        assert!(!is_timestomped(&line));
+    }
+
+    #[test]
+    fn timestomped_files() {
+        let line = Bodyfile3Line::new()
+            .with_mtime(2000)
+            .with_ctime(1000)
+            .with_crtime(1000);
+        assert_eq!(indicators(&line), vec![TimestampKind::Mtime]);
+
+        let line = Bodyfile3Line::new()
+            .with_mtime(2000)
+            .with_ctime(1000)
+            .with_crtime(3000);
+        assert_eq!(
+            indicators(&line),
+            vec![TimestampKind::Mtime, TimestampKind::Crtime]
+        );
+        assert!(is_timestomped(&line));
+    }
+}
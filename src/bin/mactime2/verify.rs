@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use dfir_toolkit::common::bodyfile::{Bodyfile3Line, HashAlgo};
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use sha2::Sha256;
+
+/// result of the verification of a single file
+#[derive(Debug, PartialEq, Eq)]
+enum VerifyResult {
+    Match,
+    Mismatch,
+    Missing,
+    Unreadable(String),
+}
+
+/// compares the md5 column of bodyfile lines with the hashes of the
+/// referenced files, which are searched below a root directory. The hash
+/// algorithm (MD5, SHA-1 or SHA-256) is derived from the length of the
+/// expected hash.
+pub(crate) struct HashVerifier {
+    root: PathBuf,
+    matches: usize,
+    mismatches: usize,
+    missing: usize,
+}
+
+impl HashVerifier {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            matches: 0,
+            mismatches: 0,
+            missing: 0,
+        }
+    }
+
+    /// verifies the file referenced by `line` and prints the result. Lines
+    /// without a hash are skipped.
+    pub fn add(&mut self, line: &Bodyfile3Line, out: &mut dyn Write) -> io::Result<()> {
+        if line.hash_algorithm() == HashAlgo::None {
+            return Ok(());
+        }
+        let name = line.get_name();
+        match self.verify(line) {
+            VerifyResult::Match => {
+                self.matches += 1;
+                writeln!(out, "{name}: OK")?;
+            }
+            VerifyResult::Mismatch => {
+                self.mismatches += 1;
+                writeln!(out, "{name}: FAILED")?;
+            }
+            VerifyResult::Missing => {
+                self.missing += 1;
+                writeln!(out, "{name}: MISSING")?;
+            }
+            VerifyResult::Unreadable(why) => {
+                self.missing += 1;
+                writeln!(out, "{name}: UNREADABLE ({why})")?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn print_summary(&self) {
+        log::info!(
+            "{} files matched, {} files did not match, {} files were missing",
+            self.matches,
+            self.mismatches,
+            self.missing
+        );
+    }
+
+    fn verify(&self, line: &Bodyfile3Line) -> VerifyResult {
+        match Self::hash_file(&self.path_of(line), line.hash_algorithm()) {
+            Ok(hash) if hash.eq_ignore_ascii_case(line.get_md5()) => VerifyResult::Match,
+            Ok(_) => VerifyResult::Mismatch,
+            Err(why) if why.kind() == ErrorKind::NotFound => VerifyResult::Missing,
+            Err(why) => VerifyResult::Unreadable(why.to_string()),
+        }
+    }
+
+    /// returns the path of the file below the root directory. Leading
+    /// slashes are removed, so that absolute names are not resolved outside
+    /// of the root directory.
+    fn path_of(&self, line: &Bodyfile3Line) -> PathBuf {
+        self.root.join(line.get_name().trim_start_matches('/'))
+    }
+
+    fn hash_file(path: &Path, algorithm: HashAlgo) -> io::Result<String> {
+        let mut file = File::open(path)?;
+        match algorithm {
+            HashAlgo::Md5 => Self::digest::<Md5>(&mut file),
+            HashAlgo::Sha1 => Self::digest::<Sha1>(&mut file),
+            HashAlgo::Sha256 => Self::digest::<Sha256>(&mut file),
+            HashAlgo::None => unreachable!("lines without hash are skipped"),
+        }
+    }
+
+    fn digest<D: Digest + io::Write>(file: &mut File) -> io::Result<String> {
+        let mut hasher = D::new();
+        io::copy(file, &mut hasher)?;
+        Ok(hex_string(&hasher.finalize()))
+    }
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
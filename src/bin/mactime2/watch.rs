@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use dfir_toolkit::common::interrupt;
+use notify::{RecursiveMode, Watcher};
+
+/// time to wait for a notification before the directory is scanned again.
+/// Rescanning is required to detect files whose size has settled, because
+/// this does not cause a notification
+const RESCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// file in the intake directory which has not been processed yet
+struct PendingFile {
+    size: u64,
+
+    /// time when the size has been seen for the first time
+    since: Instant,
+}
+
+/// intake directory, into which bodyfiles are dropped by other processes.
+/// A file is considered to be written completely if its size has not
+/// changed for the settle time.
+pub(crate) struct IntakeDirectory {
+    dir: PathBuf,
+    processed_dir: PathBuf,
+    failed_dir: PathBuf,
+    settle_time: Duration,
+    max_files: Option<usize>,
+}
+
+impl IntakeDirectory {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            processed_dir: dir.join("processed"),
+            failed_dir: dir.join("failed"),
+            dir,
+            settle_time: Duration::from_secs(2),
+            max_files: None,
+        }
+    }
+
+    /// moves processed files into `processed_dir` instead of the
+    /// subdirectory `processed` of the intake directory
+    pub fn with_processed_dir(mut self, processed_dir: Option<PathBuf>) -> Self {
+        if let Some(processed_dir) = processed_dir {
+            self.processed_dir = processed_dir;
+        }
+        self
+    }
+
+    /// moves files which could not be processed into `failed_dir` instead
+    /// of the subdirectory `failed` of the intake directory
+    pub fn with_failed_dir(mut self, failed_dir: Option<PathBuf>) -> Self {
+        if let Some(failed_dir) = failed_dir {
+            self.failed_dir = failed_dir;
+        }
+        self
+    }
+
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// stops watching after `max_files` files have been processed
+    pub fn with_max_files(mut self, max_files: Option<usize>) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// calls `process` for every file in the intake directory, including
+    /// the files which already exist, and moves the file into the processed
+    /// directory afterwards. If `process` fails, the file is moved into the
+    /// failed directory instead. Files are processed one after another, in
+    /// the order of their names. Returns the number of processed files,
+    /// including the failed ones.
+    pub fn watch(
+        &self,
+        mut process: impl FnMut(&Path) -> anyhow::Result<()>,
+    ) -> anyhow::Result<usize> {
+        for dir in [&self.processed_dir, &self.failed_dir] {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("unable to create {}", dir.display()))?;
+        }
+
+        // notifications only wake up the loop below, which scans the whole
+        // directory. A single pending notification is sufficient, so that
+        // the channel cannot grow, regardless of the number of files
+        let (tx, rx) = mpsc::sync_channel(1);
+        let mut watcher = notify::recommended_watcher(move |_| {
+            let _ = tx.try_send(());
+        })?;
+        watcher
+            .watch(&self.dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("unable to watch {}", self.dir.display()))?;
+        log::info!("watching {}", self.dir.display());
+
+        let mut pending = HashMap::new();
+        let mut processed = 0;
+        while !interrupt::is_interrupted() {
+            self.scan(&mut pending)?;
+
+            let now = Instant::now();
+            let mut ready: Vec<PathBuf> = pending
+                .iter()
+                .filter(|(_, file)| now.duration_since(file.since) >= self.settle_time)
+                .map(|(path, _)| path.clone())
+                .collect();
+            ready.sort();
+
+            for path in ready {
+                pending.remove(&path);
+                let result = process(&path);
+
+                // the file has probably not been processed completely
+                if interrupt::is_interrupted() {
+                    return Ok(processed);
+                }
+                match result {
+                    Ok(()) => Self::move_into(&path, &self.processed_dir)?,
+                    Err(why) => {
+                        log::error!("unable to process {}: {why}", path.display());
+                        Self::move_into(&path, &self.failed_dir)?;
+                    }
+                }
+                processed += 1;
+                if self.max_files == Some(processed) {
+                    return Ok(processed);
+                }
+            }
+
+            match rx.recv_timeout(RESCAN_INTERVAL) {
+                Ok(()) | Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => {
+                    bail!("watching {} has been stopped", self.dir.display())
+                }
+            }
+        }
+        Ok(processed)
+    }
+
+    /// updates the sizes of all files in the intake directory. Files which
+    /// have been removed by another process are forgotten.
+    fn scan(&self, pending: &mut HashMap<PathBuf, PendingFile>) -> anyhow::Result<()> {
+        let now = Instant::now();
+        let mut existing = HashMap::with_capacity(pending.len());
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+
+            // hidden files are often temporary files of copying tools
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(metadata) if metadata.is_file() => metadata,
+                _ => continue,
+            };
+
+            let path = entry.path();
+            let file = match pending.remove(&path) {
+                Some(file) if file.size == metadata.len() => file,
+                _ => PendingFile {
+                    size: metadata.len(),
+                    since: now,
+                },
+            };
+            existing.insert(path, file);
+        }
+        *pending = existing;
+        Ok(())
+    }
+
+    /// moves `path` into `dir`. If a file with the same name has already
+    /// been moved there, a number is appended to the name.
+    fn move_into(path: &Path, dir: &Path) -> anyhow::Result<()> {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let mut target = dir.join(file_name.as_ref());
+        let mut counter = 1;
+        while target.exists() {
+            target = dir.join(format!("{file_name}.{counter}"));
+            counter += 1;
+        }
+
+        // renaming fails if the processed directory is on another filesystem
+        if fs::rename(path, &target).is_err() {
+            fs::copy(path, &target)
+                .and_then(|_| fs::remove_file(path))
+                .with_context(|| {
+                    format!("unable to move {} to {}", path.display(), target.display())
+                })?;
+        }
+        log::info!("moved {} to {}", path.display(), target.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use std::io::Read;
+
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use prefetch_file::PrefetchFile;
+
+mod cli;
+mod prefetch_file;
+mod xpress_huffman;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for mut input in cli.prefetch_files.into_iter() {
+            let mut buffer = Vec::new();
+            input.read_to_end(&mut buffer)?;
+
+            match PrefetchFile::parse(&buffer) {
+                Ok(pf) => {
+                    for line in pf.to_bodyfile() {
+                        writeln!(output, "{line}")?;
+                    }
+                }
+                Err(why) => {
+                    log::error!("unable to parse '{}': {why}", input.path().display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
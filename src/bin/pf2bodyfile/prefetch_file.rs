@@ -0,0 +1,134 @@
+use anyhow::{bail, Result};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::unix_from_filetime;
+
+use crate::xpress_huffman;
+
+/// signature of a prefetch file which has been compressed by Windows 10 or later
+const MAM_SIGNATURE: &[u8; 3] = b"MAM";
+
+/// compression format id of LZ77+Huffman
+const COMPRESSION_XPRESS_HUFFMAN: u8 = 4;
+
+const SCCA_SIGNATURE: &[u8; 4] = b"SCCA";
+
+const EXECUTABLE_NAME_OFFSET: usize = 0x10;
+const EXECUTABLE_NAME_LENGTH: usize = 60;
+const HASH_OFFSET: usize = 0x4c;
+const METRICS_OFFSET_OFFSET: usize = 0x54;
+
+/// <https://github.com/libyal/libscca/blob/main/documentation/Windows%20Prefetch%20File%20(PF)%20format.asciidoc>
+pub(crate) struct PrefetchFile {
+    executable_name: String,
+    hash: u32,
+    run_count: u32,
+    last_run_times: Vec<u64>,
+}
+
+impl PrefetchFile {
+    /// parses a prefetch file, which may be MAM compressed
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.starts_with(MAM_SIGNATURE) {
+            Self::parse_uncompressed(&Self::decompress(data)?)
+        } else {
+            Self::parse_uncompressed(data)
+        }
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+        if data.len() < 8 {
+            bail!("file is too small to contain a MAM header");
+        }
+        let flags = data[3];
+        if flags & 0x0f != COMPRESSION_XPRESS_HUFFMAN {
+            bail!("unsupported compression format: {}", flags & 0x0f);
+        }
+        let uncompressed_size = read_u32(data, 4)? as usize;
+
+        // if the high bit is set, the header is followed by a CRC32 checksum
+        let header_size = if flags & 0x80 != 0 { 12 } else { 8 };
+        if data.len() < header_size {
+            bail!("file is too small to contain a MAM header");
+        }
+        xpress_huffman::decompress(&data[header_size..], uncompressed_size)
+    }
+
+    fn parse_uncompressed(data: &[u8]) -> Result<Self> {
+        if data.get(4..8) != Some(SCCA_SIGNATURE) {
+            bail!("missing SCCA signature");
+        }
+        let version = read_u32(data, 0)?;
+
+        let (last_run_times_offset, last_run_times_count, run_count_offset) = match version {
+            17 => (0x78, 1, 0x90),
+            23 => (0x80, 1, 0x98),
+            26 => (0x80, 8, 0xd0),
+            // there are two variants of version 30, which can be distinguished
+            // by the size of the file information
+            30 | 31 => match read_u32(data, METRICS_OFFSET_OFFSET)? {
+                0x128 => (0x80, 8, 0xc8),
+                _ => (0x80, 8, 0xd0),
+            },
+            _ => bail!("unsupported prefetch version: {version}"),
+        };
+
+        let mut last_run_times = Vec::new();
+        for idx in 0..last_run_times_count {
+            let filetime = read_u64(data, last_run_times_offset + idx * 8)?;
+            if filetime != 0 {
+                last_run_times.push(filetime);
+            }
+        }
+
+        Ok(Self {
+            executable_name: read_utf16(data, EXECUTABLE_NAME_OFFSET, EXECUTABLE_NAME_LENGTH)?,
+            hash: read_u32(data, HASH_OFFSET)?,
+            run_count: read_u32(data, run_count_offset)?,
+            last_run_times,
+        })
+    }
+
+    /// creates one bodyfile line per last run time, where the run time is
+    /// stored as atime
+    pub fn to_bodyfile(&self) -> Vec<Bodyfile3Line> {
+        let name = format!(
+            "{}-{:08X} (run count: {})",
+            self.executable_name, self.hash, self.run_count
+        );
+        self.last_run_times
+            .iter()
+            .map(|filetime| {
+                Bodyfile3Line::new()
+                    .with_name(&name)
+                    .with_atime(unix_from_filetime(*filetime))
+            })
+            .collect()
+    }
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    match data.get(offset..offset + N) {
+        Some(bytes) => Ok(bytes.try_into()?),
+        None => bail!("unexpected end of data at offset 0x{offset:x}"),
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_utf16(data: &[u8], offset: usize, length: usize) -> Result<String> {
+    let chars: Vec<u16> = match data.get(offset..offset + length) {
+        Some(bytes) => bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|c| *c != 0)
+            .collect(),
+        None => bail!("unexpected end of data at offset 0x{offset:x}"),
+    };
+    Ok(String::from_utf16_lossy(&chars))
+}
@@ -0,0 +1,251 @@
+//! Decompression of the LZ77+Huffman format, which is used by Windows 10 and
+//! later to compress prefetch files.
+//!
+//! <https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-xca/a8b7cb0a-92a6-4187-a23b-5e14273b96f8>
+
+use anyhow::{bail, Result};
+
+/// every 64k of output are encoded using a separate huffman table
+const CHUNK_SIZE: usize = 65536;
+
+/// 512 symbols with a code length of 4 bits each
+const TABLE_SIZE: usize = 256;
+
+const MAX_CODE_LENGTH: u32 = 15;
+
+/// maps every possible 15 bit prefix to a symbol and its code length
+struct DecodingTable(Vec<(u16, u8)>);
+
+impl DecodingTable {
+    fn parse(lengths: &[u8]) -> Result<Self> {
+        let mut symbols: Vec<(u8, u16)> = (0..TABLE_SIZE * 2)
+            .map(|symbol| {
+                let length = if symbol % 2 == 0 {
+                    lengths[symbol / 2] & 0x0f
+                } else {
+                    lengths[symbol / 2] >> 4
+                };
+                (length, symbol as u16)
+            })
+            .filter(|(length, _)| *length > 0)
+            .collect();
+
+        // canonical huffman codes are assigned in ascending order of the
+        // code length and the symbol value
+        symbols.sort_unstable();
+
+        let mut entries = vec![(0, 0); 1 << MAX_CODE_LENGTH];
+        let mut position = 0;
+        for (length, symbol) in symbols {
+            let count = 1 << (MAX_CODE_LENGTH - u32::from(length));
+            if position + count > entries.len() {
+                bail!("invalid huffman table");
+            }
+            entries[position..position + count].fill((symbol, length));
+            position += count;
+        }
+        Ok(Self(entries))
+    }
+}
+
+struct BitReader<'a> {
+    input: &'a [u8],
+    position: usize,
+    next_bits: u32,
+    extra_bits: i32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(input: &'a [u8], position: usize) -> Self {
+        let mut me = Self {
+            input,
+            position,
+            next_bits: 0,
+            extra_bits: 16,
+        };
+        me.next_bits = u32::from(me.read_u16()) << 16;
+        me.next_bits |= u32::from(me.read_u16());
+        me
+    }
+
+    /// reads a single byte from the input, without touching the bit buffer.
+    /// Missing bytes at the end of the input are treated as zero.
+    fn read_u8(&mut self) -> u8 {
+        let value = self.input.get(self.position).copied().unwrap_or(0);
+        self.position += 1;
+        value
+    }
+
+    fn read_u16(&mut self) -> u16 {
+        u16::from_le_bytes([self.read_u8(), self.read_u8()])
+    }
+
+    fn read_u32(&mut self) -> u32 {
+        u32::from_le_bytes([
+            self.read_u8(),
+            self.read_u8(),
+            self.read_u8(),
+            self.read_u8(),
+        ])
+    }
+
+    fn peek(&self, count: u32) -> u32 {
+        if count == 0 {
+            0
+        } else {
+            self.next_bits >> (32 - count)
+        }
+    }
+
+    fn consume(&mut self, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.next_bits <<= count;
+        self.extra_bits -= count as i32;
+        if self.extra_bits < 0 {
+            self.next_bits |= u32::from(self.read_u16()) << -self.extra_bits;
+            self.extra_bits += 16;
+        }
+    }
+}
+
+/// decompresses `input`, which must decompress to exactly `output_size` bytes
+pub(crate) fn decompress(input: &[u8], output_size: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(output_size);
+    let mut position = 0;
+
+    while output.len() < output_size {
+        if position + TABLE_SIZE > input.len() {
+            bail!("unexpected end of compressed data");
+        }
+        let table = DecodingTable::parse(&input[position..position + TABLE_SIZE])?;
+        let mut reader = BitReader::new(input, position + TABLE_SIZE);
+        let chunk_end = output_size.min(output.len() + CHUNK_SIZE);
+
+        while output.len() < chunk_end {
+            let (symbol, code_length) = table.0[reader.peek(MAX_CODE_LENGTH) as usize];
+            if code_length == 0 {
+                bail!("invalid huffman code");
+            }
+            reader.consume(code_length.into());
+
+            if symbol < 256 {
+                output.push(symbol as u8);
+                continue;
+            }
+
+            let symbol = symbol - 256;
+            let mut length = usize::from(symbol & 0x0f);
+            let offset_bits = u32::from(symbol >> 4);
+            if length == 15 {
+                length = usize::from(reader.read_u8());
+                if length == 255 {
+                    length = usize::from(reader.read_u16());
+                    if length == 0 {
+                        length = reader.read_u32() as usize;
+                    }
+                    if length < 15 {
+                        bail!("invalid match length");
+                    }
+                    length -= 15;
+                }
+                length += 15;
+            }
+            length += 3;
+            if length > output_size - output.len() {
+                bail!("match length exceeds the size of the decompressed data");
+            }
+
+            let offset = (1 << offset_bits) + reader.peek(offset_bits) as usize;
+            reader.consume(offset_bits);
+            if offset > output.len() {
+                bail!("match offset points before the start of the data");
+            }
+
+            // the source and destination of a match may overlap
+            for _ in 0..length {
+                output.push(output[output.len() - offset]);
+            }
+        }
+        position = reader.position;
+    }
+
+    output.truncate(output_size);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decompress;
+
+    /// creates compressed data using a huffman table where all 512 symbols
+    /// have a code length of 9 bits, so that the code of every symbol
+    /// equals its value. `symbols` contains pairs of values and bit counts.
+    fn compress(symbols: &[(u32, usize)]) -> Vec<u8> {
+        let mut bits = String::new();
+        for &(value, count) in symbols {
+            bits.push_str(&format!("{value:0count$b}"));
+        }
+        while !bits.len().is_multiple_of(16) {
+            bits.push('0');
+        }
+
+        let mut result = vec![0x99; 256];
+        for i in (0..bits.len()).step_by(16) {
+            let word = u16::from_str_radix(&bits[i..i + 16], 2).unwrap();
+            result.extend(word.to_le_bytes());
+        }
+        result
+    }
+
+    #[test]
+    fn literals() {
+        let input = compress(&[(b'a'.into(), 9), (b'b'.into(), 9), (b'c'.into(), 9)]);
+        assert_eq!(decompress(&input, 3).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn overlapping_match() {
+        // match of length 6 (3 + 3) at offset 3 ((1 << 1) + 1)
+        let input = compress(&[
+            (b'a'.into(), 9),
+            (b'b'.into(), 9),
+            (b'c'.into(), 9),
+            (256 + (1 << 4) + 3, 9),
+            (1, 1),
+        ]);
+        assert_eq!(decompress(&input, 9).unwrap(), b"abcabcabc");
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let input = compress(&[(b'a'.into(), 9), (256 + (2 << 4), 9), (0, 2)]);
+        assert!(decompress(&input, 4).is_err());
+    }
+
+    #[test]
+    fn match_exceeds_output_size() {
+        // match of length 6 (3 + 3), but only 2 bytes are missing
+        let input = compress(&[
+            (b'a'.into(), 9),
+            (b'b'.into(), 9),
+            (b'c'.into(), 9),
+            (256 + (1 << 4) + 3, 9),
+            (1, 1),
+        ]);
+        let why = decompress(&input, 5).unwrap_err();
+        assert!(why.to_string().contains("match length exceeds"));
+    }
+
+    #[test]
+    fn oversized_match_length() {
+        // the match length is stored in 32 bits after the bits of the symbols
+        // and two bytes, which are used to refill the bit buffer
+        let mut input = compress(&[(b'a'.into(), 9), (256 + 15, 9)]);
+        input.extend([0, 0, 255, 0, 0]);
+        input.extend(0xffff_fff0_u32.to_le_bytes());
+        let why = decompress(&input, 16).unwrap_err();
+        assert!(why.to_string().contains("match length exceeds"));
+    }
+}
@@ -1,5 +1,5 @@
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 
 /// Exporter for Windows Registry Policy Files
@@ -10,6 +10,9 @@ pub (crate) struct Cli {
     #[clap()]
     pub (crate) polfile: String,
 
+    #[clap(flatten)]
+    pub (crate) output: OutputArgs,
+
     #[clap(flatten)]
     pub (crate) verbose: clap_verbosity_flag::Verbosity,
 
@@ -1,7 +1,4 @@
-use std::{
-    fs::File,
-    io::{stdout, Read},
-};
+use std::{fs::File, io::Read};
 
 use anyhow::{anyhow, Result};
 use binread::{BinReaderExt, BinResult};
@@ -31,8 +28,15 @@ fn main() -> Result<()> {
         return Err(anyhow!("invalid version number"));
     }
 
-    let mut wtr = Writer::from_writer(stdout());
+    args.output.write_with(|output| {
+        let mut wtr = Writer::from_writer(output);
+        export_entries(&mut polfile, &mut wtr)?;
+        wtr.flush()?;
+        Ok(())
+    })
+}
 
+fn export_entries<W: std::io::Write>(polfile: &mut File, wtr: &mut Writer<W>) -> Result<()> {
     loop {
         let entry_result: BinResult<PolicyFileEntry> = polfile.read_le();
         match entry_result {
@@ -0,0 +1,27 @@
+use clap::{Parser, ValueHint};
+use clio::Input;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
+use log::LevelFilter;
+
+/// creates bodyfile from the `$I` files of the Windows Recycle Bin. Every file
+/// results in one line, which contains the original path, the original size
+/// and the deletion time (as ctime)
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// names of the `$I` files
+    #[clap(value_hint=ValueHint::FilePath, num_args=1..)]
+    pub(crate) index_files: Vec<Input>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{bail, Result};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::unix_from_filetime;
+
+const VERSION_OFFSET: usize = 0x00;
+const SIZE_OFFSET: usize = 0x08;
+const DELETION_TIME_OFFSET: usize = 0x10;
+
+/// Windows Vista to 8.1 store the path with a fixed length of `MAX_PATH`
+/// characters
+const V1_PATH_OFFSET: usize = 0x18;
+const V1_PATH_LENGTH: usize = 260 * 2;
+
+/// Windows 10 and later store the number of characters before the path
+const V2_PATH_LENGTH_OFFSET: usize = 0x18;
+const V2_PATH_OFFSET: usize = 0x1c;
+
+/// `$I` file of the Windows Recycle Bin, which contains the metadata of a
+/// deleted file. The contents of the file are stored in the matching `$R`
+/// file.
+///
+/// <https://github.com/libyal/dtformats/blob/main/documentation/Windows%20Recycle.Bin%20file%20formats.asciidoc>
+pub(crate) struct IndexFile {
+    original_path: String,
+    original_size: u64,
+    deletion_time: u64,
+}
+
+impl IndexFile {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let version = read_u64(data, VERSION_OFFSET)?;
+        let original_path = match version {
+            1 => read_utf16(data, V1_PATH_OFFSET, V1_PATH_LENGTH)?,
+            2 => {
+                let length = read_u32(data, V2_PATH_LENGTH_OFFSET)? as usize;
+                read_utf16(data, V2_PATH_OFFSET, length * 2)?
+            }
+            _ => bail!("unsupported $I version: {version}"),
+        };
+
+        Ok(Self {
+            original_path,
+            original_size: read_u64(data, SIZE_OFFSET)?,
+            deletion_time: read_u64(data, DELETION_TIME_OFFSET)?,
+        })
+    }
+
+    /// creates a bodyfile line for the deleted file, where the deletion time
+    /// is stored as ctime
+    pub fn to_bodyfile(&self) -> Bodyfile3Line {
+        Bodyfile3Line::new()
+            .with_owned_name(format!("{} (deleted)", self.original_path))
+            .with_size(self.original_size)
+            .with_ctime(unix_from_filetime(self.deletion_time))
+    }
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    match data.get(offset..offset + N) {
+        Some(bytes) => Ok(bytes.try_into()?),
+        None => bail!("unexpected end of data at offset 0x{offset:x}"),
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_utf16(data: &[u8], offset: usize, length: usize) -> Result<String> {
+    let chars: Vec<u16> = match data.get(offset..offset + length) {
+        Some(bytes) => bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|c| *c != 0)
+            .collect(),
+        None => bail!("unexpected end of data at offset 0x{offset:x}"),
+    };
+    Ok(String::from_utf16_lossy(&chars))
+}
@@ -0,0 +1,35 @@
+use std::io::Read;
+
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use index_file::IndexFile;
+
+mod cli;
+mod index_file;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for mut input in cli.index_files.into_iter() {
+            let mut buffer = Vec::new();
+            input.read_to_end(&mut buffer)?;
+
+            match IndexFile::parse(&buffer) {
+                Ok(index_file) => writeln!(output, "{}", index_file.to_bodyfile())?,
+                Err(why) => {
+                    log::error!("unable to parse '{}': {why}", input.path().display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
@@ -0,0 +1,130 @@
+use std::io::{Read, Seek, Write};
+
+use anyhow::Result;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use nt_hive2::*;
+
+/// keys whose values are all started automatically. The paths are relative
+/// to the root of `SOFTWARE`; in `NTUSER.DAT`, they are located below `Software`.
+const RUN_KEYS: [&str; 7] = [
+    "Microsoft\\Windows\\CurrentVersion\\Run",
+    "Microsoft\\Windows\\CurrentVersion\\RunOnce",
+    "Microsoft\\Windows\\CurrentVersion\\RunServices",
+    "Microsoft\\Windows\\CurrentVersion\\RunServicesOnce",
+    "Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+    "Wow6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+    "Wow6432Node\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+];
+
+/// keys of which only some values are started automatically
+const RUN_VALUES: [(&str, &[&str]); 2] = [
+    (
+        "Microsoft\\Windows NT\\CurrentVersion\\Winlogon",
+        &["Shell", "Userinit"],
+    ),
+    ("Microsoft\\Windows NT\\CurrentVersion\\Windows", &["Load", "Run"]),
+];
+
+/// prints the autostart entries of `SOFTWARE`, `NTUSER.DAT` and `SYSTEM` as
+/// bodyfile lines. The name of every line contains the key, the name of the
+/// value and the command; the last written time of the key is used as `ctime`.
+pub(crate) fn print_autoruns<RS>(
+    hive: &mut Hive<RS, CleanHive>,
+    root_key: &KeyNode,
+    output: &mut dyn Write,
+) -> Result<()>
+where
+    RS: Read + Seek,
+{
+    for prefix in ["", "Software\\"] {
+        for key_path in RUN_KEYS {
+            print_values(hive, root_key, &format!("{prefix}{key_path}"), None, output)?;
+        }
+        for (key_path, names) in RUN_VALUES {
+            print_values(hive, root_key, &format!("{prefix}{key_path}"), Some(names), output)?;
+        }
+    }
+    print_services(hive, root_key, output)
+}
+
+fn print_values<RS>(
+    hive: &mut Hive<RS, CleanHive>,
+    root_key: &KeyNode,
+    key_path: &str,
+    names: Option<&[&str]>,
+    output: &mut dyn Write,
+) -> Result<()>
+where
+    RS: Read + Seek,
+{
+    if let Some(key) = root_key.subpath(key_path, hive)? {
+        let key = key.borrow();
+        for value in key.values() {
+            if let Some(names) = names {
+                if !names.iter().any(|n| n.eq_ignore_ascii_case(value.name())) {
+                    continue;
+                }
+            }
+            print_entry(&key, key_path, value, output)?;
+        }
+    }
+    Ok(())
+}
+
+/// prints the `ImagePath` of all services of the current control set
+fn print_services<RS>(
+    hive: &mut Hive<RS, CleanHive>,
+    root_key: &KeyNode,
+    output: &mut dyn Write,
+) -> Result<()>
+where
+    RS: Read + Seek,
+{
+    let current = match root_key.subkey("Select", hive)? {
+        Some(select) => select
+            .borrow()
+            .values()
+            .iter()
+            .find(|v| v.name() == "Current")
+            .and_then(|v| match v.value() {
+                RegistryValue::RegDWord(current) => Some(*current),
+                _ => None,
+            }),
+        // this is no SYSTEM hive
+        None => return Ok(()),
+    };
+    let services_path = format!("ControlSet{:03}\\Services", current.unwrap_or(1));
+
+    if let Some(services) = root_key.subpath(&services_path, hive)? {
+        for service in services.borrow().subkeys(hive)?.iter() {
+            let service = service.borrow();
+            let key_path = format!("{services_path}\\{}", service.name());
+            for value in service.values() {
+                if value.name().eq_ignore_ascii_case("ImagePath") {
+                    print_entry(&service, &key_path, value, output)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_entry(
+    key: &KeyNode,
+    key_path: &str,
+    value: &KeyValue,
+    output: &mut dyn Write,
+) -> Result<()> {
+    let command = match value.value() {
+        RegistryValue::RegSZ(command) | RegistryValue::RegExpandSZ(command) => {
+            command.trim_end_matches('\0').to_owned()
+        }
+        RegistryValue::RegMultiSZ(commands) => commands.join(" "),
+        other => other.to_string(),
+    };
+    let bf_line = Bodyfile3Line::new()
+        .with_owned_name(format!("{key_path}\\{} = {command}", value.name()))
+        .with_ctime(key.timestamp().timestamp());
+    writeln!(output, "{bf_line}")?;
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::{path::PathBuf, fs::File};
 
 use clap::Parser;
-use dfir_toolkit::common::HasVerboseFlag;
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
 use log::LevelFilter;
 use nt_hive2::{HiveParseMode, Hive};
 
@@ -22,6 +22,14 @@ pub (crate) struct Cli {
     #[clap(short('b'), long("bodyfile"))]
     pub (crate) display_bodyfile: bool,
 
+    /// print the folders found in the shellbags (BagMRU) as bodyfile
+    #[clap(short('S'), long("shellbags"))]
+    pub (crate) display_shellbags: bool,
+
+    /// print the autostart entries (e.g. Run keys and services) as bodyfile
+    #[clap(short('A'), long("autoruns"), conflicts_with("display_shellbags"))]
+    pub (crate) display_autoruns: bool,
+
     /// ignore the base block (e.g. if it was encrypted by some ransomware)
     #[clap(short('I'), long)]
     pub (crate) ignore_base_block: bool,
@@ -30,6 +38,9 @@ pub (crate) struct Cli {
     #[clap(short('T'), long)]
     pub (crate) hide_timestamps: bool,
 
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
     #[clap(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
@@ -47,7 +58,7 @@ impl Cli {
                             std::process::exit(-1);
                         }
                     };
-                    println!("found offset at {}", offset.0);
+                    log::info!("found offset at {}", offset.0);
                     HiveParseMode::Normal(offset)
                 }
                 Err(why) => {
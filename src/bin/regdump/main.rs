@@ -3,24 +3,29 @@ use anyhow::{bail, Result};
 use dfir_toolkit::common::bodyfile::Bodyfile3Line;
 use dfir_toolkit::common::FancyParser;
 use nt_hive2::*;
-use simplelog::{Config, SimpleLogger};
 use std::fs::File;
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, Write};
 
 use crate::cli::Cli;
 
+mod autoruns;
 mod cli;
+mod shellbags;
 
 fn main() -> Result<()> {
     let mut cli = Cli::parse_cli();
-    let _ = SimpleLogger::init(cli.verbose.log_level_filter(), Config::default());
 
-    fn do_print_key<RS>(hive: &mut Hive<RS, CleanHive>, root_key: &KeyNode, cli: &Cli) -> Result<()>
+    fn do_print_key<RS>(
+        hive: &mut Hive<RS, CleanHive>,
+        root_key: &KeyNode,
+        cli: &Cli,
+        output: &mut dyn Write,
+    ) -> Result<()>
     where
         RS: Read + Seek,
     {
         let mut path = Vec::new();
-        print_key(hive, root_key, &mut path, cli)
+        print_key(hive, root_key, &mut path, cli, output)
     }
 
     match File::open(&cli.hive_file) {
@@ -45,10 +50,18 @@ fn main() -> Result<()> {
             };
 
             let root_key = &clean_hive.root_key_node().unwrap();
-            do_print_key(&mut clean_hive, root_key, &cli).unwrap();
+            cli.output.write_with(|output| {
+                if cli.display_shellbags {
+                    shellbags::print_shellbags(&mut clean_hive, root_key, output)
+                } else if cli.display_autoruns {
+                    autoruns::print_autoruns(&mut clean_hive, root_key, output)
+                } else {
+                    do_print_key(&mut clean_hive, root_key, &cli, output)
+                }
+            })?;
         }
         Err(why) => {
-            eprintln!(
+            log::error!(
                 "unable to open '{}': {}",
                 cli.hive_file.to_string_lossy(),
                 why
@@ -64,6 +77,7 @@ fn print_key<RS>(
     keynode: &KeyNode,
     path: &mut Vec<String>,
     cli: &Cli,
+    output: &mut dyn Write,
 ) -> Result<()>
 where
     RS: Read + Seek,
@@ -75,32 +89,33 @@ where
         let bf_line = Bodyfile3Line::new()
             .with_name(&current_path)
             .with_ctime(keynode.timestamp().timestamp());
-        println!("{}", bf_line);
+        writeln!(output, "{}", bf_line)?;
     } else {
         if cli.hide_timestamps {
-            println!("\n[{}]", &current_path);
+            writeln!(output, "\n[{}]", &current_path)?;
         } else {
-            println!("\n[{}]; {}", &current_path, keynode.timestamp());
+            writeln!(output, "\n[{}]; {}", &current_path, keynode.timestamp())?;
         }
 
-        print_values(keynode);
+        print_values(keynode, output)?;
     }
 
     for sk in keynode.subkeys(hive).unwrap().iter() {
-        print_key(hive, &sk.borrow(), path, cli)?;
+        print_key(hive, &sk.borrow(), path, cli, output)?;
     }
     path.pop();
 
     Ok(())
 }
 
-fn print_values(keynode: &KeyNode) {
+fn print_values(keynode: &KeyNode, output: &mut dyn Write) -> Result<()> {
     for value in keynode.values() {
         let data_type = match value.data_type() {
             Some(dt) => format!("{dt}:"),
             None => "".into(),
         };
 
-        println!("\"{}\" = {data_type}{}", value.name(), value.value());
+        writeln!(output, "\"{}\" = {data_type}{}", value.name(), value.value())?;
     }
+    Ok(())
 }
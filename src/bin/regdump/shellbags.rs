@@ -0,0 +1,237 @@
+use std::io::{Read, Seek, Write};
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use nt_hive2::*;
+
+/// locations of the `BagMRU` key in `UsrClass.dat` and `NTUSER.DAT`
+const BAGMRU_PATHS: [&str; 3] = [
+    "Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU",
+    "Software\\Microsoft\\Windows\\Shell\\BagMRU",
+    "Software\\Microsoft\\Windows\\ShellNoRoam\\BagMRU",
+];
+
+/// well-known GUIDs of root folder shell items
+const KNOWN_FOLDERS: &[(&str, &str)] = &[
+    ("20D04FE0-3AEA-1069-A2D8-08002B30309D", "My Computer"),
+    ("59031A47-3F72-44A7-89C5-5595FE6B30EE", "Users Files"),
+    ("F02C1A0D-BE21-4350-88B0-7367FC96EF3C", "Network"),
+    ("450D8FBA-AD25-11D0-98A8-0800361B1103", "My Documents"),
+    ("645FF040-5081-101B-9F08-00AA002F954E", "Recycle Bin"),
+    ("26EE0668-A00A-44D7-9371-BEB064C98683", "Control Panel"),
+    ("031E4825-7B94-4DC3-B131-E946B44C8DD5", "Libraries"),
+    ("679F85CB-0220-4080-B29B-5540CC05AAB6", "Quick Access"),
+];
+
+/// signature of the extension block which contains the long name and
+/// additional timestamps of a file entry shell item
+const BEEF0004_SIGNATURE: [u8; 4] = [0x04, 0x00, 0xef, 0xbe];
+
+/// a single shell item, as documented in
+/// <https://github.com/libyal/libfwsi/blob/main/documentation/Windows%20Shell%20Item%20format.asciidoc>
+pub(crate) struct ShellItem {
+    name: String,
+    modified: Option<i64>,
+    accessed: Option<i64>,
+    created: Option<i64>,
+    mft_reference: Option<(u64, u16)>,
+}
+
+impl ShellItem {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let size = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?) as usize;
+        let data = data.get(..size)?;
+        match data.get(2)? {
+            0x1f => Self::parse_root_folder(data),
+            0x20..=0x2f => Self::parse_volume(data),
+            0x30..=0x3f => Self::parse_file_entry(data),
+            _ => None,
+        }
+    }
+
+    fn parse_root_folder(data: &[u8]) -> Option<Self> {
+        let guid = format_guid(data.get(4..20)?);
+        let name = KNOWN_FOLDERS
+            .iter()
+            .find(|(g, _)| *g == guid)
+            .map_or_else(|| format!("{{{guid}}}"), |(_, name)| name.to_string());
+        Some(Self::without_timestamps(name))
+    }
+
+    fn parse_volume(data: &[u8]) -> Option<Self> {
+        let name = read_ascii(data.get(3..)?);
+        Some(Self::without_timestamps(name.trim_end_matches('\\').to_owned()))
+    }
+
+    fn parse_file_entry(data: &[u8]) -> Option<Self> {
+        let mut item = Self::without_timestamps(read_ascii(data.get(14..)?));
+        item.modified = fat_datetime(data.get(8..12)?);
+
+        // the extension block is optional; if it exists, it contains the long name
+        if let Some(pos) = data.windows(4).position(|w| w == BEEF0004_SIGNATURE) {
+            let ext = data.get(pos.checked_sub(4)?..)?;
+            let version = u16::from_le_bytes(ext.get(2..4)?.try_into().ok()?);
+            item.created = fat_datetime(ext.get(8..12)?);
+            item.accessed = fat_datetime(ext.get(12..16)?);
+
+            if version >= 7 {
+                let reference = u64::from_le_bytes(ext.get(20..28)?.try_into().ok()?);
+                item.mft_reference = Some((reference & 0x0000_ffff_ffff_ffff, (reference >> 48) as u16));
+            }
+
+            let name_offset = match version {
+                3 => 0x14,
+                7 => 0x26,
+                8 => 0x2a,
+                _ => 0x2e,
+            };
+            if let Some(name) = ext.get(name_offset..).map(read_utf16) {
+                if !name.is_empty() {
+                    item.name = name;
+                }
+            }
+        }
+        Some(item)
+    }
+
+    fn without_timestamps(name: String) -> Self {
+        Self {
+            name,
+            modified: None,
+            accessed: None,
+            created: None,
+            mft_reference: None,
+        }
+    }
+
+    /// creates a bodyfile line for this item, if it contains any timestamp
+    pub fn to_bodyfile(&self, path: &str) -> Option<Bodyfile3Line> {
+        if self.modified.is_none() && self.accessed.is_none() && self.created.is_none() {
+            return None;
+        }
+        let inode = match self.mft_reference {
+            Some((entry, sequence)) => format!("{entry}-{sequence}"),
+            None => "0".to_owned(),
+        };
+        Some(
+            Bodyfile3Line::new()
+                .with_name(path)
+                .with_owned_inode(inode)
+                .with_mtime(self.modified.unwrap_or(-1))
+                .with_atime(self.accessed.unwrap_or(-1))
+                .with_crtime(self.created.unwrap_or(-1)),
+        )
+    }
+}
+
+/// prints all shell items found below the `BagMRU` keys as bodyfile lines.
+/// The name of every line is the reconstructed path of the folder.
+pub(crate) fn print_shellbags<RS>(
+    hive: &mut Hive<RS, CleanHive>,
+    root_key: &KeyNode,
+    output: &mut dyn Write,
+) -> Result<()>
+where
+    RS: Read + Seek,
+{
+    for key_path in BAGMRU_PATHS {
+        if let Some(bagmru) = root_key.subpath(key_path, hive)? {
+            let mut path = Vec::new();
+            print_bagmru(hive, &bagmru.borrow(), &mut path, output)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_bagmru<RS>(
+    hive: &mut Hive<RS, CleanHive>,
+    keynode: &KeyNode,
+    path: &mut Vec<String>,
+    output: &mut dyn Write,
+) -> Result<()>
+where
+    RS: Read + Seek,
+{
+    for value in keynode.values() {
+        // shell items are stored in values with numeric names; there are
+        // other values as well, e.g. `MRUListEx` or `NodeSlot`
+        if value.name().parse::<u32>().is_err() {
+            continue;
+        }
+        let item = match value.value() {
+            RegistryValue::RegBinary(data) => match ShellItem::parse(data) {
+                Some(item) => item,
+                None => {
+                    log::warn!("unable to parse shell item in '{}'", path.join("\\"));
+                    continue;
+                }
+            },
+            _ => continue,
+        };
+
+        path.push(item.name.clone());
+        if let Some(line) = item.to_bodyfile(&path.join("\\")) {
+            writeln!(output, "{line}")?;
+        }
+        if let Some(subkey) = keynode.subkey(value.name(), hive)? {
+            print_bagmru(hive, &subkey.borrow(), path, output)?;
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8],
+        bytes[9],
+        bytes[10],
+        bytes[11],
+        bytes[12],
+        bytes[13],
+        bytes[14],
+        bytes[15]
+    )
+}
+
+/// converts a FAT date and time (as used by shell items) into a unix timestamp
+fn fat_datetime(bytes: &[u8]) -> Option<i64> {
+    let date = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let time = u16::from_le_bytes([bytes[2], bytes[3]]);
+    if date == 0 {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(
+        1980 + (date >> 9) as i32,
+        ((date >> 5) & 0x0f).into(),
+        (date & 0x1f).into(),
+    )?
+    .and_hms_opt(
+        (time >> 11).into(),
+        ((time >> 5) & 0x3f).into(),
+        ((time & 0x1f) * 2).into(),
+    )
+    .map(|dt| dt.timestamp())
+}
+
+fn read_ascii(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .take_while(|b| **b != 0)
+        .map(|b| *b as char)
+        .collect()
+}
+
+fn read_utf16(bytes: &[u8]) -> String {
+    let chars: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|c| *c != 0)
+        .collect();
+    String::from_utf16_lossy(&chars)
+}
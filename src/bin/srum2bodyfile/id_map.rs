@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+/// value of `IdType` for entries which contain a security identifier
+const ID_TYPE_SID: i64 = 3;
+
+/// contents of the `SruDbIdMapTable`, which maps the numeric ids used in the
+/// other tables to application names and user SIDs
+#[derive(Default)]
+pub(crate) struct IdMap(HashMap<i64, String>);
+
+impl IdMap {
+    /// adds an entry of the `SruDbIdMapTable`
+    pub fn insert(&mut self, id_index: i64, id_type: i64, id_blob: &[u8]) {
+        let value = if id_type == ID_TYPE_SID {
+            format_sid(id_blob)
+        } else {
+            decode_utf16(id_blob)
+        };
+        self.0.insert(id_index, value);
+    }
+
+    /// returns the name of the application or user with the id `id`, or the
+    /// id itself if it is unknown
+    pub fn resolve(&self, id: i64) -> String {
+        match self.0.get(&id) {
+            Some(value) => value.clone(),
+            None => format!("#{id}"),
+        }
+    }
+}
+
+/// application names are stored as null-terminated UTF-16LE strings
+fn decode_utf16(blob: &[u8]) -> String {
+    let chars: Vec<u16> = blob
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|c| *c != 0)
+        .collect();
+    String::from_utf16_lossy(&chars)
+}
+
+/// formats a binary security identifier, e.g. `S-1-5-18`
+fn format_sid(blob: &[u8]) -> String {
+    if blob.len() < 8 {
+        return String::from_utf8_lossy(blob).to_string();
+    }
+    let revision = blob[0];
+    let sub_authorities = usize::from(blob[1]);
+    let authority = blob[2..8]
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+
+    let mut sid = format!("S-{revision}-{authority}");
+    for chunk in blob[8..].chunks_exact(4).take(sub_authorities) {
+        let sub_authority = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        sid.push_str(&format!("-{sub_authority}"));
+    }
+    sid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdMap;
+
+    #[test]
+    fn resolve_ids() {
+        let mut id_map = IdMap::default();
+        let app: Vec<u8> = "\\device\\harddiskvolume3\\windows\\system32\\svchost.exe\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        id_map.insert(1, 0, &app);
+        id_map.insert(2, 3, &[1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0]);
+
+        assert_eq!(
+            id_map.resolve(1),
+            "\\device\\harddiskvolume3\\windows\\system32\\svchost.exe"
+        );
+        assert_eq!(id_map.resolve(2), "S-1-5-18");
+        assert_eq!(id_map.resolve(3), "#3");
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use srum_db::SrumDatabase;
+
+mod cli;
+mod id_map;
+mod network_usage;
+mod srum_db;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for path in cli.srum_files.iter() {
+            let result = SrumDatabase::open(path).and_then(|db| {
+                let id_map = db.id_map()?;
+                db.network_usage().map(|usages| (id_map, usages))
+            });
+            match result {
+                Ok((id_map, usages)) => {
+                    for usage in usages {
+                        writeln!(output, "{}", usage.to_bodyfile(&id_map))?;
+                    }
+                }
+                Err(why) => {
+                    log::error!("unable to parse '{}': {why}", path.display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
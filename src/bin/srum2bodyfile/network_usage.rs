@@ -0,0 +1,84 @@
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+use crate::id_map::IdMap;
+
+/// number of days between 1899-12-30 (the epoch of OLE automation dates)
+/// and 1970-01-01
+const OLE_EPOCH_OFFSET: f64 = 25569.0;
+
+/// converts an OLE automation date (days since 1899-12-30), as it is used
+/// by the `TimeStamp` column, into a unix timestamp
+pub(crate) fn unix_from_ole_date(days: f64) -> i64 {
+    ((days - OLE_EPOCH_OFFSET) * 86400.0).round() as i64
+}
+
+/// entry of the network data usage table, which contains the number of bytes
+/// an application has sent and received during the last measurement interval
+pub(crate) struct NetworkUsage {
+    pub timestamp: i64,
+    pub app_id: i64,
+    pub user_id: i64,
+    pub bytes_sent: i64,
+    pub bytes_received: i64,
+}
+
+impl NetworkUsage {
+    /// creates a bodyfile line whose name contains the application, the user
+    /// and the byte counts. The time of the SRUM entry is stored as atime
+    pub fn to_bodyfile(&self, id_map: &IdMap) -> Bodyfile3Line {
+        Bodyfile3Line::new()
+            .with_owned_name(format!(
+                "SRUM network usage: {} (user: {}, sent: {} bytes, received: {} bytes)",
+                id_map.resolve(self.app_id),
+                id_map.resolve(self.user_id),
+                self.bytes_sent,
+                self.bytes_received
+            ))
+            .with_atime(self.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::id_map::IdMap;
+
+    use super::{unix_from_ole_date, NetworkUsage};
+
+    #[test]
+    fn ole_date() {
+        // 2021-01-01T00:00:00+00:00
+        assert_eq!(unix_from_ole_date(44197.0), 1609459200);
+        // 2021-01-01T06:00:00+00:00
+        assert_eq!(unix_from_ole_date(44197.25), 1609480800);
+    }
+
+    #[test]
+    fn network_usage() {
+        let mut id_map = IdMap::default();
+        let app: Vec<u8> = "C:\\Program Files\\Mozilla Firefox\\firefox.exe"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        id_map.insert(272, 0, &app);
+        id_map.insert(
+            273,
+            3,
+            &[
+                1, 5, 0, 0, 0, 0, 0, 5, 21, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 233, 3, 0,
+                0,
+            ],
+        );
+
+        let usage = NetworkUsage {
+            timestamp: unix_from_ole_date(44197.0),
+            app_id: 272,
+            user_id: 273,
+            bytes_sent: 4096,
+            bytes_received: 1048576,
+        };
+        assert_eq!(
+            usage.to_bodyfile(&id_map).to_string(),
+            "0|SRUM network usage: C:\\Program Files\\Mozilla Firefox\\firefox.exe (user: S-1-5-21-1-2-3-1001, sent: 4096 bytes, received: 1048576 bytes)|0||0|0|0|1609459200|-1|-1|-1"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use libesedb::{EseDb, Record, Table, Value};
+
+use crate::id_map::IdMap;
+use crate::network_usage::{unix_from_ole_date, NetworkUsage};
+
+const ID_MAP_TABLE: &str = "SruDbIdMapTable";
+
+/// Network Data Usage Monitor
+const NETWORK_USAGE_TABLE: &str = "{973F5D5C-1D90-4944-BE8E-24B94231A174}";
+
+/// SRUM database (`SRUDB.dat`), which is an ESE database
+///
+/// <https://github.com/libyal/esedb-kb/blob/main/documentation/System%20Resource%20Usage%20Monitor%20(SRUM).asciidoc>
+pub(crate) struct SrumDatabase {
+    db: EseDb,
+}
+
+impl SrumDatabase {
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            db: EseDb::open(path)?,
+        })
+    }
+
+    pub fn id_map(&self) -> Result<IdMap> {
+        let table = self.db.table_by_name(ID_MAP_TABLE)?;
+        let columns = Columns::of(&table)?;
+
+        let mut id_map = IdMap::default();
+        for record in table.iter_records()? {
+            let record = record?;
+            let id_type = columns.integer(&record, "IdType")?;
+            let id_index = columns.integer(&record, "IdIndex")?;
+            let id_blob = columns.bytes(&record, "IdBlob")?;
+            id_map.insert(id_index, id_type, &id_blob);
+        }
+        Ok(id_map)
+    }
+
+    pub fn network_usage(&self) -> Result<Vec<NetworkUsage>> {
+        let table = self.db.table_by_name(NETWORK_USAGE_TABLE)?;
+        let columns = Columns::of(&table)?;
+
+        let mut usages = Vec::new();
+        for record in table.iter_records()? {
+            let record = record?;
+            usages.push(NetworkUsage {
+                timestamp: unix_from_ole_date(columns.date(&record, "TimeStamp")?),
+                app_id: columns.integer(&record, "AppId")?,
+                user_id: columns.integer(&record, "UserId")?,
+                bytes_sent: columns.integer(&record, "BytesSent")?,
+                bytes_received: columns.integer(&record, "BytesRecvd")?,
+            });
+        }
+        Ok(usages)
+    }
+}
+
+/// maps column names to the indices of the values in a record
+struct Columns(HashMap<String, i32>);
+
+impl Columns {
+    fn of(table: &Table) -> Result<Self> {
+        let mut columns = HashMap::new();
+        for (idx, column) in table.iter_columns()?.enumerate() {
+            columns.insert(column?.name()?, idx as i32);
+        }
+        Ok(Self(columns))
+    }
+
+    fn value(&self, record: &Record, name: &str) -> Result<Value> {
+        match self.0.get(name) {
+            Some(idx) => Ok(record.value(*idx)?),
+            None => Err(anyhow!("missing column '{name}'")),
+        }
+    }
+
+    fn integer(&self, record: &Record, name: &str) -> Result<i64> {
+        match self.value(record, name)? {
+            Value::U8(v) => Ok(v.into()),
+            Value::I16(v) => Ok(v.into()),
+            Value::U16(v) => Ok(v.into()),
+            Value::I32(v) => Ok(v.into()),
+            Value::U32(v) => Ok(v.into()),
+            Value::I64(v) | Value::Currency(v) => Ok(v),
+            Value::Null(_) => Ok(0),
+            _ => Err(anyhow!("column '{name}' does not contain an integer")),
+        }
+    }
+
+    /// returns the value of a date column as OLE automation date
+    fn date(&self, record: &Record, name: &str) -> Result<f64> {
+        match self.value(record, name)? {
+            Value::DateTime(v) => Ok(f64::from_bits(v)),
+            Value::F64(v) => Ok(v),
+            _ => Err(anyhow!("column '{name}' does not contain a date")),
+        }
+    }
+
+    fn bytes(&self, record: &Record, name: &str) -> Result<Vec<u8>> {
+        match self.value(record, name)? {
+            Value::Binary(v) | Value::LargeBinary(v) => Ok(v),
+            Value::Null(_) => Ok(Vec::new()),
+            _ => Err(anyhow!("column '{name}' does not contain binary data")),
+        }
+    }
+}
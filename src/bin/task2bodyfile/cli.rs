@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueHint};
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs};
+use log::LevelFilter;
+
+/// creates bodyfile from the XML files of Windows Scheduled Tasks (as found in
+/// `Windows\System32\Tasks`). Every action of a task results in one line.
+///
+/// The task files contain only the registration date of a task. Windows keeps
+/// the last and next run times in the `DynamicInfo` values below
+/// `Schedule\TaskCache\Tasks` in the `SOFTWARE` hive, so those are not part of
+/// the bodyfile
+#[derive(Parser)]
+#[clap(name=env!("CARGO_BIN_NAME"), author, version, long_about = None)]
+pub(crate) struct Cli {
+    /// names of task files, or of directories which are searched recursively
+    #[clap(value_hint=ValueHint::AnyPath, num_args=1..)]
+    pub(crate) task_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
+
+    #[clap(flatten)]
+    pub(crate) verbose: clap_verbosity_flag::Verbosity,
+}
+
+impl HasVerboseFlag for Cli {
+    fn log_level_filter(&self) -> LevelFilter {
+        self.verbose.log_level_filter()
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use task::ScheduledTask;
+
+mod cli;
+mod task;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut task_files = Vec::new();
+    for path in cli.task_files.iter() {
+        collect_files(path, &mut task_files)?;
+    }
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for path in task_files.iter() {
+            match std::fs::read(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| ScheduledTask::from_bytes(&bytes))
+            {
+                Ok(task) => {
+                    for line in task.to_bodyfile(&path.to_string_lossy()) {
+                        writeln!(output, "{line}")?;
+                    }
+                }
+                Err(why) => {
+                    log::error!("unable to parse '{}': {why}", path.display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
+
+/// adds `path` to `files`, or all files below `path` if it is a directory
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_dir() {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for entry in entries {
+            collect_files(&entry, files)?;
+        }
+    } else {
+        files.push(path.to_owned());
+    }
+    Ok(())
+}
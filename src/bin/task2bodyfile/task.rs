@@ -0,0 +1,267 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// action which is executed by a scheduled task
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TaskAction {
+    Exec {
+        command: String,
+        arguments: Option<String>,
+    },
+    ComHandler {
+        class_id: String,
+        data: Option<String>,
+    },
+
+    /// deprecated actions like `SendEmail` and `ShowMessage`
+    Other(String),
+}
+
+impl Display for TaskAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exec {
+                command,
+                arguments: None,
+            } => write!(f, "{command}"),
+            Self::Exec {
+                command,
+                arguments: Some(arguments),
+            } => write!(f, "{command} {arguments}"),
+            Self::ComHandler {
+                class_id,
+                data: None,
+            } => write!(f, "ComHandler {class_id}"),
+            Self::ComHandler {
+                class_id,
+                data: Some(data),
+            } => write!(f, "ComHandler {class_id} {data}"),
+            Self::Other(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// the parts of a scheduled task (as stored in `Windows\System32\Tasks`),
+/// which are relevant for a timeline
+#[derive(Debug, Default)]
+pub(crate) struct ScheduledTask {
+    uri: Option<String>,
+    registration_date: Option<i64>,
+    actions: Vec<TaskAction>,
+}
+
+impl ScheduledTask {
+    /// parses the content of a task file, which is usually encoded as UTF-16
+    /// with a byte order mark
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        // the byte order mark takes precedence over the specified encoding
+        let (xml, _, _) = encoding_rs::UTF_8.decode(bytes);
+        Self::parse(&xml)
+    }
+
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        let mut task = Self::default();
+        let mut path: Vec<String> = Vec::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    let name = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+                    task.start_element(&path, &name);
+                    path.push(name);
+                }
+                Event::Empty(element) => {
+                    let name = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+                    task.start_element(&path, &name);
+                }
+                Event::End(_) => {
+                    path.pop();
+                }
+                Event::Text(text) => task.set_value(&path, text.unescape()?.to_string()),
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(task)
+    }
+
+    fn start_element(&mut self, path: &[String], name: &str) {
+        if path.last().map(String::as_str) != Some("Actions") {
+            return;
+        }
+        self.actions.push(match name {
+            "Exec" => TaskAction::Exec {
+                command: String::new(),
+                arguments: None,
+            },
+            "ComHandler" => TaskAction::ComHandler {
+                class_id: String::new(),
+                data: None,
+            },
+            other => TaskAction::Other(other.to_owned()),
+        });
+    }
+
+    fn set_value(&mut self, path: &[String], value: String) {
+        let path: Vec<_> = path.iter().map(String::as_str).collect();
+        match (&path[..], self.actions.last_mut()) {
+            ([.., "RegistrationInfo", "URI"], _) => self.uri = Some(value),
+            ([.., "RegistrationInfo", "Date"], _) => {
+                self.registration_date = Self::parse_date(&value);
+                if self.registration_date.is_none() {
+                    log::warn!("invalid registration date: '{value}'");
+                }
+            }
+            ([.., "Exec", "Command"], Some(TaskAction::Exec { command, .. })) => *command = value,
+            ([.., "Exec", "Arguments"], Some(TaskAction::Exec { arguments, .. })) => {
+                *arguments = Some(value)
+            }
+            ([.., "ComHandler", "ClassId"], Some(TaskAction::ComHandler { class_id, .. })) => {
+                *class_id = value
+            }
+            ([.., "ComHandler", "Data"], Some(TaskAction::ComHandler { data, .. })) => {
+                *data = Some(value)
+            }
+            _ => (),
+        }
+    }
+
+    /// parses a date like `2021-02-18T10:15:00.1234567`. Dates without a
+    /// timezone are local time, and are converted as if they were UTC, so
+    /// that the timezone can be specified when creating the timeline.
+    fn parse_date(value: &str) -> Option<i64> {
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(date) => Some(date.timestamp()),
+            Err(_) => NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|date| date.timestamp()),
+        }
+    }
+
+    pub fn uri(&self) -> Option<&str> {
+        self.uri.as_deref()
+    }
+
+    pub fn registration_date(&self) -> Option<i64> {
+        self.registration_date
+    }
+
+    pub fn actions(&self) -> &[TaskAction] {
+        &self.actions
+    }
+
+    /// creates one line for every action of the task. The name consists of
+    /// the task path (the URI of the task, or `path` if the task has no URI)
+    /// and the action. The registration date is used as creation time.
+    ///
+    /// The task files do not contain the times when a task was run or will be
+    /// run next, because the task scheduler stores them in the `DynamicInfo`
+    /// value of the task's `TaskCache` key in the `SOFTWARE` hive. The
+    /// `StartBoundary` of a trigger only marks when the trigger is activated,
+    /// and is not a run time. So all other timestamps are left unset.
+    pub fn to_bodyfile(&self, path: &str) -> Vec<Bodyfile3Line> {
+        let task_path = self.uri().unwrap_or(path);
+        let line = Bodyfile3Line::new().with_crtime(self.registration_date().unwrap_or(-1));
+        if self.actions().is_empty() {
+            return vec![line.with_name(task_path)];
+        }
+        self.actions()
+            .iter()
+            .map(|action| {
+                line.clone()
+                    .with_owned_name(format!("{task_path} ({action})"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScheduledTask, TaskAction};
+
+    const TASK: &str = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Date>2021-02-18T10:15:00.1234567</Date>
+    <URI>\Updater</URI>
+  </RegistrationInfo>
+  <Actions Context="Author">
+    <Exec>
+      <Command>C:\Users\Public\updater.exe</Command>
+      <Arguments>--silent &amp; --hidden</Arguments>
+    </Exec>
+    <ComHandler>
+      <ClassId>{01575CFE-9A55-4003-A5E1-F38D1EBDCBE1}</ClassId>
+    </ComHandler>
+  </Actions>
+</Task>"#;
+
+    #[test]
+    fn parse_task() {
+        let task = ScheduledTask::parse(TASK).unwrap();
+        assert_eq!(task.uri(), Some("\\Updater"));
+        assert_eq!(task.registration_date(), Some(1613643300));
+        assert_eq!(
+            task.actions(),
+            &[
+                TaskAction::Exec {
+                    command: "C:\\Users\\Public\\updater.exe".to_owned(),
+                    arguments: Some("--silent & --hidden".to_owned())
+                },
+                TaskAction::ComHandler {
+                    class_id: "{01575CFE-9A55-4003-A5E1-F38D1EBDCBE1}".to_owned(),
+                    data: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn utf16() {
+        let bytes: Vec<u8> = [0xfeff_u16]
+            .into_iter()
+            .chain(TASK.encode_utf16())
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let task = ScheduledTask::from_bytes(&bytes).unwrap();
+        assert_eq!(task.uri(), Some("\\Updater"));
+    }
+
+    #[test]
+    fn no_run_times() {
+        let xml = TASK.replace(
+            "<Actions",
+            "<Triggers><TimeTrigger><StartBoundary>2021-02-19T08:00:00</StartBoundary>\
+             </TimeTrigger></Triggers><Actions",
+        );
+        let task = ScheduledTask::parse(&xml).unwrap();
+        let lines = task.to_bodyfile("Updater");
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            assert_eq!(*line.get_crtime(), 1613643300);
+            assert_eq!(*line.get_atime(), -1);
+            assert_eq!(*line.get_mtime(), -1);
+            assert_eq!(*line.get_ctime(), -1);
+        }
+    }
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(
+            ScheduledTask::parse_date("2021-02-18T10:15:00"),
+            Some(1613643300)
+        );
+        assert_eq!(
+            ScheduledTask::parse_date("2021-02-18T10:15:00+01:00"),
+            Some(1613639700)
+        );
+        assert_eq!(ScheduledTask::parse_date("2021-02-18"), None);
+    }
+}
@@ -1,9 +1,10 @@
 use clap::{Parser, ValueHint};
 use log::LevelFilter;
-use clio::{Input,Output};
+use clio::Input;
+use std::path::PathBuf;
 use chrono_tz::Tz;
 
-use dfir_toolkit::common::{HasVerboseFlag, TzArgument};
+use dfir_toolkit::common::{HasVerboseFlag, OutputArgs, TzArgument};
 
 /// replaces UNIX timestamps in a stream by a formatted date 
 #[derive(Parser, Debug)]
@@ -16,9 +17,13 @@ pub (crate) struct Cli {
     #[clap(flatten)]
     pub (crate) verbose: clap_verbosity_flag::Verbosity,
 
-    /// name of the file to write (default to stdout)
-    #[clap(default_value="-", value_hint=ValueHint::FilePath, value_parser)]
-    pub(crate) output_file: Output,
+    /// name of the file to write (default to stdout). This is the same as
+    /// `--output`, so the file is only created if the tool finishes successfully
+    #[clap(value_hint=ValueHint::FilePath, conflicts_with("output"))]
+    pub(crate) output_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) output: OutputArgs,
 
     /// name of offset of source timezone (or 'list' to display all possible values
     #[clap(short('f'), long("from-timezone"), display_order(300), default_value_t=TzArgument::Tz(Tz::UTC))]
@@ -27,6 +32,17 @@ pub (crate) struct Cli {
     /// name of offset of destination timezone (or 'list' to display all possible values
     #[clap(short('t'), long("to-timezone"), display_order(400), default_value_t=TzArgument::Tz(Tz::UTC))]
     pub dst_zone: TzArgument,
+
+    /// treat the input as bodyfile and replace its four timestamp columns by
+    /// formatted dates. Unset timestamps (`-1`) are replaced by `-`
+    #[clap(long("to-date"), conflicts_with("to_epoch"), display_order(500))]
+    pub(crate) to_date: bool,
+
+    /// treat the input as bodyfile and replace dates in its four timestamp
+    /// columns by UNIX timestamps. Empty columns and `-` are replaced by `-1`.
+    /// Dates without an explicit offset are interpreted in the source timezone
+    #[clap(long("to-epoch"), display_order(510))]
+    pub(crate) to_epoch: bool,
 }
 
 impl HasVerboseFlag for Cli {
@@ -1,45 +1,109 @@
-use std::io::{BufRead, Write};
+use std::io::BufRead;
+use std::path::Path;
 use regex::Regex;
 use anyhow::{bail, Result};
-use dfir_toolkit::common::{ForensicsTimestamp,FancyParser,TzArgument};
+use chrono_tz::Tz;
+use dfir_toolkit::common::{parse_datetime,ForensicsTimestamp,FancyParser,OutputArgs,TzArgument};
 use cli::Cli;
 
 mod cli;
 
+/// names of the last four columns of a bodyfile
+const TIMESTAMP_COLUMNS: [&str; 4] = ["atime", "mtime", "ctime", "crtime"];
+
 
 fn main() -> Result<()> {
     let cli = Cli::parse_cli();
 
     let mut input = cli.input_file;
-    let mut output = cli.output_file;
+    let output = match cli.output_file {
+        Some(path) if path != Path::new("-") => OutputArgs { output: Some(path) },
+        _ => cli.output,
+    };
 
     if cli.src_zone.is_list() || cli.dst_zone.is_list() {
         TzArgument::display_zones();
         return Ok(());
     }
 
+    let src_zone = cli.src_zone.into_tz().unwrap();
+    let dst_zone = cli.dst_zone.into_tz().unwrap();
     let re = Regex::new(r"^(?P<lhs>.*?)(?P<ts>\d{10})(?P<rhs>.*)$").unwrap();
     
-    for line in input.lock().lines() {
-        let content = match line {
-            Ok(line) => line,
-            Err(_) => bail!("content of input file need to be in UTF-8 (not in UTF-16)"),
-        };
-
-        let out = match re.captures(&content) {
-            Some(caps) => {
-                //let ndt = NaiveDateTime::from_timestamp_opt(caps.name("ts").unwrap().as_str().parse::<i64>().unwrap(),0).unwrap();
-                let ts = ForensicsTimestamp::new(caps.name("ts").unwrap().as_str().parse::<i64>().unwrap(),cli.src_zone.into_tz().unwrap(), cli.dst_zone.into_tz().unwrap());
-                format!("{}{}{}", caps.name("lhs").unwrap().as_str(),
-                                            ts,
-                                            caps.name("rhs").unwrap().as_str())
-
-            } 
-            None => content
-        };
-
-        output.lock().write_all((out+ "\n").as_bytes())?;
-        
+    output.write_with(|output| {
+        for (idx, line) in input.lock().lines().enumerate() {
+            let content = match line {
+                Ok(line) => line,
+                Err(_) => bail!("content of input file need to be in UTF-8 (not in UTF-16)"),
+            };
+
+            if cli.to_date || cli.to_epoch {
+                let out = if content.is_empty() || content.starts_with('#') {
+                    content
+                } else if cli.to_date {
+                    convert_bodyfile_line(&content, idx + 1, |value| to_date(value, src_zone, dst_zone))?
+                } else {
+                    convert_bodyfile_line(&content, idx + 1, |value| to_epoch(value, src_zone))?
+                };
+                writeln!(output, "{out}")?;
+                continue;
+            }
+
+            let out = match re.captures(&content) {
+                Some(caps) => {
+                    //let ndt = NaiveDateTime::from_timestamp_opt(caps.name("ts").unwrap().as_str().parse::<i64>().unwrap(),0).unwrap();
+                    let ts = ForensicsTimestamp::new(caps.name("ts").unwrap().as_str().parse::<i64>().unwrap(), src_zone, dst_zone);
+                    format!("{}{}{}", caps.name("lhs").unwrap().as_str(),
+                                                ts,
+                                                caps.name("rhs").unwrap().as_str())
+
+                } 
+                None => content
+            };
+
+            writeln!(output, "{out}")?;
+            
+        }
+        Ok(())
+    })
+}
+
+/// applies `convert` to the four timestamp columns of a bodyfile line.
+/// Because the name may contain pipe symbols, columns are counted from the end
+/// of the line.
+fn convert_bodyfile_line<F>(line: &str, line_nr: usize, convert: F) -> Result<String>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    let mut columns: Vec<&str> = line.rsplitn(5, '|').collect();
+    if columns.len() != 5 {
+        bail!("line {line_nr}: this is not a valid bodyfile line: '{line}'");
+    }
+    columns.reverse();
+
+    let mut result = columns[0].to_owned();
+    for (column, value) in TIMESTAMP_COLUMNS.iter().zip(&columns[1..]) {
+        match convert(value) {
+            Ok(value) => {
+                result.push('|');
+                result.push_str(&value);
+            }
+            Err(why) => bail!("line {line_nr}: invalid {column} '{value}': {why}"),
+        }
+    }
+    Ok(result)
+}
+
+fn to_date(value: &str, src_zone: Tz, dst_zone: Tz) -> Result<String> {
+    match value.parse::<i64>()? {
+        -1 => Ok("-".to_owned()),
+        ts => Ok(ForensicsTimestamp::new(ts, src_zone, dst_zone).to_string()),
+    }
+}
+
+fn to_epoch(value: &str, src_zone: Tz) -> Result<String> {
+    match value {
+        "" | "-" => Ok("-1".to_owned()),
+        value => Ok(parse_datetime(value, src_zone)?.to_string()),
     }
-    Ok(())
-}
\ No newline at end of file
+}
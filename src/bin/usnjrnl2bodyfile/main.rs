@@ -0,0 +1,38 @@
+use std::io::Read;
+
+use anyhow::Result;
+use cli::Cli;
+use dfir_toolkit::common::{ExitCode, FancyParser};
+use usn_record::UsnRecordIterator;
+
+mod cli;
+mod usn_record;
+
+fn main() -> ExitCode {
+    ExitCode::from(run())
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = Cli::parse_cli();
+
+    let mut errors = 0;
+    cli.output.write_with(|output| {
+        for mut input in cli.journal_files.into_iter() {
+            let mut buffer = Vec::new();
+            input.read_to_end(&mut buffer)?;
+
+            for record in UsnRecordIterator::new(&buffer) {
+                match record {
+                    Ok(record) => writeln!(output, "{}", record.to_bodyfile())?,
+                    Err(why) => {
+                        log::error!("unable to parse '{}': {why}", input.path().display());
+                        errors += 1;
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ExitCode::with_errors(errors))
+}
@@ -0,0 +1,157 @@
+use anyhow::{bail, Result};
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+use dfir_toolkit::common::unix_from_filetime;
+
+/// records are aligned to 8 byte boundaries
+const RECORD_ALIGNMENT: usize = 8;
+
+/// names of the `Reason` flags, in the order of their values
+const REASONS: &[(u32, &str)] = &[
+    (0x0000_0001, "DataOverwrite"),
+    (0x0000_0002, "DataExtend"),
+    (0x0000_0004, "DataTruncation"),
+    (0x0000_0010, "NamedDataOverwrite"),
+    (0x0000_0020, "NamedDataExtend"),
+    (0x0000_0040, "NamedDataTruncation"),
+    (0x0000_0100, "FileCreate"),
+    (0x0000_0200, "FileDelete"),
+    (0x0000_0400, "EaChange"),
+    (0x0000_0800, "SecurityChange"),
+    (0x0000_1000, "RenameOldName"),
+    (0x0000_2000, "RenameNewName"),
+    (0x0000_4000, "IndexableChange"),
+    (0x0000_8000, "BasicInfoChange"),
+    (0x0001_0000, "HardLinkChange"),
+    (0x0002_0000, "CompressionChange"),
+    (0x0004_0000, "EncryptionChange"),
+    (0x0008_0000, "ObjectIdChange"),
+    (0x0010_0000, "ReparsePointChange"),
+    (0x0020_0000, "StreamChange"),
+    (0x0040_0000, "TransactedChange"),
+    (0x0080_0000, "IntegrityChange"),
+    (0x8000_0000, "Close"),
+];
+
+/// a single `USN_RECORD_V2` or `USN_RECORD_V3`
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/api/winioctl/ns-winioctl-usn_record_v2>
+pub(crate) struct UsnRecord {
+    mft_entry: u64,
+    sequence_number: u16,
+    timestamp: u64,
+    reason: u32,
+    filename: String,
+}
+
+impl UsnRecord {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let major_version = read_u16(data, 4)?;
+        let (file_reference, timestamp_offset, filename_offset) = match major_version {
+            2 => (read_u64(data, 8)?, 32, 56),
+            // version 3 uses 128 bit file references, of which only the
+            // lower 64 bits are used by NTFS
+            3 => (read_u64(data, 8)?, 48, 72),
+            _ => bail!("unsupported USN record version: {major_version}"),
+        };
+
+        let filename_length = read_u16(data, filename_offset)? as usize;
+        let filename_start = read_u16(data, filename_offset + 2)? as usize;
+        let filename = match data.get(filename_start..filename_start + filename_length) {
+            Some(bytes) => {
+                let chars: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&chars)
+            }
+            None => bail!("filename exceeds the record boundaries"),
+        };
+
+        Ok(Self {
+            mft_entry: file_reference & 0x0000_ffff_ffff_ffff,
+            sequence_number: (file_reference >> 48) as u16,
+            timestamp: read_u64(data, timestamp_offset)?,
+            reason: read_u32(data, timestamp_offset + 8)?,
+            filename,
+        })
+    }
+
+    /// names of all flags which are set in the `Reason` field
+    pub fn reasons(&self) -> Vec<&'static str> {
+        REASONS
+            .iter()
+            .filter(|(flag, _)| self.reason & flag != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// creates a bodyfile line which contains the name of the file together
+    /// with the reasons of the change. The timestamp of the record is stored
+    /// as ctime, because it denotes a change of the MFT entry.
+    pub fn to_bodyfile(&self) -> Bodyfile3Line {
+        Bodyfile3Line::new()
+            .with_owned_name(format!("{} ({})", self.filename, self.reasons().join(", ")))
+            .with_owned_inode(format!("{}-{}", self.mft_entry, self.sequence_number))
+            .with_ctime(unix_from_filetime(self.timestamp))
+    }
+}
+
+/// iterates over all records of a `$UsnJrnl:$J` file. Because the journal
+/// is a sparse file, regions which consist of zeroes are skipped.
+pub(crate) struct UsnRecordIterator<'d> {
+    data: &'d [u8],
+    offset: usize,
+}
+
+impl<'d> UsnRecordIterator<'d> {
+    pub fn new(data: &'d [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+}
+
+impl<'d> Iterator for UsnRecordIterator<'d> {
+    type Item = Result<UsnRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record_length = match read_u32(self.data, self.offset) {
+                Ok(length) => length as usize,
+                Err(_) => return None,
+            };
+
+            if record_length == 0 {
+                self.offset += RECORD_ALIGNMENT;
+                continue;
+            }
+
+            let record = match self.data.get(self.offset..self.offset + record_length) {
+                Some(record) => record,
+                None => {
+                    self.offset = self.data.len();
+                    return Some(Err(anyhow::anyhow!("truncated USN record")));
+                }
+            };
+            self.offset += (record_length + RECORD_ALIGNMENT - 1) & !(RECORD_ALIGNMENT - 1);
+            return Some(UsnRecord::parse(record));
+        }
+    }
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    match data.get(offset..offset + N) {
+        Some(bytes) => Ok(bytes.try_into()?),
+        None => bail!("unexpected end of data at offset 0x{offset:x}"),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    Ok(u16::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(data, offset)?))
+}
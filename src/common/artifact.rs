@@ -0,0 +1,263 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::bodyfile::Bodyfile3Line;
+
+/// lines which have been created from a single artifact. Entries which could
+/// not be converted are returned as `Err` and are skipped by the driver
+pub type BodyfileLines = Box<dyn Iterator<Item = Result<Bodyfile3Line>>>;
+
+///
+/// Converts an artifact (e.g. a proprietary log file) into bodyfile lines.
+///
+/// Converters are registered in an [`ArtifactRegistry`], which selects the
+/// converter of a file by its magic bytes or by its extension. This allows
+/// other crates to reuse the `artifact2bodyfile` driver for their own
+/// formats.
+///
+/// # Example
+/// ```
+/// use std::io::{Cursor, Read};
+/// use std::path::Path;
+/// use dfir_toolkit::common::artifact::{ArtifactConverter, ArtifactRegistry, BodyfileLines};
+/// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+///
+/// /// every line of the input contains a unix timestamp and a message
+/// struct LogConverter;
+///
+/// impl ArtifactConverter for LogConverter {
+///     fn name(&self) -> &str {
+///         "log"
+///     }
+///
+///     fn extensions(&self) -> &[&str] {
+///         &["log"]
+///     }
+///
+///     fn convert(&self, input: &mut dyn Read) -> BodyfileLines {
+///         let mut content = String::new();
+///         if let Err(why) = input.read_to_string(&mut content) {
+///             return Box::new(std::iter::once(Err(why.into())));
+///         }
+///         let lines: Vec<_> = content
+///             .lines()
+///             .map(|line| -> anyhow::Result<Bodyfile3Line> {
+///                 let (ts, message) = line.split_once(' ').unwrap_or((line, ""));
+///                 Ok(Bodyfile3Line::new().with_name(message).with_mtime(ts.parse()?))
+///             })
+///             .collect();
+///         Box::new(lines.into_iter())
+///     }
+/// }
+///
+/// let registry = ArtifactRegistry::new().with_converter(LogConverter);
+/// let mut output = Vec::new();
+/// let errors = registry
+///     .convert(Path::new("app.log"), &mut Cursor::new("1609459200 started"), &mut output)
+///     .unwrap();
+/// assert_eq!(errors, 0);
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "0|started|0||0|0|0|-1|1609459200|-1|-1\n"
+/// );
+/// ```
+pub trait ArtifactConverter {
+    /// name of the artifact type, which is used in log messages
+    fn name(&self) -> &str;
+
+    /// file extensions (without the leading dot) of the artifacts which can
+    /// be converted. Extensions are compared case-insensitively
+    fn extensions(&self) -> &[&str] {
+        &[]
+    }
+
+    /// bytes at the beginning of the artifacts which can be converted
+    fn magic(&self) -> &[u8] {
+        &[]
+    }
+
+    /// converts the artifact, which is read from `input`
+    fn convert(&self, input: &mut dyn Read) -> BodyfileLines;
+}
+
+/// set of converters which are used by the `artifact2bodyfile` driver
+#[derive(Default)]
+pub struct ArtifactRegistry {
+    converters: Vec<Box<dyn ArtifactConverter>>,
+}
+
+impl ArtifactRegistry {
+    /// creates a registry without any converters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// creates a registry which contains the converters of this crate
+    pub fn with_builtin_converters() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "evtx2bodyfile")]
+        registry.register(Box::new(crate::evtx::EvtxConverter));
+        registry
+    }
+
+    pub fn with_converter<C: ArtifactConverter + 'static>(mut self, converter: C) -> Self {
+        self.register(Box::new(converter));
+        self
+    }
+
+    pub fn register(&mut self, converter: Box<dyn ArtifactConverter>) {
+        self.converters.push(converter);
+    }
+
+    /// returns the converter for the file `path`, which starts with `header`.
+    /// Matching magic bytes take precedence over matching extensions. If
+    /// multiple converters match, the first registered converter is used.
+    pub fn find(&self, path: &Path, header: &[u8]) -> Option<&dyn ArtifactConverter> {
+        let by_magic = self
+            .converters
+            .iter()
+            .find(|c| !c.magic().is_empty() && header.starts_with(c.magic()));
+        let by_extension = || {
+            let extension = path.extension()?.to_str()?;
+            self.converters.iter().find(|c| {
+                c.extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(extension))
+            })
+        };
+        by_magic.or_else(by_extension).map(|c| c.as_ref())
+    }
+
+    /// converts the artifact `path`, which is read from `input`, and writes
+    /// the bodyfile lines to `output`. Returns the number of entries which
+    /// could not be converted.
+    pub fn convert(
+        &self,
+        path: &Path,
+        input: &mut dyn Read,
+        output: &mut dyn Write,
+    ) -> Result<usize> {
+        let mut buffer = Vec::new();
+        input.read_to_end(&mut buffer)?;
+
+        let converter = match self.find(path, &buffer) {
+            Some(converter) => converter,
+            None => {
+                log::error!("no converter found for '{}'", path.display());
+                return Ok(1);
+            }
+        };
+        log::info!("converting '{}' as {}", path.display(), converter.name());
+
+        let mut errors = 0;
+        for line in converter.convert(&mut buffer.as_slice()) {
+            match line {
+                Ok(line) => writeln!(output, "{line}")?,
+                Err(why) => {
+                    log::warn!("unable to convert entry of '{}': {why}", path.display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(errors)
+    }
+
+    /// converts all files in `paths`. Returns the number of files and
+    /// entries which could not be converted.
+    pub fn convert_files<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        output: &mut dyn Write,
+    ) -> Result<usize> {
+        let mut errors = 0;
+        for path in paths {
+            let path = path.as_ref();
+            match File::open(path) {
+                Ok(mut file) => errors += self.convert(path, &mut file, output)?,
+                Err(why) => {
+                    log::error!("unable to open '{}': {why}", path.display());
+                    errors += 1;
+                }
+            }
+        }
+        Ok(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+    use std::path::Path;
+
+    use crate::common::bodyfile::Bodyfile3Line;
+
+    use super::{ArtifactConverter, ArtifactRegistry, BodyfileLines};
+
+    /// creates one line per byte of the input, and fails for zero bytes
+    struct DummyConverter;
+
+    impl ArtifactConverter for DummyConverter {
+        fn name(&self) -> &str {
+            "dummy"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["dmy"]
+        }
+
+        fn magic(&self) -> &[u8] {
+            b"DUMMY"
+        }
+
+        fn convert(&self, input: &mut dyn Read) -> BodyfileLines {
+            let mut buffer = Vec::new();
+            input.read_to_end(&mut buffer).unwrap();
+            Box::new(buffer.into_iter().skip(5).map(|b| {
+                match b {
+                    0 => Err(anyhow::anyhow!("zero byte")),
+                    b => Ok(Bodyfile3Line::new()
+                        .with_owned_name(format!("/byte/{b}"))
+                        .with_mtime(b.into())),
+                }
+            }))
+        }
+    }
+
+    fn convert(registry: &ArtifactRegistry, name: &str, input: &[u8]) -> (usize, String) {
+        let mut output = Vec::new();
+        let errors = registry
+            .convert(Path::new(name), &mut Cursor::new(input), &mut output)
+            .unwrap();
+        (errors, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn dummy_converter() {
+        let registry = ArtifactRegistry::new().with_converter(DummyConverter);
+
+        // found by magic bytes
+        let (errors, output) = convert(&registry, "sample.bin", b"DUMMY\x01\x00\x02");
+        assert_eq!(errors, 1);
+        assert_eq!(
+            output,
+            "0|/byte/1|0||0|0|0|-1|1|-1|-1\n0|/byte/2|0||0|0|0|-1|2|-1|-1\n"
+        );
+
+        // found by extension
+        let (errors, output) = convert(&registry, "sample.DMY", b"xxxxx\x03");
+        assert_eq!(errors, 0);
+        assert_eq!(output, "0|/byte/3|0||0|0|0|-1|3|-1|-1\n");
+    }
+
+    #[test]
+    fn unknown_artifact() {
+        let registry = ArtifactRegistry::new().with_converter(DummyConverter);
+        let (errors, output) = convert(&registry, "sample.txt", b"hello");
+        assert_eq!(errors, 1);
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueHint};
+
+use super::OutputWriter;
+
+/// command line argument to write the output of a tool into a file instead of
+/// stdout. Use `#[clap(flatten)]` to add it to the command line of a tool, and
+/// [`OutputArgs::write_with`] to write the output.
+#[derive(Args, Clone, Debug, Default)]
+pub struct OutputArgs {
+    /// write the output into this file instead of stdout. The file is only
+    /// created if the tool finishes successfully
+    #[clap(long("output"), value_hint=ValueHint::FilePath, display_order(800))]
+    pub output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    /// opens the output, which is either stdout or an [`AtomicOutput`] file
+    pub fn open(&self) -> Result<Box<dyn FinishableWrite>> {
+        Ok(match &self.output {
+            Some(path) => Box::new(AtomicOutput::create(path)?),
+            None => Box::new(OutputWriter::stdout()),
+        })
+    }
+
+    /// runs `f` with the output as parameter. If `f` fails, no output file
+    /// is created, and an already existing file is left untouched.
+    pub fn write_with<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn Write) -> Result<()>,
+    {
+        let mut output = self.open()?;
+        f(&mut output)?;
+        output.finish()
+    }
+}
+
+/// writer which can report if all data have been written successfully
+pub trait FinishableWrite: Write {
+    /// flushes the writer and makes the written data visible
+    fn finish(self: Box<Self>) -> Result<()>;
+}
+
+///
+/// writes into a temporary file in the directory of the target file, and
+/// renames it to the target file when [`AtomicOutput::commit`] is called.
+/// So the target file is either missing or complete, but never contains
+/// partial output. If the `AtomicOutput` is dropped without being
+/// committed, the temporary file is removed.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::AtomicOutput;
+/// use std::io::Write;
+///
+/// let target = std::env::temp_dir().join(format!("atomic_output_doctest_{}", std::process::id()));
+/// let mut output = AtomicOutput::create(&target).unwrap();
+/// writeln!(output, "hello").unwrap();
+/// assert!(!target.exists());
+///
+/// output.commit().unwrap();
+/// assert_eq!(std::fs::read_to_string(&target).unwrap(), "hello\n");
+/// # std::fs::remove_file(&target).unwrap();
+/// ```
+pub struct AtomicOutput {
+    file: Option<BufWriter<File>>,
+    tmp_path: PathBuf,
+    target: PathBuf,
+}
+
+impl AtomicOutput {
+    pub fn create(target: &Path) -> Result<Self> {
+        let file_name = target
+            .file_name()
+            .ok_or_else(|| anyhow!("invalid output file name: '{}'", target.display()))?;
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(file_name);
+        tmp_name.push(format!(".{}.tmp", std::process::id()));
+        let tmp_path = target.with_file_name(tmp_name);
+
+        let file = File::create(&tmp_path)
+            .map_err(|why| anyhow!("unable to create '{}': {why}", tmp_path.display()))?;
+        Ok(Self {
+            file: Some(BufWriter::new(file)),
+            tmp_path,
+            target: target.to_path_buf(),
+        })
+    }
+
+    /// flushes all data and replaces the target file by the temporary file
+    pub fn commit(mut self) -> Result<()> {
+        let file = self.file.take().expect("output has already been committed");
+        let result = file
+            .into_inner()
+            .map_err(|why| why.into_error())
+            .and_then(|file| file.sync_all())
+            .and_then(|_| fs::rename(&self.tmp_path, &self.target));
+        if result.is_err() {
+            let _ = fs::remove_file(&self.tmp_path);
+        }
+        Ok(result?)
+    }
+
+    fn file(&mut self) -> &mut BufWriter<File> {
+        self.file.as_mut().expect("output has already been committed")
+    }
+}
+
+impl Write for AtomicOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file().flush()
+    }
+}
+
+impl FinishableWrite for AtomicOutput {
+    fn finish(self: Box<Self>) -> Result<()> {
+        (*self).commit()
+    }
+}
+
+impl Drop for AtomicOutput {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.tmp_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use anyhow::bail;
+
+    use super::OutputArgs;
+
+    fn work_dir(test: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("atomic_output_{test}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn successful_write() {
+        let dir = work_dir("success");
+        let target = dir.join("output.txt");
+        let args = OutputArgs {
+            output: Some(target.clone()),
+        };
+
+        args.write_with(|output| {
+            writeln!(output, "line 1")?;
+            writeln!(output, "line 2")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "line 1\nline 2\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_write() {
+        let dir = work_dir("failure");
+        let target = dir.join("output.txt");
+        let args = OutputArgs {
+            output: Some(target.clone()),
+        };
+
+        let result = args.write_with(|output| {
+            writeln!(output, "line 1")?;
+            bail!("unable to read line 2")
+        });
+
+        assert!(result.is_err());
+        assert!(!target.exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
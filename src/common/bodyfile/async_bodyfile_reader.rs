@@ -0,0 +1,103 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Lines};
+
+use super::{Bodyfile3Line, Bodyfile3ParserError};
+
+///
+/// Reads bodyfile lines from an [`AsyncBufRead`], e.g. a
+/// `tokio::io::BufReader` wrapping a file or a network stream.
+///
+/// Like [`BodyfileReader`](super::BodyfileReader), this reader skips
+/// comments and a header line at the beginning of the input. Additionally,
+/// blank lines are skipped. If an I/O error occurs (which includes invalid
+/// UTF-8 sequences), the error is logged and the stream ends.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::AsyncBodyfileReader;
+/// use futures::StreamExt;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let data = "# comment\n0|a|1||0|0|0|-1|1|-1|-1\n".as_bytes();
+/// let mut reader = AsyncBodyfileReader::new(data);
+/// let line = reader.next().await.unwrap().unwrap();
+/// assert_eq!(line.get_name(), "a");
+/// assert!(reader.next().await.is_none());
+/// # });
+/// ```
+pub struct AsyncBodyfileReader<R> {
+    lines: Lines<R>,
+    line_nr: usize,
+}
+
+impl<R> AsyncBodyfileReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_nr: 0,
+        }
+    }
+}
+
+impl<R> Stream for AsyncBodyfileReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Item = Result<Bodyfile3Line, Bodyfile3ParserError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let line = match Pin::new(&mut self.lines).poll_next_line(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(None)) => return Poll::Ready(None),
+                Poll::Ready(Ok(Some(line))) => line,
+                Poll::Ready(Err(why)) => {
+                    log::error!("error while reading line {}: {why}", self.line_nr + 1);
+                    return Poll::Ready(None);
+                }
+            };
+
+            self.line_nr += 1;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty()
+                || line.starts_with('#')
+                || (self.line_nr == 1 && Bodyfile3Line::is_header(line))
+            {
+                continue;
+            }
+
+            return Poll::Ready(Some(Bodyfile3Line::try_from(line)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use matches::assert_matches;
+
+    use super::AsyncBodyfileReader;
+    use crate::common::bodyfile::Bodyfile3ParserError;
+
+    #[tokio::test]
+    async fn read_from_buffer() {
+        let data = "MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime
+0|a|1||0|0|0|-1|1|-1|-1
+
+# this is a comment
+0|b|2||0|0|0|-1|X|-1|-1\r
+0|c|3||0|0|0|-1|3|-1|-1\r
+";
+        let results: Vec<_> = AsyncBodyfileReader::new(data.as_bytes()).collect().await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().get_name(), "a");
+        assert_matches!(results[1], Err(Bodyfile3ParserError::IllegalMTime));
+        assert_eq!(results[2].as_ref().unwrap().get_name(), "c");
+    }
+}
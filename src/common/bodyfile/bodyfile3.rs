@@ -1,16 +1,26 @@
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
 use duplicate::duplicate_item;
 use getset::{Getters, Setters};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 
+use super::{EntryType, FileCategory, HashAlgo, MacbFlags, TimestampKind};
+
 ///
 /// This struct implements the bodyfile format generated by TSK 3.x
 ///
-#[derive(Debug, Getters, Setters)]
+#[derive(Debug, Clone, Getters, Setters)]
 #[getset(get = "pub with_prefix", set = "pub")]
 pub struct Bodyfile3Line {
     md5: String,
+
+    /// the accessors are implemented manually, because setting the name
+    /// discards `raw_name`
+    #[getset(skip)]
     name: String,
     inode: String,
     mode_as_string: String,
@@ -21,6 +31,15 @@ pub struct Bodyfile3Line {
     mtime: i64,
     ctime: i64,
     crtime: i64,
+
+    /// free-text notes from an optional 12th column, which is written by
+    /// some forks of mactime
+    notes: Option<String>,
+
+    /// original name, if control characters have been replaced by
+    /// [`Bodyfile3Line::with_name_sanitized`]
+    #[getset(skip)]
+    raw_name: Option<String>,
 }
 
 impl Default for Bodyfile3Line {
@@ -29,6 +48,182 @@ impl Default for Bodyfile3Line {
     }
 }
 
+/// placeholder which is written into the `md5` column of lines without hash
+/// by TSK
+pub const DEFAULT_MD5_PLACEHOLDER: &str = "0";
+
+/// conventions which are used when bodyfile lines are created. The default
+/// configuration follows TSK, which is also used by [`Bodyfile3Line::new`].
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Config, Bodyfile3Line};
+///
+/// let config = Bodyfile3Config::default().with_md5_placeholder("-");
+/// let bf = config.new_line().with_name("/tmp/a");
+/// assert_eq!(bf.to_string(), "-|/tmp/a|0||0|0|0|-1|-1|-1|-1");
+/// assert!(!config.has_hash(&bf));
+///
+/// let bf = Bodyfile3Line::try_from(bf.to_string().as_str()).unwrap();
+/// assert!(!config.has_hash(&bf));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bodyfile3Config {
+    md5_placeholder: String,
+}
+
+impl Default for Bodyfile3Config {
+    fn default() -> Self {
+        Self {
+            md5_placeholder: DEFAULT_MD5_PLACEHOLDER.to_owned(),
+        }
+    }
+}
+
+impl Bodyfile3Config {
+    /// use `placeholder` (e.g. an empty string or `-`) in the `md5` column
+    /// of lines without hash, instead of `0`
+    pub fn with_md5_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.md5_placeholder = placeholder.into();
+        self
+    }
+
+    pub fn md5_placeholder(&self) -> &str {
+        &self.md5_placeholder
+    }
+
+    /// creates a new empty bodyfile line, whose `md5` column contains the
+    /// configured placeholder
+    pub fn new_line(&self) -> Bodyfile3Line {
+        Bodyfile3Line {
+            md5: self.md5_placeholder.clone(),
+            name: "".to_owned(),
+            inode: "0".to_owned(),
+            mode_as_string: "".to_owned(),
+            uid: 0,
+            gid: 0,
+            size: 0,
+            atime: -1,
+            mtime: -1,
+            ctime: -1,
+            crtime: -1,
+            notes: None,
+            raw_name: None,
+        }
+    }
+
+    /// returns `true` if the `md5` column of `line` contains something else
+    /// than the configured placeholder
+    pub fn has_hash(&self, line: &Bodyfile3Line) -> bool {
+        line.md5 != self.md5_placeholder
+    }
+
+    /// like [`Bodyfile3Line::merge`], but the hash of `other` replaces the
+    /// configured placeholder instead of `0`
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Config;
+    ///
+    /// let config = Bodyfile3Config::default().with_md5_placeholder("-");
+    /// let mut bf = config.new_line().with_name("/tmp/a");
+    /// let other = config.new_line().with_name("/tmp/a").with_md5("b1946ac92492d2347c6235b4d2611184");
+    /// config.merge(&mut bf, &other).unwrap();
+    /// assert_eq!(bf.get_md5(), "b1946ac92492d2347c6235b4d2611184");
+    /// ```
+    pub fn merge(
+        &self,
+        line: &mut Bodyfile3Line,
+        other: &Bodyfile3Line,
+    ) -> Result<Vec<TimestampKind>, Bodyfile3MergeError> {
+        if line.identity_key() != other.identity_key() {
+            return Err(Bodyfile3MergeError::IdentityMismatch);
+        }
+
+        let mut conflicts = Vec::new();
+        for kind in TimestampKind::ALL {
+            let (own, theirs) = (line.get_timestamp(kind), other.get_timestamp(kind));
+            if own == -1 {
+                line.set_timestamp(kind, theirs);
+            } else if theirs != -1 && theirs != own {
+                log::warn!(
+                    "conflicting {kind:?} values for '{}': {own} and {theirs}, keeping {own}",
+                    line.name
+                );
+                conflicts.push(kind);
+            }
+        }
+
+        if !self.has_hash(line) {
+            line.md5 = other.md5.clone();
+        }
+        if line.size == 0 {
+            line.size = other.size;
+        }
+        if line.mode_as_string.is_empty() {
+            line.mode_as_string = other.mode_as_string.clone();
+        }
+        if line.notes.is_none() {
+            line.notes = other.notes.clone();
+        }
+        Ok(conflicts)
+    }
+
+    /// like [`Bodyfile3Line::validate`], but the configured placeholder is
+    /// accepted in the `md5` column instead of `0`
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Config, Bodyfile3Line};
+    ///
+    /// let config = Bodyfile3Config::default().with_md5_placeholder("-");
+    /// assert!(config.validate(&config.new_line()).is_empty());
+    /// assert!(!Bodyfile3Line::new().with_md5("-").validate().is_empty());
+    /// ```
+    pub fn validate(&self, line: &Bodyfile3Line) -> Vec<Bodyfile3ParserError> {
+        let mut issues = Vec::new();
+        if self.has_hash(line) && line.hash_algorithm() == HashAlgo::None {
+            issues.push(Bodyfile3ParserError::IllegalHash);
+        }
+        if !Bodyfile3Line::is_valid_mode(&line.mode_as_string) {
+            issues.push(Bodyfile3ParserError::IllegalMode);
+        }
+        for (ts, issue) in [
+            (line.atime, Bodyfile3ParserError::IllegalATime),
+            (line.mtime, Bodyfile3ParserError::IllegalMTime),
+            (line.ctime, Bodyfile3ParserError::IllegalCTime),
+            (line.crtime, Bodyfile3ParserError::IllegalCRTime),
+        ] {
+            if !Bodyfile3Line::is_valid_timestamp(ts, false) {
+                issues.push(issue);
+            }
+        }
+        issues
+    }
+
+    /// like [`Bodyfile3Line::anonymize_hashes`], but the configured
+    /// placeholder is kept instead of `0`
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Config;
+    ///
+    /// let config = Bodyfile3Config::default().with_md5_placeholder("-");
+    /// let mut bf = config.new_line();
+    /// config.anonymize_hashes(&mut bf, b"secret");
+    /// assert_eq!(bf.get_md5(), "-");
+    /// ```
+    pub fn anonymize_hashes(&self, line: &mut Bodyfile3Line, secret: &[u8]) {
+        if !self.has_hash(line) {
+            return;
+        }
+        line.md5 = hmac_sha256(secret, line.md5.to_lowercase().as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+    }
+}
+
 impl Bodyfile3Line {
     /// create a new empty bodyfile line
     ///
@@ -48,57 +243,936 @@ impl Bodyfile3Line {
     /// assert_eq!(*bf.get_mtime(), -1);
     /// assert_eq!(*bf.get_ctime(), -1);
     /// assert_eq!(*bf.get_crtime(), -1);
+    /// assert_eq!(*bf.get_notes(), None);
     /// ```
     pub fn new() -> Self {
-        Self {
-            md5: "0".to_owned(),
-            name: "".to_owned(),
-            inode: "0".to_owned(),
-            mode_as_string: "".to_owned(),
-            uid: 0,
-            gid: 0,
-            size: 0,
-            atime: -1,
-            mtime: -1,
-            ctime: -1,
-            crtime: -1,
+        Bodyfile3Config::default().new_line()
+    }
+
+    #[duplicate_item(
+        method_name attribute_name;
+        [with_md5]    [md5];
+        [with_inode]  [inode];
+        [with_mode]   [mode_as_string];
+    )]
+    pub fn method_name(mut self, attribute_name: &str) -> Self {
+        self.attribute_name = attribute_name.to_owned();
+        self
+    }
+
+    #[duplicate_item(
+        method_name attribute_name attribute_type;
+        [with_owned_md5]    [md5]            [String];
+        [with_owned_inode]  [inode]          [String];
+        [with_owned_mode]   [mode_as_string] [String];
+        [with_uid]    [uid]            [u64];
+        [with_gid]    [gid]            [u64];
+        [with_size]   [size]           [u64];
+        [with_atime]  [atime]          [i64];
+        [with_mtime]  [mtime]          [i64];
+        [with_ctime]  [ctime]          [i64];
+        [with_crtime] [crtime]         [i64];
+    )]
+    pub fn method_name(mut self, attribute_name: attribute_type) -> Self {
+        self.attribute_name = attribute_name;
+        self
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn with_name(self, name: &str) -> Self {
+        self.with_owned_name(name.to_owned())
+    }
+
+    pub fn with_owned_name(mut self, name: String) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// sets the name. The original name of a sanitized name (see
+    /// [`Bodyfile3Line::with_name_sanitized`]) is discarded.
+    pub fn set_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self.raw_name = None;
+        self
+    }
+
+    /// sets the name, but replaces control characters (e.g. a bell or an
+    /// escape sequence, which would be interpreted by a terminal, or line
+    /// breaks, which break csv output) by a visible placeholder like `\x07`.
+    /// The original name is still available using
+    /// [`Bodyfile3Line::raw_name`].
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name_sanitized("/tmp/ring\x07.txt");
+    /// assert_eq!(bf.get_name(), "/tmp/ring\\x07.txt");
+    /// assert_eq!(bf.raw_name(), "/tmp/ring\x07.txt");
+    /// ```
+    pub fn with_name_sanitized(mut self, name: &str) -> Self {
+        let mut sanitized = String::with_capacity(name.len());
+        for c in name.chars() {
+            if c.is_control() {
+                sanitized.push_str(&format!("\\x{:02x}", u32::from(c)));
+            } else {
+                sanitized.push(c);
+            }
+        }
+        self.raw_name = (sanitized != name).then(|| name.to_owned());
+        self.name = sanitized;
+        self
+    }
+
+    /// returns the name before control characters have been replaced by
+    /// [`Bodyfile3Line::with_name_sanitized`]. For all other lines, this is
+    /// the same as [`Bodyfile3Line::get_name`]. Every change of the name
+    /// (e.g. [`Bodyfile3Line::redact_users`]) discards the original name, so
+    /// that it cannot reveal what has been redacted.
+    pub fn raw_name(&self) -> &str {
+        self.raw_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// sets the notes, which are written as 12th column
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("a").with_notes("seen in case 42");
+    /// assert_eq!(bf.to_string(), "0|a|0||0|0|0|-1|-1|-1|-1|seen in case 42");
+    /// ```
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_owned());
+        self
+    }
+
+    /// sets a timestamp, but rejects values which the parser would reject as
+    /// well (i.e. values below `-1`, and values which cannot be converted
+    /// into a date), so that the line can be parsed again after it has been
+    /// written. Use the `with_*` builders for trusted values.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError};
+    ///
+    /// let mut bf = Bodyfile3Line::new();
+    /// assert!(bf.try_set_atime(5).is_ok());
+    /// assert!(matches!(bf.try_set_atime(-5), Err(Bodyfile3ParserError::IllegalATime)));
+    /// assert_eq!(*bf.get_atime(), 5);
+    /// ```
+    #[duplicate_item(
+        method_name      attribute_name error;
+        [try_set_atime]  [atime]        [IllegalATime];
+        [try_set_mtime]  [mtime]        [IllegalMTime];
+        [try_set_ctime]  [ctime]        [IllegalCTime];
+        [try_set_crtime] [crtime]       [IllegalCRTime];
+    )]
+    pub fn method_name(&mut self, value: i64) -> Result<(), Bodyfile3ParserError> {
+        if !Self::is_valid_timestamp(value, false) {
+            return Err(Bodyfile3ParserError::error);
+        }
+        self.attribute_name = value;
+        Ok(())
+    }
+
+    /// returns the timestamp of the specified kind
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+    ///
+    /// let bf = Bodyfile3Line::new().with_ctime(1234);
+    /// assert_eq!(bf.get_timestamp(TimestampKind::Ctime), 1234);
+    /// assert_eq!(bf.get_timestamp(TimestampKind::Mtime), -1);
+    /// ```
+    pub fn get_timestamp(&self, kind: TimestampKind) -> i64 {
+        match kind {
+            TimestampKind::Atime => self.atime,
+            TimestampKind::Mtime => self.mtime,
+            TimestampKind::Ctime => self.ctime,
+            TimestampKind::Crtime => self.crtime,
+        }
+    }
+
+    /// sets the timestamp of the specified kind. This is the counterpart of
+    /// [`Self::with_mtime`] and its siblings, if the kind is only known at
+    /// runtime.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+    ///
+    /// let bf = Bodyfile3Line::new().with_timestamp(TimestampKind::Crtime, 1234);
+    /// assert_eq!(*bf.get_crtime(), 1234);
+    /// ```
+    pub fn with_timestamp(mut self, kind: TimestampKind, value: i64) -> Self {
+        self.set_timestamp(kind, value);
+        self
+    }
+
+    /// returns the age of the file at `reference`, measured from the timestamp
+    /// of the specified kind, or `None` if this timestamp is not set. The age
+    /// is negative if the timestamp is later than `reference`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+    ///
+    /// // created at 2021-01-01T00:00:00Z
+    /// let bf = Bodyfile3Line::new().with_crtime(1609459200);
+    /// let incident = Utc.with_ymd_and_hms(2021, 1, 1, 6, 0, 0).unwrap();
+    ///
+    /// assert_eq!(bf.age_at(TimestampKind::Crtime, incident), Some(Duration::hours(6)));
+    /// assert!(bf.age_at(TimestampKind::Crtime, incident).unwrap() <= Duration::hours(24));
+    ///
+    /// // the mtime is not set
+    /// assert_eq!(bf.age_at(TimestampKind::Mtime, incident), None);
+    /// ```
+    pub fn age_at(&self, kind: TimestampKind, reference: DateTime<Utc>) -> Option<Duration> {
+        match self.get_timestamp(kind) {
+            -1 => None,
+            ts => Utc
+                .timestamp_opt(ts, 0)
+                .single()
+                .map(|timestamp| reference - timestamp),
+        }
+    }
+
+    /// returns `true` if the `md5` column contains something else than the
+    /// placeholder `0`. Use [`Bodyfile3Config::has_hash`] if another
+    /// placeholder is used.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// assert!(!Bodyfile3Line::new().has_hash());
+    /// assert!(Bodyfile3Line::new().with_md5("b1946ac92492d2347c6235b4d2611184").has_hash());
+    /// ```
+    pub fn has_hash(&self) -> bool {
+        Bodyfile3Config::default().has_hash(self)
+    }
+
+    /// returns `true` if the inode column contains a real value, i.e. if it
+    /// is neither empty nor the placeholder `0`, which means that the inode
+    /// is unknown
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// assert!(Bodyfile3Line::new().with_inode("87915-128-1").has_inode());
+    /// assert!(!Bodyfile3Line::new().with_inode("0").has_inode());
+    /// assert!(!Bodyfile3Line::new().with_inode("").has_inode());
+    /// ```
+    pub fn has_inode(&self) -> bool {
+        !matches!(self.inode.trim(), "" | "0")
+    }
+
+    /// returns a key which identifies the file described by this line,
+    /// independent of its timestamps. Two lines with the same key refer to
+    /// the same file. If the inode is unknown (see [`Self::has_inode`]), the
+    /// file is identified by its name alone.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf1 = Bodyfile3Line::new().with_name("/tmp/a").with_inode("1234").with_mtime(1);
+    /// let bf2 = Bodyfile3Line::new().with_name("/tmp/a").with_inode("1234").with_crtime(2);
+    /// let bf3 = Bodyfile3Line::new().with_name("/tmp/a").with_inode("1235");
+    /// assert_eq!(bf1.identity_key(), bf2.identity_key());
+    /// assert_ne!(bf1.identity_key(), bf3.identity_key());
+    ///
+    /// // `0` and an empty inode both mean that the inode is unknown
+    /// let bf4 = Bodyfile3Line::new().with_name("/tmp/a").with_inode("0");
+    /// let bf5 = Bodyfile3Line::new().with_name("/tmp/a").with_inode("");
+    /// assert_eq!(bf4.identity_key(), bf5.identity_key());
+    /// assert_ne!(bf1.identity_key(), bf4.identity_key());
+    /// ```
+    pub fn identity_key(&self) -> String {
+        // the inode cannot contain a pipe, so this key is unambiguous
+        format!("{}|{}", self.identity_inode(), self.name)
+    }
+
+    /// the inode as used in identity keys, which is empty if it is unknown
+    fn identity_inode(&self) -> &str {
+        if self.has_inode() {
+            &self.inode
+        } else {
+            ""
+        }
+    }
+
+    /// returns the same key as [`Bodyfile3Line::identity_key`], but with
+    /// the name in lowercase. Windows paths are case-insensitive, so names
+    /// which only differ in their case refer to the same file.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf1 = Bodyfile3Line::new().with_name("C:/Windows/notepad.exe").with_inode("1234");
+    /// let bf2 = Bodyfile3Line::new().with_name("C:/windows/NOTEPAD.EXE").with_inode("1234");
+    /// assert_ne!(bf1.identity_key(), bf2.identity_key());
+    /// assert_eq!(bf1.case_insensitive_identity_key(), bf2.case_insensitive_identity_key());
+    /// ```
+    pub fn case_insensitive_identity_key(&self) -> String {
+        format!("{}|{}", self.identity_inode(), self.name.to_lowercase())
+    }
+
+    /// parses the inode column, if it contains an NTFS reference in the
+    /// format used by TSK (`entry-type-sequence`, e.g. `87915-128-1`)
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, MftReference};
+    ///
+    /// let bf = Bodyfile3Line::new().with_inode("87915-128-1");
+    /// assert_eq!(
+    ///     bf.mft_reference(),
+    ///     Some(MftReference { entry: 87915, attr_type: 128, sequence: 1 })
+    /// );
+    ///
+    /// let bf = Bodyfile3Line::new().with_inode("12345");
+    /// assert_eq!(bf.mft_reference(), None);
+    ///
+    /// let bf = Bodyfile3Line::new();
+    /// assert_eq!(bf.mft_reference(), None);
+    /// ```
+    pub fn mft_reference(&self) -> Option<MftReference> {
+        let mut parts = self.inode.split('-');
+        let reference = MftReference {
+            entry: parts.next()?.parse().ok()?,
+            attr_type: parts.next()?.parse().ok()?,
+            sequence: parts.next()?.parse().ok()?,
+        };
+        match parts.next() {
+            None => Some(reference),
+            Some(_) => None,
         }
     }
 
-    #[duplicate_item(
-        method_name attribute_name;
-        [with_md5]    [md5];
-        [with_name]   [name];
-        [with_inode]  [inode];
-        [with_mode]   [mode_as_string];
-    )]
-    pub fn method_name(mut self, attribute_name: &str) -> Self {
-        self.attribute_name = attribute_name.to_owned();
-        self
+    /// returns the inode column in a canonical form, so that values written
+    /// by different tools can be compared.
+    ///
+    /// Surrounding whitespace and leading zeros of every numeric component
+    /// are removed, so that NTFS references are always written as
+    /// `entry-attr-seq`. A reference without sequence (`entry-attr`) gets the
+    /// default sequence `1`, so that e.g. `87915-128` and `87915-128-1` are
+    /// equivalent, but `87915-128-2` is not. An explicit `:$DATA` suffix,
+    /// which some tools append to denote the unnamed data stream, is removed
+    /// as well. Values which do not consist of numbers only are returned
+    /// without further changes.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf1 = Bodyfile3Line::new().with_inode("87915-128-1");
+    /// let bf2 = Bodyfile3Line::new().with_inode("087915-0128-01:$DATA");
+    /// assert_eq!(bf1.canonical_inode(), "87915-128-1");
+    /// assert_eq!(bf1.canonical_inode(), bf2.canonical_inode());
+    ///
+    /// let bf3 = Bodyfile3Line::new().with_inode("87915-128");
+    /// assert_eq!(bf1.canonical_inode(), bf3.canonical_inode());
+    /// let bf4 = Bodyfile3Line::new().with_inode("87915-128-2");
+    /// assert_ne!(bf1.canonical_inode(), bf4.canonical_inode());
+    ///
+    /// let bf = Bodyfile3Line::new().with_inode(" 00042 ");
+    /// assert_eq!(bf.canonical_inode(), "42");
+    ///
+    /// let bf = Bodyfile3Line::new().with_inode("3a1f0");
+    /// assert_eq!(bf.canonical_inode(), "3a1f0");
+    /// ```
+    pub fn canonical_inode(&self) -> String {
+        let inode = self.inode.trim();
+        let inode = inode.strip_suffix(":$DATA").unwrap_or(inode);
+        let mut parts: Option<Vec<u64>> = inode
+            .split('-')
+            .map(|part| match part.bytes().all(|b| b.is_ascii_digit()) {
+                true => part.parse().ok(),
+                false => None,
+            })
+            .collect();
+        if let Some(parts) = parts.as_mut().filter(|parts| parts.len() == 2) {
+            parts.push(1);
+        }
+        match parts {
+            Some(parts) => parts
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join("-"),
+            None => inode.to_owned(),
+        }
+    }
+
+    /// merges the values of `other` into this line, if both lines describe
+    /// the same file (see [`Bodyfile3Line::identity_key`]).
+    ///
+    /// Timestamps which are unset (`-1`) are taken from `other`. If both
+    /// lines have different values for the same timestamp, the value of
+    /// this line is kept and the timestamp kind is contained in the result.
+    /// `md5`, `size` and `mode` are taken from `other` if they have their
+    /// default values in this line. Use [`Bodyfile3Config::merge`] if the
+    /// `md5` column uses another placeholder than `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let mut bf = Bodyfile3Line::new().with_name("/tmp/a").with_mtime(1);
+    /// let other = Bodyfile3Line::new().with_name("/tmp/a").with_crtime(2).with_size(42);
+    /// let conflicts = bf.merge(&other).unwrap();
+    /// assert!(conflicts.is_empty());
+    /// assert_eq!(bf.to_string(), "0|/tmp/a|0||0|0|42|-1|1|-1|2");
+    /// ```
+    pub fn merge(&mut self, other: &Bodyfile3Line) -> Result<Vec<TimestampKind>, Bodyfile3MergeError> {
+        Bodyfile3Config::default().merge(self, other)
+    }
+
+    /// sets the timestamp of the specified kind
+    pub fn set_timestamp(&mut self, kind: TimestampKind, value: i64) {
+        match kind {
+            TimestampKind::Atime => self.atime = value,
+            TimestampKind::Mtime => self.mtime = value,
+            TimestampKind::Ctime => self.ctime = value,
+            TimestampKind::Crtime => self.crtime = value,
+        }
+    }
+
+    /// returns the name with all backslashes converted to forward slashes
+    /// and an uppercased drive letter. The stored name is not modified.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("c:\\Windows\\System32");
+    /// assert_eq!(bf.normalized_name(), "C:/Windows/System32");
+    /// assert_eq!(bf.get_name(), "c:\\Windows\\System32");
+    /// ```
+    pub fn normalized_name(&self) -> String {
+        let mut name = self.name.replace('\\', "/");
+        let mut chars = name.chars();
+        if let (Some(drive), Some(':')) = (chars.next(), chars.next()) {
+            if drive.is_ascii_lowercase() {
+                name.replace_range(..1, &drive.to_ascii_uppercase().to_string());
+            }
+        }
+        name
+    }
+
+    /// returns the path of the file and the name of the alternate data stream,
+    /// if the name references an NTFS alternate data stream (e.g.
+    /// `file.txt:Zone.Identifier`). The colon of a drive letter is not
+    /// considered as a stream separator.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:/Users/jdoe/Downloads/setup.exe");
+    /// assert_eq!(bf.alternate_data_stream(), None);
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:/Users/jdoe/Downloads/setup.exe:Zone.Identifier");
+    /// assert_eq!(
+    ///     bf.alternate_data_stream(),
+    ///     Some(("C:/Users/jdoe/Downloads/setup.exe", "Zone.Identifier"))
+    /// );
+    /// ```
+    pub fn alternate_data_stream(&self) -> Option<(&str, &str)> {
+        let start = self.name.rfind(['/', '\\']).map_or(0, |idx| idx + 1);
+        let file_name = &self.name[start..];
+        let skip = match file_name.as_bytes() {
+            [drive, b':', ..] if start == 0 && drive.is_ascii_alphabetic() => 2,
+            _ => 0,
+        };
+        let separator = start + skip + file_name[skip..].find(':')?;
+        let (path, stream) = (&self.name[..separator], &self.name[separator + 1..]);
+        if separator == start || stream.is_empty() {
+            None
+        } else {
+            Some((path, stream))
+        }
+    }
+
+    /// returns `true` if the name carries a deletion marker of TSK, i.e. a
+    /// `(deleted)` (or `(deleted-realloc)`) at the beginning or the end of
+    /// the name or a leading `* `
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("/tmp/payload.sh (deleted)");
+    /// assert!(bf.is_deleted());
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("/tmp/notes.txt");
+    /// assert!(!bf.is_deleted());
+    /// ```
+    pub fn is_deleted(&self) -> bool {
+        self.clean_name().len() != self.name.len()
+    }
+
+    /// returns the name without the deletion markers which are detected by
+    /// [`Bodyfile3Line::is_deleted`]
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("/tmp/payload.sh (deleted)");
+    /// assert_eq!(bf.clean_name(), "/tmp/payload.sh");
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("* /tmp/payload.sh");
+    /// assert_eq!(bf.clean_name(), "/tmp/payload.sh");
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("/tmp/notes.txt");
+    /// assert_eq!(bf.clean_name(), "/tmp/notes.txt");
+    /// ```
+    pub fn clean_name(&self) -> &str {
+        const MARKERS: [&str; 2] = ["(deleted)", "(deleted-realloc)"];
+
+        let mut name = self.name.as_str();
+        if let Some(stripped) = name.strip_prefix("* ") {
+            name = stripped;
+        }
+        for marker in MARKERS {
+            if let Some(stripped) = name.strip_prefix(marker) {
+                name = stripped.trim_start();
+            }
+            if let Some(stripped) = name.strip_suffix(marker) {
+                name = stripped.trim_end();
+            }
+        }
+        name
+    }
+
+    /// returns the type of the entry. The type letter of the mode column
+    /// (e.g. the `d` in `d/drwxr-xr-x`) takes precedence. Only if the mode
+    /// column is empty or the type is unknown (`-`), a type marker at the
+    /// beginning of the name (e.g. `d/` in `d//etc`), which is added by some
+    /// tools, is used. Names without a known marker result in
+    /// [`EntryType::Other`].
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, EntryType};
+    /// use std::convert::TryFrom;
+    ///
+    /// let bf = Bodyfile3Line::try_from("0|/etc|12|d/drwxr-xr-x|0|0|4096|-1|1|-1|-1").unwrap();
+    /// assert_eq!(bf.entry_type(), EntryType::Directory);
+    ///
+    /// let bf = Bodyfile3Line::try_from("0|/etc/passwd|13|r/rrw-r--r--|0|0|42|-1|1|-1|-1").unwrap();
+    /// assert_eq!(bf.entry_type(), EntryType::File);
+    ///
+    /// // the marker in the name is used if the mode column is empty
+    /// let bf = Bodyfile3Line::try_from("0|d//etc|12||0|0|4096|-1|1|-1|-1").unwrap();
+    /// assert_eq!(bf.entry_type(), EntryType::Directory);
+    ///
+    /// // the mode column wins if both disagree
+    /// let bf = Bodyfile3Line::try_from("0|d//etc/passwd|13|r/rrw-r--r--|0|0|42|-1|1|-1|-1").unwrap();
+    /// assert_eq!(bf.entry_type(), EntryType::File);
+    /// ```
+    pub fn entry_type(&self) -> EntryType {
+        let from_mode = self.mode_as_string.chars().next().and_then(EntryType::from_marker);
+        let from_name = || match self.name.as_bytes() {
+            [marker @ (b'r' | b'd' | b'l'), b'/', ..] => EntryType::from_marker(*marker as char),
+            _ => None,
+        };
+        from_mode.or_else(from_name).unwrap_or(EntryType::Other)
+    }
+
+    /// returns the category of the file, which is derived from the
+    /// extension of its name
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, FileCategory};
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:/Windows/notepad.exe");
+    /// assert_eq!(bf.category(), FileCategory::Executable);
+    /// ```
+    pub fn category(&self) -> FileCategory {
+        FileCategory::from_name(&self.name)
+    }
+
+    /// returns the depth of the file in the directory tree, which is the
+    /// number of path separators in its name. `/` and `\` are both treated
+    /// as separators, consecutive separators are counted once and a trailing
+    /// separator is ignored. A file in the root directory has a depth of 1.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// assert_eq!(Bodyfile3Line::new().with_name("/etc/passwd").path_depth(), 2);
+    /// assert_eq!(Bodyfile3Line::new().with_name("C:\\Windows\\notepad.exe").path_depth(), 2);
+    /// assert_eq!(Bodyfile3Line::new().with_name("/tmp//a/").path_depth(), 2);
+    /// ```
+    pub fn path_depth(&self) -> usize {
+        self.name
+            .trim_end_matches(['/', '\\'])
+            .split(['/', '\\'])
+            .skip(1)
+            .filter(|component| !component.is_empty())
+            .count()
+    }
+
+    /// returns the algorithm of the hash in the `md5` column, which is derived
+    /// from its length. The placeholder `0` results in [`HashAlgo::None`].
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, HashAlgo};
+    ///
+    /// let bf = Bodyfile3Line::new().with_md5("b1946ac92492d2347c6235b4d2611184");
+    /// assert_eq!(bf.hash_algorithm(), HashAlgo::Md5);
+    ///
+    /// let bf = Bodyfile3Line::new().with_md5("f572d396fae9206628714fb2ce00f72e94f2258f");
+    /// assert_eq!(bf.hash_algorithm(), HashAlgo::Sha1);
+    ///
+    /// let bf = Bodyfile3Line::new()
+    ///     .with_md5("5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03");
+    /// assert_eq!(bf.hash_algorithm(), HashAlgo::Sha256);
+    ///
+    /// assert_eq!(Bodyfile3Line::new().hash_algorithm(), HashAlgo::None);
+    /// ```
+    pub fn hash_algorithm(&self) -> HashAlgo {
+        HashAlgo::from_hex(&self.md5)
+    }
+
+    /// checks all columns and returns every issue which has been found, in
+    /// the order of the columns. In contrast to parsing, this does not stop
+    /// at the first issue, and it can be used for lines which have been
+    /// built programmatically. A valid line results in an empty vec.
+    ///
+    /// The following is checked:
+    ///
+    ///  - the `md5` column contains either the placeholder `0` or a MD5, SHA1
+    ///    or SHA256 hash ([`Bodyfile3ParserError::IllegalHash`]; use
+    ///    [`Bodyfile3Config::validate`] if another placeholder is used),
+    ///  - the mode is empty or uses the format of TSK
+    ///    ([`Bodyfile3ParserError::IllegalMode`]), and
+    ///  - every timestamp is either the sentinel `-1` or a valid unix
+    ///    timestamp (e.g. [`Bodyfile3ParserError::IllegalATime`]).
+    ///
+    /// Values which are valid, but implausible, are reported by
+    /// [`Bodyfile3Line::warnings`] instead.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_mode("r/rrwxrwxrwx").with_mtime(1609459200);
+    /// assert!(bf.validate().is_empty());
+    ///
+    /// let bf = bf.with_md5("not a hash").with_crtime(-5);
+    /// let issues: Vec<_> = bf.validate().iter().map(|e| e.to_string()).collect();
+    /// assert_eq!(issues, vec!["IllegalHash", "IllegalCRTime"]);
+    /// ```
+    pub fn validate(&self) -> Vec<Bodyfile3ParserError> {
+        Bodyfile3Config::default().validate(self)
+    }
+
+    /// returns all values of the line which are valid, but implausible. In
+    /// contrast to [`Bodyfile3Line::validate`], these do not prevent the line
+    /// from being used.
+    ///
+    /// Currently, timestamps after the year 3000 are reported as
+    /// [`Bodyfile3Warning::SuspiciousTimestampUnit`], because they usually
+    /// are timestamps in milliseconds which have been stored as seconds.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3Warning, TimestampKind};
+    ///
+    /// let bf = Bodyfile3Line::new().with_atime(1609459200).with_mtime(1609459200000);
+    /// assert_eq!(
+    ///     bf.warnings(),
+    ///     vec![Bodyfile3Warning::SuspiciousTimestampUnit {
+    ///         kind: TimestampKind::Mtime,
+    ///         timestamp: 1609459200000
+    ///     }]
+    /// );
+    /// ```
+    pub fn warnings(&self) -> Vec<Bodyfile3Warning> {
+        TimestampKind::ALL
+            .into_iter()
+            .filter_map(|kind| {
+                let timestamp = self.get_timestamp(kind);
+                (timestamp >= Bodyfile3Warning::LATEST_PLAUSIBLE_TIMESTAMP)
+                    .then_some(Bodyfile3Warning::SuspiciousTimestampUnit { kind, timestamp })
+            })
+            .collect()
+    }
+
+    /// returns the MACB string (e.g. `m.c.`) of all timestamps which are
+    /// equal to `timestamp`. See [`MacbFlags`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_mtime(1577836800).with_atime(1577836801);
+    /// assert_eq!(bf.to_macb_string(1577836800), "m...");
+    /// assert_eq!(bf.to_macb_string(1577836801), ".a..");
+    /// ```
+    pub fn to_macb_string(&self, timestamp: i64) -> String {
+        MacbFlags::from_line_at_time(self, timestamp).to_string()
+    }
+
+    /// replaces the user name in profile paths (e.g. `C:/Users/jdoe/...` or
+    /// `/home/jdoe/...`) by a pseudonym like `user001`. The same user name
+    /// (compared case-insensitively) is always replaced by the same
+    /// pseudonym, as long as the same `mapping` is used. All other parts of
+    /// the path, including the drive letter and the path separators, are kept.
+    ///
+    /// Well-known profile directories like `Public` or `Default` are not
+    /// replaced.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut mapping = HashMap::new();
+    /// let mut bf = Bodyfile3Line::new().with_name("C:\\Users\\jdoe\\NTUSER.DAT");
+    /// bf.redact_users(&mut mapping);
+    /// assert_eq!(bf.get_name(), "C:\\Users\\user001\\NTUSER.DAT");
+    /// ```
+    pub fn redact_users(&mut self, mapping: &mut HashMap<String, String>) {
+        let mut name = String::with_capacity(self.name.len());
+        let mut in_profiles_dir = false;
+        for part in self.name.split_inclusive(['/', '\\']) {
+            let component = part.trim_end_matches(['/', '\\']);
+            if in_profiles_dir && !component.is_empty() && !Self::is_well_known_profile(component) {
+                let next_id = mapping.len() + 1;
+                let pseudonym = mapping
+                    .entry(component.to_lowercase())
+                    .or_insert_with(|| format!("user{next_id:03}"));
+                name.push_str(pseudonym);
+                name.push_str(&part[component.len()..]);
+            } else {
+                name.push_str(part);
+            }
+            in_profiles_dir = Self::is_profiles_dir(component);
+        }
+        self.set_name(name);
+    }
+
+    /// replaces the hash in the `md5` column by its HMAC-SHA256, which is
+    /// keyed with `secret`. Hashes are compared case-insensitively, so that
+    /// equal hashes are always replaced by the same value, as long as the
+    /// same `secret` is used. This allows to check whether a file is
+    /// contained in a shared timeline without revealing the real hashes.
+    /// The placeholder `0` is kept; use [`Bodyfile3Config::anonymize_hashes`]
+    /// if another placeholder is used.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let mut bf = Bodyfile3Line::new().with_md5("b1946ac92492d2347c6235b4d2611184");
+    /// bf.anonymize_hashes(b"secret");
+    /// assert_eq!(bf.get_md5().len(), 64);
+    /// assert_ne!(bf.get_md5(), "b1946ac92492d2347c6235b4d2611184");
+    ///
+    /// let mut bf = Bodyfile3Line::new();
+    /// bf.anonymize_hashes(b"secret");
+    /// assert_eq!(bf.get_md5(), "0");
+    /// ```
+    pub fn anonymize_hashes(&mut self, secret: &[u8]) {
+        Bodyfile3Config::default().anonymize_hashes(self, secret)
+    }
+
+    /// removes a leading path `prefix` (e.g. the mount point which has been
+    /// passed to `fls -m`) from the name. The prefix is only removed if it is
+    /// followed by a path separator or if it is the complete name, so
+    /// `/mnt/c` is not removed from `/mnt/cdrom`. Returns `true` if the name
+    /// has been changed.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let mut bf = Bodyfile3Line::new().with_name("/mnt/c/Windows/notepad.exe");
+    /// assert!(bf.strip_name_prefix("/mnt/c"));
+    /// assert_eq!(bf.get_name(), "/Windows/notepad.exe");
+    ///
+    /// let mut bf = Bodyfile3Line::new().with_name("/mnt/cdrom/setup.exe");
+    /// assert!(!bf.strip_name_prefix("/mnt/c"));
+    /// assert_eq!(bf.get_name(), "/mnt/cdrom/setup.exe");
+    /// ```
+    pub fn strip_name_prefix(&mut self, prefix: &str) -> bool {
+        let prefix = prefix.trim_end_matches(['/', '\\']);
+        match self.name.strip_prefix(prefix) {
+            Some("") => {
+                self.set_name("/".to_owned());
+                true
+            }
+            Some(rest) if rest.starts_with(['/', '\\']) => {
+                self.set_name(rest.to_owned());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// returns the name of the user whose profile directory contains the
+    /// file (e.g. `jdoe` for `C:/Users/jdoe/NTUSER.DAT` or `/home/jdoe/.bashrc`).
+    /// Files in well-known profile directories like `Public` or `Default`
+    /// and files outside of profile directories belong to no user.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:\\Users\\jdoe\\NTUSER.DAT");
+    /// assert_eq!(bf.profile_user(), Some("jdoe"));
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("/home/alice/.bashrc");
+    /// assert_eq!(bf.profile_user(), Some("alice"));
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:/Users/Public/Desktop/desktop.ini");
+    /// assert_eq!(bf.profile_user(), None);
+    ///
+    /// let bf = Bodyfile3Line::new().with_name("C:/Windows/notepad.exe");
+    /// assert_eq!(bf.profile_user(), None);
+    /// ```
+    pub fn profile_user(&self) -> Option<&str> {
+        let mut components = self.name.split(['/', '\\']).filter(|c| !c.is_empty());
+        components.find(|component| Self::is_profiles_dir(component))?;
+        components
+            .next()
+            .filter(|user| !Self::is_well_known_profile(user))
+    }
+
+    fn is_profiles_dir(component: &str) -> bool {
+        ["Users", "Documents and Settings", "home"]
+            .iter()
+            .any(|dir| component.eq_ignore_ascii_case(dir))
     }
 
-    #[duplicate_item(
-        method_name attribute_name attribute_type;
-        [with_owned_md5]    [md5]            [String];
-        [with_owned_name]   [name]           [String];
-        [with_owned_inode]  [inode]          [String];
-        [with_owned_mode]   [mode_as_string] [String];
-        [with_uid]    [uid]            [u64];
-        [with_gid]    [gid]            [u64];
-        [with_size]   [size]           [u64];
-        [with_atime]  [atime]          [i64];
-        [with_mtime]  [mtime]          [i64];
-        [with_ctime]  [ctime]          [i64];
-        [with_crtime] [crtime]         [i64];
-    )]
-    pub fn method_name(mut self, attribute_name: attribute_type) -> Self {
-        self.attribute_name = attribute_name;
-        self
+    fn is_well_known_profile(component: &str) -> bool {
+        ["Public", "Default", "Default User", "All Users"]
+            .iter()
+            .any(|profile| component.eq_ignore_ascii_case(profile))
+    }
+}
+
+/// reference to an NTFS attribute, as stored by TSK in the inode column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MftReference {
+    /// number of the MFT entry
+    pub entry: u64,
+
+    /// type of the attribute, e.g. `128` for `$DATA`
+    pub attr_type: u32,
+
+    /// the third number of the inode column
+    pub sequence: u32,
+}
+
+/// HMAC as specified in RFC 2104, using SHA-256
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// owned snapshot of all fields of a [`Bodyfile3Line`], which can be
+/// constructed and destructured directly
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Fields, Bodyfile3Line};
+///
+/// let bf = Bodyfile3Line::from(Bodyfile3Fields {
+///     name: "sample.txt".to_owned(),
+///     size: 4,
+///     mtime: 12342,
+///     ..Default::default()
+/// });
+/// assert_eq!(bf.to_string(), "0|sample.txt|0||0|0|4|-1|12342|-1|-1");
+///
+/// let Bodyfile3Fields { name, mtime, .. } = bf.fields();
+/// assert_eq!(name, "sample.txt");
+/// assert_eq!(mtime, 12342);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bodyfile3Fields {
+    pub md5: String,
+    pub name: String,
+    pub inode: String,
+    pub mode_as_string: String,
+    pub uid: u64,
+    pub gid: u64,
+    pub size: u64,
+    pub atime: i64,
+    pub mtime: i64,
+    pub ctime: i64,
+    pub crtime: i64,
+    pub notes: Option<String>,
+}
+
+impl Default for Bodyfile3Fields {
+    /// uses the same default values as [`Bodyfile3Line::new`]
+    fn default() -> Self {
+        Bodyfile3Line::new().fields()
+    }
+}
+
+impl Bodyfile3Line {
+    /// returns a copy of all fields of this line
+    pub fn fields(&self) -> Bodyfile3Fields {
+        Bodyfile3Fields {
+            md5: self.md5.clone(),
+            name: self.name.clone(),
+            inode: self.inode.clone(),
+            mode_as_string: self.mode_as_string.clone(),
+            uid: self.uid,
+            gid: self.gid,
+            size: self.size,
+            atime: self.atime,
+            mtime: self.mtime,
+            ctime: self.ctime,
+            crtime: self.crtime,
+            notes: self.notes.clone(),
+        }
+    }
+}
+
+impl From<Bodyfile3Fields> for Bodyfile3Line {
+    fn from(fields: Bodyfile3Fields) -> Self {
+        Self {
+            md5: fields.md5,
+            name: fields.name,
+            inode: fields.inode,
+            mode_as_string: fields.mode_as_string,
+            uid: fields.uid,
+            gid: fields.gid,
+            size: fields.size,
+            atime: fields.atime,
+            mtime: fields.mtime,
+            ctime: fields.ctime,
+            crtime: fields.crtime,
+            notes: fields.notes,
+            raw_name: None,
+        }
     }
 }
 
 impl fmt::Display for Bodyfile3Line {
-    /// exports the line to the format parsable by, eg. `mactime`
+    /// exports the line to the format parsable by, eg. `mactime`. The notes
+    /// are only written if they have been set.
     ///
     /// # Example
     /// ```
@@ -134,13 +1208,30 @@ impl fmt::Display for Bodyfile3Line {
             self.mtime,
             self.ctime,
             self.crtime
-        )
+        )?;
+        match &self.notes {
+            Some(notes) => write!(f, "|{notes}"),
+            None => Ok(()),
+        }
     }
 }
 
 #[derive(Debug)]
 pub enum Bodyfile3ParserError {
-    /// indicates that number of columns is not valid
+    /// indicates that number of columns is not valid. This is no longer
+    /// returned by the parser, which reports
+    /// [`TooFewColumns`](Self::TooFewColumns) or
+    /// [`TooManyColumns`](Self::TooManyColumns) instead. The variant is only
+    /// kept so that existing matches still compile; use
+    /// [`Bodyfile3ParserError::is_wrong_number_of_columns`] to handle both.
+    #[deprecated(
+        since = "0.8.1",
+        note = "match TooFewColumns and TooManyColumns, or use is_wrong_number_of_columns()"
+    )]
+    WrongNumberOfColumns,
+
+    /// indicates that the line has less than 11 columns, i.e. some column is
+    /// missing. `found` is the number of columns of the line.
     ///
     /// # Examples
     /// ```
@@ -149,12 +1240,55 @@ pub enum Bodyfile3ParserError {
     /// use std::convert::TryFrom;
     /// use matches::assert_matches;
     ///
-    /// assert_matches!(Bodyfile3Line::try_from(""), Err(Bodyfile3ParserError::WrongNumberOfColumns));
-    /// assert_matches!(Bodyfile3Line::try_from("|||||||||"), Err(Bodyfile3ParserError::WrongNumberOfColumns));
+    /// assert_matches!(Bodyfile3Line::try_from(""), Err(Bodyfile3ParserError::TooFewColumns { found: 1 }));
+    /// assert_matches!(Bodyfile3Line::try_from("|||||||||"), Err(Bodyfile3ParserError::TooFewColumns { found: 10 }));
     /// assert_matches!(Bodyfile3Line::try_from("0||0||0|0|0|-1|-1|-1|-1"), Ok(_));
     /// assert_matches!(Bodyfile3Line::try_from("0|{\"activity_id\":null,\"channel_name\":\"Microsoft-Windows-PowerShell/Operational\",\"custom_data\":{\"EventData\":{\"ContextInfo\":\"        Severity = Warning\\r\\n        Host Name = ConsoleHost\\r\\n        Host Version = 4.0\\r\\n        Host ID = 5635c559-63c7-4bdc-8bb6-e2aa0448e7b9\\r\\n        Host Application = powershell get-VMNetworkAdapter -ManagementOS | fl | out-file -encoding ASCII VMNetworkAdapterInstances.txt\\r\\n        Engine Version = 4.0\\r\\n        Runspace ID = d315d83a-8923-4530-9553-e63551c33cbc\\r\\n        Pipeline ID = 1\\r\\n        Command Name = \\r\\n        Command Type = Script\\r\\n        Script Name = \\r\\n        Command Path = \\r\\n        Sequence Number = 15\\r\\n        User = TEST\\\\SYSTEM\\r\\n        Shell ID = Microsoft.PowerShell\\r\\n\",\"Payload\":\"Error Message = Could not load file or assembly 'System.Data, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089' or one of its dependencies. The media is write protected. (Exception from HRESULT: 0x80070013)\\r\\nFully Qualified Error ID = System.IO.FileLoadException\\r\\n\",\"UserData\":\"\"}},\"event_id\":4100,\"event_record_id\":2424468,\"provider_name\":\"Microsoft-Windows-PowerShell\"}|0||0|0|0|-1|1645178371|-1|-1"), Ok(_));
     /// ```
-    WrongNumberOfColumns,
+    TooFewColumns { found: usize },
+
+    /// indicates that the line has more than 11 columns. This is only
+    /// reported if surplus columns have been disallowed using
+    /// [`Bodyfile3ParserOptions::with_strict_columns`]; otherwise the surplus
+    /// columns are part of the name. `found` is the number of columns of the
+    /// line.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate matches;
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError, Bodyfile3ParserOptions};
+    /// use matches::assert_matches;
+    ///
+    /// let options = Bodyfile3ParserOptions::default().with_strict_columns(true);
+    /// assert_matches!(Bodyfile3Line::parse("0|a|b|0||0|0|0|-1|-1|-1|-1", &options), Err(Bodyfile3ParserError::TooManyColumns { found: 12 }));
+    /// assert_matches!(Bodyfile3Line::try_from("0|a|b|0||0|0|0|-1|-1|-1|-1"), Ok(_));
+    /// ```
+    TooManyColumns { found: usize },
+
+    /// indicates that the mode does not match the format used by TSK (e.g.
+    /// `r/rrwxrwxrwx`). The mode is only validated if this has been enabled
+    /// using [`Bodyfile3ParserOptions::with_mode_validation`].
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate matches;
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError, Bodyfile3ParserOptions};
+    /// use matches::assert_matches;
+    ///
+    /// let options = Bodyfile3ParserOptions::default().with_mode_validation(true);
+    /// assert_matches!(Bodyfile3Line::parse("0||0|r/rrwxrwxrwx|0|0|0|-1|-1|-1|-1", &options), Ok(_));
+    /// assert_matches!(Bodyfile3Line::parse("0||0|d/dr-xr-x---|0|0|0|-1|-1|-1|-1", &options), Ok(_));
+    /// assert_matches!(Bodyfile3Line::parse("0||0||0|0|0|-1|-1|-1|-1", &options), Ok(_));
+    /// assert_matches!(Bodyfile3Line::parse("0||0|rrwxrwxrwx|0|0|0|-1|-1|-1|-1", &options), Err(Bodyfile3ParserError::IllegalMode));
+    /// assert_matches!(Bodyfile3Line::parse("0||0|b.txt|0|0|0|-1|-1|-1|-1", &options), Err(Bodyfile3ParserError::IllegalMode));
+    /// assert_matches!(Bodyfile3Line::try_from("0||0|b.txt|0|0|0|-1|-1|-1|-1"), Ok(_));
+    /// ```
+    IllegalMode,
+
+    /// indicates that the `md5` column contains neither the placeholder `0`
+    /// nor a MD5, SHA1 or SHA256 hash. This is only reported by
+    /// [`Bodyfile3Line::validate`], because the parser accepts any value.
+    IllegalHash,
 
     /// indicates that the uid is syntactically invalid
     ///
@@ -267,6 +1401,16 @@ pub enum Bodyfile3ParserError {
     /// assert_eq!(*valid_bf.get_crtime(), 5);
     /// ```
     IllegalCRTime,
+
+    /// indicates that a line has too few columns and could not be joined
+    /// with the following lines to a complete line. This is reported by
+    /// readers which try to reassemble names containing line breaks.
+    IncompleteMultilineName,
+
+    /// indicates that a line is not valid UTF-8. This is reported by readers
+    /// which decode strictly, and by [`Bodyfile3LineBytes`](super::Bodyfile3LineBytes)
+    /// if any column other than the name is not valid UTF-8.
+    InvalidUtf8,
 }
 
 /// implements `Display` for this enum
@@ -286,11 +1430,117 @@ impl fmt::Display for Bodyfile3ParserError {
 
 impl Error for Bodyfile3ParserError {}
 
+impl Bodyfile3ParserError {
+    /// returns the name of the error, without any details, so that errors
+    /// can be counted per kind
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError};
+    ///
+    /// let error = Bodyfile3Line::try_from("0|a|b").unwrap_err();
+    /// assert_eq!(error.to_string(), "TooFewColumns { found: 3 }");
+    /// assert_eq!(error.kind(), "TooFewColumns");
+    /// assert_eq!(Bodyfile3ParserError::IllegalUid.kind(), "IllegalUid");
+    /// ```
+    pub fn kind(&self) -> &'static str {
+        match self {
+            #[allow(deprecated)]
+            Self::WrongNumberOfColumns => "WrongNumberOfColumns",
+            Self::TooFewColumns { .. } => "TooFewColumns",
+            Self::TooManyColumns { .. } => "TooManyColumns",
+            Self::IllegalMode => "IllegalMode",
+            Self::IllegalHash => "IllegalHash",
+            Self::IllegalUid => "IllegalUid",
+            Self::IllegalGid => "IllegalGid",
+            Self::IllegalSize => "IllegalSize",
+            Self::IllegalATime => "IllegalATime",
+            Self::IllegalMTime => "IllegalMTime",
+            Self::IllegalCTime => "IllegalCTime",
+            Self::IllegalCRTime => "IllegalCRTime",
+            Self::IncompleteMultilineName => "IncompleteMultilineName",
+            Self::InvalidUtf8 => "InvalidUtf8",
+        }
+    }
+}
+
+/// value of a line which is valid, but implausible (see
+/// [`Bodyfile3Line::warnings`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bodyfile3Warning {
+    /// indicates that a timestamp is after the year 3000, which suggests
+    /// that it is in milliseconds instead of seconds
+    SuspiciousTimestampUnit { kind: TimestampKind, timestamp: i64 },
+}
+
+impl Bodyfile3Warning {
+    /// 3000-01-01T00:00:00Z. Timestamps in milliseconds are far beyond this
+    /// value for all dates after 1971
+    pub const LATEST_PLAUSIBLE_TIMESTAMP: i64 = 32503680000;
+}
+
+impl fmt::Display for Bodyfile3Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SuspiciousTimestampUnit { kind, timestamp } => write!(
+                f,
+                "SuspiciousTimestampUnit: {kind:?} {timestamp} is after the year 3000, it may be in milliseconds instead of seconds"
+            ),
+        }
+    }
+}
+
+impl Bodyfile3ParserError {
+    /// returns `true` if the line has too few or too many columns. This
+    /// replaces the deprecated `WrongNumberOfColumns` variant.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// assert!(Bodyfile3Line::try_from("0|sample.txt|0||0").unwrap_err().is_wrong_number_of_columns());
+    /// assert!(!Bodyfile3Line::try_from("0|sample.txt|0||x|0|0|-1|-1|-1|-1").unwrap_err().is_wrong_number_of_columns());
+    /// ```
+    #[allow(deprecated)]
+    pub fn is_wrong_number_of_columns(&self) -> bool {
+        matches!(
+            self,
+            Self::WrongNumberOfColumns | Self::TooFewColumns { .. } | Self::TooManyColumns { .. }
+        )
+    }
+}
+
+/// error which is returned by [`Bodyfile3Line::merge`]
+#[derive(Debug)]
+pub enum Bodyfile3MergeError {
+    /// the lines describe different files, i.e. their identity keys differ
+    IdentityMismatch,
+}
+
+impl fmt::Display for Bodyfile3MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for Bodyfile3MergeError {}
+
 impl TryFrom<&str> for Bodyfile3Line {
     type Error = Bodyfile3ParserError;
 
     /// parses a bodyfile line
     ///
+    /// The name is the only column which may contain pipe symbols, e.g. if it
+    /// contains JSON data. Such a line is always split correctly if
+    ///
+    ///  - the `md5` column contains no pipe symbol, and
+    ///  - none of the nine columns from `inode` to `crtime` contains a pipe symbol,
+    ///
+    /// which is true for all lines written by TSK and by the tools of this
+    /// crate. If missing timestamps are padded (see
+    /// [`Bodyfile3Line::try_from_lenient`]), only lines which have all
+    /// columns or whose name contains no pipe symbol are split correctly.
+    ///
     /// # Example
     /// ```
     /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError};
@@ -308,48 +1558,373 @@ impl TryFrom<&str> for Bodyfile3Line {
     /// assert_eq!(*bf_line.get_ctime(), 8);
     /// assert_eq!(*bf_line.get_crtime(), 9);
     /// ```
-
     fn try_from(line: &str) -> Result<Self, Self::Error> {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() < 11 {
-            return Err(Self::Error::WrongNumberOfColumns);
-        }
-
-        let name_chunks = parts.len() - 10;
-        let md5 = parts[0];
-        let name = parts[1..name_chunks + 1].join("|");
-        let inode = parts[2 + name_chunks - 1];
-        let mode = parts[3 + name_chunks - 1];
-        let uid = str::parse::<u64>(parts[4 + name_chunks - 1]).or(Err(Self::Error::IllegalUid))?;
-        let gid = str::parse::<u64>(parts[5 + name_chunks - 1]).or(Err(Self::Error::IllegalGid))?;
-
-        let size =
-            str::parse::<u64>(parts[6 + name_chunks - 1]).or(Err(Self::Error::IllegalSize))?;
-        let atime =
-            str::parse::<i64>(parts[7 + name_chunks - 1]).or(Err(Self::Error::IllegalATime))?;
-        if atime < -1 {
-            return Err(Self::Error::IllegalATime);
-        }
-        let mtime =
-            str::parse::<i64>(parts[8 + name_chunks - 1]).or(Err(Self::Error::IllegalMTime))?;
-        if mtime < -1 {
-            return Err(Self::Error::IllegalMTime);
-        }
-        let ctime =
-            str::parse::<i64>(parts[9 + name_chunks - 1]).or(Err(Self::Error::IllegalCTime))?;
-        if ctime < -1 {
-            return Err(Self::Error::IllegalCTime);
-        }
-        let crtime =
-            str::parse::<i64>(parts[10 + name_chunks - 1]).or(Err(Self::Error::IllegalCRTime))?;
-        if crtime < -1 {
-            return Err(Self::Error::IllegalCRTime);
+        Self::parse(line, &Bodyfile3ParserOptions::default())
+    }
+}
+
+/// options which control how strict the bodyfile parser is. The default
+/// options are used by `TryFrom<&str>`.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Bodyfile3ParserOptions {
+    allow_negative_timestamps: bool,
+    pad_missing_timestamps: bool,
+    allow_hex_size: bool,
+    validate_mode: bool,
+    trim_numbers: bool,
+    strict_columns: bool,
+    normalize_md5: bool,
+    notes_column: bool,
+    strip_checksum: bool,
+}
+
+impl Bodyfile3ParserOptions {
+    /// accept timestamps below `-1` as real (pre-1970) timestamps. `-1`
+    /// always remains the sentinel for an unset timestamp.
+    pub fn with_negative_timestamps(mut self, allow: bool) -> Self {
+        self.allow_negative_timestamps = allow;
+        self
+    }
+
+    /// accept lines with 7 to 10 columns and treat the missing trailing
+    /// timestamps as unset (`-1`)
+    pub fn with_padding(mut self, pad: bool) -> Self {
+        self.pad_missing_timestamps = pad;
+        self
+    }
+
+    /// accept sizes which are written as hexadecimal value with a `0x`
+    /// prefix (e.g. `0x4d2`). Decimal values are accepted as well.
+    pub fn with_hex_size(mut self, allow: bool) -> Self {
+        self.allow_hex_size = allow;
+        self
+    }
+
+    /// reject lines whose mode does not look like a TSK mode string, which
+    /// consists of a type character, a `/`, another type character and nine
+    /// permission characters (e.g. `r/rrwxrwxrwx`). An empty mode is still
+    /// accepted. A malformed mode is a strong hint that the columns of the
+    /// line have not been split correctly, e.g. because the name contains a
+    /// pipe symbol and some other column is missing.
+    pub fn with_mode_validation(mut self, validate: bool) -> Self {
+        self.validate_mode = validate;
+        self
+    }
+
+    /// accept numeric columns (`uid`, `gid`, `size` and the timestamps) which
+    /// are padded with leading or trailing spaces or tabs (e.g. `   500`)
+    pub fn with_number_trimming(mut self, trim: bool) -> Self {
+        self.trim_numbers = trim;
+        self
+    }
+
+    /// reject lines with more than 11 columns instead of treating the surplus
+    /// columns as part of the name. Use this if names are known to contain no
+    /// pipe symbols, to detect lines with an unescaped pipe symbol in another
+    /// column.
+    pub fn with_strict_columns(mut self, strict: bool) -> Self {
+        self.strict_columns = strict;
+        self
+    }
+
+    /// convert the `md5` column to lowercase, so that hashes written by
+    /// different tools can be compared as strings. The placeholder `0` is not
+    /// affected. This is only applied by [`Bodyfile3Line::parse`], because
+    /// [`Bodyfile3LineRef`] does not copy the column.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserOptions};
+    ///
+    /// let options = Bodyfile3ParserOptions::default().with_md5_normalization(true);
+    /// let line = "B1946AC92492D2347C6235B4D2611184|hello.txt|0||0|0|6|-1|-1|-1|-1";
+    /// let bf_line = Bodyfile3Line::parse(line, &options).unwrap();
+    /// assert_eq!(bf_line.get_md5(), "b1946ac92492d2347c6235b4d2611184");
+    /// ```
+    pub fn with_md5_normalization(mut self, normalize: bool) -> Self {
+        self.normalize_md5 = normalize;
+        self
+    }
+
+    /// accept a 12th column after `crtime`, which contains free-text notes
+    /// (as written by some forks of mactime). Lines with 11 columns are still
+    /// accepted and have no notes. Lines with more than 11 columns are always
+    /// treated as having notes, so that the notes must not contain a pipe
+    /// symbol, and names with pipe symbols are only split correctly if the
+    /// line has notes. With [`Self::with_strict_columns`], lines with more
+    /// than 12 columns are rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserOptions};
+    ///
+    /// let options = Bodyfile3ParserOptions::default().with_notes_column(true);
+    /// let line = "0|/tmp/a|0||0|0|0|-1|1|-1|-1|copied from USB stick";
+    /// let bf_line = Bodyfile3Line::parse(line, &options).unwrap();
+    /// assert_eq!(bf_line.get_name(), "/tmp/a");
+    /// assert_eq!(bf_line.get_notes().as_deref(), Some("copied from USB stick"));
+    /// assert_eq!(bf_line.to_string(), line);
+    /// ```
+    pub fn with_notes_column(mut self, notes: bool) -> Self {
+        self.notes_column = notes;
+        self
+    }
+
+    /// ignore a trailing checksum column (see
+    /// [`append_checksum`](super::append_checksum)). Without this option, a
+    /// line with a checksum column is rejected, or its columns are shifted
+    /// into the name if the checksum happens to be a valid timestamp.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{append_checksum, Bodyfile3Line, Bodyfile3ParserOptions};
+    ///
+    /// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1";
+    /// let with_checksum = append_checksum(line);
+    /// assert!(Bodyfile3Line::try_from(with_checksum.as_str()).is_err());
+    ///
+    /// let options = Bodyfile3ParserOptions::default().with_checksum_stripping(true);
+    /// let bf_line = Bodyfile3Line::parse(&with_checksum, &options).unwrap();
+    /// assert_eq!(bf_line.to_string(), line);
+    /// ```
+    pub fn with_checksum_stripping(mut self, strip: bool) -> Self {
+        self.strip_checksum = strip;
+        self
+    }
+
+    fn numeric_column<'a>(&self, value: &'a str) -> &'a str {
+        if self.trim_numbers {
+            value.trim_matches(|c: char| c.is_ascii_whitespace())
+        } else {
+            value
+        }
+    }
+}
+
+impl Bodyfile3Line {
+    /// parses a bodyfile line and accepts timestamps before 1970
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_allow_negative("0||0||0|0|0|-2|-1|-1|-1").unwrap();
+    /// assert_eq!(*bf_line.get_atime(), -2);
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_allow_negative("0||0||0|0|0|-1|-1|-1|-1").unwrap();
+    /// assert_eq!(*bf_line.get_atime(), -1);
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_allow_negative("0||0||0|0|0|-1|-1|-1|-2208988800").unwrap();
+    /// assert_eq!(*bf_line.get_crtime(), -2208988800);
+    ///
+    /// // the default parser still rejects negative timestamps
+    /// assert!(Bodyfile3Line::try_from("0||0||0|0|0|-2|-1|-1|-1").is_err());
+    /// ```
+    pub fn try_from_allow_negative(line: &str) -> Result<Self, Bodyfile3ParserError> {
+        Self::parse(
+            line,
+            &Bodyfile3ParserOptions::default().with_negative_timestamps(true),
+        )
+    }
+
+    /// parses a bodyfile line which may lack some of the trailing timestamp
+    /// columns. Missing timestamps are set to `-1`, but at least the columns
+    /// up to and including `size` must be present. Lines which lack some
+    /// columns cannot be split correctly if their name contains pipe symbols.
+    /// Whitespace around numeric columns is ignored, and the `md5` column is
+    /// converted to lowercase.
+    ///
+    /// # Example
+    /// ```
+    /// extern crate matches;
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Bodyfile3ParserError};
+    /// use matches::assert_matches;
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_lenient("0|sample.txt|0||0|0|4|5|6").unwrap();
+    /// assert_eq!(*bf_line.get_size(), 4);
+    /// assert_eq!(*bf_line.get_atime(), 5);
+    /// assert_eq!(*bf_line.get_mtime(), 6);
+    /// assert_eq!(*bf_line.get_ctime(), -1);
+    /// assert_eq!(*bf_line.get_crtime(), -1);
+    ///
+    /// assert_matches!(Bodyfile3Line::try_from_lenient("0|sample.txt|0||0"), Err(Bodyfile3ParserError::TooFewColumns { found: 5 }));
+    /// assert_matches!(Bodyfile3Line::try_from("0|sample.txt|0||0|0|4|5|6"), Err(Bodyfile3ParserError::TooFewColumns { found: 9 }));
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_lenient("0|sample.txt|0||0|0|\t4|5 |6").unwrap();
+    /// assert_eq!(*bf_line.get_size(), 4);
+    /// assert_eq!(*bf_line.get_atime(), 5);
+    ///
+    /// let bf_line = Bodyfile3Line::try_from_lenient("D41D8CD98F00B204E9800998ECF8427E|empty|0||0|0|0").unwrap();
+    /// assert_eq!(bf_line.get_md5(), "d41d8cd98f00b204e9800998ecf8427e");
+    /// ```
+    pub fn try_from_lenient(line: &str) -> Result<Self, Bodyfile3ParserError> {
+        Self::parse(
+            line,
+            &Bodyfile3ParserOptions::default()
+                .with_padding(true)
+                .with_number_trimming(true)
+                .with_md5_normalization(true),
+        )
+    }
+
+    /// checks if `line` is a header line, which contains the column names
+    /// instead of values (e.g. `MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime`).
+    /// Column names are compared case-insensitive.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+    ///
+    /// assert!(Bodyfile3Line::is_header("MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime"));
+    /// assert!(Bodyfile3Line::is_header("md5|name|inode|mode_as_string|UID|GID|size|atime|mtime|ctime|crtime\n"));
+    /// assert!(!Bodyfile3Line::is_header("0|name|inode|mode|0|0|0|-1|-1|-1|-1"));
+    /// assert!(!Bodyfile3Line::is_header("MD5|name|inode|mode|uid|gid|size"));
+    /// ```
+    pub fn is_header(line: &str) -> bool {
+        const COLUMNS: [&[&str]; 11] = [
+            &["md5"],
+            &["name"],
+            &["inode"],
+            &["mode", "mode_as_string"],
+            &["uid"],
+            &["gid"],
+            &["size"],
+            &["atime"],
+            &["mtime"],
+            &["ctime"],
+            &["crtime"],
+        ];
+        let parts: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('|').collect();
+        parts.len() == COLUMNS.len()
+            && parts
+                .iter()
+                .zip(COLUMNS.iter())
+                .all(|(part, names)| names.iter().any(|n| part.eq_ignore_ascii_case(n)))
+    }
+
+    /// parses a bodyfile line using the specified parser options
+    pub fn parse(line: &str, options: &Bodyfile3ParserOptions) -> Result<Self, Bodyfile3ParserError> {
+        let mut bf_line = Bodyfile3LineRef::parse(line, options)?.to_owned();
+        if options.normalize_md5 {
+            bf_line.md5.make_ascii_lowercase();
+        }
+        Ok(bf_line)
+    }
+
+    /// parses a bodyfile line without copying the string columns. See
+    /// [`Bodyfile3LineRef`] for details.
+    pub fn try_parse_ref(line: &str) -> Result<Bodyfile3LineRef<'_>, Bodyfile3ParserError> {
+        Bodyfile3LineRef::parse(line, &Bodyfile3ParserOptions::default())
+    }
+
+    fn parse_size(value: &str, options: &Bodyfile3ParserOptions) -> Option<u64> {
+        let value = options.numeric_column(value);
+        match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(hex) if options.allow_hex_size => u64::from_str_radix(hex, 16).ok(),
+            _ => str::parse::<u64>(value).ok(),
+        }
+    }
+
+    fn is_valid_mode(mode: &str) -> bool {
+        let is_type = |c: &u8| c.is_ascii_alphabetic() || *c == b'-';
+        let is_perm = |c: &u8| b"rwxsStT-".contains(c);
+        match mode.as_bytes() {
+            [] => true,
+            [t1, b'/', t2, perms @ ..] => {
+                is_type(t1) && is_type(t2) && perms.len() == 9 && perms.iter().all(is_perm)
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_timestamp(value: &str, options: &Bodyfile3ParserOptions) -> Option<i64> {
+        let ts = str::parse::<i64>(options.numeric_column(value)).ok()?;
+        if Self::is_valid_timestamp(ts, options.allow_negative_timestamps) {
+            Some(ts)
+        } else {
+            None
         }
+    }
+
+    fn is_valid_timestamp(ts: i64, allow_negative: bool) -> bool {
+        // the value must also be convertible into a date
+        (ts >= -1 || allow_negative) && NaiveDateTime::from_timestamp_opt(ts, 0).is_some()
+    }
+}
+
+/// borrowed view of a bodyfile line, which references the string columns
+/// of the parsed line instead of copying them. This is useful if lines only
+/// need to be inspected or filtered; use [`Bodyfile3LineRef::to_owned`] to
+/// convert the lines which should be kept.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+///
+/// let line = "0|/tmp/a|1234|r/rrwxrwxrwx|0|0|12|-1|1|-1|-1";
+/// let bf_ref = Bodyfile3Line::try_parse_ref(line).unwrap();
+/// assert_eq!(bf_ref.name, "/tmp/a");
+/// assert_eq!(bf_ref.mtime, 1);
+///
+/// let bf = bf_ref.to_owned();
+/// assert_eq!(bf.to_string(), line);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bodyfile3LineRef<'a> {
+    pub md5: &'a str,
+    pub name: &'a str,
+    pub inode: &'a str,
+    pub mode_as_string: &'a str,
+    pub uid: u64,
+    pub gid: u64,
+    pub size: u64,
+    pub atime: i64,
+    pub mtime: i64,
+    pub ctime: i64,
+    pub crtime: i64,
+    pub notes: Option<&'a str>,
+}
+
+/// `md5`, `name`, the nine columns from `inode` to `crtime` and the optional
+/// notes of a line, as returned by [`Bodyfile3LineRef::split_columns`]
+type Columns<'a> = (&'a str, &'a str, [&'a str; 9], Option<&'a str>);
+
+impl<'a> Bodyfile3LineRef<'a> {
+    /// parses a bodyfile line using the specified parser options
+    pub fn parse(line: &'a str, options: &Bodyfile3ParserOptions) -> Result<Self, Bodyfile3ParserError> {
+        let line = if options.strip_checksum {
+            super::strip_checksum(line)
+        } else {
+            line
+        };
+        let (md5, name, tail, notes) = Self::split_columns(line, options)?;
+
+        let inode = tail[0];
+        let mode = tail[1];
+        if options.validate_mode && !Bodyfile3Line::is_valid_mode(mode) {
+            return Err(Bodyfile3ParserError::IllegalMode);
+        }
+        let uid = str::parse::<u64>(options.numeric_column(tail[2]))
+            .or(Err(Bodyfile3ParserError::IllegalUid))?;
+        let gid = str::parse::<u64>(options.numeric_column(tail[3]))
+            .or(Err(Bodyfile3ParserError::IllegalGid))?;
+
+        let size = Bodyfile3Line::parse_size(tail[4], options)
+            .ok_or(Bodyfile3ParserError::IllegalSize)?;
+        let atime = Bodyfile3Line::parse_timestamp(tail[5], options)
+            .ok_or(Bodyfile3ParserError::IllegalATime)?;
+        let mtime = Bodyfile3Line::parse_timestamp(tail[6], options)
+            .ok_or(Bodyfile3ParserError::IllegalMTime)?;
+        let ctime = Bodyfile3Line::parse_timestamp(tail[7], options)
+            .ok_or(Bodyfile3ParserError::IllegalCTime)?;
+        let crtime = Bodyfile3Line::parse_timestamp(tail[8], options)
+            .ok_or(Bodyfile3ParserError::IllegalCRTime)?;
+
         Ok(Self {
-            md5: md5.to_owned(),
-            name: name.to_owned(),
-            inode: inode.to_owned(),
-            mode_as_string: mode.to_owned(),
+            md5,
+            name,
+            inode,
+            mode_as_string: mode,
             uid,
             gid,
             size,
@@ -357,6 +1932,89 @@ impl TryFrom<&str> for Bodyfile3Line {
             mtime,
             ctime,
             crtime,
+            notes,
         })
     }
+
+    /// splits `line` into `md5`, `name`, the nine columns from `inode` to
+    /// `crtime` and the optional notes. The pipe symbols are located with
+    /// `memchr` and the columns are sliced directly from `line`, so that no
+    /// allocation is necessary.
+    fn split_columns(
+        line: &'a str,
+        options: &Bodyfile3ParserOptions,
+    ) -> Result<Columns<'a>, Bodyfile3ParserError> {
+        let bytes = line.as_bytes();
+        let found = memchr::memchr_iter(b'|', bytes).count() + 1;
+
+        // the notes are the last column, which is split off before the
+        // remaining columns are assigned
+        if options.notes_column && found > 11 {
+            if options.strict_columns && found > 12 {
+                return Err(Bodyfile3ParserError::TooManyColumns { found });
+            }
+            let notes_start = memchr::memrchr(b'|', bytes).unwrap();
+            let options = options.with_notes_column(false).with_strict_columns(false);
+            let (md5, name, tail, _) = Self::split_columns(&line[..notes_start], &options)?;
+            return Ok((md5, name, tail, Some(&line[notes_start + 1..])));
+        }
+
+        let padded = options.pad_missing_timestamps && (7..11).contains(&found);
+        if found < 11 && !padded {
+            return Err(Bodyfile3ParserError::TooFewColumns { found });
+        }
+        if options.strict_columns && found > 11 {
+            return Err(Bodyfile3ParserError::TooManyColumns { found });
+        }
+
+        // Column accounting: `md5` is always the first column, and the nine
+        // columns from `inode` to `crtime` are anchored at the end of the line.
+        // None of them can contain a pipe symbol: `md5`, `uid`, `gid`, `size`
+        // and the timestamps are numeric, and neither TSK inodes (e.g.
+        // `87915-128-1`) nor mode strings (e.g. `r/rrwxrwxrwx`) contain pipes.
+        // So every surplus column belongs to the name, regardless of whether
+        // the columns after the name are empty. Lines with missing columns
+        // are split at their first two pipe symbols.
+        //
+        // There are at least 6 pipe symbols, so the `unwrap`s cannot fail
+        let md5_end = memchr::memchr(b'|', bytes).unwrap();
+        let name_end = if padded {
+            md5_end + 1 + memchr::memchr(b'|', &bytes[md5_end + 1..]).unwrap()
+        } else {
+            memchr::memrchr_iter(b'|', bytes).nth(8).unwrap()
+        };
+
+        // pipe symbols are ASCII characters, so all slices start and end
+        // at character boundaries
+        let mut tail = ["-1"; 9];
+        let mut column_start = name_end + 1;
+        let column_ends = memchr::memchr_iter(b'|', &bytes[column_start..])
+            .map(|idx| name_end + 1 + idx)
+            .chain(std::iter::once(line.len()));
+        for (column, column_end) in tail.iter_mut().zip(column_ends) {
+            *column = &line[column_start..column_end];
+            column_start = column_end + 1;
+        }
+
+        Ok((&line[..md5_end], &line[md5_end + 1..name_end], tail, None))
+    }
+
+    /// creates an owned [`Bodyfile3Line`] with the same content
+    pub fn to_owned(self) -> Bodyfile3Line {
+        Bodyfile3Line {
+            md5: self.md5.to_owned(),
+            name: self.name.to_owned(),
+            inode: self.inode.to_owned(),
+            mode_as_string: self.mode_as_string.to_owned(),
+            uid: self.uid,
+            gid: self.gid,
+            size: self.size,
+            atime: self.atime,
+            mtime: self.mtime,
+            ctime: self.ctime,
+            crtime: self.crtime,
+            notes: self.notes.map(str::to_owned),
+            raw_name: None,
+        }
+    }
 }
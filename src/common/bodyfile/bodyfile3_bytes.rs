@@ -0,0 +1,235 @@
+use std::fmt;
+
+use super::{Bodyfile3Line, Bodyfile3ParserError, Bodyfile3ParserOptions};
+
+/// bodyfile line which keeps the original bytes of its name.
+///
+/// Linux file names can be arbitrary bytes, which cannot be stored in a
+/// [`Bodyfile3Line`] without replacing invalid UTF-8 sequences. This type
+/// stores the name as it has been read, so that it can be matched exactly
+/// and written again without loss. All other columns must be valid UTF-8.
+///
+/// The [`Display`](fmt::Display) implementation escapes all bytes of the
+/// name which are not printable, so that the result can be shown safely.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::Bodyfile3LineBytes;
+///
+/// let raw = b"0|/tmp/caf\xe9|1234|r/rrw-r--r--|0|0|42|-1|1609459200|-1|-1";
+/// let bf = Bodyfile3LineBytes::try_from(&raw[..]).unwrap();
+/// assert_eq!(bf.name(), b"/tmp/caf\xe9");
+/// assert_eq!(bf.line().get_name(), "/tmp/caf\u{fffd}");
+/// assert_eq!(*bf.line().get_mtime(), 1609459200);
+/// assert_eq!(bf.to_bytes(), raw);
+/// assert_eq!(
+///     bf.to_string(),
+///     "0|/tmp/caf\\xe9|1234|r/rrw-r--r--|0|0|42|-1|1609459200|-1|-1"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bodyfile3LineBytes {
+    line: Bodyfile3Line,
+    name: Vec<u8>,
+}
+
+impl Bodyfile3LineBytes {
+    /// parses a bodyfile line using the specified parser options
+    pub fn parse(
+        line: &[u8],
+        options: &Bodyfile3ParserOptions,
+    ) -> Result<Self, Bodyfile3ParserError> {
+        let bf_line = Bodyfile3Line::parse(&String::from_utf8_lossy(line), options)?;
+
+        // the md5 column contains no pipe symbol, and the pipe symbols of the
+        // name are not changed by replacing invalid UTF-8 sequences
+        let name_start = memchr::memchr(b'|', line).unwrap_or(line.len()) + 1;
+        let pipes_in_name = bf_line.get_name().matches('|').count();
+        let name_end = memchr::memchr_iter(b'|', &line[name_start..])
+            .nth(pipes_in_name)
+            .map_or(line.len(), |idx| name_start + idx);
+
+        if std::str::from_utf8(&line[..name_start]).is_err()
+            || std::str::from_utf8(&line[name_end..]).is_err()
+        {
+            return Err(Bodyfile3ParserError::InvalidUtf8);
+        }
+
+        Ok(Self {
+            line: bf_line,
+            name: line[name_start..name_end].to_vec(),
+        })
+    }
+
+    /// the original bytes of the name
+    pub fn name(&self) -> &[u8] {
+        &self.name[..]
+    }
+
+    /// the name as `OsStr`, e.g. to compare it with the names of a mounted
+    /// filesystem
+    #[cfg(unix)]
+    pub fn name_as_os_str(&self) -> &std::ffi::OsStr {
+        use std::os::unix::ffi::OsStrExt;
+        std::ffi::OsStr::from_bytes(&self.name)
+    }
+
+    /// the parsed line, whose name contains U+FFFD instead of invalid UTF-8
+    /// sequences
+    pub fn line(&self) -> &Bodyfile3Line {
+        &self.line
+    }
+
+    pub fn into_line(self) -> Bodyfile3Line {
+        self.line
+    }
+
+    /// returns `true` if the name is valid UTF-8, i.e. if [`Self::line`]
+    /// contains the original name
+    pub fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.name).is_ok()
+    }
+
+    /// exports the line with the original name
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let line = &self.line;
+        let mut bytes = Vec::with_capacity(self.name.len() + 64);
+        bytes.extend_from_slice(line.get_md5().as_bytes());
+        bytes.push(b'|');
+        bytes.extend_from_slice(&self.name);
+        bytes.extend_from_slice(
+            format!(
+                "|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+                line.get_inode(),
+                line.get_mode_as_string(),
+                line.get_uid(),
+                line.get_gid(),
+                line.get_size(),
+                line.get_atime(),
+                line.get_mtime(),
+                line.get_ctime(),
+                line.get_crtime()
+            )
+            .as_bytes(),
+        );
+        if let Some(notes) = line.get_notes() {
+            bytes.push(b'|');
+            bytes.extend_from_slice(notes.as_bytes());
+        }
+        bytes
+    }
+
+    /// escapes control characters and invalid UTF-8 sequences as `\xNN`.
+    /// Backslashes are doubled, so that the escaped name is unambiguous
+    fn escaped_name(&self) -> String {
+        let mut escaped = String::with_capacity(self.name.len());
+        let escape_bytes = |escaped: &mut String, bytes: &[u8]| {
+            for b in bytes {
+                escaped.push_str(&format!("\\x{b:02x}"));
+            }
+        };
+
+        let mut rest = &self.name[..];
+        while !rest.is_empty() {
+            let (valid, invalid) = match std::str::from_utf8(rest) {
+                Ok(valid) => (valid, &rest[rest.len()..]),
+                Err(why) => {
+                    let (valid, tail) = rest.split_at(why.valid_up_to());
+                    let invalid_len = why.error_len().unwrap_or(tail.len());
+                    // the prefix has been validated by `from_utf8`
+                    (std::str::from_utf8(valid).unwrap(), &tail[..invalid_len])
+                }
+            };
+            for c in valid.chars() {
+                if c == '\\' {
+                    escaped.push_str("\\\\");
+                } else if c.is_control() {
+                    escape_bytes(&mut escaped, c.to_string().as_bytes());
+                } else {
+                    escaped.push(c);
+                }
+            }
+            escape_bytes(&mut escaped, invalid);
+            rest = &rest[valid.len() + invalid.len()..];
+        }
+        escaped
+    }
+}
+
+impl TryFrom<&[u8]> for Bodyfile3LineBytes {
+    type Error = Bodyfile3ParserError;
+
+    /// parses a bodyfile line using the default parser options
+    fn try_from(line: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse(line, &Bodyfile3ParserOptions::default())
+    }
+}
+
+impl From<Bodyfile3Line> for Bodyfile3LineBytes {
+    fn from(line: Bodyfile3Line) -> Self {
+        Self {
+            name: line.get_name().as_bytes().to_vec(),
+            line,
+        }
+    }
+}
+
+impl fmt::Display for Bodyfile3LineBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = &self.line;
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            line.get_md5(),
+            self.escaped_name(),
+            line.get_inode(),
+            line.get_mode_as_string(),
+            line.get_uid(),
+            line.get_gid(),
+            line.get_size(),
+            line.get_atime(),
+            line.get_mtime(),
+            line.get_ctime(),
+            line.get_crtime()
+        )?;
+        match line.get_notes() {
+            Some(notes) => write!(f, "|{notes}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bodyfile3LineBytes;
+    use crate::common::bodyfile::Bodyfile3ParserError;
+
+    #[test]
+    fn preserve_non_utf8_name() {
+        let raw = b"0|/home/alice/\xff\xfe|x|1234|r/rrw-r--r--|0|0|42|-1|1609459200|-1|-1";
+        let bf = Bodyfile3LineBytes::try_from(&raw[..]).unwrap();
+        assert_eq!(bf.name(), b"/home/alice/\xff\xfe|x");
+        assert!(!bf.is_utf8());
+        assert_eq!(bf.line().get_inode(), "1234");
+        assert_eq!(bf.to_bytes(), raw);
+
+        let reparsed = Bodyfile3LineBytes::try_from(&bf.to_bytes()[..]).unwrap();
+        assert_eq!(reparsed.name(), bf.name());
+    }
+
+    #[test]
+    fn escape_name() {
+        let raw = b"0|a\\b\tc\xc3\xa4\xc3|1||0|0|0|-1|1|-1|-1";
+        let bf = Bodyfile3LineBytes::try_from(&raw[..]).unwrap();
+        assert_eq!(bf.escaped_name(), "a\\\\b\\x09c\u{e4}\\xc3");
+    }
+
+    #[test]
+    fn invalid_columns() {
+        let raw = b"0|a|1\xff||0|0|0|-1|1|-1|-1";
+        assert!(matches!(
+            Bodyfile3LineBytes::try_from(&raw[..]),
+            Err(Bodyfile3ParserError::InvalidUtf8)
+        ));
+    }
+}
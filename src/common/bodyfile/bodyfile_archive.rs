@@ -0,0 +1,145 @@
+use std::fs::File;
+use std::io::{BufRead, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+
+/// entry of an archive which has been opened for reading, together with the
+/// path which is used to report errors (e.g. `evidence.zip/host1.bodyfile`)
+pub(crate) type ArchiveEntry = (PathBuf, Box<dyn BufRead + Send>);
+
+/// content of a bodyfile which has been read from a tar archive, or the error
+/// which occurred while reading it
+type TarEntry = Result<(PathBuf, Vec<u8>), (PathBuf, std::io::Error)>;
+
+/// iterates over all bodyfiles (`*.bodyfile` and `*.txt`) inside of a `.zip`,
+/// `.tar`, `.tar.gz` or `.tgz` archive. All other entries are skipped.
+///
+/// Every entry is decompressed into memory before it is being read. Entries
+/// of zip archives are read one after the other, but tar archives cannot be
+/// accessed randomly, so all their bodyfiles are read when the archive is
+/// opened.
+pub(crate) enum ArchiveEntries {
+    Zip {
+        path: PathBuf,
+        archive: ZipArchive<File>,
+        next_index: usize,
+    },
+    Tar(std::vec::IntoIter<TarEntry>),
+}
+
+impl ArchiveEntries {
+    /// returns `true` if `path` has the extension of a supported archive format
+    pub fn is_archive(path: &Path) -> bool {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        [".zip", ".tar", ".tar.gz", ".tgz"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+    }
+
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        {
+            Ok(Self::Zip {
+                path: path.to_owned(),
+                archive: ZipArchive::new(file)?,
+                next_index: 0,
+            })
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("tar"))
+        {
+            Self::read_tar(path, file)
+        } else {
+            Self::read_tar(path, GzDecoder::new(file))
+        }
+    }
+
+    fn read_tar(path: &Path, reader: impl Read) -> std::io::Result<Self> {
+        let mut archive = tar::Archive::new(reader);
+        let mut entries = Vec::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path()?.into_owned();
+            if !is_bodyfile(&name) {
+                log::debug!("skipping '{}' in '{}'", name.display(), path.display());
+                continue;
+            }
+
+            let entry_path = path.join(name);
+            let mut data = Vec::new();
+            match entry.read_to_end(&mut data) {
+                Ok(_) => entries.push(Ok((entry_path, data))),
+                Err(why) => {
+                    // the following entries cannot be found after a corrupt one
+                    entries.push(Err((entry_path, why)));
+                    break;
+                }
+            }
+        }
+        Ok(Self::Tar(entries.into_iter()))
+    }
+}
+
+impl Iterator for ArchiveEntries {
+    type Item = Result<ArchiveEntry, (PathBuf, std::io::Error)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Zip {
+                path,
+                archive,
+                next_index,
+            } => {
+                while *next_index < archive.len() {
+                    let index = *next_index;
+                    *next_index += 1;
+
+                    let mut entry = match archive.by_index(index) {
+                        Ok(entry) => entry,
+                        Err(why) => return Some(Err((path.clone(), why.into()))),
+                    };
+                    let name = PathBuf::from(entry.name());
+                    if entry.is_dir() || !is_bodyfile(&name) {
+                        log::debug!("skipping '{}' in '{}'", name.display(), path.display());
+                        continue;
+                    }
+
+                    let entry_path = path.join(name);
+                    let mut data = Vec::new();
+                    return match entry.read_to_end(&mut data) {
+                        Ok(_) => Some(Ok((
+                            entry_path,
+                            Box::new(Cursor::new(data)) as Box<dyn BufRead + Send>,
+                        ))),
+                        Err(why) => Some(Err((entry_path, why))),
+                    };
+                }
+                None
+            }
+            Self::Tar(entries) => entries.next().map(|entry| {
+                entry.map(|(entry_path, data)| {
+                    (
+                        entry_path,
+                        Box::new(Cursor::new(data)) as Box<dyn BufRead + Send>,
+                    )
+                })
+            }),
+        }
+    }
+}
+
+fn is_bodyfile(name: &Path) -> bool {
+    name.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("bodyfile") || ext.eq_ignore_ascii_case("txt"))
+}
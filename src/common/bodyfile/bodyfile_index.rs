@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::Bodyfile3Line;
+
+///
+/// Sparse offset index over a bodyfile which is **already sorted by mtime**.
+///
+/// Every `sample_rate`-th line is recorded together with its byte offset,
+/// so that [`BodyfileIndex::seek_to_time`] only needs to scan at most
+/// `sample_rate` lines after a binary search. The index does not verify the
+/// sort order; using it with unsorted input yields arbitrary positions.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, BodyfileIndex};
+/// use std::io::{BufRead, Cursor};
+///
+/// let sorted = "0|a|1||0|0|0|-1|100|-1|-1
+/// 0|b|2||0|0|0|-1|200|-1|-1
+/// 0|c|3||0|0|0|-1|300|-1|-1
+/// 0|d|4||0|0|0|-1|400|-1|-1
+/// 0|e|5||0|0|0|-1|500|-1|-1
+/// ";
+/// let mut index = BodyfileIndex::build(Cursor::new(sorted), 2).unwrap();
+/// let reader = index.seek_to_time(250).unwrap();
+/// let mut line = String::new();
+/// reader.read_line(&mut line).unwrap();
+/// let bf_line = Bodyfile3Line::try_from(line.trim_end()).unwrap();
+/// assert_eq!(bf_line.get_name(), "c");
+/// assert_eq!(*bf_line.get_mtime(), 300);
+/// ```
+pub struct BodyfileIndex<R: Read + Seek> {
+    reader: BufReader<R>,
+    samples: Vec<(i64, u64)>,
+}
+
+impl BodyfileIndex<File> {
+    /// opens a sorted bodyfile and creates an index over it
+    pub fn open<P: AsRef<Path>>(path: P, sample_rate: usize) -> std::io::Result<Self> {
+        Self::build(File::open(path)?, sample_rate)
+    }
+}
+
+impl<R: Read + Seek> BodyfileIndex<R> {
+    /// reads the whole input once and records the mtime and offset of every
+    /// `sample_rate`-th parseable line
+    pub fn build(reader: R, sample_rate: usize) -> std::io::Result<Self> {
+        let sample_rate = sample_rate.max(1);
+        let mut reader = BufReader::new(reader);
+        let mut samples = Vec::new();
+        let mut offset = reader.seek(SeekFrom::Start(0))?;
+        let mut line_nr = 0;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            let size = reader.read_line(&mut line)?;
+            if size == 0 {
+                break;
+            }
+
+            if let Some(mtime) = Self::mtime_of(&line) {
+                if line_nr % sample_rate == 0 {
+                    samples.push((mtime, offset));
+                }
+                line_nr += 1;
+            }
+            offset += size as u64;
+        }
+
+        Ok(Self { reader, samples })
+    }
+
+    /// positions the underlying reader at the first line whose mtime is at
+    /// or after `t` and returns it. If there is no such line, the reader is
+    /// positioned at the end of the input.
+    pub fn seek_to_time(&mut self, t: i64) -> std::io::Result<&mut BufReader<R>> {
+        let idx = self.samples.partition_point(|(mtime, _)| *mtime < t);
+        let mut offset = match idx {
+            0 => 0,
+            idx => self.samples[idx - 1].1,
+        };
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let size = self.reader.read_line(&mut line)?;
+            if size == 0 {
+                break;
+            }
+            if matches!(Self::mtime_of(&line), Some(mtime) if mtime >= t) {
+                break;
+            }
+            offset += size as u64;
+        }
+
+        self.reader.seek(SeekFrom::Start(offset))?;
+        Ok(&mut self.reader)
+    }
+
+    fn mtime_of(line: &str) -> Option<i64> {
+        if line.starts_with('#') {
+            return None;
+        }
+        Bodyfile3Line::try_from(line.trim_end_matches(['\r', '\n']))
+            .ok()
+            .map(|l| *l.get_mtime())
+    }
+}
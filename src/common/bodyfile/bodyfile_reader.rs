@@ -0,0 +1,793 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+
+#[cfg(feature = "archive")]
+use super::bodyfile_archive::ArchiveEntries;
+use super::line_checksum::checksum_column_start;
+use super::{
+    strip_checksum_column, Bodyfile3Line, Bodyfile3LineBytes, Bodyfile3ParserError, OnUnparseable,
+};
+
+///
+/// Reads bodyfiles which have been split into multiple parts (e.g.
+/// `part-000`, `part-001.gz`, ...) as one stream of [`Bodyfile3Line`]s.
+///
+/// Parts ending with `.gz` (and `.zst`, if the `zstd` feature is enabled)
+/// are decompressed transparently. Archives (`.zip`, `.tar`, `.tar.gz` and
+/// `.tgz`) are expanded to all `*.bodyfile` and `*.txt` entries they contain, which are read as separate parts in the
+/// order of the archive; errors report a path like
+/// `evidence.zip/host1.bodyfile`. Other entries are skipped. A part named
+/// `-` is read from stdin. Comments and a
+/// header line at the beginning of every part are skipped. Lines are
+/// numbered continuously across all parts; errors report both this number
+/// and the position inside of the part. By default, invalid UTF-8 sequences
+/// are replaced by U+FFFD and reported as a warning (see
+/// [`BodyfileReader::with_strict_utf8`] and [`BodyfileReader::with_bytes`]
+/// for alternatives). A trailing checksum column, as written by
+/// `bodyfile_merge merge --checksums`, is removed (see
+/// [`strip_checksum_column`]).
+///
+/// # Example
+/// ```no_run
+/// use dfir_toolkit::common::bodyfile::BodyfileReader;
+/// use std::path::PathBuf;
+///
+/// let parts = [PathBuf::from("part-000"), PathBuf::from("part-001.gz")];
+/// for line in BodyfileReader::from_paths(&parts) {
+///     match line {
+///         Ok(bf_line) => println!("{bf_line}"),
+///         Err(why) => eprintln!("{why}"),
+///     }
+/// }
+/// ```
+///
+/// Use [`BodyfileReader::with_raw`] to receive the original text of every
+/// line together with the parsed line, and
+/// [`BodyfileReader::with_on_unparseable`] to skip lines which are no
+/// bodyfile lines at all.
+pub struct BodyfileReader {
+    parts: VecDeque<PathBuf>,
+    #[cfg(feature = "archive")]
+    archive: Option<ArchiveEntries>,
+    current: Option<(PathBuf, Box<dyn BufRead + Send>)>,
+    line_in_part: usize,
+    line_nr: usize,
+    on_unparseable: OnUnparseable,
+    strict_utf8: bool,
+    skipped_lines: usize,
+    collected_lines: Vec<(usize, String)>,
+}
+
+/// part which has been opened for reading, or the error which occurred
+type OpenedPart = Result<(PathBuf, Box<dyn BufRead + Send>), (PathBuf, std::io::Error)>;
+
+#[derive(Debug)]
+pub enum BodyfileReaderErrorKind {
+    Io(std::io::Error),
+    Parser(Bodyfile3ParserError),
+}
+
+/// error which occurred while reading a specific line
+#[derive(Debug)]
+pub struct BodyfileReaderError {
+    pub path: PathBuf,
+
+    /// line number inside of the part (starting with 1), or `0` if the part could not be opened
+    pub line_in_part: usize,
+
+    /// line number inside of the whole stream (starting with 1)
+    pub line_nr: usize,
+    pub kind: BodyfileReaderErrorKind,
+}
+
+impl fmt::Display for BodyfileReaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            BodyfileReaderErrorKind::Io(why) => write!(
+                f,
+                "IO error in '{}' (line {}): {why}",
+                self.path.display(),
+                self.line_in_part
+            ),
+            BodyfileReaderErrorKind::Parser(why) => write!(
+                f,
+                "parser error in '{}', line {} (line {} of the stream): {why}",
+                self.path.display(),
+                self.line_in_part,
+                self.line_nr
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BodyfileReaderError {}
+
+impl BodyfileReader {
+    /// creates a reader which reads all `paths` in the specified order
+    pub fn from_paths(paths: &[PathBuf]) -> Self {
+        Self {
+            parts: paths.iter().cloned().collect(),
+            #[cfg(feature = "archive")]
+            archive: None,
+            current: None,
+            line_in_part: 0,
+            line_nr: 0,
+            on_unparseable: OnUnparseable::default(),
+            strict_utf8: false,
+            skipped_lines: 0,
+            collected_lines: Vec::new(),
+        }
+    }
+
+    /// specifies how lines which cannot be parsed are handled. By default,
+    /// they are returned as [`BodyfileReaderErrorKind::Parser`] errors.
+    /// Skipped lines are counted, and a warning with their number is logged
+    /// after the last part has been read.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dfir_toolkit::common::bodyfile::{BodyfileReader, OnUnparseable};
+    /// use std::path::PathBuf;
+    ///
+    /// let mut reader = BodyfileReader::from_paths(&[PathBuf::from("bodyfile")])
+    ///     .with_on_unparseable(OnUnparseable::Collect);
+    /// for line in reader.by_ref() {
+    ///     println!("{}", line.unwrap());
+    /// }
+    /// for (line_nr, line) in reader.collected_lines() {
+    ///     eprintln!("line {line_nr} is not a bodyfile line: {line}");
+    /// }
+    /// ```
+    pub fn with_on_unparseable(mut self, on_unparseable: OnUnparseable) -> Self {
+        self.on_unparseable = on_unparseable;
+        self
+    }
+
+    /// number of lines which have been skipped because they could not be
+    /// parsed (see [`BodyfileReader::with_on_unparseable`])
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    /// line numbers (inside of the whole stream) and texts of all lines which
+    /// could not be parsed, if [`OnUnparseable::Collect`] has been specified
+    pub fn collected_lines(&self) -> &[(usize, String)] {
+        &self.collected_lines[..]
+    }
+
+    /// yields every parsed line together with its original text. The text
+    /// is the line exactly as it has been read, only without the final
+    /// `\n`; a preceding `\r` and any other whitespace is kept. Invalid
+    /// UTF-8 sequences are replaced by U+FFFD in the text as well.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dfir_toolkit::common::bodyfile::BodyfileReader;
+    /// use std::path::PathBuf;
+    ///
+    /// for line in BodyfileReader::from_paths(&[PathBuf::from("bodyfile")]).with_raw() {
+    ///     let (bf_line, raw) = line.unwrap();
+    ///     println!("{} was read from '{raw}'", bf_line.get_name());
+    /// }
+    /// ```
+    pub fn with_raw(self) -> RawBodyfileReader {
+        RawBodyfileReader(self)
+    }
+
+    /// treats lines which are not valid UTF-8 as unparseable, instead of
+    /// replacing invalid sequences by U+FFFD. Such lines are reported as
+    /// [`Bodyfile3ParserError::InvalidUtf8`] errors, unless they are skipped
+    /// (see [`BodyfileReader::with_on_unparseable`]).
+    pub fn with_strict_utf8(mut self, strict_utf8: bool) -> Self {
+        self.strict_utf8 = strict_utf8;
+        self
+    }
+
+    /// yields every line as [`Bodyfile3LineBytes`], which keeps the original
+    /// bytes of names which are not valid UTF-8
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dfir_toolkit::common::bodyfile::BodyfileReader;
+    /// use std::io::Write;
+    /// use std::path::PathBuf;
+    ///
+    /// let mut stdout = std::io::stdout();
+    /// for line in BodyfileReader::from_paths(&[PathBuf::from("bodyfile")]).with_bytes() {
+    ///     stdout.write_all(&line.unwrap().to_bytes()).unwrap();
+    ///     stdout.write_all(b"\n").unwrap();
+    /// }
+    /// ```
+    pub fn with_bytes(self) -> BytesBodyfileReader {
+        BytesBodyfileReader(self)
+    }
+
+    /// yields the bytes of every line without parsing it, and without the
+    /// final `\n`. Comments and headers are skipped nevertheless. This can
+    /// be used by tools which parse the lines themselves, but read multiple
+    /// or compressed parts.
+    pub fn with_unparsed(self) -> UnparsedBodyfileReader {
+        UnparsedBodyfileReader(self)
+    }
+
+    fn open(path: &Path) -> std::io::Result<Box<dyn BufRead + Send>> {
+        if path == Path::new("-") {
+            return Ok(Box::new(BufReader::new(std::io::stdin())));
+        }
+
+        let file = File::open(path)?;
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            return Ok(Box::new(BufReader::new(GzDecoder::new(file))));
+        }
+
+        #[cfg(feature = "zstd")]
+        if path.extension().is_some_and(|ext| ext == "zst") {
+            return Ok(Box::new(BufReader::new(zstd::Decoder::new(file)?)));
+        }
+
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    fn error(&self, path: PathBuf, kind: BodyfileReaderErrorKind) -> BodyfileReaderError {
+        BodyfileReaderError {
+            path,
+            line_in_part: self.line_in_part,
+            line_nr: self.line_nr,
+            kind,
+        }
+    }
+}
+
+impl BodyfileReader {
+    /// opens the next file, or the next entry of the current archive
+    #[cfg_attr(not(feature = "archive"), allow(clippy::never_loop))]
+    fn next_part(&mut self) -> Option<OpenedPart> {
+        loop {
+            #[cfg(feature = "archive")]
+            if let Some(archive) = self.archive.as_mut() {
+                match archive.next() {
+                    Some(entry) => return Some(entry),
+                    None => self.archive = None,
+                }
+            }
+
+            let path = self.parts.pop_front()?;
+
+            #[cfg(feature = "archive")]
+            if ArchiveEntries::is_archive(&path) {
+                match ArchiveEntries::open(&path) {
+                    Ok(archive) => self.archive = Some(archive),
+                    Err(why) => return Some(Err((path, why))),
+                }
+                continue;
+            }
+
+            return match Self::open(&path) {
+                Ok(reader) => Some(Ok((path, reader))),
+                Err(why) => Some(Err((path, why))),
+            };
+        }
+    }
+
+    /// reads the next line of the stream without the final `\n`
+    fn next_buffer(&mut self) -> Option<Result<Vec<u8>, BodyfileReaderError>> {
+        loop {
+            if self.current.is_none() {
+                self.line_in_part = 0;
+                match self.next_part() {
+                    Some(Ok(part)) => self.current = Some(part),
+                    Some(Err((path, why))) => {
+                        return Some(Err(self.error(path, BodyfileReaderErrorKind::Io(why))))
+                    }
+                    None => {
+                        if self.skipped_lines > 0 {
+                            log::warn!("skipped {} unparseable lines", self.skipped_lines);
+                        }
+                        return None;
+                    }
+                }
+            }
+
+            let (path, reader) = self.current.as_mut().unwrap();
+            let mut buffer = Vec::new();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) => {
+                    self.current = None;
+                    continue;
+                }
+                Ok(_) => (),
+                Err(why) => {
+                    let path = path.clone();
+                    self.current = None;
+                    return Some(Err(self.error(path, BodyfileReaderErrorKind::Io(why))));
+                }
+            }
+
+            self.line_in_part += 1;
+            self.line_nr += 1;
+            if buffer.ends_with(b"\n") {
+                buffer.pop();
+            }
+            return Some(Ok(buffer));
+        }
+    }
+
+    /// returns `true` if `line` is a comment or a header, which are skipped
+    fn is_skipped(&self, line: &str) -> bool {
+        line.starts_with('#') || (self.line_in_part == 1 && Bodyfile3Line::is_header(line))
+    }
+
+    fn parser_error(&self, why: Bodyfile3ParserError) -> BodyfileReaderError {
+        let path = self
+            .current
+            .as_ref()
+            .map(|(path, _)| path.clone())
+            .unwrap_or_default();
+        self.error(path, BodyfileReaderErrorKind::Parser(why))
+    }
+
+    /// handles a line which could not be parsed as requested by
+    /// `on_unparseable`. Returns `None` if the line is skipped
+    fn unparseable(
+        &mut self,
+        why: Bodyfile3ParserError,
+        raw: String,
+    ) -> Option<BodyfileReaderError> {
+        match self.on_unparseable {
+            OnUnparseable::Error => return Some(self.parser_error(why)),
+            OnUnparseable::Skip => (),
+            OnUnparseable::Collect => self.collected_lines.push((self.line_nr, raw)),
+        }
+        log::debug!("skipping line {}: {why}", self.line_nr);
+        self.skipped_lines += 1;
+        None
+    }
+
+    fn next_with_raw(&mut self) -> Option<Result<(Bodyfile3Line, String), BodyfileReaderError>> {
+        loop {
+            let buffer = match self.next_buffer()? {
+                Ok(buffer) => buffer,
+                Err(why) => return Some(Err(why)),
+            };
+
+            let raw = match String::from_utf8(buffer) {
+                Ok(line) => line,
+                Err(why) if self.strict_utf8 => {
+                    let raw = String::from_utf8_lossy(why.as_bytes()).into_owned();
+                    if self.is_skipped(&raw) {
+                        continue;
+                    }
+                    match self.unparseable(Bodyfile3ParserError::InvalidUtf8, raw) {
+                        Some(why) => return Some(Err(why)),
+                        None => continue,
+                    }
+                }
+                Err(why) => {
+                    log::warn!(
+                        "invalid UTF-8 in '{}', line {}, replacing invalid characters",
+                        self.current
+                            .as_ref()
+                            .map_or(Path::new(""), |(path, _)| path)
+                            .display(),
+                        self.line_in_part
+                    );
+                    String::from_utf8_lossy(why.as_bytes()).into_owned()
+                }
+            };
+            let line = strip_checksum_column(raw.trim_end_matches(['\r', '\n']));
+            if self.is_skipped(line) {
+                continue;
+            }
+
+            match Bodyfile3Line::try_from(line) {
+                Ok(bf_line) => return Some(Ok((bf_line, raw))),
+                Err(why) => {
+                    if let Some(why) = self.unparseable(why, raw) {
+                        return Some(Err(why));
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_bytes(&mut self) -> Option<Result<Bodyfile3LineBytes, BodyfileReaderError>> {
+        loop {
+            let mut buffer = match self.next_buffer()? {
+                Ok(buffer) => buffer,
+                Err(why) => return Some(Err(why)),
+            };
+            while buffer.ends_with(b"\r") || buffer.ends_with(b"\n") {
+                buffer.pop();
+            }
+            if let Some(pos) = checksum_column_start(&buffer) {
+                buffer.truncate(pos);
+            }
+            if self.is_skipped(&String::from_utf8_lossy(&buffer)) {
+                continue;
+            }
+
+            match Bodyfile3LineBytes::try_from(&buffer[..]) {
+                Ok(bf_line) => return Some(Ok(bf_line)),
+                Err(why) => {
+                    let raw = String::from_utf8_lossy(&buffer).into_owned();
+                    if let Some(why) = self.unparseable(why, raw) {
+                        return Some(Err(why));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl BodyfileReader {
+    fn next_unparsed(&mut self) -> Option<Result<Vec<u8>, BodyfileReaderError>> {
+        loop {
+            let buffer = match self.next_buffer()? {
+                Ok(buffer) => buffer,
+                Err(why) => return Some(Err(why)),
+            };
+            if self.is_skipped(String::from_utf8_lossy(&buffer).trim_end_matches('\r')) {
+                continue;
+            }
+            return Some(Ok(buffer));
+        }
+    }
+}
+
+impl Iterator for BodyfileReader {
+    type Item = Result<Bodyfile3Line, BodyfileReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_raw()
+            .map(|result| result.map(|(bf_line, _)| bf_line))
+    }
+}
+
+/// reader which yields every parsed line together with its original text
+/// (see [`BodyfileReader::with_raw`])
+pub struct RawBodyfileReader(BodyfileReader);
+
+impl Iterator for RawBodyfileReader {
+    type Item = Result<(Bodyfile3Line, String), BodyfileReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_with_raw()
+    }
+}
+
+/// reader which keeps the original bytes of all names (see
+/// [`BodyfileReader::with_bytes`])
+pub struct BytesBodyfileReader(BodyfileReader);
+
+impl Iterator for BytesBodyfileReader {
+    type Item = Result<Bodyfile3LineBytes, BodyfileReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_bytes()
+    }
+}
+
+/// reader which yields the lines without parsing them (see
+/// [`BodyfileReader::with_unparsed`])
+pub struct UnparsedBodyfileReader(BodyfileReader);
+
+impl Iterator for UnparsedBodyfileReader {
+    type Item = Result<Vec<u8>, BodyfileReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_unparsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::common::bodyfile::append_checksum;
+
+    use super::{Bodyfile3ParserError, BodyfileReader, BodyfileReaderErrorKind, OnUnparseable};
+
+    fn work_dir(test: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("bodyfile_reader_{test}_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn read_parts() {
+        let dir = work_dir("read_parts");
+        let part0 = dir.join("part-000");
+        let part1 = dir.join("part-001.gz");
+
+        fs::write(
+            &part0,
+            "MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime
+0|a|1||0|0|0|-1|1|-1|-1
+0|b|2||0|0|0|-1|2|-1|-1
+",
+        )
+        .unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(
+                b"0|c|3||0|0|0|-1|3|-1|-1\n0|d|X||0|0|0|-1|X|-1|-1\n0|e|5||0|0|0|-1|5|-1|-1\n",
+            )
+            .unwrap();
+        fs::write(&part1, encoder.finish().unwrap()).unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(&[part0, part1.clone()]).collect();
+        assert_eq!(results.len(), 5);
+
+        let names: Vec<_> = results
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .map(|l| l.get_name().to_owned())
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c", "e"]);
+
+        let error = results[3].as_ref().unwrap_err();
+        assert_eq!(error.path, part1);
+        assert_eq!(error.line_in_part, 2);
+        assert_eq!(error.line_nr, 5);
+        assert!(matches!(error.kind, BodyfileReaderErrorKind::Parser(_)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn read_zstd_part() {
+        let dir = work_dir("read_zstd_part");
+        let part = dir.join("part-000.zst");
+        let content = zstd::encode_all(
+            &b"0|a|1||0|0|0|-1|1|-1|-1\n0|b|2||0|0|0|-1|2|-1|-1\n"[..],
+            0,
+        )
+        .unwrap();
+        fs::write(&part, content).unwrap();
+
+        let names: Vec<_> = BodyfileReader::from_paths(&[part])
+            .map(|l| l.unwrap().get_name().to_owned())
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn read_zip_archive() {
+        use zip::write::FileOptions;
+        use zip::ZipWriter;
+
+        let dir = work_dir("read_zip_archive");
+        let archive = dir.join("evidence.zip");
+
+        let mut writer = ZipWriter::new(fs::File::create(&archive).unwrap());
+        for (name, content) in [
+            (
+                "host1.bodyfile",
+                "0|a|1||0|0|0|-1|1|-1|-1\n0|b|2||0|0|0|-1|2|-1|-1\n",
+            ),
+            ("README.md", "this is no bodyfile\n"),
+            (
+                "host2/fls.txt",
+                "0|c|3||0|0|0|-1|3|-1|-1\n0|d|X||0|0|0|-1|X|-1|-1\n0|e|5||0|0|0|-1|5|-1|-1\n",
+            ),
+        ] {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(std::slice::from_ref(&archive)).collect();
+        assert_eq!(results.len(), 5);
+
+        let names: Vec<_> = results
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .map(|l| l.get_name().to_owned())
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c", "e"]);
+
+        let error = results[3].as_ref().unwrap_err();
+        assert_eq!(error.path, archive.join("host2/fls.txt"));
+        assert_eq!(error.line_in_part, 2);
+        assert_eq!(error.line_nr, 4);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn raw_lines() {
+        let dir = work_dir("raw_lines");
+        let part = dir.join("raw");
+        let input = "0|a|1||0|0|0|-1|1|-1|-1\r\n# comment\n0|b  |2||0|0|0|-1|2|-1|-1\r\r\n0|c|3||0|0|0|-1|3|-1|-1";
+        fs::write(&part, input).unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(&[part])
+            .with_raw()
+            .map(Result::unwrap)
+            .collect();
+        let raw: Vec<_> = results.iter().map(|(_, raw)| raw.as_str()).collect();
+        assert_eq!(
+            raw,
+            vec![
+                "0|a|1||0|0|0|-1|1|-1|-1\r",
+                "0|b  |2||0|0|0|-1|2|-1|-1\r\r",
+                "0|c|3||0|0|0|-1|3|-1|-1",
+            ]
+        );
+        assert_eq!(results[1].0.get_name(), "b  ");
+        assert_eq!(*results[1].0.get_mtime(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unparsed_lines() {
+        let dir = work_dir("unparsed_lines");
+        let part0 = dir.join("part-000");
+        let part1 = dir.join("part-001");
+        fs::write(
+            &part0,
+            "MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime\n0|a|1||0|0|0|-1|1|-1|-1\n",
+        )
+        .unwrap();
+        fs::write(&part1, b"# comment\nno bodyfile\xff\r\n").unwrap();
+
+        let lines: Vec<_> = BodyfileReader::from_paths(&[part0, part1])
+            .with_unparsed()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![&b"0|a|1||0|0|0|-1|1|-1|-1"[..], &b"no bodyfile\xff\r"[..]]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let dir = work_dir("invalid_utf8");
+        let part = dir.join("invalid-utf8");
+        fs::write(&part, b"0|a\xffb|1||0|0|0|-1|1|-1|-1\n").unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(&[part]).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].as_ref().unwrap().get_name(), "a\u{fffd}b");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn strict_utf8() {
+        let dir = work_dir("strict_utf8");
+        let part = dir.join("invalid-utf8");
+        fs::write(
+            &part,
+            b"0|a\xffb|1||0|0|0|-1|1|-1|-1\n0|c|2||0|0|0|-1|2|-1|-1\n",
+        )
+        .unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(&[part])
+            .with_strict_utf8(true)
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            results[0].as_ref().unwrap_err().kind,
+            BodyfileReaderErrorKind::Parser(Bodyfile3ParserError::InvalidUtf8)
+        ));
+        assert_eq!(results[1].as_ref().unwrap().get_name(), "c");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn preserve_bytes() {
+        let dir = work_dir("preserve_bytes");
+        let part = dir.join("non-utf8");
+        let input =
+            b"# comment\n0|/tmp/a\xffb|1||0|0|0|-1|1|-1|-1\r\n0|/tmp/c|2||0|0|0|-1|2|-1|-1\n";
+        fs::write(&part, input).unwrap();
+
+        let lines: Vec<_> = BodyfileReader::from_paths(&[part])
+            .with_bytes()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].name(), b"/tmp/a\xffb");
+        assert_eq!(lines[0].to_bytes(), b"0|/tmp/a\xffb|1||0|0|0|-1|1|-1|-1");
+        assert_eq!(lines[1].name(), b"/tmp/c");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn strip_checksums() {
+        let dir = work_dir("strip_checksums");
+        let part = dir.join("checksums");
+        let line = "0|/tmp/a|1||0|0|0|-1|1|-1|-1";
+        fs::write(
+            &part,
+            format!(
+                "{}\n0|/tmp/b|2||0|0|0|-1|2|-1|-1|sha256:xyz\n",
+                append_checksum(line)
+            ),
+        )
+        .unwrap();
+
+        let results: Vec<_> = BodyfileReader::from_paths(std::slice::from_ref(&part)).collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().to_string(), line);
+        // only columns in the format of `append_checksum` are removed
+        assert!(results[1].is_err());
+
+        let lines: Vec<_> = BodyfileReader::from_paths(&[part]).with_bytes().collect();
+        assert_eq!(lines[0].as_ref().unwrap().to_bytes(), line.as_bytes());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const BANNER_INPUT: &str = "0|a|1||0|0|0|-1|1|-1|-1
+=== Volume C: ===
+0|b|2||0|0|0|-1|2|-1|-1
+";
+
+    fn read_banner(test: &str, on_unparseable: OnUnparseable) -> (Vec<String>, BodyfileReader) {
+        let dir = work_dir(test);
+        let part = dir.join("banner");
+        fs::write(&part, BANNER_INPUT).unwrap();
+
+        let mut reader = BodyfileReader::from_paths(&[part]).with_on_unparseable(on_unparseable);
+        let results: Vec<_> = reader
+            .by_ref()
+            .map(|r| r.map_or_else(|why| why.to_string(), |l| l.get_name().to_owned()))
+            .collect();
+
+        fs::remove_dir_all(&dir).unwrap();
+        (results, reader)
+    }
+
+    #[test]
+    fn banner_is_error() {
+        let (results, reader) = read_banner("banner_is_error", OnUnparseable::Error);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], "a");
+        assert!(results[1].starts_with("parser error in"));
+        assert_eq!(results[2], "b");
+        assert_eq!(reader.skipped_lines(), 0);
+    }
+
+    #[test]
+    fn skip_banner() {
+        let (results, reader) = read_banner("skip_banner", OnUnparseable::Skip);
+        assert_eq!(results, vec!["a", "b"]);
+        assert_eq!(reader.skipped_lines(), 1);
+        assert!(reader.collected_lines().is_empty());
+    }
+
+    #[test]
+    fn collect_banner() {
+        let (results, reader) = read_banner("collect_banner", OnUnparseable::Collect);
+        assert_eq!(results, vec!["a", "b"]);
+        assert_eq!(reader.skipped_lines(), 1);
+        assert_eq!(
+            reader.collected_lines(),
+            &[(2, "=== Volume C: ===".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::Write;
+
+use super::{append_checksum, Bodyfile3Line};
+
+/// line separator which is used when writing bodyfiles
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NewlineStyle {
+    /// `\n`
+    #[default]
+    Unix,
+
+    /// `\r\n`
+    Windows,
+}
+
+impl NewlineStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unix => "\n",
+            Self::Windows => "\r\n",
+        }
+    }
+}
+
+/// options which control how [`write_bodyfile`] separates lines. By default,
+/// lines are terminated by `\n`, including the last line.
+#[derive(Clone, Copy, Debug)]
+pub struct BodyfileWriterOptions {
+    newline_style: NewlineStyle,
+    final_newline: bool,
+    checksums: bool,
+}
+
+impl Default for BodyfileWriterOptions {
+    fn default() -> Self {
+        Self {
+            newline_style: NewlineStyle::default(),
+            final_newline: true,
+            checksums: false,
+        }
+    }
+}
+
+impl BodyfileWriterOptions {
+    /// use the specified line separator
+    pub fn with_newline_style(mut self, style: NewlineStyle) -> Self {
+        self.newline_style = style;
+        self
+    }
+
+    /// terminate the last line with a line separator as well
+    pub fn with_final_newline(mut self, final_newline: bool) -> Self {
+        self.final_newline = final_newline;
+        self
+    }
+
+    /// append a checksum column to every line (see [`append_checksum`]), so
+    /// that lines which have been altered after the export can be detected
+    pub fn with_checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+}
+
+/// writes all `lines` to `writer`
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{write_bodyfile, Bodyfile3Line, BodyfileWriterOptions, NewlineStyle};
+///
+/// let lines = vec![Bodyfile3Line::new().with_name("a"), Bodyfile3Line::new().with_name("b")];
+/// let mut output = Vec::new();
+/// let options = BodyfileWriterOptions::default()
+///     .with_newline_style(NewlineStyle::Windows)
+///     .with_final_newline(false);
+/// write_bodyfile(&mut output, &lines, &options).unwrap();
+/// assert_eq!(output, b"0|a|0||0|0|0|-1|-1|-1|-1\r\n0|b|0||0|0|0|-1|-1|-1|-1");
+/// ```
+pub fn write_bodyfile<'a, W, I>(
+    writer: &mut W,
+    lines: I,
+    options: &BodyfileWriterOptions,
+) -> std::io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Bodyfile3Line>,
+{
+    let newline = options.newline_style.as_str();
+    let mut lines = lines.into_iter().peekable();
+    while let Some(line) = lines.next() {
+        if options.checksums {
+            write!(writer, "{}", append_checksum(&line.to_string()))?;
+        } else {
+            write!(writer, "{line}")?;
+        }
+        if options.final_newline || lines.peek().is_some() {
+            writer.write_all(newline.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_bodyfile, BodyfileWriterOptions, NewlineStyle};
+    use crate::common::bodyfile::{
+        verify_checksum, Bodyfile3Line, Bodyfile3ParserOptions, ChecksumStatus,
+    };
+
+    fn write(style: NewlineStyle, final_newline: bool) -> Vec<u8> {
+        let lines = vec![
+            Bodyfile3Line::new().with_name("a").with_mtime(1),
+            Bodyfile3Line::new().with_name("b").with_mtime(2),
+        ];
+        let options = BodyfileWriterOptions::default()
+            .with_newline_style(style)
+            .with_final_newline(final_newline);
+        let mut output = Vec::new();
+        write_bodyfile(&mut output, &lines, &options).unwrap();
+        output
+    }
+
+    #[test]
+    fn default_options() {
+        let lines = vec![Bodyfile3Line::new().with_name("a")];
+        let mut output = Vec::new();
+        write_bodyfile(&mut output, &lines, &BodyfileWriterOptions::default()).unwrap();
+        assert_eq!(output, b"0|a|0||0|0|0|-1|-1|-1|-1\n");
+    }
+
+    #[test]
+    fn unix_with_final_newline() {
+        assert_eq!(
+            write(NewlineStyle::Unix, true),
+            b"0|a|0||0|0|0|-1|1|-1|-1\n0|b|0||0|0|0|-1|2|-1|-1\n"
+        );
+    }
+
+    #[test]
+    fn unix_without_final_newline() {
+        assert_eq!(
+            write(NewlineStyle::Unix, false),
+            b"0|a|0||0|0|0|-1|1|-1|-1\n0|b|0||0|0|0|-1|2|-1|-1"
+        );
+    }
+
+    #[test]
+    fn windows_with_final_newline() {
+        assert_eq!(
+            write(NewlineStyle::Windows, true),
+            b"0|a|0||0|0|0|-1|1|-1|-1\r\n0|b|0||0|0|0|-1|2|-1|-1\r\n"
+        );
+    }
+
+    #[test]
+    fn windows_without_final_newline() {
+        assert_eq!(
+            write(NewlineStyle::Windows, false),
+            b"0|a|0||0|0|0|-1|1|-1|-1\r\n0|b|0||0|0|0|-1|2|-1|-1"
+        );
+    }
+
+    #[test]
+    fn checksums() {
+        let lines = vec![Bodyfile3Line::new().with_name("a")];
+        let options = BodyfileWriterOptions::default().with_checksums(true);
+        let mut output = Vec::new();
+        write_bodyfile(&mut output, &lines, &options).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let line = output.trim_end();
+        assert_eq!(verify_checksum(line), ChecksumStatus::Valid);
+        let options = Bodyfile3ParserOptions::default().with_checksum_stripping(true);
+        let bf_line = Bodyfile3Line::parse(line, &options).unwrap();
+        assert_eq!(bf_line.to_string(), lines[0].to_string());
+    }
+
+    #[test]
+    fn no_lines() {
+        let mut output = Vec::new();
+        write_bodyfile(&mut output, &Vec::new(), &BodyfileWriterOptions::default()).unwrap();
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// type of the file system entry described by a bodyfile line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EntryType {
+    /// regular file (`r`)
+    File,
+
+    /// directory (`d`)
+    Directory,
+
+    /// symbolic link (`l`)
+    Symlink,
+
+    /// any other type (e.g. devices, pipes or sockets), or an entry whose
+    /// type is unknown
+    Other,
+}
+
+impl EntryType {
+    /// converts the type letter used by TSK (e.g. the `d` in `d/drwxr-xr-x`).
+    /// `-` means that the type is unknown and results in `None`.
+    pub fn from_marker(marker: char) -> Option<Self> {
+        match marker {
+            'r' => Some(Self::File),
+            'd' => Some(Self::Directory),
+            'l' => Some(Self::Symlink),
+            '-' => None,
+            _ => Some(Self::Other),
+        }
+    }
+}
+
+impl fmt::Display for EntryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::File => "file",
+            Self::Directory => "directory",
+            Self::Symlink => "symlink",
+            Self::Other => "other",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use clap::ValueEnum;
+
+/// rough classification of a file, which is derived from its extension
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FileCategory {
+    /// executables and libraries, e.g. `.exe`, `.dll`, `.so`
+    Executable,
+
+    /// script files, e.g. `.ps1`, `.bat`, `.sh`
+    Script,
+
+    /// office documents, PDF and text files
+    Document,
+
+    /// archives and compressed files, e.g. `.zip`, `.tar.gz`
+    Archive,
+
+    /// images, audio and video files
+    Media,
+
+    /// log files, e.g. `.log`, `.evtx`
+    Log,
+
+    /// configuration files, e.g. `.ini`, `.xml`
+    Config,
+
+    /// files without an extension or with an unknown extension
+    Unknown,
+}
+
+impl FileCategory {
+    const EXTENSIONS: &'static [(&'static str, FileCategory)] = &[
+        ("exe", Self::Executable),
+        ("dll", Self::Executable),
+        ("sys", Self::Executable),
+        ("com", Self::Executable),
+        ("scr", Self::Executable),
+        ("cpl", Self::Executable),
+        ("ocx", Self::Executable),
+        ("msi", Self::Executable),
+        ("so", Self::Executable),
+        ("dylib", Self::Executable),
+        ("elf", Self::Executable),
+        ("ps1", Self::Script),
+        ("psm1", Self::Script),
+        ("bat", Self::Script),
+        ("cmd", Self::Script),
+        ("vbs", Self::Script),
+        ("vbe", Self::Script),
+        ("js", Self::Script),
+        ("jse", Self::Script),
+        ("wsf", Self::Script),
+        ("hta", Self::Script),
+        ("sh", Self::Script),
+        ("py", Self::Script),
+        ("pl", Self::Script),
+        ("rb", Self::Script),
+        ("doc", Self::Document),
+        ("docx", Self::Document),
+        ("docm", Self::Document),
+        ("xls", Self::Document),
+        ("xlsx", Self::Document),
+        ("xlsm", Self::Document),
+        ("ppt", Self::Document),
+        ("pptx", Self::Document),
+        ("odt", Self::Document),
+        ("ods", Self::Document),
+        ("odp", Self::Document),
+        ("rtf", Self::Document),
+        ("pdf", Self::Document),
+        ("txt", Self::Document),
+        ("csv", Self::Document),
+        ("md", Self::Document),
+        ("zip", Self::Archive),
+        ("rar", Self::Archive),
+        ("7z", Self::Archive),
+        ("tar", Self::Archive),
+        ("gz", Self::Archive),
+        ("tgz", Self::Archive),
+        ("bz2", Self::Archive),
+        ("xz", Self::Archive),
+        ("zst", Self::Archive),
+        ("cab", Self::Archive),
+        ("iso", Self::Archive),
+        ("jpg", Self::Media),
+        ("jpeg", Self::Media),
+        ("png", Self::Media),
+        ("gif", Self::Media),
+        ("bmp", Self::Media),
+        ("tif", Self::Media),
+        ("tiff", Self::Media),
+        ("mp3", Self::Media),
+        ("wav", Self::Media),
+        ("mp4", Self::Media),
+        ("avi", Self::Media),
+        ("mkv", Self::Media),
+        ("mov", Self::Media),
+        ("wmv", Self::Media),
+        ("log", Self::Log),
+        ("evtx", Self::Log),
+        ("evt", Self::Log),
+        ("etl", Self::Log),
+        ("ini", Self::Config),
+        ("cfg", Self::Config),
+        ("conf", Self::Config),
+        ("xml", Self::Config),
+        ("json", Self::Config),
+        ("yaml", Self::Config),
+        ("yml", Self::Config),
+        ("reg", Self::Config),
+    ];
+
+    /// determines the category of a file by the extension of its name. The
+    /// extension is compared case-insensitive.
+    pub fn from_name(name: &str) -> Self {
+        let basename = name.rsplit(['/', '\\']).next().unwrap_or(name);
+        match basename.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => Self::EXTENSIONS
+                .iter()
+                .find(|(ext, _)| extension.eq_ignore_ascii_case(ext))
+                .map_or(Self::Unknown, |(_, category)| *category),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for FileCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.to_possible_value().unwrap();
+        write!(f, "{}", name.get_name())
+    }
+}
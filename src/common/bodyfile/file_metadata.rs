@@ -0,0 +1,108 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::{Bodyfile3Line, EntryType, MftReference, TimestampKind};
+
+/// structured metadata of a file, for analyses which are interested in the
+/// file itself instead of single timeline rows. All columns of a bodyfile
+/// line are parsed: unset timestamps (`-1`) become `None`.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, EntryType, FileMetadata};
+///
+/// let bf = Bodyfile3Line::try_from(
+///     "0|/usr/bin/passwd|87915-128-1|r/rrwsr-xr-x|0|0|59976|-1|1609459200|-1|-1",
+/// )
+/// .unwrap();
+/// let metadata = FileMetadata::from(&bf);
+/// assert_eq!(metadata.entry_type, EntryType::File);
+/// assert_eq!(metadata.permissions, Some(0o4755));
+/// assert_eq!(metadata.mft_reference.unwrap().entry, 87915);
+/// assert_eq!(metadata.modified.unwrap().to_rfc3339(), "2021-01-01T00:00:00+00:00");
+/// assert_eq!(metadata.accessed, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub name: String,
+
+    /// the inode column as it has been read
+    pub inode: String,
+
+    /// NTFS reference, if the inode column contains one
+    pub mft_reference: Option<MftReference>,
+
+    pub entry_type: EntryType,
+
+    /// permission bits of the mode string (e.g. `0o755` for `rwxr-xr-x`),
+    /// including the setuid, setgid and sticky bits. `None` if the mode
+    /// column is empty or is not a TSK mode string
+    pub permissions: Option<u32>,
+
+    pub size: u64,
+
+    /// user id of the owner
+    pub uid: u64,
+
+    /// group id of the owner
+    pub gid: u64,
+
+    pub accessed: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub changed: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl FileMetadata {
+    /// parses the permissions of a TSK mode string like `r/rrwxr-xr-x`
+    fn parse_permissions(mode: &str) -> Option<u32> {
+        let perms = match mode.as_bytes() {
+            [_, b'/', _, perms @ ..] if perms.len() == 9 => perms,
+            _ => return None,
+        };
+        perms.iter().enumerate().try_fold(0, |bits, (idx, c)| {
+            let bit = 1 << (8 - idx);
+            let special = match idx {
+                2 => 0o4000,
+                5 => 0o2000,
+                8 => 0o1000,
+                _ => 0,
+            };
+            let value = match (c, idx % 3) {
+                (b'-', _) => 0,
+                (b'r', 0) | (b'w', 1) | (b'x', 2) => bit,
+                (b's', 2) if idx != 8 => bit | special,
+                (b'S', 2) if idx != 8 => special,
+                (b't', 2) if idx == 8 => bit | special,
+                (b'T', 2) if idx == 8 => special,
+                _ => return None,
+            };
+            Some(bits | value)
+        })
+    }
+
+    fn datetime(line: &Bodyfile3Line, kind: TimestampKind) -> Option<DateTime<Utc>> {
+        match line.get_timestamp(kind) {
+            -1 => None,
+            ts => Utc.timestamp_opt(ts, 0).single(),
+        }
+    }
+}
+
+impl From<&Bodyfile3Line> for FileMetadata {
+    fn from(line: &Bodyfile3Line) -> Self {
+        Self {
+            name: line.get_name().to_owned(),
+            inode: line.get_inode().to_owned(),
+            mft_reference: line.mft_reference(),
+            entry_type: line.entry_type(),
+            permissions: Self::parse_permissions(line.get_mode_as_string()),
+            size: *line.get_size(),
+            uid: *line.get_uid(),
+            gid: *line.get_gid(),
+            accessed: Self::datetime(line, TimestampKind::Atime),
+            modified: Self::datetime(line, TimestampKind::Mtime),
+            changed: Self::datetime(line, TimestampKind::Ctime),
+            created: Self::datetime(line, TimestampKind::Crtime),
+        }
+    }
+}
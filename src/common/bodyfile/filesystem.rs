@@ -0,0 +1,133 @@
+use std::fmt;
+
+use clap::ValueEnum;
+
+use super::{Bodyfile3Line, TimestampKind};
+
+/// 1980-01-01T00:00:00Z, the earliest date which can be stored by FAT
+const FAT_MIN: i64 = 315532800;
+
+/// 2107-12-31T23:59:59Z, the latest date which can be stored by FAT
+const FAT_MAX: i64 = 4354819199;
+
+/// 1601-01-01T00:00:00Z, the epoch of Windows FILETIME values
+const NTFS_MIN: i64 = -11644473600;
+
+/// 1901-12-13T20:45:52Z, the earliest date of a signed 32-bit unix timestamp
+const EXT_MIN: i64 = i32::MIN as i64;
+
+/// 2038-01-19T03:14:07Z, the latest date of a signed 32-bit unix timestamp
+const EXT_MAX: i64 = i32::MAX as i64;
+
+/// 2446-05-10T22:38:55Z, the latest date which can be stored by ext4 using
+/// its extra epoch bits
+const EXT4_MAX: i64 = 15032385535;
+
+/// filesystem whose timestamp constraints are used by
+/// [`Filesystem::check`]. Dates are compared in UTC, because the timezone of
+/// FAT timestamps is unknown.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Filesystem {
+    /// FAT12, FAT16, FAT32 and exFAT
+    Fat,
+
+    /// NTFS
+    Ntfs,
+
+    /// ext2 and ext3, which do not store a creation time
+    Ext,
+
+    /// ext4
+    Ext4,
+}
+
+/// describes a timestamp which cannot be stored by a filesystem
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampViolation {
+    pub kind: TimestampKind,
+    pub timestamp: i64,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for TimestampViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}: {}", self.kind, self.timestamp, self.reason)
+    }
+}
+
+impl Filesystem {
+    /// returns all timestamps of `line` which cannot have been stored by this
+    /// filesystem. Unset timestamps (`-1`) are always valid.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, Filesystem, TimestampKind};
+    ///
+    /// let bf = Bodyfile3Line::new().with_mtime(1609459201);
+    /// let violations = Filesystem::Fat.check(&bf);
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(violations[0].kind, TimestampKind::Mtime);
+    /// assert!(Filesystem::Ntfs.check(&bf).is_empty());
+    ///
+    /// let bf = Bodyfile3Line::new().with_crtime(1609459200);
+    /// assert_eq!(Filesystem::Ext.check(&bf)[0].kind, TimestampKind::Crtime);
+    /// assert!(Filesystem::Ext4.check(&bf).is_empty());
+    /// ```
+    pub fn check(&self, line: &Bodyfile3Line) -> Vec<TimestampViolation> {
+        TimestampKind::ALL
+            .iter()
+            .filter_map(|kind| {
+                let timestamp = line.get_timestamp(*kind);
+                if timestamp == -1 {
+                    None
+                } else {
+                    self.check_timestamp(*kind, timestamp)
+                        .map(|reason| TimestampViolation {
+                            kind: *kind,
+                            timestamp,
+                            reason,
+                        })
+                }
+            })
+            .collect()
+    }
+
+    fn check_timestamp(&self, kind: TimestampKind, timestamp: i64) -> Option<&'static str> {
+        match self {
+            Self::Fat => {
+                if !(FAT_MIN..=FAT_MAX).contains(&timestamp) {
+                    Some("FAT can only store dates between 1980 and 2107")
+                } else if kind == TimestampKind::Mtime && timestamp % 2 != 0 {
+                    Some("FAT stores the modification time with a granularity of 2 seconds")
+                } else if kind == TimestampKind::Ctime {
+                    Some("FAT does not store a metadata change time")
+                } else {
+                    None
+                }
+            }
+            Self::Ntfs => {
+                if timestamp < NTFS_MIN {
+                    Some("NTFS cannot store dates before 1601")
+                } else {
+                    None
+                }
+            }
+            Self::Ext => {
+                if kind == TimestampKind::Crtime {
+                    Some("ext2 and ext3 do not store a creation time")
+                } else if !(EXT_MIN..=EXT_MAX).contains(&timestamp) {
+                    Some("ext2 and ext3 can only store dates between 1901 and 2038")
+                } else {
+                    None
+                }
+            }
+            Self::Ext4 => {
+                if !(EXT_MIN..=EXT4_MAX).contains(&timestamp) {
+                    Some("ext4 can only store dates between 1901 and 2446")
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
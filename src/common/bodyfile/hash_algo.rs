@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// algorithm of the hash in the `md5` column of a bodyfile line. Although
+/// the column is named `md5`, some collectors store SHA-1 or SHA-256 hashes
+/// in it; the algorithm is derived from the number of hex digits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashAlgo {
+    /// no hash, e.g. the placeholder `0`
+    None,
+
+    /// 32 hex digits
+    Md5,
+
+    /// 40 hex digits
+    Sha1,
+
+    /// 64 hex digits
+    Sha256,
+}
+
+impl HashAlgo {
+    /// detects the algorithm of a hex encoded hash. Values which are no hex
+    /// strings of a known length result in [`HashAlgo::None`].
+    pub fn from_hex(value: &str) -> Self {
+        if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Self::None;
+        }
+        match value.len() {
+            32 => Self::Md5,
+            40 => Self::Sha1,
+            64 => Self::Sha256,
+            _ => Self::None,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::None => "none",
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+        };
+        write!(f, "{name}")
+    }
+}
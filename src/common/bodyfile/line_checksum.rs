@@ -0,0 +1,141 @@
+use sha2::{Digest, Sha256};
+
+/// prefix of the checksum column, which distinguishes it from the `crtime`
+/// column: a timestamp never starts with a letter
+pub const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// number of bytes of the SHA-256 hash which are stored in the checksum
+/// column
+const CHECKSUM_BYTES: usize = 8;
+
+/// result of [`verify_checksum`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// the checksum matches the line
+    Valid,
+
+    /// the line has been changed after the checksum has been computed
+    Altered,
+
+    /// the line has no checksum column
+    Missing,
+}
+
+/// computes the checksum of a bodyfile line, which is the hex encoded
+/// SHA-256 hash of the line, truncated to 64 bits. `line` must not contain
+/// a line break.
+pub fn line_checksum(line: &str) -> String {
+    Sha256::digest(line.as_bytes())[..CHECKSUM_BYTES]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// appends the checksum of `line` as an additional column, which can be
+/// removed by the bodyfile parser (see
+/// [`Bodyfile3ParserOptions::with_checksum_stripping`](super::Bodyfile3ParserOptions::with_checksum_stripping))
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{append_checksum, Bodyfile3Line, Bodyfile3ParserOptions};
+///
+/// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1";
+/// let with_checksum = append_checksum(line);
+/// assert!(with_checksum.starts_with(&format!("{line}|sha256:")));
+///
+/// // the checksum is not part of the parsed line
+/// let options = Bodyfile3ParserOptions::default().with_checksum_stripping(true);
+/// let bf_line = Bodyfile3Line::parse(&with_checksum, &options).unwrap();
+/// assert_eq!(bf_line.to_string(), line);
+/// ```
+pub fn append_checksum(line: &str) -> String {
+    format!("{line}|{CHECKSUM_PREFIX}{}", line_checksum(line))
+}
+
+/// removes the checksum column, if the line has one
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::strip_checksum;
+///
+/// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1";
+/// assert_eq!(strip_checksum(&format!("{line}|sha256:0123456789abcdef")), line);
+/// assert_eq!(strip_checksum(line), line);
+/// ```
+pub fn strip_checksum(line: &str) -> &str {
+    split_checksum(line).0
+}
+
+/// removes the checksum column, but only if it has exactly the format which
+/// is written by [`append_checksum`] (`sha256:` followed by 16 hex digits).
+/// This is used by the bodyfile readers, so that files which have been
+/// written with `bodyfile_merge merge --checksums` can be read again, while
+/// other surplus columns are still reported.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::strip_checksum_column;
+///
+/// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1";
+/// assert_eq!(strip_checksum_column(&format!("{line}|sha256:0123456789abcdef")), line);
+/// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|sha256:0123";
+/// assert_eq!(strip_checksum_column(line), line);
+/// ```
+pub fn strip_checksum_column(line: &str) -> &str {
+    match checksum_column_start(line.as_bytes()) {
+        Some(pos) => &line[..pos],
+        None => line,
+    }
+}
+
+/// returns the position of the pipe symbol which precedes a checksum
+/// column in the format written by [`append_checksum`]
+pub(crate) fn checksum_column_start(line: &[u8]) -> Option<usize> {
+    let pos = line.iter().rposition(|c| *c == b'|')?;
+    let checksum = line[pos + 1..].strip_prefix(CHECKSUM_PREFIX.as_bytes())?;
+    (checksum.len() == 2 * CHECKSUM_BYTES && checksum.iter().all(u8::is_ascii_hexdigit))
+        .then_some(pos)
+}
+
+/// recomputes the checksum of a line, which has been written using
+/// [`append_checksum`], and compares it with the checksum column
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{append_checksum, verify_checksum, ChecksumStatus};
+///
+/// let line = append_checksum("0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1");
+/// assert_eq!(verify_checksum(&line), ChecksumStatus::Valid);
+/// assert_eq!(verify_checksum(&line.replacen("|0|0|0|", "|0|0|42|", 1)), ChecksumStatus::Altered);
+/// assert_eq!(verify_checksum("0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1"), ChecksumStatus::Missing);
+/// ```
+pub fn verify_checksum(line: &str) -> ChecksumStatus {
+    match split_checksum(line) {
+        (_, None) => ChecksumStatus::Missing,
+        (line, Some(checksum)) if checksum.eq_ignore_ascii_case(&line_checksum(line)) => {
+            ChecksumStatus::Valid
+        }
+        (_, Some(_)) => ChecksumStatus::Altered,
+    }
+}
+
+/// splits a line into its content and the value of its checksum column,
+/// without the prefix. Lines without checksum column are returned unchanged.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::split_checksum;
+///
+/// let line = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1";
+/// assert_eq!(split_checksum(&format!("{line}|sha256:0123456789abcdef")), (line, Some("0123456789abcdef")));
+/// assert_eq!(split_checksum(line), (line, None));
+/// ```
+pub fn split_checksum(line: &str) -> (&str, Option<&str>) {
+    match line.rsplit_once('|') {
+        Some((base, column)) => match column.strip_prefix(CHECKSUM_PREFIX) {
+            Some(checksum) => (base, Some(checksum)),
+            None => (line, None),
+        },
+        None => (line, None),
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use bitflags::bitflags;
+
+use super::{Bodyfile3Line, TimestampKind};
+
+bitflags! {
+    /// set of timestamps of a bodyfile line which share the same value. In a
+    /// timeline, every event of a file is annotated with these flags, which
+    /// are displayed as `macb` string with dots for unset flags.
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, MacbFlags};
+    ///
+    /// let bf = Bodyfile3Line::new().with_mtime(10).with_atime(20).with_crtime(20);
+    /// assert_eq!(MacbFlags::from_line_at_time(&bf, 10), MacbFlags::M);
+    /// assert_eq!(MacbFlags::from_line_at_time(&bf, 10).to_string(), "m...");
+    /// assert_eq!(MacbFlags::from_line_at_time(&bf, 20).to_string(), ".a.b");
+    /// assert_eq!(MacbFlags::from_line_at_time(&bf, 30), MacbFlags::NONE);
+    /// ```
+    #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+    pub struct MacbFlags: u8 {
+        const NONE = 0b00000000;
+        const M = 0b00000001;
+        const A = 0b00000010;
+        const C = 0b00000100;
+        const B = 0b00001000;
+    }
+}
+
+impl MacbFlags {
+    /// returns the flags of all timestamps of `line` which are equal to
+    /// `timestamp`. Unset timestamps (`-1`) never match.
+    pub fn from_line_at_time(line: &Bodyfile3Line, timestamp: i64) -> Self {
+        let mut flags = Self::NONE;
+        if timestamp == -1 {
+            return flags;
+        }
+        if *line.get_mtime() == timestamp {
+            flags |= Self::M;
+        }
+        if *line.get_atime() == timestamp {
+            flags |= Self::A;
+        }
+        if *line.get_ctime() == timestamp {
+            flags |= Self::C;
+        }
+        if *line.get_crtime() == timestamp {
+            flags |= Self::B;
+        }
+        flags
+    }
+}
+
+impl fmt::Display for MacbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let m = if self.contains(Self::M) { 'm' } else { '.' };
+        let a = if self.contains(Self::A) { 'a' } else { '.' };
+        let c = if self.contains(Self::C) { 'c' } else { '.' };
+        let b = if self.contains(Self::B) { 'b' } else { '.' };
+        write!(f, "{}{}{}{}", m, a, c, b)
+    }
+}
+
+impl From<TimestampKind> for MacbFlags {
+    /// returns the flag of a single timestamp kind
+    ///
+    /// # Example
+    /// ```
+    /// use dfir_toolkit::common::bodyfile::{MacbFlags, TimestampKind};
+    ///
+    /// assert_eq!(MacbFlags::from(TimestampKind::Crtime), MacbFlags::B);
+    /// assert_eq!(MacbFlags::from(TimestampKind::Ctime).to_string(), "..c.");
+    /// ```
+    fn from(kind: TimestampKind) -> Self {
+        match kind {
+            TimestampKind::Mtime => Self::M,
+            TimestampKind::Atime => Self::A,
+            TimestampKind::Ctime => Self::C,
+            TimestampKind::Crtime => Self::B,
+        }
+    }
+}
@@ -34,7 +34,21 @@
 //! let bf_line = Bodyfile3Line::try_from(str_line).unwrap();
 //! assert_eq!(str_line, bf_line.to_string());
 //! ```
+//!
+//! This works because all other columns cannot contain a pipe symbol: the
+//! first column (`md5`) and the last nine columns (`inode` to `crtime`) are
+//! assigned first, and all remaining columns are joined to form the name.
+//! As a consequence, writing a line with [`Bodyfile3Line::to_string`] and
+//! parsing it again always yields the original line, as long as the inode
+//! and the mode string do not contain a pipe symbol.
 //! 
+//! # Thread safety
+//! [`Bodyfile3Line`], [`Bodyfile3Fields`] and [`Bodyfile3ParserError`] only
+//! consist of owned data and are `Send` and `Sync`. Lines can be created or
+//! parsed on worker threads and be passed to other threads, e.g. using a
+//! channel. [`BodyfileIndex`] is `Send` if the underlying reader is `Send`,
+//! and [`BodyfileReader`] is `Send`.
+//!
 //! # Handling of pipes
 //! Normally, a filename should not contain a pipe symbol (|), but if 
 //! [bodyfile] is being used together with other sources, this may happen. So we 
@@ -50,15 +64,849 @@
 //! ```
 //! 
 pub mod bodyfile3;
+mod bodyfile3_bytes;
+#[cfg(feature = "archive")]
+mod bodyfile_archive;
+#[cfg(feature = "tokio")]
+mod async_bodyfile_reader;
+mod bodyfile_index;
+mod bodyfile_reader;
+mod bodyfile_writer;
+mod entry_type;
+mod file_category;
+mod file_metadata;
+mod filesystem;
+mod hash_algo;
+mod line_checksum;
+mod macb_flags;
+mod on_unparseable;
+mod path_resolver;
+mod timestamp_kind;
+mod window_dedup;
+#[cfg(feature = "tokio")]
+pub use async_bodyfile_reader::*;
 pub use bodyfile3::*;
+pub use bodyfile3_bytes::*;
+pub use bodyfile_index::*;
+pub use bodyfile_reader::*;
+pub use bodyfile_writer::*;
+pub use entry_type::*;
+pub use file_category::*;
+pub use file_metadata::*;
+pub use filesystem::*;
+pub use hash_algo::*;
+pub use line_checksum::*;
+pub use macb_flags::*;
+pub use on_unparseable::*;
+pub use path_resolver::*;
+pub use timestamp_kind::*;
+pub use window_dedup::*;
 
 #[cfg(test)]
 mod tests {
-    use super::Bodyfile3Line;
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    use matches::assert_matches;
+
+    use super::{
+        Bodyfile3Config, Bodyfile3Fields, Bodyfile3Line, Bodyfile3MergeError, Bodyfile3ParserError,
+        Bodyfile3ParserOptions, Bodyfile3Warning, BodyfileIndex, BodyfileReader, EntryType,
+        FileCategory, FileMetadata, MftReference, PathResolver, TimestampKind,
+    };
+
+    fn _assert_send_sync<T: Send + Sync>() {}
+    fn _assert_send<T: Send>() {}
+
+    #[test]
+    fn thread_safety() {
+        _assert_send_sync::<Bodyfile3Line>();
+        _assert_send_sync::<Bodyfile3Fields>();
+        _assert_send_sync::<Bodyfile3ParserError>();
+        _assert_send::<BodyfileIndex<Cursor<Vec<u8>>>>();
+        _assert_send::<BodyfileReader>();
+    }
 
     #[test]
     fn sample1() {
         let bf = Bodyfile3Line::new();
         assert_eq!(bf.get_md5(), "0");
     }
+
+    #[test]
+    fn fields_roundtrip() {
+        let str_line = "4bad420da66571dac7f1ace995cc55c6|sample.txt|87915-128-1|r/rrwxrwxrwx|1003|500|126378|12341|12342|12343|12344";
+        let bf = Bodyfile3Line::try_from(str_line).unwrap();
+        let fields = bf.fields();
+        assert_eq!(fields.md5, "4bad420da66571dac7f1ace995cc55c6");
+        assert_eq!(fields.crtime, 12344);
+
+        let bf = Bodyfile3Line::from(fields.clone());
+        assert_eq!(bf.to_string(), str_line);
+        assert_eq!(bf.fields(), fields);
+    }
+
+    #[test]
+    fn fields_default() {
+        let fields = Bodyfile3Fields::default();
+        assert_eq!(Bodyfile3Line::from(fields).to_string(), Bodyfile3Line::new().to_string());
+    }
+
+    #[test]
+    fn large_timestamp() {
+        // 9999-12-31T23:59:59+00:00
+        let bf = Bodyfile3Line::try_from("0||0||0|0|0|-1|253402300799|-1|-1").unwrap();
+        assert_eq!(*bf.get_mtime(), 253402300799);
+    }
+
+    #[test]
+    fn out_of_range_timestamp() {
+        assert_matches!(
+            Bodyfile3Line::try_from("0||0||0|0|0|-1|9223372036854775807|-1|-1"),
+            Err(Bodyfile3ParserError::IllegalMTime)
+        );
+        assert_matches!(
+            Bodyfile3Line::try_from("0||0||0|0|0|-1|-1|-1|9999999999999999999"),
+            Err(Bodyfile3ParserError::IllegalCRTime)
+        );
+        assert_matches!(
+            Bodyfile3Line::try_from_allow_negative("0||0||0|0|0|-9223372036854775807|-1|-1|-1"),
+            Err(Bodyfile3ParserError::IllegalATime)
+        );
+    }
+
+    #[test]
+    fn redact_users() {
+        let mut mapping = HashMap::new();
+        let mut bf1 = Bodyfile3Line::new().with_name("C:/Users/jdoe/Desktop/a.txt");
+        let mut bf2 = Bodyfile3Line::new().with_name("C:\\Users\\JDoe\\AppData");
+        let mut bf3 = Bodyfile3Line::new().with_name("D:/Users/alice/b.txt");
+        let mut bf4 = Bodyfile3Line::new().with_name("C:/Users/Public/c.txt");
+
+        for bf in [&mut bf1, &mut bf2, &mut bf3, &mut bf4] {
+            bf.redact_users(&mut mapping);
+        }
+
+        assert_eq!(bf1.get_name(), "C:/Users/user001/Desktop/a.txt");
+        assert_eq!(bf2.get_name(), "C:\\Users\\user001\\AppData");
+        assert_eq!(bf3.get_name(), "D:/Users/user002/b.txt");
+        assert_eq!(bf4.get_name(), "C:/Users/Public/c.txt");
+    }
+
+    #[test]
+    fn anonymize_hashes() {
+        let secret = b"case 42";
+        let mut bf1 = Bodyfile3Line::new().with_md5("b1946ac92492d2347c6235b4d2611184");
+        let mut bf2 = Bodyfile3Line::new().with_md5("B1946AC92492D2347C6235B4D2611184");
+        let mut bf3 = Bodyfile3Line::new().with_md5("d41d8cd98f00b204e9800998ecf8427e");
+        let mut bf4 = Bodyfile3Line::new();
+
+        for bf in [&mut bf1, &mut bf2, &mut bf3, &mut bf4] {
+            bf.anonymize_hashes(secret);
+        }
+
+        assert_eq!(
+            bf1.get_md5(),
+            "9cf00b56eedaf1aa3af6d0443a1afb7745e5ec78552577ec72a49f195ab6dc93"
+        );
+        assert_eq!(bf1.get_md5(), bf2.get_md5());
+        assert_ne!(bf1.get_md5(), bf3.get_md5());
+        assert_eq!(bf4.get_md5(), "0");
+
+        // keys which are longer than a block are hashed first
+        let mut bf = Bodyfile3Line::new().with_md5("b1946ac92492d2347c6235b4d2611184");
+        bf.anonymize_hashes(&[b'x'; 100]);
+        assert_eq!(
+            bf.get_md5(),
+            "14fab86441a5515391223d0158b06628e1afd7e476dacb34dcf8c976b9a5c0dd"
+        );
+    }
+
+    #[test]
+    fn reconstruct_paths() {
+        let mut lines = vec![
+            Bodyfile3Line::new().with_name("C:").with_inode("5-144-1"),
+            Bodyfile3Line::new().with_name("5/Users").with_inode("64-144-1"),
+            Bodyfile3Line::new().with_name("64/jdoe").with_inode("70-144-1"),
+            Bodyfile3Line::new().with_name("70/Desktop").with_inode("75-144-1"),
+            Bodyfile3Line::new().with_name("75/notes.txt").with_inode("81-128-2"),
+            Bodyfile3Line::new().with_name("75/notes.txt").with_inode("81-48-3"),
+            Bodyfile3Line::new().with_name("/tmp/a").with_inode("90-128-1"),
+        ];
+        PathResolver::reconstruct_paths(&mut lines);
+
+        let names: Vec<_> = lines.iter().map(|l| l.get_name().as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "C:",
+                "/Users",
+                "/Users/jdoe",
+                "/Users/jdoe/Desktop",
+                "/Users/jdoe/Desktop/notes.txt",
+                "/Users/jdoe/Desktop/notes.txt",
+                "/tmp/a",
+            ]
+        );
+    }
+
+    #[test]
+    fn reconstruct_paths_with_absolute_parent() {
+        let mut lines = vec![
+            Bodyfile3Line::new().with_name("C:/Windows/").with_inode("30-144-1"),
+            Bodyfile3Line::new().with_name("30/notepad.exe").with_inode("31-128-1"),
+        ];
+        PathResolver::reconstruct_paths(&mut lines);
+        assert_eq!(lines[1].get_name(), "C:/Windows/notepad.exe");
+    }
+
+    #[test]
+    fn unresolvable_paths() {
+        let mut lines = vec![
+            Bodyfile3Line::new().with_name("41/a").with_inode("40-144-1"),
+            Bodyfile3Line::new().with_name("40/b").with_inode("41-144-1"),
+            Bodyfile3Line::new().with_name("99/c").with_inode("42-128-1"),
+        ];
+        PathResolver::reconstruct_paths(&mut lines);
+
+        let names: Vec<_> = lines.iter().map(|l| l.get_name().as_str()).collect();
+        assert_eq!(names, vec!["41/a", "40/b", "99/c"]);
+    }
+
+    #[test]
+    fn md5_normalization() {
+        let options = Bodyfile3ParserOptions::default().with_md5_normalization(true);
+        let bf_line =
+            Bodyfile3Line::parse("B1946AC92492D2347C6235B4D2611184|a|0||0|0|6|-1|-1|-1|-1", &options)
+                .unwrap();
+        assert_eq!(bf_line.get_md5(), "b1946ac92492d2347c6235b4d2611184");
+
+        let bf_line = Bodyfile3Line::parse("0|a|0||0|0|6|-1|-1|-1|-1", &options).unwrap();
+        assert_eq!(bf_line.get_md5(), "0");
+
+        // the default parser keeps the original case
+        let bf_line =
+            Bodyfile3Line::try_from("B1946AC92492D2347C6235B4D2611184|a|0||0|0|6|-1|-1|-1|-1")
+                .unwrap();
+        assert_eq!(bf_line.get_md5(), "B1946AC92492D2347C6235B4D2611184");
+    }
+
+    #[test]
+    fn hex_size() {
+        let options = Bodyfile3ParserOptions::default().with_hex_size(true);
+        let bf = Bodyfile3Line::parse("0||0||0|0|1234|-1|-1|-1|-1", &options).unwrap();
+        assert_eq!(*bf.get_size(), 1234);
+        let bf = Bodyfile3Line::parse("0||0||0|0|0x4d2|-1|-1|-1|-1", &options).unwrap();
+        assert_eq!(*bf.get_size(), 1234);
+        assert_matches!(
+            Bodyfile3Line::parse("0||0||0|0|-1|-1|-1|-1|-1", &options),
+            Err(Bodyfile3ParserError::IllegalSize)
+        );
+
+        // hexadecimal sizes are not accepted by default
+        assert_matches!(
+            Bodyfile3Line::try_from("0||0||0|0|0x4d2|-1|-1|-1|-1"),
+            Err(Bodyfile3ParserError::IllegalSize)
+        );
+    }
+
+    #[test]
+    fn borrowed_view() {
+        for line in [
+            "4bad420da66571dac7f1ace995cc55c6|sample.txt|87915-128-1|r/rrwxrwxrwx|1003|500|126378|12341|12342|12343|12344",
+            "0|name|with|pipes|0||0|0|0|-1|1|-1|-1",
+            "0||0||0|0|0|-1|-1|-1|-1",
+        ] {
+            let bf_ref = Bodyfile3Line::try_parse_ref(line).unwrap();
+            let bf = Bodyfile3Line::try_from(line).unwrap();
+            assert_eq!(bf_ref.name, bf.get_name());
+            assert_eq!(bf_ref.to_owned().fields(), bf.fields());
+            assert_eq!(bf_ref.to_owned().to_string(), line);
+        }
+
+        assert_matches!(
+            Bodyfile3Line::try_parse_ref("0|sample.txt|0||0|0|4|5|6"),
+            Err(Bodyfile3ParserError::TooFewColumns { found: 9 })
+        );
+    }
+
+    #[test]
+    fn adversarial_pipes_in_name() {
+        for name in [
+            "a|b|c",
+            "|",
+            "||",
+            "|leading",
+            "trailing|",
+            "0|name|0||0|0|0|-1|-1|-1|-1",
+        ] {
+            let bf = Bodyfile3Line::new()
+                .with_name(name)
+                .with_inode("87915-128-1")
+                .with_mode("")
+                .with_uid(1)
+                .with_gid(2)
+                .with_size(3)
+                .with_atime(4)
+                .with_mtime(5)
+                .with_ctime(6)
+                .with_crtime(7);
+            let parsed = Bodyfile3Line::try_from(bf.to_string().as_str()).unwrap();
+            assert_eq!(parsed.fields(), bf.fields(), "roundtrip failed for {name:?}");
+        }
+    }
+
+    #[test]
+    fn adversarial_empty_mode() {
+        let bf = Bodyfile3Line::try_from("0|x|y|z||0|1|2|3|4|5|6").unwrap();
+        assert_eq!(bf.get_name(), "x|y");
+        assert_eq!(bf.get_inode(), "z");
+        assert_eq!(bf.get_mode_as_string(), "");
+        assert_eq!(*bf.get_uid(), 0);
+        assert_eq!(*bf.get_gid(), 1);
+        assert_eq!(*bf.get_size(), 2);
+        assert_eq!(*bf.get_crtime(), 6);
+    }
+
+    #[test]
+    fn category() {
+        let category = |name| Bodyfile3Line::new().with_name(name).category();
+        assert_eq!(category("C:/Windows/notepad.exe"), FileCategory::Executable);
+        assert_eq!(category("C:\\Users\\jdoe\\report.DOCX"), FileCategory::Document);
+        assert_eq!(category("/tmp/backup.tar.gz"), FileCategory::Archive);
+        assert_eq!(category("/usr/bin/ls"), FileCategory::Unknown);
+        assert_eq!(category("/home/jdoe/.bashrc"), FileCategory::Unknown);
+    }
+
+    #[test]
+    fn merge_complementary_timestamps() {
+        let mut bf = Bodyfile3Line::new().with_name("/tmp/a").with_inode("12").with_mtime(10).with_atime(11);
+        let other = Bodyfile3Line::new()
+            .with_name("/tmp/a")
+            .with_inode("12")
+            .with_md5("4bad420da66571dac7f1ace995cc55c6")
+            .with_mode("r/rrwxrwxrwx")
+            .with_size(42)
+            .with_atime(11)
+            .with_crtime(5);
+
+        assert!(bf.merge(&other).unwrap().is_empty());
+        assert_eq!(
+            bf.to_string(),
+            "4bad420da66571dac7f1ace995cc55c6|/tmp/a|12|r/rrwxrwxrwx|0|0|42|11|10|-1|5"
+        );
+    }
+
+    #[test]
+    fn merge_conflict() {
+        let mut bf = Bodyfile3Line::new().with_name("/tmp/a").with_mtime(10).with_size(1);
+        let other = Bodyfile3Line::new().with_name("/tmp/a").with_mtime(20).with_ctime(30).with_size(2);
+
+        assert_eq!(bf.merge(&other).unwrap(), vec![TimestampKind::Mtime]);
+        assert_eq!(*bf.get_mtime(), 10);
+        assert_eq!(*bf.get_ctime(), 30);
+        assert_eq!(*bf.get_size(), 1);
+    }
+
+    #[test]
+    fn merge_different_files() {
+        let mut bf = Bodyfile3Line::new().with_name("/tmp/a").with_inode("1");
+        let other = Bodyfile3Line::new().with_name("/tmp/a").with_inode("2").with_mtime(1);
+        assert_matches!(bf.merge(&other), Err(Bodyfile3MergeError::IdentityMismatch));
+        assert_eq!(*bf.get_mtime(), -1);
+    }
+
+    #[test]
+    fn merge_without_inode() {
+        let mut bf = Bodyfile3Line::new().with_name("/tmp/a").with_inode("0");
+        let other = Bodyfile3Line::new().with_name("/tmp/b").with_inode("0").with_mtime(1);
+        assert!(!bf.has_inode());
+        assert_matches!(bf.merge(&other), Err(Bodyfile3MergeError::IdentityMismatch));
+        assert_eq!(*bf.get_mtime(), -1);
+
+        let other = Bodyfile3Line::new().with_name("/tmp/a").with_inode("").with_mtime(1);
+        assert!(bf.merge(&other).unwrap().is_empty());
+        assert_eq!(*bf.get_mtime(), 1);
+    }
+
+    #[test]
+    fn valid_mode() {
+        let options = Bodyfile3ParserOptions::default().with_mode_validation(true);
+        let bf = Bodyfile3Line::parse("0|/tmp/a|12|r/rrwxr-xr-x|0|0|42|-1|1|-1|-1", &options).unwrap();
+        assert_eq!(bf.get_mode_as_string(), "r/rrwxr-xr-x");
+
+        let bf = Bodyfile3Line::parse("0|/tmp|13|d/drwxrwxrwt|0|0|0|-1|1|-1|-1", &options).unwrap();
+        assert_eq!(bf.get_mode_as_string(), "d/drwxrwxrwt");
+    }
+
+    #[test]
+    fn garbage_mode() {
+        let options = Bodyfile3ParserOptions::default().with_mode_validation(true);
+
+        // the mode column is missing, so a part of the name ends up as inode
+        // and the inode ends up as mode
+        let line = "0|ls -l | wc|12|0|0|42|-1|1|-1|-1";
+        assert!(Bodyfile3Line::try_from(line).is_ok());
+        assert_matches!(Bodyfile3Line::parse(line, &options), Err(Bodyfile3ParserError::IllegalMode));
+
+        for mode in ["r/rrwx", "r/rrwxrwxrwxr", "rrwxrwxrwx", "r|rrwxrwxrwx", "r/rrwxrwxrw?"] {
+            let line = format!("0|/tmp/a|12|{mode}|0|0|42|-1|1|-1|-1");
+            assert_matches!(Bodyfile3Line::parse(&line, &options), Err(Bodyfile3ParserError::IllegalMode));
+        }
+    }
+
+    #[test]
+    fn validate_clean_line() {
+        let bf = Bodyfile3Line::try_from(
+            "4bad420da66571dac7f1ace995cc55c6|/tmp/a|12|r/rrwxr-xr-x|0|0|42|-1|1|-1|1609459200",
+        )
+        .unwrap();
+        assert!(bf.validate().is_empty());
+        assert!(Bodyfile3Line::new().validate().is_empty());
+    }
+
+    #[test]
+    fn validate_multiple_issues() {
+        let bf = Bodyfile3Line::new()
+            .with_md5("4bad420da66571dac7f1ace995cc55")
+            .with_mode("rwxr-xr-x")
+            .with_atime(-2)
+            .with_mtime(1)
+            .with_crtime(i64::MAX);
+
+        let issues = bf.validate();
+        assert_eq!(issues.len(), 4);
+        assert_matches!(issues[0], Bodyfile3ParserError::IllegalHash);
+        assert_matches!(issues[1], Bodyfile3ParserError::IllegalMode);
+        assert_matches!(issues[2], Bodyfile3ParserError::IllegalATime);
+        assert_matches!(issues[3], Bodyfile3ParserError::IllegalCRTime);
+    }
+
+    #[test]
+    fn suspicious_timestamp_unit() {
+        let bf = Bodyfile3Line::new().with_mtime(1609459200000);
+        assert!(bf.validate().is_empty());
+        assert_eq!(
+            bf.warnings(),
+            vec![Bodyfile3Warning::SuspiciousTimestampUnit {
+                kind: TimestampKind::Mtime,
+                timestamp: 1609459200000
+            }]
+        );
+
+        let bf = Bodyfile3Line::new().with_mtime(1609459200);
+        assert!(bf.warnings().is_empty());
+        assert!(Bodyfile3Line::new().warnings().is_empty());
+    }
+
+    #[test]
+    fn strip_mount_point() {
+        let names = [
+            ("/mnt/c/Windows/System32/cmd.exe", "/Windows/System32/cmd.exe"),
+            ("/mnt/c/Users/jdoe/NTUSER.DAT", "/Users/jdoe/NTUSER.DAT"),
+            ("/mnt/c", "/"),
+            ("/mnt/cdrom/autorun.inf", "/mnt/cdrom/autorun.inf"),
+            ("/var/log/syslog", "/var/log/syslog"),
+        ];
+        for (name, expected) in names {
+            let mut bf = Bodyfile3Line::new().with_name(name);
+            assert_eq!(bf.strip_name_prefix("/mnt/c"), name != expected);
+            assert_eq!(bf.get_name(), expected);
+
+            let mut bf = Bodyfile3Line::new().with_name(name);
+            bf.strip_name_prefix("/mnt/c/");
+            assert_eq!(bf.get_name(), expected);
+        }
+    }
+
+    #[test]
+    fn clone_with_timestamp() {
+        let bf = Bodyfile3Line::new()
+            .with_name("/etc/passwd")
+            .with_mtime(1609459200)
+            .with_ctime(1609459200);
+        let normalized = bf.clone().with_timestamp(TimestampKind::Ctime, 1609459300);
+
+        assert_eq!(*bf.get_ctime(), 1609459200);
+        assert_eq!(*normalized.get_ctime(), 1609459300);
+        assert_eq!(normalized.get_name(), bf.get_name());
+        assert_eq!(normalized.get_mtime(), bf.get_mtime());
+    }
+
+    /// names of bodyfile lines created by `evtx2bodyfile` from PowerShell and
+    /// ETW events, which contain JSON with pipe symbols
+    const JSON_NAMES: [&str; 5] = [
+        r#"{"event_record_id":1842,"timestamp":"2023-03-01T10:15:00Z","event_id":4104,"level":5,"computer":"WS01","provider_name":"Microsoft-Windows-PowerShell","channel_name":"Microsoft-Windows-PowerShell/Operational","activity_id":null,"custom_data":{"EventData":{"ScriptBlockText":"Get-Process | Where-Object {$_.CPU -gt 100} | Select-Object -First 5 | Format-Table"}}}"#,
+        r#"{"event_id":4688,"custom_data":{"EventData":{"CommandLine":"cmd.exe /c echo 0|0|0|-1|-1|-1|-1"}}}"#,
+        r#"{"event_id":800,"custom_data":{"EventData":{"Payload":"a|","Extra":["|","||",{"x":"|1|2|3|4|5|6|7|8|9"}]}}}"#,
+        r#"{"event_id":4103,"custom_data":{"UserData":{"ContextInfo":"Host Application = powershell.exe -c \"dir | % { $_.Name }\"\r\n"}}}|"#,
+        r#"|{"provider_name":"Microsoft-Windows-Kernel-Process","custom_data":{"ImageName":"\\Device\\HarddiskVolume3\\a|b.exe"}}"#,
+    ];
+
+    #[test]
+    fn json_in_name() {
+        for (idx, name) in JSON_NAMES.iter().enumerate() {
+            let mtime = 1677665700 + idx as i64;
+            for (inode, mode) in [("0", ""), ("87915-128-1", "r/rrwxrwxrwx")] {
+                let line = format!("0|{name}|{inode}|{mode}|0|0|0|-1|{mtime}|-1|-1");
+                let bf = Bodyfile3Line::try_from(line.as_str()).unwrap();
+                assert_eq!(bf.get_name(), name);
+                assert_eq!(bf.get_inode(), inode);
+                assert_eq!(bf.get_mode_as_string(), mode);
+                assert_eq!(*bf.get_mtime(), mtime);
+                assert_eq!(bf.to_string(), line);
+
+                let options = Bodyfile3ParserOptions::default().with_mode_validation(true);
+                assert_eq!(Bodyfile3Line::parse(&line, &options).unwrap().get_name(), name);
+            }
+        }
+    }
+
+    /// padding missing timestamps is ambiguous for names with pipe symbols,
+    /// because the surplus columns might belong to the name or to the missing
+    /// timestamps. Complete lines must not be affected by padding; in this
+    /// short line, the pipe is taken as column separator and the line is
+    /// rejected, because the mode ends up in the `uid` column.
+    #[test]
+    fn json_in_name_with_padding() {
+        let options = Bodyfile3ParserOptions::default().with_padding(true);
+        for name in JSON_NAMES {
+            let line = format!("0|{name}|0||0|0|0|-1|1677665700|-1|-1");
+            assert_eq!(Bodyfile3Line::parse(&line, &options).unwrap().get_name(), name);
+        }
+
+        assert_matches!(
+            Bodyfile3Line::parse(r#"0|{"a":"x|y"}|0||0|0|4|5|6"#, &options),
+            Err(Bodyfile3ParserError::IllegalUid)
+        );
+    }
+
+    #[test]
+    fn padded_numbers() {
+        let line = "0||0|| 3 |4|5|-1|-1|-1|-1";
+        let bf = Bodyfile3Line::try_from_lenient(line).unwrap();
+        assert_eq!(*bf.get_uid(), 3);
+        assert_eq!(*bf.get_gid(), 4);
+        assert_eq!(*bf.get_size(), 5);
+        assert_matches!(Bodyfile3Line::try_from(line), Err(Bodyfile3ParserError::IllegalUid));
+
+        let line = "0|sample.txt|0||0|\t500|   12|  1609459200|-1|-1|-1";
+        let bf = Bodyfile3Line::try_from_lenient(line).unwrap();
+        assert_eq!(*bf.get_gid(), 500);
+        assert_eq!(*bf.get_size(), 12);
+        assert_eq!(*bf.get_atime(), 1609459200);
+        assert_matches!(Bodyfile3Line::try_from(line), Err(Bodyfile3ParserError::IllegalGid));
+    }
+
+    #[test]
+    fn checked_setters() {
+        let mut bf = Bodyfile3Line::new();
+        assert_matches!(bf.try_set_atime(-5), Err(Bodyfile3ParserError::IllegalATime));
+        assert_eq!(*bf.get_atime(), -1);
+        assert!(bf.try_set_atime(5).is_ok());
+        assert_eq!(*bf.get_atime(), 5);
+
+        assert!(bf.try_set_mtime(-1).is_ok());
+        assert_matches!(bf.try_set_ctime(i64::MAX), Err(Bodyfile3ParserError::IllegalCTime));
+        assert_matches!(bf.try_set_crtime(-2), Err(Bodyfile3ParserError::IllegalCRTime));
+
+        // the line can be parsed again
+        let parsed = Bodyfile3Line::try_from(bf.to_string().as_str()).unwrap();
+        assert_eq!(parsed.fields(), bf.fields());
+    }
+
+    #[test]
+    fn canonical_inode() {
+        let tsk = Bodyfile3Line::try_from("0|/a|87915-128-1|r/rrwxrwxrwx|0|0|0|-1|-1|-1|-1").unwrap();
+        let padded = Bodyfile3Line::try_from("0|/a|087915-128-001|r/rrwxrwxrwx|0|0|0|-1|-1|-1|-1").unwrap();
+        let with_stream = Bodyfile3Line::new().with_inode("87915-128-1:$DATA");
+        assert_eq!(tsk.canonical_inode(), "87915-128-1");
+        assert_eq!(padded.canonical_inode(), tsk.canonical_inode());
+        assert_eq!(with_stream.canonical_inode(), tsk.canonical_inode());
+
+        // named streams are different attributes and must be kept
+        let ads = Bodyfile3Line::new().with_inode("87915-128-4:Zone.Identifier");
+        assert_eq!(ads.canonical_inode(), "87915-128-4:Zone.Identifier");
+
+        assert_eq!(Bodyfile3Line::new().with_inode("0").canonical_inode(), "0");
+        assert_eq!(Bodyfile3Line::new().with_inode("").canonical_inode(), "");
+    }
+
+    #[test]
+    fn column_count_errors() {
+        let ten_columns = "0|sample.txt|0||0|0|0|-1|-1|-1";
+        let error = Bodyfile3Line::try_from(ten_columns).unwrap_err();
+        assert_matches!(error, Bodyfile3ParserError::TooFewColumns { found: 10 });
+        assert!(error.is_wrong_number_of_columns());
+
+        let twelve_columns = "0|sample.txt|x|0||0|0|0|-1|-1|-1|-1";
+        let strict = Bodyfile3ParserOptions::default().with_strict_columns(true);
+        let error = Bodyfile3Line::parse(twelve_columns, &strict).unwrap_err();
+        assert_matches!(error, Bodyfile3ParserError::TooManyColumns { found: 12 });
+        assert!(error.is_wrong_number_of_columns());
+
+        // the deprecated variant is still recognized, but never returned
+        #[allow(deprecated)]
+        let deprecated = Bodyfile3ParserError::WrongNumberOfColumns;
+        assert!(deprecated.is_wrong_number_of_columns());
+        assert_eq!(deprecated.kind(), "WrongNumberOfColumns");
+
+        // without strict mode, the surplus column is part of the name
+        assert_eq!(
+            Bodyfile3Line::try_from(twelve_columns).unwrap().get_name(),
+            "sample.txt|x"
+        );
+    }
+
+    /// the column splitting of the parser before it used `memchr`, which is
+    /// kept to verify that the optimized parser splits lines identically
+    fn split_with_vec(line: &str, options: (bool, bool)) -> Result<Vec<&str>, usize> {
+        let (pad, strict) = options;
+        let mut parts: Vec<&str> = line.split('|').collect();
+        if pad && (7..11).contains(&parts.len()) {
+            parts.resize(11, "-1");
+        }
+        if parts.len() < 11 || (strict && parts.len() > 11) {
+            return Err(parts.len());
+        }
+        let (head, tail) = parts.split_at(parts.len() - 9);
+        let name_start = head[0].len() + 1;
+        let name_len = head[1..].iter().map(|p| p.len()).sum::<usize>() + head.len() - 2;
+        let mut columns = vec![head[0], &line[name_start..name_start + name_len]];
+        columns.extend_from_slice(tail);
+        Ok(columns)
+    }
+
+    #[test]
+    fn memchr_parse_equivalence() {
+        let names = [
+            "",
+            "sample.txt",
+            "/Windows/System32/notepad.exe",
+            "ls -l | grep foo | wc -l",
+            "|",
+            "|||",
+            "a||b|",
+            "{\"a\":\"x|y\",\"b\":[1,2]}",
+            "C:/Users/J\u{fc}rgen/\u{e4}|\u{f6}.txt",
+        ];
+        let tails = [
+            "|87915-128-1|r/rrwxrwxrwx|1003|500|126378|12341|12342|12343|12344",
+            "|0||0|0|0|-1|-1|-1|-1",
+            "|0||0|0|0|-1|-1|-1",
+            "|0||0|0|0|-1|-1",
+            "|0||0|0|0|-1",
+            "|0||0|0|0",
+            "|0||0|0",
+            "|0||0",
+        ];
+        let options = [(false, false), (true, false), (false, true), (true, true)];
+
+        for name in names {
+            for tail in tails {
+                let line = format!("d41d8cd98f00b204e9800998ecf8427e|{name}{tail}");
+                for (pad, strict) in options {
+                    let parser_options = Bodyfile3ParserOptions::default()
+                        .with_padding(pad)
+                        .with_strict_columns(strict);
+                    let expected = split_with_vec(&line, (pad, strict));
+                    let result = Bodyfile3Line::parse(&line, &parser_options);
+                    match (expected, result) {
+                        (Ok(columns), Ok(bf)) => {
+                            assert_eq!(bf.get_md5(), columns[0], "{line}");
+                            assert_eq!(bf.get_name(), columns[1], "{line}");
+                            assert_eq!(bf.get_inode(), columns[2], "{line}");
+                            assert_eq!(bf.get_mode_as_string(), columns[3], "{line}");
+                            assert_eq!(bf.get_uid().to_string(), columns[4], "{line}");
+                            assert_eq!(bf.get_crtime().to_string(), columns[10], "{line}");
+                        }
+                        // the columns are split identically, but the surplus
+                        // columns of the name shifted a non-numeric value into
+                        // one of the numeric columns
+                        (Ok(columns), Err(error)) => {
+                            assert!(!error.is_wrong_number_of_columns(), "{line}");
+                            assert!(
+                                columns[4..].iter().any(|c| c.parse::<i64>().is_err()),
+                                "{line}: {error:?}"
+                            );
+                        }
+                        (Err(found), Err(Bodyfile3ParserError::TooFewColumns { found: f }))
+                        | (Err(found), Err(Bodyfile3ParserError::TooManyColumns { found: f })) => {
+                            assert_eq!(found, f, "{line}")
+                        }
+                        (expected, result) => {
+                            panic!("{line}: expected {expected:?}, found {result:?}")
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn file_metadata() {
+        let bf = Bodyfile3Line::try_from(
+            "0|C:/Users/jdoe/NTUSER.DAT|93552-128-2|r/rrw-rwSr-T|1003|500|262144|1577092511|1577092512|1577092513|-1",
+        )
+        .unwrap();
+        let metadata = FileMetadata::from(&bf);
+        assert_eq!(metadata.name, "C:/Users/jdoe/NTUSER.DAT");
+        assert_eq!(metadata.inode, "93552-128-2");
+        assert_eq!(
+            metadata.mft_reference,
+            Some(MftReference {
+                entry: 93552,
+                attr_type: 128,
+                sequence: 2
+            })
+        );
+        assert_eq!(metadata.entry_type, EntryType::File);
+        assert_eq!(metadata.permissions, Some(0o3664));
+        assert_eq!(metadata.size, 262144);
+        assert_eq!(metadata.uid, 1003);
+        assert_eq!(metadata.gid, 500);
+        assert_eq!(metadata.accessed.unwrap().timestamp(), 1577092511);
+        assert_eq!(metadata.modified.unwrap().timestamp(), 1577092512);
+        assert_eq!(metadata.changed.unwrap().timestamp(), 1577092513);
+        assert_eq!(metadata.created, None);
+
+        let bf = Bodyfile3Line::new().with_name("d//etc").with_inode("12");
+        let metadata = FileMetadata::from(&bf);
+        assert_eq!(metadata.entry_type, EntryType::Directory);
+        assert_eq!(metadata.mft_reference, None);
+        assert_eq!(metadata.permissions, None);
+        assert_eq!(metadata.modified, None);
+
+        let bf = Bodyfile3Line::new().with_mode("d/drwxr-xq-x");
+        assert_eq!(FileMetadata::from(&bf).permissions, None);
+    }
+
+    #[test]
+    fn notes_column() {
+        let options = Bodyfile3ParserOptions::default().with_notes_column(true);
+
+        let eleven_columns = "0|/tmp/a|12|r/rrw-r--r--|0|0|42|-1|1609459200|-1|-1";
+        let bf = Bodyfile3Line::parse(eleven_columns, &options).unwrap();
+        assert_eq!(bf.get_name(), "/tmp/a");
+        assert_eq!(*bf.get_crtime(), -1);
+        assert_eq!(*bf.get_notes(), None);
+        assert_eq!(bf.to_string(), eleven_columns);
+
+        let twelve_columns = "0|/tmp/a|12|r/rrw-r--r--|0|0|42|-1|1609459200|-1|-1|found in ~/.cache";
+        let bf = Bodyfile3Line::parse(twelve_columns, &options).unwrap();
+        assert_eq!(bf.get_name(), "/tmp/a");
+        assert_eq!(*bf.get_mtime(), 1609459200);
+        assert_eq!(*bf.get_crtime(), -1);
+        assert_eq!(bf.get_notes().as_deref(), Some("found in ~/.cache"));
+        assert_eq!(bf.to_string(), twelve_columns);
+
+        // names with pipe symbols are split correctly if the line has notes
+        let bf = Bodyfile3Line::parse("0|a|b|0||0|0|0|-1|-1|-1|-1|", &options).unwrap();
+        assert_eq!(bf.get_name(), "a|b");
+        assert_eq!(bf.get_notes().as_deref(), Some(""));
+
+        let strict = options.with_strict_columns(true);
+        assert_matches!(
+            Bodyfile3Line::parse("0|a|b|0||0|0|0|-1|-1|-1|-1|x", &strict),
+            Err(Bodyfile3ParserError::TooManyColumns { found: 13 })
+        );
+
+        // the default parser treats the surplus column as part of the name,
+        // so that the remaining columns are shifted
+        assert_matches!(
+            Bodyfile3Line::try_from(twelve_columns),
+            Err(Bodyfile3ParserError::IllegalSize)
+        );
+    }
+
+    #[test]
+    fn sanitized_name() {
+        let bf = Bodyfile3Line::new()
+            .with_name_sanitized("C:/Users/jdoe/bell\x07\r\n.txt")
+            .with_mtime(1609459200);
+        assert_eq!(bf.get_name(), "C:/Users/jdoe/bell\\x07\\x0d\\x0a.txt");
+        assert_eq!(bf.raw_name(), "C:/Users/jdoe/bell\x07\r\n.txt");
+
+        // the written line contains only the sanitized name
+        assert_eq!(
+            bf.to_string(),
+            "0|C:/Users/jdoe/bell\\x07\\x0d\\x0a.txt|0||0|0|0|-1|1609459200|-1|-1"
+        );
+
+        // names without control characters are not changed
+        let bf = Bodyfile3Line::new().with_name_sanitized("C:\\Windows\\notepad.exe");
+        assert_eq!(bf.get_name(), "C:\\Windows\\notepad.exe");
+        assert_eq!(bf.raw_name(), bf.get_name());
+    }
+
+    #[test]
+    fn sanitized_name_is_discarded_by_name_changes() {
+        let sanitized = Bodyfile3Line::new().with_name_sanitized("C:/Users/jdoe/bell\x07.txt");
+
+        // the redacted user name must not be revealed by the original name
+        let mut bf = sanitized.clone();
+        bf.redact_users(&mut HashMap::new());
+        assert_eq!(bf.get_name(), "C:/Users/user001/bell\\x07.txt");
+        assert_eq!(bf.raw_name(), bf.get_name());
+
+        let mut bf = sanitized.clone();
+        assert!(bf.strip_name_prefix("C:/Users"));
+        assert_eq!(bf.raw_name(), "/jdoe/bell\\x07.txt");
+
+        let mut bf = sanitized.clone();
+        bf.set_name("/tmp/a".to_owned());
+        assert_eq!(bf.raw_name(), "/tmp/a");
+        assert_eq!(sanitized.clone().with_name("/tmp/b").raw_name(), "/tmp/b");
+
+        // merging keeps the name, so the original name is still valid
+        let mut bf = sanitized;
+        bf.merge(&Bodyfile3Line::new().with_name(bf.get_name()).with_mtime(1))
+            .unwrap();
+        assert_eq!(bf.raw_name(), "C:/Users/jdoe/bell\x07.txt");
+    }
+
+    #[test]
+    fn md5_placeholder() {
+        for placeholder in ["", "-"] {
+            let config = Bodyfile3Config::default().with_md5_placeholder(placeholder);
+            let bf = config.new_line().with_name("/tmp/a").with_mtime(1609459200);
+            assert_eq!(bf.get_md5(), placeholder);
+            assert!(!config.has_hash(&bf));
+            assert_eq!(
+                bf.to_string(),
+                format!("{placeholder}|/tmp/a|0||0|0|0|-1|1609459200|-1|-1")
+            );
+
+            // the placeholder survives writing and parsing the line
+            let parsed = Bodyfile3Line::try_from(bf.to_string().as_str()).unwrap();
+            assert_eq!(parsed.get_md5(), placeholder);
+            assert!(!config.has_hash(&parsed));
+            assert_eq!(parsed.to_string(), bf.to_string());
+
+            // the placeholder is neither validated nor anonymized as a hash,
+            // and it is replaced by the hash of a merged line
+            assert!(config.validate(&bf).is_empty());
+            let mut anonymized = bf.clone();
+            config.anonymize_hashes(&mut anonymized, b"secret");
+            assert_eq!(anonymized.get_md5(), placeholder);
+            let mut merged = bf.clone();
+            let other = config
+                .new_line()
+                .with_name("/tmp/a")
+                .with_md5("b1946ac92492d2347c6235b4d2611184");
+            assert!(config.merge(&mut merged, &other).unwrap().is_empty());
+            assert_eq!(merged.get_md5(), "b1946ac92492d2347c6235b4d2611184");
+
+            let bf = bf.with_md5("b1946ac92492d2347c6235b4d2611184");
+            assert!(config.has_hash(&bf));
+            assert!(bf.to_string().starts_with("b1946ac92492d2347c6235b4d2611184|"));
+        }
+
+        // the default configuration uses the placeholder of TSK
+        let config = Bodyfile3Config::default();
+        assert_eq!(config.md5_placeholder(), "0");
+        assert!(!config.has_hash(&Bodyfile3Line::new()));
+        assert!(!Bodyfile3Line::new().has_hash());
+        assert!(config.has_hash(&Bodyfile3Line::new().with_md5("-")));
+    }
 }
@@ -0,0 +1,17 @@
+use clap::ValueEnum;
+
+/// specifies how readers handle lines which cannot be parsed, e.g. banners
+/// like `=== Volume C: ===` which collector wrappers inject into the stream
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnUnparseable {
+    /// report every unparseable line as an error
+    #[default]
+    Error,
+
+    /// skip unparseable lines, and only report their number
+    Skip,
+
+    /// skip unparseable lines, but keep them, so that they can be reviewed
+    /// after the stream has been read
+    Collect,
+}
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Bodyfile3Line;
+
+/// MFT entry of the root directory of every NTFS volume
+const ROOT_ENTRY: u64 = 5;
+
+///
+/// Reconstructs full paths in bodyfiles whose names are relative to the
+/// parent directory, which is referenced by its MFT entry number (e.g.
+/// `1234/report.docx`). The MFT entries of all directories are taken from
+/// the inode column (see [`Bodyfile3Line::mft_reference`]), so the resolver
+/// must be created from the complete set of lines. Entry `5` is the root
+/// directory of the volume.
+///
+/// Names which cannot be resolved, because a parent is missing or because
+/// the parent references form a cycle, are left unchanged and a warning is
+/// logged.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, PathResolver};
+///
+/// let mut lines = vec![
+///     Bodyfile3Line::new().with_name("5/Users").with_inode("64-144-1"),
+///     Bodyfile3Line::new().with_name("64/jdoe").with_inode("70-144-1"),
+///     Bodyfile3Line::new().with_name("70/notes.txt").with_inode("81-128-2"),
+/// ];
+/// PathResolver::reconstruct_paths(&mut lines);
+/// assert_eq!(lines[2].get_name(), "/Users/jdoe/notes.txt");
+/// ```
+pub struct PathResolver {
+    /// name of every MFT entry, as found in the bodyfile
+    names: HashMap<u64, String>,
+}
+
+impl PathResolver {
+    /// collects the names of all MFT entries. If there are multiple lines
+    /// for the same entry, the name of the first line is used.
+    pub fn new<'a, I: IntoIterator<Item = &'a Bodyfile3Line>>(lines: I) -> Self {
+        let mut names = HashMap::new();
+        for bf_line in lines {
+            if let Some(reference) = bf_line.mft_reference() {
+                names
+                    .entry(reference.entry)
+                    .or_insert_with(|| bf_line.get_name().clone());
+            }
+        }
+        Self { names }
+    }
+
+    /// replaces all relative names in `lines` by their full paths
+    pub fn reconstruct_paths(lines: &mut [Bodyfile3Line]) {
+        let resolver = Self::new(lines.iter());
+        for bf_line in lines.iter_mut() {
+            if let Some(path) = resolver.full_path(bf_line.get_name()) {
+                bf_line.set_name(path);
+            }
+        }
+    }
+
+    /// returns the full path of `name`, or `None` if `name` is no relative
+    /// name or cannot be resolved
+    pub fn full_path(&self, name: &str) -> Option<String> {
+        let mut components = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = name;
+        let base = loop {
+            match Self::split_parent(current) {
+                None if components.is_empty() => return None,
+                None => break current.trim_end_matches(['/', '\\']),
+                Some((parent, file_name)) => {
+                    components.push(file_name);
+                    if parent == ROOT_ENTRY {
+                        break "";
+                    }
+                    if !visited.insert(parent) {
+                        log::warn!("unable to resolve '{name}': cycle at MFT entry {parent}");
+                        return None;
+                    }
+                    current = match self.names.get(&parent) {
+                        Some(parent_name) => parent_name,
+                        None => {
+                            log::warn!("unable to resolve '{name}': missing MFT entry {parent}");
+                            return None;
+                        }
+                    }
+                }
+            }
+        };
+
+        let mut path = base.to_owned();
+        for component in components.iter().rev() {
+            path.push('/');
+            path.push_str(component);
+        }
+        Some(path)
+    }
+
+    /// splits a relative name into the MFT entry of its parent and the
+    /// remaining name
+    fn split_parent(name: &str) -> Option<(u64, &str)> {
+        let (parent, file_name) = name.split_once('/')?;
+        if file_name.is_empty() || parent.is_empty() || !parent.bytes().all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+        Some((parent.parse().ok()?, file_name))
+    }
+}
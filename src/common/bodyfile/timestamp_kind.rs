@@ -0,0 +1,26 @@
+use clap::ValueEnum;
+
+/// identifies one of the four timestamps of a bodyfile line
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimestampKind {
+    /// last access time
+    #[value(name = "a", alias = "atime")]
+    Atime,
+
+    /// last modification time
+    #[value(name = "m", alias = "mtime")]
+    Mtime,
+
+    /// last change time of metadata
+    #[value(name = "c", alias = "ctime")]
+    Ctime,
+
+    /// creation time
+    #[value(name = "b", alias = "crtime")]
+    Crtime,
+}
+
+impl TimestampKind {
+    /// all timestamp kinds in the order of the bodyfile columns
+    pub const ALL: [TimestampKind; 4] = [Self::Atime, Self::Mtime, Self::Ctime, Self::Crtime];
+}
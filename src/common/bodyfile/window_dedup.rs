@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use super::{Bodyfile3Fields, Bodyfile3Line};
+
+/// removes duplicate lines from a stream of bodyfile lines, while keeping
+/// only the last `window` lines in memory.
+///
+/// # Precondition
+///
+/// The input must be sorted by `mtime`. Duplicates have the same `mtime`
+/// and therefore appear next to each other in a sorted stream, so it
+/// suffices to compare every line with the most recent lines only. If the
+/// input is not sorted, duplicates which are more than `window` lines apart
+/// will not be detected and are passed through unchanged.
+///
+/// Lines are considered equal if all of their fields are equal. Use
+/// [`WindowDedup::with_canonical_inodes`] to compare the inode columns in
+/// their canonical form (see [`Bodyfile3Line::canonical_inode`]).
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, WindowDedup};
+///
+/// let lines = vec![
+///     Bodyfile3Line::new().with_name("a").with_mtime(1),
+///     Bodyfile3Line::new().with_name("a").with_mtime(1),
+///     Bodyfile3Line::new().with_name("b").with_mtime(2),
+/// ];
+/// let deduped: Vec<_> = WindowDedup::new(lines.into_iter(), 8)
+///     .map(|l| l.to_string())
+///     .collect();
+/// assert_eq!(deduped.len(), 2);
+/// ```
+pub struct WindowDedup<I> {
+    inner: I,
+    window: usize,
+    canonical_inodes: bool,
+    recent: VecDeque<Bodyfile3Fields>,
+}
+
+impl<I> WindowDedup<I>
+where
+    I: Iterator<Item = Bodyfile3Line>,
+{
+    /// creates a new deduplicating iterator. A `window` of `0` is treated
+    /// like a window of `1`, i.e. only directly adjacent duplicates are
+    /// removed.
+    pub fn new(inner: I, window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            inner,
+            window,
+            canonical_inodes: false,
+            recent: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// compare the inode columns in their canonical form, so that lines
+    /// written by different tools are detected as duplicates as well
+    pub fn with_canonical_inodes(mut self, canonical_inodes: bool) -> Self {
+        self.canonical_inodes = canonical_inodes;
+        self
+    }
+}
+
+impl<I> Iterator for WindowDedup<I>
+where
+    I: Iterator<Item = Bodyfile3Line>,
+{
+    type Item = Bodyfile3Line;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in self.inner.by_ref() {
+            let mut fields = line.fields();
+            if self.canonical_inodes {
+                fields.inode = line.canonical_inode();
+            }
+            if self.recent.contains(&fields) {
+                log::trace!("dropping duplicate line '{line}'");
+                continue;
+            }
+
+            if self.recent.len() == self.window {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(fields);
+            return Some(line);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowDedup;
+    use crate::common::bodyfile::Bodyfile3Line;
+
+    fn line(name: &str, mtime: i64) -> Bodyfile3Line {
+        Bodyfile3Line::new().with_name(name).with_mtime(mtime)
+    }
+
+    fn dedup(lines: Vec<Bodyfile3Line>, window: usize) -> Vec<String> {
+        WindowDedup::new(lines.into_iter(), window)
+            .map(|l| l.get_name().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn adjacent_duplicates_are_removed() {
+        let lines = vec![
+            line("a", 1),
+            line("a", 1),
+            line("b", 2),
+            line("c", 2),
+            line("b", 2),
+            line("c", 2),
+            line("d", 3),
+        ];
+        assert_eq!(dedup(lines, 2), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn duplicates_outside_of_window_are_kept() {
+        let lines = vec![
+            line("a", 1),
+            line("b", 1),
+            line("c", 1),
+            line("a", 1),
+        ];
+        assert_eq!(dedup(lines, 2), vec!["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn canonical_inodes() {
+        let lines = vec![
+            line("a", 1).with_inode("87915-128-1"),
+            line("a", 1).with_inode("087915-128-01"),
+            line("a", 1).with_inode("87915-128"),
+        ];
+        let deduped = WindowDedup::new(lines.clone().into_iter(), 2).count();
+        assert_eq!(deduped, 3);
+
+        let deduped = WindowDedup::new(lines.into_iter(), 2)
+            .with_canonical_inodes(true)
+            .count();
+        assert_eq!(deduped, 1);
+    }
+
+    #[test]
+    fn lines_with_different_timestamps_are_kept() {
+        let lines = vec![line("a", 1), line("a", 2), line("a", 2)];
+        assert_eq!(dedup(lines, 0), vec!["a", "a"]);
+    }
+}
@@ -0,0 +1,184 @@
+//! Canonical mapping of a [`Bodyfile3Line`] to fields of the
+//! [Elastic Common Schema](https://www.elastic.co/guide/en/ecs/current/index.html).
+//!
+//! | bodyfile column  | ECS field        |
+//! |------------------|------------------|
+//! | `md5`            | `file.hash.md5` (omitted if `0`) |
+//! | `name`           | `file.path`, `file.name`, `file.directory` |
+//! | `inode`          | `file.inode`     |
+//! | `mode_as_string` | `file.mode`      |
+//! | `uid`            | `user.id`        |
+//! | `gid`            | `group.id`       |
+//! | `size`           | `file.size`      |
+//! | `atime`          | `file.accessed`  |
+//! | `mtime`          | `file.mtime`     |
+//! | `ctime`          | `file.ctime`     |
+//! | `crtime`         | `file.created`   |
+//!
+//! A single row of a timeline, i.e. a file together with one of its
+//! timestamps, additionally gets `@timestamp` and `event.start`, which are
+//! set to the timestamp of the row (see [`to_ecs_event_document`]).
+//! `event.created` is deliberately not used for the creation time of the
+//! file, because ECS defines it as the time when the event has been
+//! collected.
+//!
+//! Timestamps are expressed in milliseconds since the epoch (which is the
+//! format used by the `es4forensics` index mapping). Unset timestamps (`-1`)
+//! are omitted.
+
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use crate::common::bodyfile::{Bodyfile3Line, TimestampKind};
+
+/// converts a bodyfile line into an ECS document
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+/// use dfir_toolkit::common::ecs::to_ecs_document;
+/// use serde_json::json;
+///
+/// let bf_line = Bodyfile3Line::try_from(
+///     "4bad420da66571dac7f1ace995cc55c6|/tmp/sample.txt|87915-128-1|r/rrwxrwxrwx|1003|500|126378|12341|12342|-1|-1").unwrap();
+/// assert_eq!(to_ecs_document(&bf_line), json!({
+///     "file": {
+///         "path": "/tmp/sample.txt",
+///         "name": "sample.txt",
+///         "directory": "/tmp",
+///         "extension": "txt",
+///         "inode": "87915-128-1",
+///         "mode": "r/rrwxrwxrwx",
+///         "size": 126378,
+///         "hash": { "md5": "4bad420da66571dac7f1ace995cc55c6" },
+///         "accessed": 12341000,
+///         "mtime": 12342000,
+///     },
+///     "user": { "id": "1003" },
+///     "group": { "id": "500" },
+/// }));
+/// ```
+pub fn to_ecs_document(line: &Bodyfile3Line) -> Value {
+    let mut file = Map::new();
+    let path = Path::new(line.get_name());
+
+    file.insert("path".into(), json!(line.get_name()));
+    if let Some(name) = path.file_name() {
+        file.insert("name".into(), json!(name.to_string_lossy()));
+    }
+    if let Some(directory) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        file.insert("directory".into(), json!(directory.to_string_lossy()));
+    }
+    if let Some(extension) = path.extension() {
+        file.insert("extension".into(), json!(extension.to_string_lossy()));
+    }
+    file.insert("inode".into(), json!(line.get_inode()));
+    file.insert("mode".into(), json!(line.get_mode_as_string()));
+    file.insert("size".into(), json!(line.get_size()));
+
+    if line.get_md5() != "0" {
+        file.insert("hash".into(), json!({ "md5": line.get_md5() }));
+    }
+
+    for kind in TimestampKind::ALL {
+        if let Some(ts) = timestamp_millis(line, kind) {
+            file.insert(file_field(kind).into(), json!(ts));
+        }
+    }
+
+    json!({
+        "file": file,
+        "user": { "id": line.get_uid().to_string() },
+        "group": { "id": line.get_gid().to_string() },
+    })
+}
+
+/// converts a single row of a timeline into an ECS document, which is the
+/// document created by [`to_ecs_document`] with `@timestamp` and
+/// `event.start` set to the timestamp `kind` of the line. Returns `None` if
+/// this timestamp is not set.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::bodyfile::{Bodyfile3Line, TimestampKind};
+/// use dfir_toolkit::common::ecs::to_ecs_event_document;
+/// use serde_json::json;
+///
+/// let bf_line = Bodyfile3Line::try_from("0|/tmp/a|1|r/rrw-r--r--|0|0|0|100|200|-1|-1").unwrap();
+/// let doc = to_ecs_event_document(&bf_line, TimestampKind::Mtime).unwrap();
+/// assert_eq!(doc["@timestamp"], json!(200000));
+/// assert_eq!(doc["event"]["start"], json!(200000));
+/// assert_eq!(doc["file"]["accessed"], json!(100000));
+///
+/// assert!(to_ecs_event_document(&bf_line, TimestampKind::Crtime).is_none());
+/// ```
+pub fn to_ecs_event_document(line: &Bodyfile3Line, kind: TimestampKind) -> Option<Value> {
+    let ts = timestamp_millis(line, kind)?;
+    let mut doc = to_ecs_document(line);
+    let fields = doc.as_object_mut()?;
+    fields.insert("@timestamp".into(), json!(ts));
+    fields.insert("event".into(), json!({ "start": ts }));
+    Some(doc)
+}
+
+/// name of the field of the `file` object which holds the timestamp `kind`
+fn file_field(kind: TimestampKind) -> &'static str {
+    match kind {
+        TimestampKind::Atime => "accessed",
+        TimestampKind::Mtime => "mtime",
+        TimestampKind::Ctime => "ctime",
+        TimestampKind::Crtime => "created",
+    }
+}
+
+fn timestamp_millis(line: &Bodyfile3Line, kind: TimestampKind) -> Option<i64> {
+    match line.get_timestamp(kind) {
+        -1 => None,
+        ts => Some(ts * 1000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{to_ecs_document, to_ecs_event_document};
+    use crate::common::bodyfile::{Bodyfile3Line, TimestampKind};
+
+    #[test]
+    fn timestamps_are_mapped_to_their_fields() {
+        // every timestamp column has a distinct value
+        let bf_line = Bodyfile3Line::try_from("0|/tmp/a|1|r/rrw-r--r--|0|0|0|1|2|3|4").unwrap();
+        let doc = to_ecs_document(&bf_line);
+        assert_eq!(doc["file"]["accessed"], json!(1000));
+        assert_eq!(doc["file"]["mtime"], json!(2000));
+        assert_eq!(doc["file"]["ctime"], json!(3000));
+        assert_eq!(doc["file"]["created"], json!(4000));
+        assert!(doc.get("@timestamp").is_none());
+        assert!(doc.get("event").is_none());
+
+        for (kind, expected) in [
+            (TimestampKind::Atime, 1000),
+            (TimestampKind::Mtime, 2000),
+            (TimestampKind::Ctime, 3000),
+            (TimestampKind::Crtime, 4000),
+        ] {
+            let doc = to_ecs_event_document(&bf_line, kind).unwrap();
+            assert_eq!(doc["@timestamp"], json!(expected));
+            assert_eq!(doc["event"], json!({ "start": expected }));
+            assert_eq!(doc["file"]["created"], json!(4000));
+        }
+    }
+
+    #[test]
+    fn sentinels_are_omitted() {
+        let bf_line = Bodyfile3Line::try_from("0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|2|-1|-1").unwrap();
+        let doc = to_ecs_document(&bf_line);
+        assert_eq!(doc["file"]["mtime"], json!(2000));
+        for field in ["accessed", "ctime", "created"] {
+            assert!(doc["file"].get(field).is_none());
+        }
+        assert!(to_ecs_event_document(&bf_line, TimestampKind::Atime).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+use std::process::Termination;
+
+/// exit codes which are used by all tools, so that scripts can distinguish
+/// between complete and partial results.
+///
+/// `main` returns an [`ExitCode`] instead of a `Result`. Errors are printed
+/// in the same format as if `main` returned the `Result`:
+///
+/// ```no_run
+/// use anyhow::Result;
+/// use dfir_toolkit::common::ExitCode;
+///
+/// fn main() -> ExitCode {
+///     ExitCode::from(run())
+/// }
+///
+/// fn run() -> Result<ExitCode> {
+///     let skipped_lines = 0;
+///     Ok(ExitCode::with_errors(skipped_lines))
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    /// the tool has processed its complete input
+    Success = 0,
+
+    /// the tool has been aborted because of an error
+    Failure = 1,
+
+    /// the tool has finished, but some parts of the input (e.g. lines or
+    /// files) could not be parsed and have been skipped
+    PartialSuccess = 2,
+
+    /// the command line arguments are invalid
+    InvalidArguments = 3,
+}
+
+impl ExitCode {
+    /// returns [`ExitCode::PartialSuccess`] if `errors` parts of the input
+    /// have been skipped, and [`ExitCode::Success`] otherwise
+    pub fn with_errors(errors: usize) -> Self {
+        if errors == 0 {
+            Self::Success
+        } else {
+            Self::PartialSuccess
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        *self as i32
+    }
+}
+
+impl<E: Debug> From<Result<ExitCode, E>> for ExitCode {
+    fn from(result: Result<ExitCode, E>) -> Self {
+        match result {
+            Ok(exit_code) => exit_code,
+            Err(why) => {
+                eprintln!("Error: {why:?}");
+                Self::Failure
+            }
+        }
+    }
+}
+
+impl Termination for ExitCode {
+    fn report(self) -> std::process::ExitCode {
+        std::process::ExitCode::from(self as u8)
+    }
+}
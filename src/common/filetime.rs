@@ -0,0 +1,24 @@
+/// number of seconds between 1601-01-01 and 1970-01-01
+const FILETIME_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+/// number of FILETIME intervals (100ns) per second
+const FILETIME_TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// converts a Windows `FILETIME` (100ns intervals since 1601-01-01) into a
+/// unix timestamp. An unset value (`0`) is converted into the bodyfile
+/// sentinel `-1`.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::unix_from_filetime;
+///
+/// assert_eq!(unix_from_filetime(132539328000000000), 1609459200);
+/// assert_eq!(unix_from_filetime(0), -1);
+/// ```
+pub fn unix_from_filetime(filetime: u64) -> i64 {
+    if filetime == 0 {
+        -1
+    } else {
+        (filetime / FILETIME_TICKS_PER_SECOND) as i64 - FILETIME_EPOCH_OFFSET
+    }
+}
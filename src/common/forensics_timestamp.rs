@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use chrono::{NaiveDateTime, LocalResult};
+use chrono::{DateTime, NaiveDateTime, LocalResult};
 use chrono_tz::Tz;
 use chrono::offset::TimeZone;
 
@@ -17,10 +17,10 @@ impl ForensicsTimestamp {
             unix_ts, src_zone, dst_zone
         }
     }
-}
 
-impl Display for ForensicsTimestamp {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// returns the timestamp in the destination timezone, or `None` if the
+    /// timestamp is not set
+    pub fn as_datetime(&self) -> Option<DateTime<Tz>> {
         if self.unix_ts >= 0 {
             let src_timestamp = match self.src_zone
                 .from_local_datetime(&NaiveDateTime::from_timestamp_opt(self.unix_ts, 0).unwrap())
@@ -31,10 +31,18 @@ impl Display for ForensicsTimestamp {
                 LocalResult::Single(t) => t,
                 LocalResult::Ambiguous(t1, _t2) => t1,
             };
-            let dst_timestamp = src_timestamp.with_timezone(&self.dst_zone);
-            write!(f, "{}", dst_timestamp.to_rfc3339())
+            Some(src_timestamp.with_timezone(&self.dst_zone))
         } else {
-            write!(f, "0000-00-00T00:00:00+00:00")
+            None
+        }
+    }
+}
+
+impl Display for ForensicsTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.as_datetime() {
+            Some(dst_timestamp) => write!(f, "{}", dst_timestamp.to_rfc3339()),
+            None => write!(f, "0000-00-00T00:00:00+00:00")
         }
     }
-}
\ No newline at end of file
+}
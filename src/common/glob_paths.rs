@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+
+/// expands glob patterns like `*.bodyfile` and alternatives like
+/// `{host1,host2}.bodyfile` in a list of input paths, for shells which do not
+/// expand them (e.g. `cmd.exe` on Windows). Paths which exist are used as
+/// they are, even if they contain glob characters. The matches of every
+/// alternative are sorted by name; the order of the alternatives and of the
+/// arguments is kept.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::expand_glob_paths;
+/// use std::path::PathBuf;
+///
+/// let dir = std::env::temp_dir().join(format!("expand_glob_paths_doctest_{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// std::fs::write(dir.join("b.bodyfile"), "").unwrap();
+/// std::fs::write(dir.join("a.bodyfile"), "").unwrap();
+///
+/// let paths = expand_glob_paths(&[dir.join("*.bodyfile")]).unwrap();
+/// assert_eq!(paths, vec![dir.join("a.bodyfile"), dir.join("b.bodyfile")]);
+///
+/// let paths = expand_glob_paths(&[dir.join("{b,a}.bodyfile")]).unwrap();
+/// assert_eq!(paths, vec![dir.join("b.bodyfile"), dir.join("a.bodyfile")]);
+///
+/// assert!(expand_glob_paths(&[dir.join("*.gz")]).is_err());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn expand_glob_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut result = Vec::with_capacity(paths.len());
+    for path in paths {
+        let pattern = path.to_string_lossy();
+        if path.exists() || !pattern.contains(['*', '?', '[', '{']) {
+            result.push(path.clone());
+            continue;
+        }
+
+        let mut found = false;
+        for alternative in expand_braces(&pattern) {
+            let mut matches = glob::glob(&alternative)
+                .map_err(|why| anyhow!("invalid pattern '{alternative}': {why}"))?
+                .collect::<Result<Vec<_>, _>>()?;
+            matches.sort();
+            found |= !matches.is_empty();
+            result.extend(matches);
+        }
+        if !found {
+            bail!("no file matches the pattern '{pattern}'");
+        }
+    }
+    Ok(result)
+}
+
+/// creates one pattern for every alternative of the groups like `{a,b}` in
+/// `pattern`. Nested groups are not supported; braces which do not contain
+/// a comma are kept as they are.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(start) = pattern.find('{') else {
+        return vec![pattern.to_owned()];
+    };
+    let Some(end) = pattern[start..].find('}').map(|len| start + len) else {
+        return vec![pattern.to_owned()];
+    };
+
+    let (prefix, alternatives, suffix) = (
+        &pattern[..start],
+        &pattern[start + 1..end],
+        &pattern[end + 1..],
+    );
+    let suffixes = expand_braces(suffix);
+    if !alternatives.contains(',') {
+        let prefix = &pattern[..=end];
+        return suffixes.iter().map(|s| format!("{prefix}{s}")).collect();
+    }
+    alternatives
+        .split(',')
+        .flat_map(|alternative| {
+            suffixes
+                .iter()
+                .map(move |s| format!("{prefix}{alternative}{s}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_braces;
+
+    #[test]
+    fn braces() {
+        assert_eq!(expand_braces("a.bodyfile"), vec!["a.bodyfile"]);
+        assert_eq!(
+            expand_braces("{a,b}.bodyfile"),
+            vec!["a.bodyfile", "b.bodyfile"]
+        );
+        assert_eq!(
+            expand_braces("{h1,h2}/{fls,mft}.txt"),
+            vec!["h1/fls.txt", "h1/mft.txt", "h2/fls.txt", "h2/mft.txt"]
+        );
+        assert_eq!(expand_braces("{a}/{b,}"), vec!["{a}/b", "{a}/"]);
+        assert_eq!(expand_braces("{a,b"), vec!["{a,b"]);
+    }
+}
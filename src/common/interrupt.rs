@@ -0,0 +1,61 @@
+//! graceful handling of `SIGINT` (Ctrl-C). Tools which install the handler
+//! stop reading their input after the first `SIGINT`, write the output which
+//! has been produced so far, and exit with [`INTERRUPTED_EXIT_CODE`]. A
+//! second `SIGINT` terminates the process immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// exit code of a tool which has been interrupted by `SIGINT` (`128 + 2`,
+/// as used by most shells)
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// installs a handler for `SIGINT` in a separate thread. Afterwards,
+/// [`is_interrupted`] returns `true` as soon as `SIGINT` has been received.
+pub fn install_handler() {
+    std::thread::spawn(|| {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(why) => {
+                log::warn!("unable to install signal handler: {why}");
+                return;
+            }
+        };
+        runtime.block_on(async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            log::warn!("received SIGINT, writing the output which has been produced so far");
+            interrupt();
+
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(INTERRUPTED_EXIT_CODE);
+            }
+        });
+    });
+}
+
+/// returns `true` if `SIGINT` has been received
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// behaves as if `SIGINT` had been received
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interrupt, is_interrupted};
+
+    #[test]
+    fn interrupt_sets_flag() {
+        interrupt();
+        assert!(is_interrupted());
+    }
+}
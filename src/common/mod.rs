@@ -1,13 +1,31 @@
+pub mod artifact;
 pub mod bodyfile;
+mod atomic_output;
+#[cfg(feature = "elastic")]
+pub mod ecs;
+mod exit_code;
 mod forensics_timestamp;
+mod glob_paths;
+#[cfg(feature = "tokio")]
+pub mod interrupt;
 mod parse_cli;
 mod rfc3339_datetime;
+mod time_range;
+pub mod tz;
 mod tzargument;
 mod file_input;
+mod filetime;
+mod output_writer;
 
+pub use atomic_output::*;
+pub use exit_code::*;
 pub use forensics_timestamp::*;
+pub use glob_paths::*;
 pub use parse_cli::*;
 pub use rfc3339_datetime::*;
+pub use time_range::*;
 pub use tzargument::*;
 
-pub use file_input::*;
\ No newline at end of file
+pub use file_input::*;
+pub use filetime::*;
+pub use output_writer::*;
\ No newline at end of file
@@ -0,0 +1,146 @@
+use std::io::{stdout, BufWriter, Stdout, Write};
+
+use anyhow::Result;
+
+use super::FinishableWrite;
+
+/// capacity of the buffer of an [`OutputWriter`]. Timeline rows are usually
+/// shorter than 200 bytes, so several hundred rows are written at once
+pub const OUTPUT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// buffered writer for the output of a tool. Writing row by row into an
+/// unbuffered sink (like `stdout().lock()`) results in one syscall per row,
+/// which is slow for large timelines. All buffered data are flushed when the
+/// writer is dropped, even on an early return. Call [`OutputWriter::finish`]
+/// to be notified of errors during the final flush.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::OutputWriter;
+/// use std::io::Write;
+///
+/// let mut buffer = Vec::new();
+/// {
+///     let mut output = OutputWriter::new(&mut buffer);
+///     writeln!(output, "hello").unwrap();
+/// }
+/// assert_eq!(buffer, b"hello\n");
+/// ```
+pub struct OutputWriter<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl OutputWriter<Stdout> {
+    pub fn stdout() -> Self {
+        Self::new(stdout())
+    }
+}
+
+impl<W: Write> OutputWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(OUTPUT_BUFFER_SIZE, inner)
+    }
+
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self {
+            inner: BufWriter::with_capacity(capacity, inner),
+        }
+    }
+
+    /// flushes all buffered data. In contrast to dropping the writer, errors
+    /// are reported
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for OutputWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.inner.write_all(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for OutputWriter<W> {
+    fn drop(&mut self) {
+        // there is no way to report an error here; use `finish()` instead
+        if !std::thread::panicking() {
+            let _ = self.inner.flush();
+        }
+    }
+}
+
+impl FinishableWrite for OutputWriter<Stdout> {
+    fn finish(self: Box<Self>) -> Result<()> {
+        Ok((*self).finish()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::Write;
+    use std::rc::Rc;
+
+    use super::OutputWriter;
+
+    /// sink which records every single write call, like a syscall would
+    #[derive(Default, Clone)]
+    struct RecordingSink {
+        data: Rc<RefCell<Vec<u8>>>,
+        writes: Rc<RefCell<usize>>,
+    }
+
+    impl Write for RecordingSink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            *self.writes.borrow_mut() += 1;
+            self.data.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn write_rows(output: &mut impl Write) -> std::io::Result<()> {
+        for idx in 0..1000 {
+            writeln!(output, "0|/tmp/file_{idx}|0|r/rrwxrwxrwx|0|0|42|-1|1|-1|-1")?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn flush_on_drop() {
+        let sink = RecordingSink::default();
+        let mut output = OutputWriter::new(sink.clone());
+        write_rows(&mut output).unwrap();
+        drop(output);
+
+        let mut expected = Vec::new();
+        write_rows(&mut expected).unwrap();
+        assert_eq!(*sink.data.borrow(), expected);
+    }
+
+    #[test]
+    fn fewer_writes() {
+        let mut unbuffered = RecordingSink::default();
+        write_rows(&mut unbuffered).unwrap();
+
+        let buffered = RecordingSink::default();
+        let mut output = OutputWriter::new(buffered.clone());
+        write_rows(&mut output).unwrap();
+        output.finish().unwrap();
+
+        assert!(*unbuffered.writes.borrow() >= 1000);
+        assert!(*buffered.writes.borrow() <= 2);
+        assert_eq!(*buffered.data.borrow(), *unbuffered.data.borrow());
+    }
+}
@@ -5,6 +5,8 @@ use clap_complete::{generate, Generator, Shell};
 use log::LevelFilter;
 use simplelog::{Config, TermLogger, TerminalMode, ColorChoice};
 
+use super::ExitCode;
+
 pub trait HasVerboseFlag {
     fn log_level_filter(&self)-> LevelFilter;
 }
@@ -20,16 +22,21 @@ impl<P> FancyParser<P> for P
 where
     P: Parser + HasVerboseFlag,
 {
+    /// parses the command line, and exits with [`ExitCode::InvalidArguments`]
+    /// if the arguments are invalid
     fn parse_cli() -> P {
         Self::parse_markdown_help();
         Self::parse_autocomplete();
-        let cli = P::parse();
-
-        let _ = TermLogger::init(
-            cli.log_level_filter(),
-            Config::default(),
-            TerminalMode::Stderr,
-            ColorChoice::Auto);
+        let cli = match P::try_parse() {
+            Ok(cli) => cli,
+            Err(why) if why.use_stderr() => {
+                let _ = why.print();
+                exit(ExitCode::InvalidArguments.code());
+            }
+            // `--help` and `--version`
+            Err(why) => why.exit(),
+        };
+        init_logging(cli.log_level_filter());
         cli
     }
 
@@ -67,6 +74,16 @@ where
     }
 }
 
+/// initializes the logger of a tool, which writes to stderr. Tools which use
+/// [`FancyParser::parse_cli`] need not call this function, because the logger
+/// is initialized with the verbosity level of the command line.
+///
+/// By default, only errors are logged. Use `-v` to log warnings (e.g. parser
+/// errors), `-vv` to log progress messages and `-vvv` for debugging details.
+pub fn init_logging(level: LevelFilter) {
+    let _ = TermLogger::init(level, Config::default(), TerminalMode::Stderr, ColorChoice::Auto);
+}
+
 fn print_completions<G: Generator>(gen: G, cmd: &mut Command) {
     generate(gen, cmd, cmd.get_name().to_string(), &mut std::io::stdout());
 }
\ No newline at end of file
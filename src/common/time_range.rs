@@ -0,0 +1,228 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use clap::Args;
+
+use super::TzArgument;
+
+/// command line arguments to restrict the output of a tool to a time range.
+/// Use `#[clap(flatten)]` to add them to the command line of a tool, and
+/// [`TimeRangeArgs::resolve`] to convert them into a [`TimeRange`].
+///
+/// Absolute values can be written as `2023-01-01`, `2023-01-01T10:00:00`,
+/// `2023-01-01 10:00:00` or in full RFC 3339 syntax. Relative values like
+/// `-2h` or `+30m` (units: `s`, `m`, `h`, `d`, `w`) are relative to
+/// `--relative-to`.
+#[derive(Args, Clone, Debug)]
+pub struct TimeRangeArgs {
+    /// ignore all events before this point in time
+    #[clap(long("from"), allow_hyphen_values = true, display_order(700))]
+    pub from: Option<String>,
+
+    /// ignore all events after this point in time
+    #[clap(long("to"), allow_hyphen_values = true, display_order(710))]
+    pub to: Option<String>,
+
+    /// timezone of `--from`, `--to` and `--relative-to`, if they contain no explicit offset
+    #[clap(long("timezone"), default_value_t = TzArgument::Tz(Tz::UTC), display_order(720))]
+    pub timezone: TzArgument,
+
+    /// reference point for relative values of `--from` and `--to` (use 'now' for the current time)
+    #[clap(long("relative-to"), display_order(730))]
+    pub relative_to: Option<String>,
+}
+
+/// a time range in unix timestamps. Both ends are inclusive, and missing ends
+/// are unbounded.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::common::TimeRange;
+///
+/// let range = TimeRange::new(Some(100), None);
+/// assert!(!range.contains(99));
+/// assert!(range.contains(100));
+/// assert!(range.contains(i64::MAX));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+impl TimeRange {
+    pub fn new(from: Option<i64>, to: Option<i64>) -> Self {
+        Self { from, to }
+    }
+
+    pub fn from(&self) -> Option<i64> {
+        self.from
+    }
+
+    pub fn to(&self) -> Option<i64> {
+        self.to
+    }
+
+    pub fn contains(&self, timestamp: i64) -> bool {
+        match (self.from, self.to) {
+            (Some(from), _) if timestamp < from => false,
+            (_, Some(to)) if timestamp > to => false,
+            _ => true,
+        }
+    }
+}
+
+impl TimeRangeArgs {
+    /// parses all values, using the specified timezone, and checks that the
+    /// start of the range is not after its end
+    pub fn resolve(&self) -> Result<TimeRange> {
+        let tz = self
+            .timezone
+            .into_tz()
+            .ok_or_else(|| anyhow!("'list' is no valid timezone for a time range"))?;
+
+        let relative_to = match self.relative_to.as_deref() {
+            None => None,
+            Some("now") => Some(Utc::now().timestamp()),
+            Some(value) => Some(parse_datetime(value, tz)?),
+        };
+
+        let from = self
+            .from
+            .as_deref()
+            .map(|value| parse_bound(value, tz, relative_to))
+            .transpose()?;
+        let to = self
+            .to
+            .as_deref()
+            .map(|value| parse_bound(value, tz, relative_to))
+            .transpose()?;
+
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                bail!(
+                    "invalid time range: --from ({}) is after --to ({})",
+                    self.from.as_deref().unwrap_or_default(),
+                    self.to.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        Ok(TimeRange { from, to })
+    }
+}
+
+fn parse_bound(value: &str, tz: Tz, relative_to: Option<i64>) -> Result<i64> {
+    match parse_relative(value) {
+        Some(offset) => match relative_to {
+            Some(reference) => Ok(reference + offset),
+            None => bail!("the relative value '{value}' requires --relative-to"),
+        },
+        None => parse_datetime(value, tz),
+    }
+}
+
+fn parse_relative(value: &str) -> Option<i64> {
+    let (sign, value) = match value.chars().next()? {
+        '+' => (1, &value[1..]),
+        '-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let factor = match value.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let count = value[..value.len() - 1].parse::<i64>().ok()?;
+    count.checked_mul(factor).map(|offset| sign * offset)
+}
+
+/// parses a date in one of the formats accepted by [`TimeRangeArgs`] and
+/// returns it as unix timestamp. Dates without an explicit offset are
+/// interpreted in the timezone `tz`.
+///
+/// # Example
+/// ```
+/// use chrono_tz::Tz;
+/// use dfir_toolkit::common::parse_datetime;
+///
+/// assert_eq!(parse_datetime("2021-01-01T00:00:00+00:00", Tz::UTC).unwrap(), 1609459200);
+/// assert_eq!(parse_datetime("2021-01-01 01:00:00", Tz::Europe__Berlin).unwrap(), 1609459200);
+/// assert!(parse_datetime("yesterday", Tz::UTC).is_err());
+/// ```
+pub fn parse_datetime(value: &str, tz: Tz) -> Result<i64> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.timestamp());
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
+        })
+        .map_err(|_| anyhow!("invalid timestamp: '{value}'"))?;
+
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(timestamp) | LocalResult::Ambiguous(timestamp, _) => {
+            Ok(timestamp.timestamp())
+        }
+        LocalResult::None => bail!("'{value}' does not exist in the timezone {tz}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::{TimeRange, TimeRangeArgs};
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[clap(flatten)]
+        time_range: TimeRangeArgs,
+    }
+
+    fn resolve(args: &[&str]) -> anyhow::Result<TimeRange> {
+        TestCli::try_parse_from([&["test"][..], args].concat())
+            .unwrap()
+            .time_range
+            .resolve()
+    }
+
+    #[test]
+    fn absolute_range() {
+        let range = resolve(&["--from", "2021-01-01", "--to", "2021-01-01T01:00:00"]).unwrap();
+        assert_eq!(range, TimeRange::new(Some(1609459200), Some(1609462800)));
+    }
+
+    #[test]
+    fn timezone() {
+        let range = resolve(&["--from", "2021-01-01", "--timezone", "Europe/Berlin"]).unwrap();
+        assert_eq!(range.from(), Some(1609459200 - 3600));
+
+        // explicit offsets win over the timezone
+        let range = resolve(&["--from", "2021-01-01T00:00:00+00:00", "--timezone", "Europe/Berlin"]).unwrap();
+        assert_eq!(range.from(), Some(1609459200));
+    }
+
+    #[test]
+    fn relative_range() {
+        let range = resolve(&["--relative-to", "2021-01-01", "--from", "-1h", "--to", "+30m"]).unwrap();
+        assert_eq!(range, TimeRange::new(Some(1609459200 - 3600), Some(1609459200 + 1800)));
+        assert!(resolve(&["--from", "-1h"]).is_err());
+    }
+
+    #[test]
+    fn from_after_to() {
+        let result = resolve(&["--from", "2021-01-02", "--to", "2021-01-01"]);
+        assert!(result.unwrap_err().to_string().contains("is after --to"));
+    }
+
+    #[test]
+    fn invalid_timestamp() {
+        assert!(resolve(&["--from", "yesterday"]).is_err());
+    }
+}
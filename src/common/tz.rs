@@ -0,0 +1,155 @@
+//! conversion between Windows timezone names (e.g. `W. Europe Standard Time`),
+//! which are used in the registry, and IANA timezones
+
+use std::str::FromStr;
+
+use chrono_tz::Tz;
+
+/// mapping of Windows timezone names to IANA timezones, taken from
+/// <https://github.com/unicode-org/cldr/blob/main/common/supplemental/windowsZones.xml>.
+/// The first entry of every Windows timezone is its default mapping
+/// (territory `001`); the following entries are only used to map IANA
+/// timezones to Windows timezones.
+const WINDOWS_ZONES: [(&str, &str); 75] = [
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+    ("Pacific Standard Time", "PST8PDT"),
+    ("Mountain Standard Time", "MST7MDT"),
+    ("Central Standard Time", "CST6CDT"),
+    ("Eastern Standard Time", "EST5EDT"),
+    ("Eastern Standard Time", "America/Toronto"),
+    ("Pacific Standard Time", "America/Vancouver"),
+    ("UTC", "UTC"),
+    ("UTC", "Etc/GMT"),
+    ("GMT Standard Time", "Europe/Dublin"),
+    ("GMT Standard Time", "Europe/Lisbon"),
+    ("W. Europe Standard Time", "Europe/Amsterdam"),
+    ("W. Europe Standard Time", "Europe/Rome"),
+    ("W. Europe Standard Time", "Europe/Stockholm"),
+    ("W. Europe Standard Time", "Europe/Vienna"),
+    ("W. Europe Standard Time", "Europe/Zurich"),
+    ("Central Europe Standard Time", "Europe/Prague"),
+    ("Romance Standard Time", "Europe/Brussels"),
+    ("Romance Standard Time", "Europe/Copenhagen"),
+    ("Romance Standard Time", "Europe/Madrid"),
+    ("GTB Standard Time", "Europe/Athens"),
+    ("FLE Standard Time", "Europe/Helsinki"),
+    ("India Standard Time", "Asia/Calcutta"),
+    ("China Standard Time", "Asia/Hong_Kong"),
+    ("AUS Eastern Standard Time", "Australia/Melbourne"),
+];
+
+/// returns the IANA timezone which is used for a Windows timezone name
+///
+/// # Example
+/// ```
+/// use chrono_tz::Tz;
+/// use dfir_toolkit::common::tz::windows_to_iana;
+///
+/// assert_eq!(windows_to_iana("W. Europe Standard Time"), Some(Tz::Europe__Berlin));
+/// assert_eq!(windows_to_iana("Unknown Standard Time"), None);
+/// ```
+pub fn windows_to_iana(name: &str) -> Option<Tz> {
+    WINDOWS_ZONES
+        .iter()
+        .find(|(windows, _)| *windows == name)
+        .and_then(|(_, iana)| Tz::from_str(iana).ok())
+}
+
+/// returns the Windows timezone name which is used for an IANA timezone
+///
+/// # Example
+/// ```
+/// use chrono_tz::Tz;
+/// use dfir_toolkit::common::tz::iana_to_windows;
+///
+/// assert_eq!(iana_to_windows(&Tz::Europe__Vienna), Some("W. Europe Standard Time"));
+/// assert_eq!(iana_to_windows(&Tz::Antarctica__Troll), None);
+/// ```
+pub fn iana_to_windows(tz: &Tz) -> Option<&'static str> {
+    WINDOWS_ZONES
+        .iter()
+        .find(|(_, iana)| *iana == tz.name())
+        .map(|(windows, _)| *windows)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use super::{iana_to_windows, windows_to_iana, WINDOWS_ZONES};
+
+    #[test]
+    fn well_known_zones() {
+        assert_eq!(windows_to_iana("Eastern Standard Time"), Some(Tz::America__New_York));
+        assert_eq!(windows_to_iana("Tokyo Standard Time"), Some(Tz::Asia__Tokyo));
+        assert_eq!(windows_to_iana("UTC"), Some(Tz::Etc__UTC));
+
+        assert_eq!(iana_to_windows(&Tz::America__New_York), Some("Eastern Standard Time"));
+        assert_eq!(iana_to_windows(&Tz::Europe__Paris), Some("Romance Standard Time"));
+        assert_eq!(iana_to_windows(&Tz::UTC), Some("UTC"));
+    }
+
+    /// every IANA name of the table must be known by chrono-tz, and every
+    /// default mapping must be mapped back to the same Windows timezone
+    #[test]
+    fn consistent_table() {
+        for (windows, iana) in WINDOWS_ZONES {
+            let tz: Tz = iana.parse().unwrap();
+            assert_eq!(iana_to_windows(&tz), Some(windows));
+        }
+        for (windows, _) in WINDOWS_ZONES {
+            let tz = windows_to_iana(windows).unwrap();
+            assert_eq!(iana_to_windows(&tz), Some(windows));
+        }
+    }
+}
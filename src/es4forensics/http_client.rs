@@ -0,0 +1,161 @@
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use elasticsearch::http::response::Response;
+use elasticsearch::http::StatusCode;
+use elasticsearch::Elasticsearch;
+use rand::Rng;
+use serde_json::Value;
+
+pub const DEFAULT_MAX_RETRIES: u32 = 8;
+
+/// time to wait before a failed request is sent again. The delay is doubled
+/// for every further retry, up to [`MAX_BACKOFF`].
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// response of elasticsearch, whose body has already been read completely
+pub struct HttpResponse {
+    status_code: StatusCode,
+    body: String,
+}
+
+impl HttpResponse {
+    pub fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    pub fn text(&self) -> &str {
+        &self.body
+    }
+
+    pub fn json(&self) -> Result<Value> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+
+    /// returns an error if elasticsearch has answered with a client or server error
+    pub fn error_for_status_code(&self) -> Result<()> {
+        if self.status_code.is_client_error() || self.status_code.is_server_error() {
+            bail!("elasticsearch answered with status code {}", self.status_code)
+        }
+        Ok(())
+    }
+}
+
+/// wrapper around an [`Elasticsearch`] client, which sends requests again if
+/// they failed because of a transient error.
+///
+/// Network errors, timeouts, server errors (`5xx`) and `429 Too Many
+/// Requests` are considered to be transient. Between two attempts, the
+/// client waits for an exponentially increasing delay with some random
+/// jitter. All other responses, including client errors (`4xx`), are
+/// returned to the caller without retrying.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Elasticsearch,
+    max_retries: u32,
+    initial_backoff: Duration,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl HttpClient {
+    pub fn new(client: Elasticsearch) -> Self {
+        Self {
+            client,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            connect_timeout: None,
+            read_timeout: None,
+        }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// sets the maximum time to wait until elasticsearch has accepted the
+    /// connection and has sent the header of its response
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// sets the maximum time to wait for the body of a response
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = Some(read_timeout);
+        self
+    }
+
+    /// sends the request created by `request`, which is called again for
+    /// every retry. `operation` describes the request in log messages.
+    pub async fn send<F, Fut>(&self, operation: &str, request: F) -> Result<HttpResponse>
+    where
+        F: Fn(Elasticsearch) -> Fut,
+        Fut: Future<Output = Result<Response, elasticsearch::Error>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut retry = 0;
+        loop {
+            let reason = match self.try_send(&request).await {
+                Ok(response) if !is_retryable(response.status_code()) => return Ok(response),
+                Ok(response) => format!("status code {}", response.status_code()),
+                Err(why) => why.to_string(),
+            };
+
+            if retry == self.max_retries {
+                return Err(anyhow!(
+                    "{operation} failed {} times, last error was: {reason}",
+                    retry + 1
+                ));
+            }
+            retry += 1;
+
+            let delay = with_jitter(backoff);
+            log::warn!(
+                "{operation} failed ({reason}), retrying in {} ms",
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    async fn try_send<F, Fut>(&self, request: &F) -> Result<HttpResponse>
+    where
+        F: Fn(Elasticsearch) -> Fut,
+        Fut: Future<Output = Result<Response, elasticsearch::Error>>,
+    {
+        let response = with_timeout(self.connect_timeout, request(self.client.clone())).await??;
+        let status_code = response.status_code();
+        let body = with_timeout(self.read_timeout, response.text()).await??;
+        Ok(HttpResponse { status_code, body })
+    }
+}
+
+fn is_retryable(status_code: StatusCode) -> bool {
+    status_code == StatusCode::TOO_MANY_REQUESTS || status_code.is_server_error()
+}
+
+/// returns a random delay between the half and the full `backoff`, so that
+/// multiple clients do not retry at the same time
+fn with_jitter(backoff: Duration) -> Duration {
+    let half = backoff / 2;
+    half + half.mul_f64(rand::thread_rng().gen::<f64>())
+}
+
+async fn with_timeout<F: Future>(timeout: Option<Duration>, future: F) -> Result<F::Output> {
+    match timeout {
+        None => Ok(future.await),
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| anyhow!("no response within {} ms", timeout.as_millis())),
+    }
+}
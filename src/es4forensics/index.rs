@@ -1,12 +1,20 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
 use anyhow::{bail, Result};
 use base64::{engine::general_purpose, Engine};
-use elasticsearch::{BulkOperation, BulkParts, Elasticsearch};
-use serde_json::Value;
+use elasticsearch::{BulkOperation, BulkParts};
+use futures::FutureExt;
+use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use tokio::task::JoinSet;
+use tokio::time::Instant;
 use tokio_async_drop::tokio_async_drop;
 
 use crate::es4forensics::ecs::TimelineObject;
+use crate::es4forensics::HttpClient;
 
+#[derive(Clone)]
 struct ElasticDocument {
     id: String,
     content: Value,
@@ -21,7 +29,7 @@ impl From<ElasticDocument> for (String, Value) {
 impl From<Value> for ElasticDocument {
     fn from(val: Value) -> Self {
         let mut hasher: Sha256 = Sha256::new();
-        hasher.update(val.to_string());
+        hasher.update(Self::identity_of(&val));
         let result = hasher.finalize();
         Self {
             id: general_purpose::URL_SAFE_NO_PAD.encode(result),
@@ -30,21 +38,102 @@ impl From<Value> for ElasticDocument {
     }
 }
 
+impl ElasticDocument {
+    /// file events are identified by the file (inode and path) and the kinds
+    /// of timestamps the event consists of, so that importing the same data
+    /// again overwrites the existing documents. All other documents are
+    /// identified by their content.
+    fn identity_of(val: &Value) -> String {
+        let file = &val["file"];
+        match (
+            file["inode"].as_str(),
+            file["path"].as_str(),
+            val["@timestamp"].as_i64(),
+        ) {
+            (Some(inode), Some(path), Some(ts)) => {
+                let kinds: String = [("mtime", 'm'), ("accessed", 'a'), ("ctime", 'c'), ("created", 'b')]
+                    .iter()
+                    .map(|(key, flag)| {
+                        if file[*key].as_i64() == Some(ts) {
+                            *flag
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                format!("{inode}|{path}|{kinds}")
+            }
+            _ => val.to_string(),
+        }
+    }
+}
+
+/// returns the action and the document, as they are sent in the body of a
+/// bulk operation. The name of the index is not part of the action, because
+/// it is specified in the URL of the bulk operation.
+///
+/// # Example
+/// ```
+/// use dfir_toolkit::es4forensics::bulk_entry;
+/// use serde_json::json;
+///
+/// let (action, document) = bulk_entry(json!({"message": "sample"}), false);
+/// assert_eq!(action, json!({"index": {}}));
+/// assert_eq!(document, json!({"message": "sample"}));
+///
+/// let (action, _) = bulk_entry(json!({"message": "sample"}), true);
+/// assert!(action["index"]["_id"].is_string());
+/// ```
+pub fn bulk_entry(document: Value, dedup: bool) -> (Value, Value) {
+    let (id, content) = ElasticDocument::from(document).into();
+    let action = if dedup {
+        json!({"index": {"_id": id}})
+    } else {
+        json!({"index": {}})
+    };
+    (action, content)
+}
+
+/// a batch which has been sent to elasticsearch
+struct SentBatch {
+    id: u64,
+    documents: usize,
+    acknowledged: bool,
+}
+
 pub struct Index {
     name: String,
-    client: Elasticsearch,
+    client: HttpClient,
 
     cache_size: usize,
     document_cache: Option<Vec<ElasticDocument>>,
+    dedup: bool,
+
+    flush_interval: Option<Duration>,
+    batch_started: Option<Instant>,
+
+    max_in_flight: usize,
+    in_flight: JoinSet<Result<u64>>,
+    sent_batches: VecDeque<SentBatch>,
+    next_batch_id: u64,
+    acknowledged_documents: usize,
 }
 
 impl Index {
-    pub fn new(name: String, client: Elasticsearch) -> Self {
+    pub fn new(name: String, client: HttpClient) -> Self {
         Self {
             name,
             client,
             cache_size: 10000,
             document_cache: Some(Vec::new()),
+            dedup: true,
+            flush_interval: None,
+            batch_started: None,
+            max_in_flight: 1,
+            in_flight: JoinSet::new(),
+            sent_batches: VecDeque::new(),
+            next_batch_id: 0,
+            acknowledged_documents: 0,
         }
     }
 
@@ -68,67 +157,152 @@ impl Index {
     }
 
     pub async fn add_bulk_document(&mut self, document: Value) -> Result<()> {
+        self.collect_finished_batches()?;
+
         if let Some(c) = self.document_cache.as_mut() {
+            if c.is_empty() {
+                self.batch_started = Some(Instant::now());
+            }
             c.push(document.into())
         }
 
-        if self.document_cache.as_ref().unwrap().len() >= self.cache_size {
-            self.flush().await
+        let is_expired = self
+            .flush_deadline()
+            .map(|deadline| deadline <= Instant::now())
+            .unwrap_or(false);
+        if self.pending_documents() >= self.cache_size || is_expired {
+            self.send_batch().await
         } else {
             Ok(())
         }
     }
 
+    /// sends all pending documents and waits until elasticsearch has
+    /// acknowledged all bulk operations
     pub async fn flush(&mut self) -> Result<()> {
-        match self.document_cache.as_ref() {
-            None => log::trace!("There is no document cache"),
-
-            Some(document_cache) => {
-                log::info!(
-                    "flushing document cache with {} entries",
-                    document_cache.len()
-                );
-                if document_cache.is_empty() {
-                    log::trace!("Document cache is empty");
-                } else {
-                    let parts = BulkParts::Index(&self.name);
-
-                    let item_count = self.document_cache.as_ref().unwrap().len();
-                    let items: Vec<BulkOperation<Value>> = self
-                        .document_cache
-                        .replace(Vec::new())
-                        .unwrap()
-                        .into_iter()
-                        .map(|v| {
-                            let (id, val) = v.into();
-                            BulkOperation::create(id, val).into()
-                        })
-                        .collect();
-                    let bulk = self.client.bulk(parts).body(items);
-
-                    let response = bulk.send().await?;
-
-                    if !response.status_code().is_success() {
-                        log::error!(
-                            "error {} while sending bulk operation",
-                            response.status_code()
-                        );
-                        log::error!("{}", response.text().await?);
-                        bail!("error while sending bulk operation");
-                    } else {
-                        let json: Value = response.json().await?;
-                        if json["errors"].as_bool().unwrap() {
-                            log::error!("error while writing to elasticsearch: {json}");
-                        } else {
-                            log::trace!("successfully wrote {item_count} items");
-                        }
-                    }
-                }
+        self.send_batch().await?;
+        while !self.in_flight.is_empty() {
+            self.wait_for_batch().await?;
+        }
+        Ok(())
+    }
+
+    /// sends all pending documents in one bulk operation, without waiting
+    /// for the response. If `max_in_flight` bulk operations are outstanding
+    /// already, this waits until one of them has finished.
+    pub async fn send_batch(&mut self) -> Result<()> {
+        let documents = match self.document_cache.as_mut() {
+            None => {
+                log::trace!("There is no document cache");
+                return Ok(());
+            }
+            Some(document_cache) if document_cache.is_empty() => {
+                log::trace!("Document cache is empty");
+                return Ok(());
+            }
+            Some(document_cache) => std::mem::take(document_cache),
+        };
+        self.batch_started = None;
+
+        while self.in_flight.len() >= self.max_in_flight {
+            self.wait_for_batch().await?;
+        }
+
+        log::info!("flushing document cache with {} entries", documents.len());
+        let id = self.next_batch_id;
+        self.next_batch_id += 1;
+        self.sent_batches.push_back(SentBatch {
+            id,
+            documents: documents.len(),
+            acknowledged: false,
+        });
+
+        let client = self.client.clone();
+        let name = self.name.clone();
+        let dedup = self.dedup;
+        self.in_flight.spawn(async move {
+            send_bulk(&client, &name, &documents, dedup).await?;
+            Ok(id)
+        });
+        Ok(())
+    }
+
+    /// returns the time at which the pending documents must be sent, if a
+    /// flush interval has been configured
+    pub fn flush_deadline(&self) -> Option<Instant> {
+        match (self.flush_interval, self.batch_started) {
+            (Some(interval), Some(started)) if self.pending_documents() > 0 => {
+                Some(started + interval)
+            }
+            _ => None,
+        }
+    }
+
+    async fn wait_for_batch(&mut self) -> Result<()> {
+        if let Some(result) = self.in_flight.join_next().await {
+            self.batch_finished(result?)?;
+        }
+        Ok(())
+    }
+
+    /// handles all bulk operations which have finished in the meantime
+    fn collect_finished_batches(&mut self) -> Result<()> {
+        while let Some(Some(result)) = self.in_flight.join_next().now_or_never() {
+            self.batch_finished(result?)?;
+        }
+        Ok(())
+    }
+
+    fn batch_finished(&mut self, result: Result<u64>) -> Result<()> {
+        let id = result?;
+        if let Some(batch) = self.sent_batches.iter_mut().find(|b| b.id == id) {
+            batch.acknowledged = true;
+        }
+
+        // bulk operations may finish in any order, but only documents which
+        // have been acknowledged without any gap are counted
+        while let Some(batch) = self.sent_batches.front() {
+            if !batch.acknowledged {
+                break;
             }
+            self.acknowledged_documents += batch.documents;
+            self.sent_batches.pop_front();
         }
         Ok(())
     }
 
+    /// returns the number of documents which have been acknowledged by
+    /// elasticsearch, including all documents which have been added before
+    pub fn acknowledged_documents(&self) -> usize {
+        self.acknowledged_documents
+    }
+
+    /// returns the number of documents which have been added, but which have
+    /// not been sent to elasticsearch yet
+    pub fn pending_documents(&self) -> usize {
+        self.document_cache.as_ref().map(Vec::len).unwrap_or_default()
+    }
+
+    /// if `dedup` is set (which is the default), documents are stored using a
+    /// deterministic id, so that importing the same data twice does not
+    /// create duplicate documents. Otherwise, elasticsearch assigns a new id
+    /// to every document.
+    pub fn set_dedup(&mut self, dedup: bool) {
+        self.dedup = dedup;
+    }
+
+    /// if a flush interval is set, pending documents are sent after this
+    /// interval even if the document cache is not full yet
+    pub fn set_flush_interval(&mut self, flush_interval: Option<Duration>) {
+        self.flush_interval = flush_interval;
+    }
+
+    /// sets the maximum number of bulk operations which may be outstanding
+    /// at the same time (the default is 1)
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.max_in_flight = max_in_flight.max(1);
+    }
+
     pub async fn set_cache_size(&mut self, cache_size: usize) -> Result<()> {
         if self.cache_size > cache_size {
             self.flush().await?;
@@ -138,6 +312,77 @@ impl Index {
     }
 }
 
+/// sends the documents in one bulk operation. Transient errors are handled
+/// by the [`HttpClient`], which sends the operation again. If elasticsearch
+/// rejects any of the documents, the whole batch is treated as failed, so
+/// that it is never counted as acknowledged.
+async fn send_bulk(
+    client: &HttpClient,
+    name: &str,
+    documents: &[ElasticDocument],
+    dedup: bool,
+) -> Result<()> {
+    let response = client
+        .send("bulk operation", |client| {
+            let items: Vec<BulkOperation<Value>> = documents
+                .iter()
+                .cloned()
+                .map(|v| {
+                    let (id, val) = v.into();
+                    if dedup {
+                        BulkOperation::index(val).id(id).into()
+                    } else {
+                        BulkOperation::index(val).into()
+                    }
+                })
+                .collect();
+            async move { client.bulk(BulkParts::Index(name)).body(items).send().await }
+        })
+        .await?;
+
+    if !response.status_code().is_success() {
+        log::error!(
+            "error {} while sending bulk operation",
+            response.status_code()
+        );
+        log::error!("{}", response.text());
+        bail!("error while sending bulk operation");
+    }
+
+    let json = response.json()?;
+    match json["errors"].as_bool() {
+        Some(false) => {
+            log::trace!("successfully wrote {} items", documents.len());
+            Ok(())
+        }
+        Some(true) => {
+            log::error!("error while writing to elasticsearch: {json}");
+            bail!(
+                "elasticsearch rejected {} of {} documents",
+                failed_items(&json),
+                documents.len()
+            )
+        }
+        None => {
+            log::error!("unexpected response to bulk operation: {json}");
+            bail!("elasticsearch did not report the result of the bulk operation")
+        }
+    }
+}
+
+/// returns the number of items of a bulk response which contain an error
+fn failed_items(response: &Value) -> usize {
+    response["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter(|item| item["index"].get("error").is_some())
+                .count()
+        })
+        .unwrap_or_default()
+}
+
 impl Drop for Index {
     fn drop(&mut self) {
         tokio_async_drop!({
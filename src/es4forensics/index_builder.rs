@@ -1,4 +1,6 @@
-use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+use anyhow::Result;
 use elasticsearch::{
     auth::Credentials,
     cat::CatIndicesParts,
@@ -7,12 +9,12 @@ use elasticsearch::{
         transport::{SingleNodeConnectionPool, TransportBuilder},
         Url,
     },
-    indices::IndicesCreateParts,
+    indices::{IndicesCreateParts, IndicesDeleteParts},
     Elasticsearch,
 };
-use serde_json::{json, Value};
+use serde_json::json;
 
-use crate::es4forensics::{Protocol, index::Index};
+use crate::es4forensics::{http_client::DEFAULT_MAX_RETRIES, index::Index, HttpClient, Protocol};
 
 pub struct IndexBuilder {
     host: Option<String>,
@@ -21,6 +23,9 @@ pub struct IndexBuilder {
     index_name: String,
     do_certificate_validation: bool,
     credentials: Option<Credentials>,
+    max_retries: u32,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
 }
 
 const DEFAULT_HOST: &str = "localhost";
@@ -39,6 +44,9 @@ impl IndexBuilder {
             index_name,
             do_certificate_validation: true,
             credentials: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            connect_timeout: None,
+            read_timeout: None,
         }
     }
 
@@ -62,6 +70,22 @@ impl IndexBuilder {
         self
     }
 
+    /// sets the number of times a request is sent again after a transient error
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = Some(read_timeout);
+        self
+    }
+
     pub fn host(&self) -> &str {
         match self.host.as_ref() {
             Some(h) => h,
@@ -86,6 +110,30 @@ impl IndexBuilder {
         Ok(Index::new(self.index_name, client))
     }
 
+    /// deletes the index, if it exists
+    pub async fn delete_index(&self) -> Result<()> {
+        let client = self.create_client()?;
+
+        if self.client_has_index(&client).await? {
+            log::info!("delete index '{}'", self.index_name);
+            let name = self.index_name.as_str();
+            let response = client
+                .send("deleting the index", |client| async move {
+                    client
+                        .indices()
+                        .delete(IndicesDeleteParts::Index(&[name]))
+                        .send()
+                        .await
+                })
+                .await?;
+            if let Err(why) = response.error_for_status_code() {
+                log::error!("Error while deleting index: {}", response.text());
+                return Err(why);
+            }
+        }
+        Ok(())
+    }
+
     pub async fn create_index(&self) -> Result<Index> {
         let client = self.create_client()?;
 
@@ -124,29 +172,47 @@ impl IndexBuilder {
                                 },
                                 "macb_long": {
                                     "type": "keyword"
+                                },
+                                "size": {
+                                    "type": "long"
+                                },
+                                // TSK uses inodes like `87915-128-1` for NTFS,
+                                // which are kept in `_source` only
+                                "inode": {
+                                    "type": "long",
+                                    "ignore_malformed": true
+                                },
+                                "path": {
+                                    "type": "keyword",
+                                    "fields": {
+                                        "text": {
+                                            "type": "text"
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                 }
             });
-            let parts = IndicesCreateParts::Index(&self.index_name);
+            let name = self.index_name.as_str();
             let response = client
-                .indices()
-                .create(parts)
-                .body(index_body)
-                .send()
+                .send("creating the index", |client| {
+                    let index_body = index_body.clone();
+                    async move {
+                        client
+                            .indices()
+                            .create(IndicesCreateParts::Index(name))
+                            .body(index_body)
+                            .send()
+                            .await
+                    }
+                })
                 .await?;
-            match response.error_for_status_code_ref() {
-                Ok(_response) => (),
-                Err(why) => {
-                    log::error!(
-                        "Error while creating index: {}",
-                        response.text().await?
-                    );
-                    log::error!("error message was: {}", why);
-                    return Err(anyhow!(why))
-                }
+            if let Err(why) = response.error_for_status_code() {
+                log::error!("Error while creating index: {}", response.text());
+                log::error!("error message was: {}", why);
+                return Err(why);
             }
 
             //let pipeline_id = format!("{}_pipeline", self.index_name());
@@ -191,7 +257,7 @@ impl IndexBuilder {
         }
     }
 */
-    fn create_client(&self) -> Result<Elasticsearch> {
+    fn create_client(&self) -> Result<HttpClient> {
         let url = Url::parse(&format!("{}://{}:{}", self.protocol, self.host(), self.port()))?;
         let conn_pool = SingleNodeConnectionPool::new(url);
         let mut transport_builder = TransportBuilder::new(conn_pool)
@@ -206,25 +272,36 @@ impl IndexBuilder {
             transport_builder = transport_builder.auth(credentials.clone());
         }
         let transport = transport_builder.build()?;
-        Ok(Elasticsearch::new(transport))
+        let mut client = HttpClient::new(Elasticsearch::new(transport)).with_max_retries(self.max_retries);
+        if let Some(connect_timeout) = self.connect_timeout {
+            client = client.with_connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            client = client.with_read_timeout(read_timeout);
+        }
+        Ok(client)
     }
 
-    async fn client_has_index(&self, client: &Elasticsearch) -> Result<bool> {
+    async fn client_has_index(&self, client: &HttpClient) -> Result<bool> {
         log::info!("test if index '{}' exists", self.index_name);
 
         let response = client
-            .cat()
-            .indices(CatIndicesParts::Index(&["*"]))
-            .format("json")
-            .send()
+            .send("listing the indices", |client| async move {
+                client
+                    .cat()
+                    .indices(CatIndicesParts::Index(&["*"]))
+                    .format("json")
+                    .send()
+                    .await
+            })
             .await?;
-        response.error_for_status_code_ref()?;
+        response.error_for_status_code()?;
 
-        if response.content_length().unwrap_or(0) == 0 {
+        if response.text().is_empty() {
             log::debug!("empty result; index does not seem to exist");
             Ok(false)
         } else {
-            let response_body = response.json::<Value>().await?;
+            let response_body = response.json()?;
 
             match response_body.as_array() {
                 None => {
@@ -72,6 +72,9 @@ mod index;
 #[cfg(feature="elasticsearch")]
 mod index_builder;
 
+#[cfg(feature="elasticsearch")]
+mod http_client;
+
 mod timestamp;
 mod utils;
 mod ecs;
@@ -84,6 +87,9 @@ pub use index::*;
 
 #[cfg(feature="elasticsearch")]
 pub use index_builder::*;
+
+#[cfg(feature="elasticsearch")]
+pub use http_client::*;
 pub use timestamp::*;
 pub use ecs::*;
 pub use protocol::*;
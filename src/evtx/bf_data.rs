@@ -2,17 +2,20 @@ use std::collections::HashMap;
 
 use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Utc};
-use dfir_toolkit::common::bodyfile::Bodyfile3Line;
-use dfir_toolkit::es4forensics::{objects::WindowsEvent, TimelineObject};
+use crate::common::bodyfile::Bodyfile3Line;
+use crate::es4forensics::{objects::WindowsEvent, TimelineObject};
 use evtx::SerializedEvtxRecord;
 use getset::{Getters, Setters};
 use serde::Serialize;
 use serde_json::{json, Value};
 
-use crate::macros::from_json;
+use super::describe_event;
+use super::macros::from_json;
 
+/// the data of a single Windows event, which is stored in the name column
+/// of a bodyfile line
 #[derive(Serialize, Getters, Setters)]
-pub(crate) struct BfData<'a> {
+pub struct BfData<'a> {
     event_record_id: u64,
     timestamp: DateTime<Utc>,
     #[getset(get)]
@@ -25,19 +28,52 @@ pub(crate) struct BfData<'a> {
     custom_data: HashMap<&'a String, &'a Value>,
 
     #[serde(skip)]
-    #[getset(set = "pub (crate)")]
+    #[getset(set = "pub")]
     enable_json_output: bool,
+
+    /// use a human readable description (see [`describe_event`]) as name of
+    /// the bodyfile line, instead of the JSON representation of the event
+    #[serde(skip)]
+    #[getset(set = "pub")]
+    descriptive_name: bool,
 }
 
 impl<'a> BfData<'a> {
-    pub(crate) fn try_into_mactime(&self) -> Result<String> {
-        let bf_line = Bodyfile3Line::new()
+    /// creates a bodyfile line, whose modification time is the timestamp of
+    /// the event
+    pub fn to_bodyfile_line(&self) -> Bodyfile3Line {
+        let name = if self.descriptive_name {
+            self.description()
+        } else {
+            json!(self).to_string()
+        };
+        Bodyfile3Line::new()
             .with_mtime(self.timestamp.timestamp())
-            .with_owned_name(json!(self).to_string());
-        Ok(bf_line.to_string())
+            .with_owned_name(name)
+    }
+
+    /// human readable description of the event, like
+    /// `[Sysmon 1] Process Create: C:\Windows\System32\cmd.exe`
+    pub fn description(&self) -> String {
+        let channel = match self.channel_name {
+            Value::String(channel) => channel.to_owned(),
+            channel => channel.to_string(),
+        };
+        let event_data = self
+            .custom_data
+            .iter()
+            .find_map(|(key, value)| (key.as_str() == "EventData").then_some(*value));
+        match self.event_id.as_u64() {
+            Some(event_id) => describe_event(&channel, event_id, event_data),
+            None => format!("[{channel} {}]", self.event_id),
+        }
+    }
+
+    pub fn try_into_mactime(&self) -> Result<String> {
+        Ok(self.to_bodyfile_line().to_string())
     }
 
-    pub(crate) fn try_into_json(&self) -> Result<String> {
+    pub fn try_into_json(&self) -> Result<String> {
         let event: WindowsEvent = self.try_into()?;
         let values: Vec<Value> = event.into_values().collect();
         if values.len() != 1 {
@@ -112,6 +148,7 @@ impl<'a> TryFrom<&'a SerializedEvtxRecord<Value>> for BfData<'a> {
             activity_id,
             custom_data,
             enable_json_output: false,
+            descriptive_name: false,
         })
     }
 }
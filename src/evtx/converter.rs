@@ -0,0 +1,70 @@
+use std::io::{Cursor, Read};
+
+use evtx::{EvtxParser, SerializedEvtxRecord};
+use ouroboros::self_referencing;
+use serde_json::Value;
+
+use crate::common::artifact::{ArtifactConverter, BodyfileLines};
+use crate::common::bodyfile::Bodyfile3Line;
+
+use super::BfData;
+
+/// converts Windows event logs into the same bodyfile lines as
+/// `evtx2bodyfile`
+#[derive(Default)]
+pub struct EvtxConverter;
+
+#[self_referencing]
+struct EvtxLines {
+    parser: EvtxParser<Cursor<Vec<u8>>>,
+
+    #[borrows(mut parser)]
+    #[not_covariant]
+    records: Box<dyn Iterator<Item = evtx::err::Result<SerializedEvtxRecord<Value>>> + 'this>,
+}
+
+impl Iterator for EvtxLines {
+    type Item = anyhow::Result<Bodyfile3Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.with_records_mut(|records| {
+            records.next().map(|record| {
+                let record = record?;
+                Ok(BfData::try_from(&record)?.to_bodyfile_line())
+            })
+        })
+    }
+}
+
+impl ArtifactConverter for EvtxConverter {
+    fn name(&self) -> &str {
+        "evtx"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["evtx"]
+    }
+
+    fn magic(&self) -> &[u8] {
+        b"ElfFile\x00"
+    }
+
+    fn convert(&self, input: &mut dyn Read) -> BodyfileLines {
+        let mut buffer = Vec::new();
+        if let Err(why) = input.read_to_end(&mut buffer) {
+            return Box::new(std::iter::once(Err(why.into())));
+        }
+        match EvtxParser::from_buffer(buffer) {
+            Ok(parser) => Box::new(
+                EvtxLinesBuilder {
+                    parser,
+                    records_builder: |parser: &mut EvtxParser<Cursor<Vec<u8>>>| {
+                        Box::new(parser.records_json_value())
+                    },
+                }
+                .build(),
+            ),
+            Err(why) => Box::new(std::iter::once(Err(why.into()))),
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use serde_json::Value;
+
+/// human readable description of an event, optionally followed by the
+/// value of a field of the event data which identifies the affected object
+struct EventDescription {
+    channel: &'static str,
+    event_id: u64,
+    description: &'static str,
+    detail: Option<&'static str>,
+}
+
+const SECURITY: &str = "Security";
+const SYSTEM: &str = "System";
+const POWERSHELL: &str = "Microsoft-Windows-PowerShell/Operational";
+const SYSMON: &str = "Microsoft-Windows-Sysmon/Operational";
+
+/// short labels of well-known channels, which are used instead of their
+/// full names
+const CHANNEL_LABELS: &[(&str, &str)] = &[
+    (SECURITY, "Security"),
+    (SYSTEM, "System"),
+    (POWERSHELL, "PowerShell"),
+    (SYSMON, "Sysmon"),
+];
+
+macro_rules! event {
+    ($channel: expr, $event_id: expr, $description: expr) => {
+        EventDescription {
+            channel: $channel,
+            event_id: $event_id,
+            description: $description,
+            detail: None,
+        }
+    };
+    ($channel: expr, $event_id: expr, $description: expr, $detail: expr) => {
+        EventDescription {
+            channel: $channel,
+            event_id: $event_id,
+            description: $description,
+            detail: Some($detail),
+        }
+    };
+}
+
+const EVENT_DESCRIPTIONS: &[EventDescription] = &[
+    event!(SECURITY, 1102, "Audit Log Cleared", "SubjectUserName"),
+    event!(SECURITY, 4624, "Logon", "TargetUserName"),
+    event!(SECURITY, 4625, "Failed Logon", "TargetUserName"),
+    event!(SECURITY, 4634, "Logoff", "TargetUserName"),
+    event!(SECURITY, 4647, "User Initiated Logoff", "TargetUserName"),
+    event!(
+        SECURITY,
+        4648,
+        "Logon With Explicit Credentials",
+        "TargetUserName"
+    ),
+    event!(
+        SECURITY,
+        4672,
+        "Special Privileges Assigned",
+        "SubjectUserName"
+    ),
+    event!(SECURITY, 4688, "Process Create", "NewProcessName"),
+    event!(SECURITY, 4689, "Process Exit", "ProcessName"),
+    event!(SECURITY, 4697, "Service Installed", "ServiceName"),
+    event!(SECURITY, 4698, "Scheduled Task Created", "TaskName"),
+    event!(SECURITY, 4720, "User Account Created", "TargetUserName"),
+    event!(SECURITY, 4726, "User Account Deleted", "TargetUserName"),
+    event!(
+        SECURITY,
+        4732,
+        "Member Added To Local Group",
+        "TargetUserName"
+    ),
+    event!(SYSTEM, 104, "Event Log Cleared"),
+    event!(SYSTEM, 1074, "Shutdown Initiated"),
+    event!(SYSTEM, 6005, "Event Log Service Started"),
+    event!(SYSTEM, 6006, "Event Log Service Stopped"),
+    event!(SYSTEM, 7036, "Service State Changed", "param1"),
+    event!(SYSTEM, 7040, "Service Start Type Changed", "param1"),
+    event!(SYSTEM, 7045, "Service Installed", "ServiceName"),
+    event!(POWERSHELL, 4103, "Module Logging"),
+    event!(POWERSHELL, 4104, "Script Block Logging", "Path"),
+    event!(POWERSHELL, 40961, "Console Starting"),
+    event!(POWERSHELL, 40962, "Console Ready"),
+    event!(SYSMON, 1, "Process Create", "Image"),
+    event!(SYSMON, 2, "File Creation Time Changed", "TargetFilename"),
+    event!(SYSMON, 3, "Network Connection", "DestinationIp"),
+    event!(SYSMON, 5, "Process Terminated", "Image"),
+    event!(SYSMON, 6, "Driver Loaded", "ImageLoaded"),
+    event!(SYSMON, 7, "Image Loaded", "ImageLoaded"),
+    event!(SYSMON, 8, "CreateRemoteThread", "TargetImage"),
+    event!(SYSMON, 10, "Process Access", "TargetImage"),
+    event!(SYSMON, 11, "File Create", "TargetFilename"),
+    event!(
+        SYSMON,
+        12,
+        "Registry Object Added Or Deleted",
+        "TargetObject"
+    ),
+    event!(SYSMON, 13, "Registry Value Set", "TargetObject"),
+    event!(SYSMON, 15, "File Stream Created", "TargetFilename"),
+    event!(SYSMON, 22, "DNS Query", "QueryName"),
+    event!(SYSMON, 23, "File Delete", "TargetFilename"),
+];
+
+/// creates a human readable name for an event, like
+/// `[Sysmon 1] Process Create: C:\Windows\System32\cmd.exe`.
+///
+/// Unknown channels are displayed with their full name, and unknown events
+/// only consist of the channel and the numeric event id (e.g.
+/// `[Sysmon 255]`). `event_data` is the `EventData` element of the event,
+/// which contains the field which is displayed after the description.
+pub fn describe_event(channel: &str, event_id: u64, event_data: Option<&Value>) -> String {
+    let label = CHANNEL_LABELS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(channel))
+        .map_or(channel, |(_, label)| *label);
+
+    let description = EVENT_DESCRIPTIONS
+        .iter()
+        .find(|e| e.event_id == event_id && e.channel.eq_ignore_ascii_case(channel));
+
+    match description {
+        None => format!("[{label} {event_id}]"),
+        Some(description) => {
+            let detail = description
+                .detail
+                .and_then(|field| event_data.and_then(|data| data.get(field)))
+                .and_then(|value| match value {
+                    Value::String(s) if !s.is_empty() => Some(s.to_owned()),
+                    Value::String(_) | Value::Null => None,
+                    value => Some(value.to_string()),
+                });
+            match detail {
+                Some(detail) => {
+                    format!("[{label} {event_id}] {}: {detail}", description.description)
+                }
+                None => format!("[{label} {event_id}] {}", description.description),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::describe_event;
+
+    #[test]
+    fn sysmon_process_create() {
+        let event_data = json!({
+            "RuleName": "-",
+            "Image": "C:\\Windows\\System32\\cmd.exe",
+            "CommandLine": "cmd.exe /c whoami"
+        });
+        assert_eq!(
+            describe_event("Microsoft-Windows-Sysmon/Operational", 1, Some(&event_data)),
+            "[Sysmon 1] Process Create: C:\\Windows\\System32\\cmd.exe"
+        );
+    }
+
+    #[test]
+    fn missing_detail() {
+        assert_eq!(
+            describe_event("Microsoft-Windows-Sysmon/Operational", 1, None),
+            "[Sysmon 1] Process Create"
+        );
+        assert_eq!(
+            describe_event("System", 104, None),
+            "[System 104] Event Log Cleared"
+        );
+    }
+
+    #[test]
+    fn unknown_events() {
+        assert_eq!(
+            describe_event("Microsoft-Windows-Sysmon/Operational", 255, None),
+            "[Sysmon 255]"
+        );
+        assert_eq!(
+            describe_event("Microsoft-Windows-TaskScheduler/Operational", 106, None),
+            "[Microsoft-Windows-TaskScheduler/Operational 106]"
+        );
+    }
+}
@@ -1,5 +1,19 @@
 mod event_id;
 mod range;
+#[cfg(feature = "evtx2bodyfile")]
+mod bf_data;
+#[cfg(feature = "evtx2bodyfile")]
+mod converter;
+#[cfg(feature = "evtx2bodyfile")]
+mod event_description;
+#[cfg(feature = "evtx2bodyfile")]
+mod macros;
 
 pub use event_id::*;
-pub use range::*;
\ No newline at end of file
+pub use range::*;
+#[cfg(feature = "evtx2bodyfile")]
+pub use bf_data::*;
+#[cfg(feature = "evtx2bodyfile")]
+pub use converter::*;
+#[cfg(feature = "evtx2bodyfile")]
+pub use event_description::*;
@@ -0,0 +1 @@
+mod unknown_artifact;
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+/// tests if files without a converter are skipped, and if this results in
+/// a partial result
+#[test]
+fn unknown_artifact() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push("sample.bodyfile");
+
+    let mut cmd = Command::cargo_bin("artifact2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).assert().code(2);
+
+    let output = result.get_output();
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no converter found"));
+}
@@ -0,0 +1,40 @@
+use assert_cmd::Command;
+use calamine::{open_workbook, Data, Reader, Xlsx};
+
+/// tests if timestamps are stored as dates, so that Excel can sort and
+/// filter them
+#[test]
+fn timestamp_as_date() {
+    let path = std::env::temp_dir().join(format!("dates_{}.xlsx", std::process::id()));
+
+    let mut cmd = Command::cargo_bin("bodyfile2xlsx").unwrap();
+    cmd.arg(&path)
+        .arg("-b")
+        .arg("-")
+        .write_stdin("0|/tmp/sample.txt|1234|r/rrw-r--r--|1000|1000|4096|-1|1609459200|-1|-1\n")
+        .assert()
+        .success();
+
+    let mut xlsx: Xlsx<_> = open_workbook(&path).unwrap();
+    let range = xlsx.worksheet_range("Timeline").unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        range.get_value((0, 8)),
+        Some(&Data::String("Mtime".to_owned()))
+    );
+    assert_eq!(
+        range.get_value((1, 1)),
+        Some(&Data::String("/tmp/sample.txt".to_owned()))
+    );
+    assert_eq!(range.get_value((1, 6)), Some(&Data::Float(4096.0)));
+
+    // 2021-01-01 00:00:00 UTC
+    match range.get_value((1, 8)) {
+        Some(Data::DateTime(datetime)) => assert_eq!(datetime.as_f64(), 44197.0),
+        other => panic!("expected a date, found {other:?}"),
+    }
+
+    // unset timestamps result in empty cells
+    assert!(matches!(range.get_value((1, 7)), None | Some(Data::Empty)));
+}
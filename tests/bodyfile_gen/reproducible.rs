@@ -0,0 +1,45 @@
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+fn generate(seed: &str) -> String {
+    let mut cmd = Command::cargo_bin("bodyfile_gen").unwrap();
+    let result = cmd
+        .arg("--lines")
+        .arg("1000")
+        .arg("--seed")
+        .arg(seed)
+        .arg("--unset-ratio")
+        .arg("0.2")
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if the same seed always yields the same bodyfile
+#[test]
+fn same_seed_same_output() {
+    let output = generate("42");
+    assert_eq!(output, generate("42"));
+    assert_ne!(output, generate("43"));
+}
+
+/// tests if every generated line is a valid bodyfile line within the time range
+#[test]
+fn every_line_parses() {
+    let output = generate("1234");
+    assert_eq!(output.lines().count(), 1000);
+
+    // 2020-01-01T00:00:00Z and 2023-12-31T23:59:59Z
+    let (from, to) = (1577836800, 1704067199);
+    for line in output.lines() {
+        let bf_line = Bodyfile3Line::try_from(line).unwrap();
+        for ts in [
+            *bf_line.get_atime(),
+            *bf_line.get_mtime(),
+            *bf_line.get_ctime(),
+            *bf_line.get_crtime(),
+        ] {
+            assert!(ts == -1 || (from..=to).contains(&ts), "{line}");
+        }
+    }
+}
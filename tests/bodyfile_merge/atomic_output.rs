@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+fn work_dir(name: &str) -> PathBuf {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("bodyfile_merge_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// tests if a read error during the merge leaves no partial output file
+#[test]
+fn no_partial_output() {
+    let dir = work_dir("atomic_output");
+    let host1 = dir.join("host1.bodyfile");
+    let host2 = dir.join("host2.bodyfile");
+    let merged = dir.join("merged.bodyfile");
+
+    fs::write(&host1, "0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459800|-1|-1\n").unwrap();
+    fs::write(
+        &host2,
+        b"0|/etc/hosts|2|r/rrw-r--r--|0|0|128|-1|1609459200|-1|-1\n0|/tmp/\xff|3|r/rrw-r--r--|0|0|0|-1|1609470000|-1|-1\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("bodyfile_merge").unwrap();
+    cmd.arg("merge")
+        .arg(&host1)
+        .arg(&host2)
+        .arg("--output")
+        .arg(&merged)
+        .assert()
+        .failure();
+
+    assert!(!merged.exists());
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
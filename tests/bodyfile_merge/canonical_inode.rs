@@ -0,0 +1,39 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+/// tests if inode values of different tools, which describe the same NTFS
+/// attribute, are written identically
+#[test]
+fn canonical_inode() {
+    let dir = std::env::temp_dir().join(format!("bodyfile_merge_canonical_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let tsk = dir.join("tsk.bodyfile");
+    let other = dir.join("other.bodyfile");
+    fs::write(&tsk, "0|/a.txt|87915-128-1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n").unwrap();
+    fs::write(
+        &other,
+        "# comments are not changed\n0|/a.txt|087915-128-01:$DATA|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("bodyfile_merge").unwrap();
+    let result = cmd
+        .arg("merge")
+        .arg("--canonical-inode")
+        .arg(&tsk)
+        .arg(&other)
+        .ok();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(result.is_ok());
+
+    assert_eq!(
+        String::from_utf8(result.unwrap().stdout).unwrap(),
+        "# comments are not changed
+0|/a.txt|87915-128-1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1
+0|/a.txt|87915-128-1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1
+"
+    );
+}
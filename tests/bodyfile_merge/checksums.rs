@@ -0,0 +1,111 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+/// tests if a line which has been modified after the merge is detected by
+/// `verify`
+#[test]
+fn detect_altered_line() {
+    let dir = std::env::temp_dir().join(format!("bodyfile_merge_checksums_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("input.bodyfile");
+    let merged = dir.join("merged.bodyfile");
+    fs::write(
+        &input,
+        "0|/tmp/a|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1
+0|/tmp/b|2|r/rrw-r--r--|0|0|34|-1|1609459201|-1|-1
+",
+    )
+    .unwrap();
+
+    Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("merge")
+        .arg("--checksums")
+        .arg("-o")
+        .arg(&merged)
+        .arg(&input)
+        .assert()
+        .success();
+
+    let contents = fs::read_to_string(&merged).unwrap();
+    assert!(contents.lines().all(|line| line.contains("|sha256:")));
+
+    Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("verify")
+        .arg(&merged)
+        .assert()
+        .success();
+
+    // change the size of the second file
+    fs::write(&merged, contents.replace("|0|0|34|", "|0|0|35|")).unwrap();
+    let result = Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("verify")
+        .arg(&merged)
+        .assert()
+        .failure();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("line 2 has been altered"));
+    assert!(!stderr.contains("line 1 has been altered"));
+    assert!(stderr.contains("found 1 altered lines"));
+}
+
+/// tests if a bodyfile which has been merged using `--checksums` can be read
+/// by mactime2, which ignores the checksum column
+#[test]
+fn read_by_mactime2() {
+    let dir = std::env::temp_dir().join(format!(
+        "bodyfile_merge_checksums_mactime2_{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("input.bodyfile");
+    let merged = dir.join("merged.bodyfile");
+    fs::write(
+        &input,
+        "0|/tmp/a|1|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1
+0|/tmp/b|2|r/rrw-r--r--|0|0|34|-1|1609459201|-1|1609459100
+",
+    )
+    .unwrap();
+
+    Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("merge")
+        .arg("--checksums")
+        .arg("-o")
+        .arg(&merged)
+        .arg(&input)
+        .assert()
+        .success();
+
+    let result = Command::cargo_bin("mactime2")
+        .unwrap()
+        .arg("-b")
+        .arg(&merged)
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,size,macb,name")
+        .assert()
+        .success();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(result.get_output().stdout.clone()).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "Date,Size,Type,File Name",
+            "2020-12-31T23:58:20+00:00,34,...b,\"/tmp/b\"",
+            "2021-01-01T00:00:00+00:00,12,m...,\"/tmp/a\"",
+            "2021-01-01T00:00:01+00:00,34,m...,\"/tmp/b\"",
+        ]
+    );
+}
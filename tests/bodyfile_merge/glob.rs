@@ -0,0 +1,37 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+/// tests if a glob pattern, which has not been expanded by the shell, is
+/// expanded to all matching files in sorted order
+#[test]
+fn glob_pattern() {
+    let dir = std::env::temp_dir().join(format!("bodyfile_merge_glob_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    // both lines have the same timestamp, so their order is the order of the inputs
+    fs::write(
+        dir.join("host2.bodyfile"),
+        "0|/etc/hosts|2|r/rrw-r--r--|0|0|128|-1|1609459200|-1|-1\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("host1.bodyfile"),
+        "0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459200|-1|-1\n",
+    )
+    .unwrap();
+    fs::write(dir.join("notes.txt"), "this is not a bodyfile\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("bodyfile_merge").unwrap();
+    let result = cmd.arg("merge").arg(dir.join("*.bodyfile")).ok();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(result.is_ok());
+
+    assert_eq!(
+        String::from_utf8(result.unwrap().stdout).unwrap(),
+        "0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459200|-1|-1
+0|/etc/hosts|2|r/rrw-r--r--|0|0|128|-1|1609459200|-1|-1
+"
+    );
+}
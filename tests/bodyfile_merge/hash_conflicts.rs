@@ -0,0 +1,53 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+/// tests if only files whose hash differs between the bodyfiles are reported
+#[test]
+fn report_hash_conflicts() {
+    let dir = std::env::temp_dir().join(format!("bodyfile_merge_conflicts_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let first = dir.join("first.bodyfile");
+    let second = dir.join("second.bodyfile");
+    let report = dir.join("conflicts.tsv");
+    fs::write(
+        &first,
+        "b1946ac92492d2347c6235b4d2611184|/bin/ls|12|r/rrwxr-xr-x|0|0|42|-1|1609459200|-1|-1
+d41d8cd98f00b204e9800998ecf8427e|/etc/hosts|13|r/rrw-r--r--|0|0|12|-1|1609459200|-1|-1
+",
+    )
+    .unwrap();
+    fs::write(
+        &second,
+        "5d41402abc4b2a76b9719d911017c592|/bin/ls|12|r/rrwxr-xr-x|0|0|42|-1|1609459300|-1|-1
+d41d8cd98f00b204e9800998ecf8427e|/etc/hosts|13|r/rrw-r--r--|0|0|12|-1|1609459300|-1|-1
+",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("bodyfile_merge").unwrap();
+    let result = cmd
+        .arg("merge")
+        .arg("--report-hash-conflicts")
+        .arg(&report)
+        .arg(&first)
+        .arg(&second)
+        .ok();
+    let content = fs::read_to_string(&report);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(result.is_ok());
+
+    let first = first.to_string_lossy();
+    let second = second.to_string_lossy();
+    assert_eq!(
+        content.unwrap(),
+        format!(
+            "# identity\thash\tsource
+12|/bin/ls\tb1946ac92492d2347c6235b4d2611184\t{first}
+12|/bin/ls\t5d41402abc4b2a76b9719d911017c592\t{second}
+"
+        )
+    );
+}
@@ -0,0 +1,6 @@
+mod split;
+mod atomic_output;
+mod glob;
+mod canonical_inode;
+mod hash_conflicts;
+mod checksums;
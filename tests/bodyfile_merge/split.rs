@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+const HOST1: &str = "# bodyfile of host1
+0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459800|-1|-1
+0|/etc/hosts|2|r/rrw-r--r--|0|0|128|-1|1609459200|-1|-1
+0|/tmp/a|3|r/rrw-r--r--|0|0|0|-1|1609470000|-1|-1
+";
+
+const HOST2: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459800|-1|-1
+0|/var/log/syslog|7|r/rrw-r--r--|0|0|4096|1609460000|1609465000|-1|-1
+";
+
+fn work_dir(name: &str) -> PathBuf {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("bodyfile_merge_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// tests if a merged bodyfile can be split into the original bodyfiles
+#[test]
+fn merge_and_split() {
+    let dir = work_dir("split");
+    let host1 = dir.join("host1.bodyfile");
+    let host2 = dir.join("host2.bodyfile");
+    let merged = dir.join("merged.bodyfile");
+    let origins = dir.join("merged.origins");
+    fs::write(&host1, HOST1).unwrap();
+    fs::write(&host2, HOST2).unwrap();
+
+    Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("merge")
+        .arg("--origins")
+        .arg(&origins)
+        .arg("-o")
+        .arg(&merged)
+        .arg(&host1)
+        .arg(&host2)
+        .assert()
+        .success();
+
+    let merged_content = fs::read_to_string(&merged).unwrap();
+    assert_eq!(merged_content.lines().count(), 6);
+    assert!(merged_content.starts_with("# bodyfile of host1\n"));
+
+    let split_dir = dir.join("split");
+    fs::create_dir_all(&split_dir).unwrap();
+    Command::cargo_bin("bodyfile_merge")
+        .unwrap()
+        .arg("split")
+        .arg("--origins")
+        .arg(&origins)
+        .arg("-d")
+        .arg(&split_dir)
+        .arg(&merged)
+        .assert()
+        .success();
+
+    for (path, expected) in [(host1, HOST1), (host2, HOST2)] {
+        let name = path.to_string_lossy().replace(['/', '\\', ':'], "_");
+        let content = fs::read(split_dir.join(name)).unwrap();
+        assert_eq!(content, expected.as_bytes());
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+fn visits(filename: &str) -> Vec<(String, i64)> {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("browser2bodyfile");
+    data_path.push(filename);
+
+    let mut cmd = Command::cargo_bin("browser2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .map(|line| {
+            assert_eq!(*line.get_mtime(), -1);
+            assert_eq!(*line.get_ctime(), -1);
+            assert_eq!(*line.get_crtime(), -1);
+            (line.get_name().to_owned(), *line.get_atime())
+        })
+        .collect()
+}
+
+/// tests if the visits of a Firefox `places.sqlite` are converted, using
+/// microseconds since 1970-01-01
+#[test]
+fn firefox_history() {
+    assert_eq!(
+        visits("places.sqlite"),
+        vec![
+            ("https://www.example.com/ (Example Domain)".to_owned(), 1613606400),
+            ("https://www.example.org/download.zip".to_owned(), 1613606460),
+            ("https://www.example.com/ (Example Domain)".to_owned(), 1613692800),
+        ]
+    );
+}
+
+/// tests if the visits of a Chrome `History` are converted, using
+/// microseconds since 1601-01-01
+#[test]
+fn chrome_history() {
+    assert_eq!(
+        visits("History"),
+        vec![
+            (
+                "https://www.google.com/search?q=dfir (dfir - Google Search)".to_owned(),
+                1613606400
+            ),
+            ("https://github.com/dfir-dd".to_owned(), 1613606460),
+            ("https://github.com/dfir-dd".to_owned(), 1613692800),
+        ]
+    );
+}
@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use assert_cmd::Command;
+
+use super::mock_server::{default_response, MockServer};
+
+fn sample_documents(count: usize) -> String {
+    (0..count)
+        .map(|idx| {
+            format!(
+                r#"{{"@timestamp":{},"message":"/{idx}","file":{{"path":"/{idx}","inode":"{idx}"}}}}"#,
+                1577092511000 + idx * 1000
+            ) + "\n"
+        })
+        .collect()
+}
+
+fn import(server: &MockServer, documents: String, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    cmd.arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        .arg("import")
+        .args(args)
+        .arg("-")
+        .write_stdin(documents)
+        .assert()
+}
+
+fn bulk_requests(server: &MockServer) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .count()
+}
+
+/// tests if no more than `--max-in-flight` bulk operations are outstanding
+#[test]
+fn bounded_concurrency() {
+    let current = Arc::new(AtomicUsize::new(0));
+    let maximum = Arc::new(AtomicUsize::new(0));
+
+    let server = {
+        let current = Arc::clone(&current);
+        let maximum = Arc::clone(&maximum);
+        MockServer::start_with(move |request| {
+            if request.path.contains("/_bulk") {
+                let running = current.fetch_add(1, Ordering::SeqCst) + 1;
+                maximum.fetch_max(running, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(100));
+                current.fetch_sub(1, Ordering::SeqCst);
+            }
+            default_response(request)
+        })
+    };
+
+    import(
+        &server,
+        sample_documents(20),
+        &["--batch-size", "2", "--max-in-flight", "3"],
+    )
+    .success();
+
+    assert_eq!(bulk_requests(&server), 10);
+    let maximum = maximum.load(Ordering::SeqCst);
+    assert!(maximum <= 3, "{maximum} bulk operations were sent concurrently");
+    assert!(maximum > 1, "bulk operations have not been sent concurrently");
+}
+
+/// tests if a bulk operation which is rejected with `429 Too Many Requests`
+/// is sent again
+#[test]
+fn retry_after_too_many_requests() {
+    let bulk_count = AtomicUsize::new(0);
+    let server = MockServer::start_with(move |request| {
+        if request.path.contains("/_bulk") && bulk_count.fetch_add(1, Ordering::SeqCst) == 0 {
+            (429, r#"{"error":"too many requests"}"#.to_owned())
+        } else {
+            default_response(request)
+        }
+    });
+
+    import(&server, sample_documents(4), &["--batch-size", "2"]).success();
+
+    let bulk_bodies: Vec<_> = server
+        .requests()
+        .into_iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .map(|r| r.body)
+        .collect();
+    assert_eq!(bulk_bodies.len(), 3);
+    // the rejected batch has been sent again
+    assert_eq!(bulk_bodies[0], bulk_bodies[1]);
+}
+
+/// tests if a batch which is not full is sent after the flush interval, even
+/// if the input has not been closed yet
+#[test]
+fn flush_after_interval() {
+    let server = MockServer::start();
+    let port = server.port();
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("es4forensics"))
+        .args([
+            "--index",
+            "elastic4forensics_test",
+            "--proto",
+            "http",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            &port.to_string(),
+            "--password",
+            "elastic",
+            "import",
+            "--batch-size",
+            "100",
+            "--flush-interval",
+            "100",
+            "-",
+        ])
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    {
+        use std::io::Write;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(sample_documents(3).as_bytes()).unwrap();
+        stdin.flush().unwrap();
+
+        thread::sleep(Duration::from_millis(1000));
+        assert_eq!(bulk_requests(&server), 1);
+    }
+
+    assert!(child.wait().unwrap().success());
+    assert_eq!(bulk_requests(&server), 1);
+}
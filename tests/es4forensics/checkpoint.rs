@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+use super::mock_server::{default_response, MockServer};
+
+const SAMPLE_DOCUMENTS: &str = r#"{"@timestamp":1577092511000,"message":"/a","file":{"path":"/a","inode":"1"}}
+{"@timestamp":1577092512000,"message":"/b","file":{"path":"/b","inode":"2"}}
+{"@timestamp":1577092513000,"message":"/c","file":{"path":"/c","inode":"3"}}
+{"@timestamp":1577092514000,"message":"/d","file":{"path":"/d","inode":"4"}}
+{"@timestamp":1577092515000,"message":"/e","file":{"path":"/e","inode":"5"}}
+"#;
+
+fn import(server: &MockServer, checkpoint: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    cmd.arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        // the simulated failure must not be hidden by a retry
+        .arg("--retries")
+        .arg("0")
+        .arg("import")
+        .arg("--bulk-size")
+        .arg("2")
+        .arg("--checkpoint")
+        .arg(checkpoint)
+        .args(args)
+        .arg("-")
+        .write_stdin(SAMPLE_DOCUMENTS)
+        .assert()
+}
+
+/// returns the messages of all documents which have been sent in bulk operations
+fn imported_messages(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .flat_map(|r| {
+            r.body
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+        .skip(1)
+        // every action is followed by its document
+        .step_by(2)
+        .map(|document| document["message"].as_str().unwrap().to_owned())
+        .collect()
+}
+
+/// tests if an import which fails during the second bulk operation can be
+/// resumed, and if only the remaining lines are imported
+#[test]
+fn resume_after_failure() {
+    let checkpoint =
+        std::env::temp_dir().join(format!("es4forensics_checkpoint_{}", std::process::id()));
+    let _ = fs::remove_file(&checkpoint);
+
+    let bulk_requests = AtomicUsize::new(0);
+    let failing_server = MockServer::start_with(move |request| {
+        if request.path.contains("/_bulk") && bulk_requests.fetch_add(1, Ordering::SeqCst) == 1 {
+            (500, r#"{"error":"simulated failure"}"#.to_owned())
+        } else {
+            default_response(request)
+        }
+    });
+    import(&failing_server, &checkpoint, &[]).failure();
+    assert_eq!(fs::read_to_string(&checkpoint).unwrap().trim(), "2");
+
+    let server = MockServer::start();
+    import(&server, &checkpoint, &["--resume"]).success();
+    assert_eq!(imported_messages(&server), vec!["/c", "/d", "/e"]);
+    assert!(!checkpoint.exists());
+}
+
+/// tests if a bulk operation, in which elasticsearch rejected documents,
+/// does not advance the checkpoint
+#[test]
+fn rejected_documents() {
+    let checkpoint = std::env::temp_dir().join(format!(
+        "es4forensics_checkpoint_rejected_{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&checkpoint);
+
+    let bulk_requests = AtomicUsize::new(0);
+    let rejecting_server = MockServer::start_with(move |request| {
+        if request.path.contains("/_bulk") && bulk_requests.fetch_add(1, Ordering::SeqCst) == 1 {
+            (
+                200,
+                r#"{"took":1,"errors":true,"items":[{"index":{"status":201}},{"index":{"status":400,"error":{"type":"mapper_parsing_exception"}}}]}"#.to_owned(),
+            )
+        } else {
+            default_response(request)
+        }
+    });
+    let result = import(&rejecting_server, &checkpoint, &[]).failure();
+    assert!(String::from_utf8_lossy(&result.get_output().stderr)
+        .contains("elasticsearch rejected 1 of 2 documents"));
+    assert_eq!(fs::read_to_string(&checkpoint).unwrap().trim(), "2");
+
+    let server = MockServer::start();
+    import(&server, &checkpoint, &["--resume"]).success();
+    assert_eq!(imported_messages(&server), vec!["/c", "/d", "/e"]);
+    assert!(!checkpoint.exists());
+}
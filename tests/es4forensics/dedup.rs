@@ -0,0 +1,74 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+use super::mock_server::MockServer;
+
+const SAMPLE_DOCUMENTS: &str = r#"{"@timestamp":1577092511000,"message":"/Users/Administrator","file":{"path":"/Users/Administrator","size":92,"inode":"93552-48-2","mtime":1577092511000}}
+{"@timestamp":1577092599000,"message":"/Users/Administrator","file":{"path":"/Users/Administrator","size":92,"inode":"93552-48-2","mtime":1577092511000,"created":1577092599000}}
+"#;
+
+/// imports the sample documents and returns the bulk actions which have been sent
+fn import(args: &[&str]) -> Vec<Value> {
+    let server = MockServer::start();
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    cmd.arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        .arg("import")
+        .args(args)
+        .arg("-")
+        .write_stdin(SAMPLE_DOCUMENTS)
+        .assert()
+        .success();
+
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .flat_map(|r| {
+            r.body
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+        // every action is followed by its document
+        .step_by(2)
+        .collect()
+}
+
+/// tests if importing the same data twice yields the same document ids
+#[test]
+fn same_ids_across_runs() {
+    let first_run = import(&[]);
+    let second_run = import(&[]);
+    assert_eq!(first_run.len(), 2);
+
+    let ids = |actions: &[Value]| -> Vec<String> {
+        actions
+            .iter()
+            .map(|action| action["index"]["_id"].as_str().unwrap().to_owned())
+            .collect()
+    };
+    let first_ids = ids(&first_run);
+    assert_eq!(first_ids, ids(&second_run));
+
+    // the two events refer to different timestamps of the same file
+    assert_ne!(first_ids[0], first_ids[1]);
+}
+
+/// tests if `--no-dedup` lets elasticsearch assign the document ids
+#[test]
+fn no_dedup() {
+    let actions = import(&["--no-dedup"]);
+    assert_eq!(actions.len(), 2);
+    for action in actions {
+        assert!(action["index"].get("_id").is_none());
+    }
+}
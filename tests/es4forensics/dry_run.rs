@@ -0,0 +1,73 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+use super::mock_server::MockServer;
+
+const SAMPLE_DOCUMENTS: &str = r#"{"@timestamp":1577092511000,"message":"/Users/Administrator","file":{"path":"/Users/Administrator","size":92,"inode":"93552-48-2","mtime":1577092511000}}
+{"@timestamp":1577092599000,"message":"/Users/Administrator","file":{"path":"/Users/Administrator","size":92,"inode":"93552-48-2","mtime":1577092511000,"created":1577092599000}}
+"#;
+
+/// runs a dry run against the mock server, and returns the server, stdout
+/// and stderr
+fn dry_run(args: &[&str]) -> (MockServer, String, String) {
+    let server = MockServer::start();
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    let result = cmd
+        .arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        .arg("import")
+        .arg("--dry-run")
+        .args(args)
+        .arg("-")
+        .write_stdin(SAMPLE_DOCUMENTS)
+        .assert()
+        .success();
+
+    let output = result.get_output();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+    (server, stdout, stderr)
+}
+
+/// tests if a dry run sends no requests, but prints the bulk operations
+#[test]
+fn no_requests() {
+    let (server, stdout, stderr) = dry_run(&[]);
+    assert!(server.requests().is_empty());
+
+    let lines: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    for action in lines.iter().step_by(2) {
+        assert!(action["index"]["_id"].is_string());
+    }
+    assert_eq!(lines[1]["file"]["size"], 92);
+
+    assert!(stderr.contains("index: elastic4forensics_test"));
+    assert!(stderr.contains("documents: 2"));
+}
+
+/// tests if `--dry-run-sample` prints only some documents, but counts all
+#[test]
+fn sample() {
+    let (server, stdout, stderr) = dry_run(&["--dry-run-sample", "1", "--no-dedup"]);
+    assert!(server.requests().is_empty());
+
+    let lines: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0]["index"].get("_id").is_none());
+    assert!(stderr.contains("documents: 2"));
+}
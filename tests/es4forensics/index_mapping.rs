@@ -0,0 +1,95 @@
+use assert_cmd::Command;
+
+use super::mock_server::MockServer;
+
+const SAMPLE_DOCUMENT: &str = r#"{"@timestamp":1577092511000,"message":"/Users/Administrator","file":{"path":"/Users/Administrator","size":92,"inode":"93552-48-2"}}"#;
+
+fn import(server: &MockServer, args: &[&str]) {
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    cmd.arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        .arg("import")
+        .args(args)
+        .arg("-")
+        .write_stdin(SAMPLE_DOCUMENT)
+        .assert()
+        .success();
+}
+
+/// tests if the index is created with an explicit mapping before the first bulk request
+#[test]
+fn mapping_is_sent_before_bulk() {
+    let server = MockServer::start();
+    import(&server, &[]);
+
+    let requests = server.requests();
+    let create = requests
+        .iter()
+        .position(|r| r.method == "PUT" && r.path == "/elastic4forensics_test")
+        .expect("index has not been created");
+    let bulk = requests
+        .iter()
+        .position(|r| r.path.contains("/_bulk"))
+        .expect("no bulk request has been sent");
+    assert!(create < bulk);
+
+    let mapping: serde_json::Value = serde_json::from_str(&requests[create].body).unwrap();
+    let properties = &mapping["mappings"]["properties"];
+    assert_eq!(properties["@timestamp"]["type"], "date");
+    assert_eq!(properties["file"]["properties"]["size"]["type"], "long");
+    assert_eq!(properties["file"]["properties"]["inode"]["type"], "long");
+    assert_eq!(properties["file"]["properties"]["path"]["type"], "keyword");
+    assert_eq!(
+        properties["file"]["properties"]["path"]["fields"]["text"]["type"],
+        "text"
+    );
+}
+
+/// tests if an existing index is not created again
+#[test]
+fn existing_index_is_kept() {
+    let server = MockServer::start_with(|request| {
+        if request.path.starts_with("/_cat/indices") {
+            (200, r#"[{"index":"elastic4forensics_test"}]"#.to_owned())
+        } else {
+            super::mock_server::default_response(request)
+        }
+    });
+    import(&server, &[]);
+
+    let requests = server.requests();
+    assert!(!requests.iter().any(|r| r.method == "PUT" || r.method == "DELETE"));
+    assert!(requests.iter().any(|r| r.path.contains("/_bulk")));
+}
+
+/// tests if `--recreate-index` drops the index before creating it again
+#[test]
+fn recreate_index() {
+    let server = MockServer::start_with(|request| {
+        if request.path.starts_with("/_cat/indices") {
+            (200, r#"[{"index":"elastic4forensics_test"}]"#.to_owned())
+        } else {
+            super::mock_server::default_response(request)
+        }
+    });
+    import(&server, &["--recreate-index"]);
+
+    let requests = server.requests();
+    let delete = requests
+        .iter()
+        .position(|r| r.method == "DELETE" && r.path == "/elastic4forensics_test")
+        .expect("index has not been deleted");
+    let bulk = requests
+        .iter()
+        .position(|r| r.path.contains("/_bulk"))
+        .expect("no bulk request has been sent");
+    assert!(delete < bulk);
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use super::mock_server::MockServer;
+
+const SAMPLE_DOCUMENTS: &str = r#"{"@timestamp":1577092511000,"message":"/a","file":{"path":"/a","inode":"1"}}
+{"@timestamp":1577092512000,"message":"/b","file":{"path":"/b","inode":"2"}}
+{"@timestamp":1577092513000,"message":"/c","file":{"path":"/c","inode":"3"}}
+"#;
+
+/// tests if the pending documents are imported after SIGINT, even if the
+/// input has not been closed yet
+#[cfg(unix)]
+#[test]
+fn flush_on_interrupt() {
+    let checkpoint =
+        std::env::temp_dir().join(format!("es4forensics_interrupt_{}", std::process::id()));
+    let _ = fs::remove_file(&checkpoint);
+
+    let server = MockServer::start();
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin("es4forensics"))
+        .args([
+            "--index",
+            "elastic4forensics_test",
+            "--proto",
+            "http",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            &server.port().to_string(),
+            "--password",
+            "elastic",
+            "import",
+            "--batch-size",
+            "100",
+            "--checkpoint",
+        ])
+        .arg(&checkpoint)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // the input stays open until the process has terminated
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(SAMPLE_DOCUMENTS.as_bytes()).unwrap();
+    stdin.flush().unwrap();
+    thread::sleep(Duration::from_millis(500));
+
+    let status = Command::new("kill")
+        .arg("-INT")
+        .arg(child.id().to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output = child.wait_with_output().unwrap();
+    drop(stdin);
+    assert_eq!(output.status.code(), Some(130));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("interrupted after importing 3 documents"));
+
+    let bulk_requests: Vec<_> = server
+        .requests()
+        .into_iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .collect();
+    assert_eq!(bulk_requests.len(), 1);
+    assert_eq!(bulk_requests[0].body.lines().count(), 6);
+    assert_eq!(fs::read_to_string(&checkpoint).unwrap().trim(), "3");
+
+    fs::remove_file(&checkpoint).unwrap();
+}
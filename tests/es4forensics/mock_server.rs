@@ -0,0 +1,124 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// a request which has been received by the [`MockServer`]
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+type Responder = dyn Fn(&MockRequest) -> (u16, String) + Send + Sync;
+
+/// minimal HTTP server which mimics the parts of the elasticsearch API used
+/// by `es4forensics`, and which records all requests
+pub struct MockServer {
+    port: u16,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// starts a server which answers requests like an empty elasticsearch
+    /// instance would do
+    pub fn start() -> Self {
+        Self::start_with(default_response)
+    }
+
+    /// starts a server which uses `responder` to create the status code
+    /// and the body of every response
+    pub fn start_with<F>(responder: F) -> Self
+    where
+        F: Fn(&MockRequest) -> (u16, String) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responder: Arc<Responder> = Arc::new(responder);
+
+        let server_requests = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().map_while(Result::ok) {
+                let requests = Arc::clone(&server_requests);
+                let responder = Arc::clone(&responder);
+                thread::spawn(move || handle_connection(stream, requests, responder));
+            }
+        });
+
+        Self { port, requests }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+pub fn default_response(request: &MockRequest) -> (u16, String) {
+    if request.path.starts_with("/_cat/indices") {
+        (200, "[]".to_owned())
+    } else if request.path.contains("/_bulk") {
+        (200, r#"{"took":1,"errors":false,"items":[]}"#.to_owned())
+    } else {
+        (200, r#"{"acknowledged":true}"#.to_owned())
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    responder: Arc<Responder>,
+) {
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            break;
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_owned();
+        let path = parts.next().unwrap_or_default().to_owned();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((key, value)) = header.split_once(':') {
+                if key.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let request = MockRequest {
+            method,
+            path,
+            body: String::from_utf8_lossy(&body).to_string(),
+        };
+        let (status, response) = responder(&request);
+        requests.lock().unwrap().push(request);
+
+        let response = format!(
+            "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\nx-elastic-product: Elasticsearch\r\ncontent-length: {}\r\n\r\n{response}",
+            response.len()
+        );
+        if writer.write_all(response.as_bytes()).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub(crate) mod mock_server;
+mod index_mapping;
+mod dedup;
+mod checkpoint;
+mod backpressure;
+mod interrupt;
+mod retry;
+mod dry_run;
@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use assert_cmd::Command;
+
+use super::mock_server::{default_response, MockServer};
+
+const DOCUMENTS: &str = r#"{"@timestamp":1577092511000,"message":"/a","file":{"path":"/a","inode":"1"}}
+{"@timestamp":1577092512000,"message":"/b","file":{"path":"/b","inode":"2"}}
+"#;
+
+fn import(server: &MockServer) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("es4forensics").unwrap();
+    cmd.arg("--index")
+        .arg("elastic4forensics_test")
+        .arg("--proto")
+        .arg("http")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(server.port().to_string())
+        .arg("--password")
+        .arg("elastic")
+        .arg("--retries")
+        .arg("3")
+        .arg("import")
+        .arg("-")
+        .write_stdin(DOCUMENTS)
+        .assert()
+}
+
+fn bulk_bodies(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .into_iter()
+        .filter(|r| r.path.contains("/_bulk"))
+        .map(|r| r.body)
+        .collect()
+}
+
+/// tests if a bulk operation succeeds after two `503 Service Unavailable`
+#[test]
+fn retry_after_service_unavailable() {
+    let bulk_count = AtomicUsize::new(0);
+    let server = MockServer::start_with(move |request| {
+        if request.path.contains("/_bulk") && bulk_count.fetch_add(1, Ordering::SeqCst) < 2 {
+            (503, r#"{"error":"unavailable"}"#.to_owned())
+        } else {
+            default_response(request)
+        }
+    });
+
+    import(&server).success();
+
+    let bodies = bulk_bodies(&server);
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies[0], bodies[1]);
+    assert_eq!(bodies[1], bodies[2]);
+}
+
+/// tests if the index is created even if elasticsearch is temporarily unavailable
+#[test]
+fn retry_index_creation() {
+    let cat_count = AtomicUsize::new(0);
+    let server = MockServer::start_with(move |request| {
+        if request.path.starts_with("/_cat/indices") && cat_count.fetch_add(1, Ordering::SeqCst) == 0 {
+            (502, String::new())
+        } else {
+            default_response(request)
+        }
+    });
+
+    import(&server).success();
+
+    let requests = server.requests();
+    assert_eq!(requests.iter().filter(|r| r.path.starts_with("/_cat/indices")).count(), 2);
+    assert!(requests.iter().any(|r| r.method == "PUT" && r.path == "/elastic4forensics_test"));
+}
+
+/// tests if client errors are not retried
+#[test]
+fn no_retry_after_client_error() {
+    let server = MockServer::start_with(|request| {
+        if request.path.contains("/_bulk") {
+            (400, r#"{"error":"bad request"}"#.to_owned())
+        } else {
+            default_response(request)
+        }
+    });
+
+    import(&server).failure();
+    assert_eq!(bulk_bodies(&server).len(), 1);
+}
+
+/// tests if the import fails if elasticsearch does not recover
+#[test]
+fn give_up_after_max_retries() {
+    let server = MockServer::start_with(|request| {
+        if request.path.contains("/_bulk") {
+            (503, r#"{"error":"unavailable"}"#.to_owned())
+        } else {
+            default_response(request)
+        }
+    });
+
+    import(&server).failure();
+    assert_eq!(bulk_bodies(&server).len(), 4);
+}
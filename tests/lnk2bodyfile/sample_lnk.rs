@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+/// tests if target path and timestamps are read from a lnk file
+#[test]
+fn sample_lnk() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("lnk2bodyfile");
+    data_path.push("notepad.lnk");
+
+    let mut cmd = Command::cargo_bin("lnk2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let bf_line = Bodyfile3Line::try_from(stdout.trim_end()).unwrap();
+
+    assert_eq!(
+        bf_line.get_name(),
+        &format!(
+            "{} -> C:\\Windows\\System32\\notepad.exe test.txt (volume serial: 1234-ABCD, drive type: fixed)",
+            data_path.display()
+        )
+    );
+    assert_eq!(*bf_line.get_size(), 196608);
+    assert_eq!(*bf_line.get_crtime(), 1609459200);
+    assert_eq!(*bf_line.get_atime(), 1609462800);
+    assert_eq!(*bf_line.get_mtime(), 1609466400);
+    assert_eq!(*bf_line.get_ctime(), -1);
+}
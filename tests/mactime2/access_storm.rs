@@ -0,0 +1,74 @@
+use assert_cmd::Command;
+
+/// 2021-01-01T00:00:00+00:00
+const SCAN_START: i64 = 1609459200;
+
+/// creates a bodyfile with an antivirus scan, which reads 100 files within
+/// 50 seconds, and some files which are changed during and after the scan
+fn scan_bodyfile() -> String {
+    let mut bodyfile = String::new();
+    for i in 0..100 {
+        bodyfile.push_str(&format!(
+            "0|C:/Windows/System32/file{i}.dll|{i}|r/rrwxrwxrwx|0|0|0|{}|1577836800|1577836800|1577836800\n",
+            SCAN_START + i / 2
+        ));
+    }
+    bodyfile.push_str(&format!(
+        "0|C:/Users/jdoe/report.docx|200|r/rrwxrwxrwx|0|0|0|{ts}|{ts}|1577836800|1577836800\n",
+        ts = SCAN_START + 10
+    ));
+    bodyfile.push_str(&format!(
+        "0|C:/Users/jdoe/notes.txt|201|r/rrwxrwxrwx|0|0|0|-1|{}|1577836800|1577836800\n",
+        SCAN_START + 3600
+    ));
+    bodyfile
+}
+
+fn mactime2(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .arg("--from")
+        .arg("2021-01-01T00:00:00")
+        .args(args)
+        .write_stdin(scan_bodyfile())
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// tests if the scan is collapsed into one summary row, while the other
+/// events are kept
+#[test]
+fn collapse_access_storm() {
+    assert_eq!(
+        mactime2(&["--collapse-access-storm", "50"]),
+        vec![
+            "Date,Type,File Name",
+            "2021-01-01T00:00:00+00:00,.a..,\"access storm: 100 atime-only events within 49 seconds\"",
+            "2021-01-01T00:00:10+00:00,ma..,\"C:/Users/jdoe/report.docx\"",
+            "2021-01-01T01:00:00+00:00,m...,\"C:/Users/jdoe/notes.txt\"",
+        ]
+    );
+}
+
+/// tests if bursts which last longer than the window are not collapsed
+#[test]
+fn window_too_short() {
+    let lines = mactime2(&[
+        "--collapse-access-storm",
+        "50",
+        "--access-storm-window",
+        "10",
+    ]);
+    assert_eq!(lines.len(), 1 + 100 + 2);
+    assert!(!lines.iter().any(|line| line.contains("access storm")));
+}
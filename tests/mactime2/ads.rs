@@ -0,0 +1,54 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|C:/Temp/setup.exe|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|C:/Temp/setup.exe:Zone.Identifier|1|r/rrwxrwxrwx|0|0|0|-1|1609459201|-1|-1
+0|C:/Windows/notepad.exe|2|r/rrwxrwxrwx|0|0|0|-1|1609459202|-1|-1
+";
+
+fn names(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().skip(1).map(String::from).collect()
+}
+
+/// tests if `--only-ads` displays only alternate data streams
+#[test]
+fn only_ads() {
+    assert_eq!(
+        names(&["--only-ads"]),
+        vec!["\"C:/Temp/setup.exe:Zone.Identifier\""]
+    );
+}
+
+/// tests if `--no-ads` hides alternate data streams
+#[test]
+fn no_ads() {
+    assert_eq!(
+        names(&["--no-ads"]),
+        vec!["\"C:/Temp/setup.exe\"", "\"C:/Windows/notepad.exe\""]
+    );
+}
+
+/// tests if both filters cannot be combined
+#[test]
+fn conflicting_filters() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--only-ads")
+        .arg("--no-ads")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(3);
+}
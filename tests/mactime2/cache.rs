@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/mnt/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1
+0|/mnt/etc/passwd|2|r/rrw-r--r--|0|0|6|-1|1609459300|-1|-1
+";
+
+fn prepare(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("mactime2_cache_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let bodyfile = dir.join("sample.bodyfile");
+    fs::write(&bodyfile, SAMPLE_BODYFILE).unwrap();
+    let cache = dir.join("sample.cache");
+    (dir, bodyfile, cache)
+}
+
+fn timeline(bodyfile: &Path, cache: &Path, args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg(bodyfile)
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,name")
+        .arg("--cache")
+        .arg(cache)
+        .args(args)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+fn replace_bytes(data: &mut [u8], from: &[u8], to: &[u8]) {
+    assert_eq!(from.len(), to.len());
+    let mut idx = 0;
+    while idx + from.len() <= data.len() {
+        if &data[idx..idx + from.len()] == from {
+            data[idx..idx + from.len()].copy_from_slice(to);
+        }
+        idx += 1;
+    }
+}
+
+/// tests if the cache is created by the first run and used by the second
+/// run, and if options are still applied to cached lines
+#[test]
+fn use_cache() {
+    let (dir, bodyfile, cache) = prepare("use");
+    assert_eq!(
+        timeline(&bodyfile, &cache, &[]),
+        vec![
+            "Date,File Name",
+            "2021-01-01T00:00:00+00:00,\"/mnt/etc/hosts\"",
+            "2021-01-01T00:01:40+00:00,\"/mnt/etc/passwd\"",
+        ]
+    );
+
+    // modify the cache to see if it is actually used
+    let mut content = fs::read(&cache).unwrap();
+    assert!(content.starts_with(b"MT2CACHE"));
+    replace_bytes(&mut content, b"/mnt/etc/hosts", b"/mnt/etc/HOSTS");
+    fs::write(&cache, &content).unwrap();
+
+    assert_eq!(
+        timeline(&bodyfile, &cache, &["--strip-prefix", "/mnt"]),
+        vec![
+            "Date,File Name",
+            "2021-01-01T00:00:00+00:00,\"/etc/HOSTS\"",
+            "2021-01-01T00:01:40+00:00,\"/etc/passwd\"",
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// tests if a cache with another version is ignored and replaced
+#[test]
+fn version_mismatch() {
+    let (dir, bodyfile, cache) = prepare("version");
+    let expected = timeline(&bodyfile, &cache, &[]);
+
+    let mut content = fs::read(&cache).unwrap();
+    content[8..12].copy_from_slice(&99u32.to_le_bytes());
+    replace_bytes(&mut content, b"/mnt/etc/hosts", b"/mnt/etc/HOSTS");
+    fs::write(&cache, &content).unwrap();
+
+    assert_eq!(timeline(&bodyfile, &cache, &[]), expected);
+    assert_eq!(fs::read(&cache).unwrap()[8..12], 1u32.to_le_bytes());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// tests if the cache is not used after the bodyfile has been changed
+#[test]
+fn changed_bodyfile() {
+    let (dir, bodyfile, cache) = prepare("changed");
+    timeline(&bodyfile, &cache, &[]);
+
+    fs::write(&bodyfile, "0|/mnt/etc/group|3|r/rrw-r--r--|0|0|6|-1|1609459500|-1|-1\n").unwrap();
+    assert_eq!(
+        timeline(&bodyfile, &cache, &[]),
+        vec![
+            "Date,File Name",
+            "2021-01-01T00:05:00+00:00,\"/mnt/etc/group\"",
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
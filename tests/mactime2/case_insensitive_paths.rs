@@ -0,0 +1,56 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|C:/Windows/notepad.exe|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|C:/windows/NOTEPAD.EXE|1|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+";
+
+fn run(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if names which only differ in their case are counted as one file
+#[test]
+fn count_files() {
+    assert_eq!(run(&["--count-only"]), "events: 2\nfiles: 2\n");
+    assert_eq!(
+        run(&["--count-only", "--case-insensitive-paths"]),
+        "events: 2\nfiles: 1\n"
+    );
+}
+
+/// tests if names which only differ in their case are deduplicated by
+/// `--first-per-file`, without changing the displayed name
+#[test]
+fn first_per_file() {
+    let args = ["-d", "--fields", "name", "--first-per-file"];
+    assert_eq!(
+        run(&args).lines().skip(1).collect::<Vec<_>>(),
+        vec!["\"C:/Windows/notepad.exe\"", "\"C:/windows/NOTEPAD.EXE\""]
+    );
+    assert_eq!(
+        run(&[&args[..], &["--case-insensitive-paths"]].concat())
+            .lines()
+            .skip(1)
+            .collect::<Vec<_>>(),
+        vec!["\"C:/Windows/notepad.exe\""]
+    );
+}
+
+/// tests if regular expressions match names case-insensitively
+#[test]
+fn where_expression() {
+    let args = ["--count-only", "--where", r#"name ~ "/windows/""#];
+    assert_eq!(run(&args), "events: 1\nfiles: 1\n");
+    assert_eq!(
+        run(&[&args[..], &["--case-insensitive-paths"]].concat()),
+        "events: 2\nfiles: 1\n"
+    );
+}
@@ -0,0 +1,73 @@
+use assert_cmd::Command;
+use chrono::DateTime;
+use serde_json::Value;
+
+const SAMPLE_BODYFILE: &str =
+    "0|C:/Windows/notepad.exe|1|r/rrwxrwxrwx|0|0|42|1609459201|1609459202|-1|1609459200
+0|/tmp/empty|2|r/rrw-r--r--|0|0|0|-1|-1|-1|-1
+";
+
+fn export(input: &str) -> Value {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("case-uco")
+        .write_stdin(input)
+        .ok();
+    assert!(result.is_ok());
+    serde_json::from_slice(&result.unwrap().stdout).unwrap()
+}
+
+fn facet<'a>(object: &'a Value, facet_type: &str) -> &'a Value {
+    object["uco-core:hasFacet"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|facet| facet["@type"] == facet_type)
+        .unwrap()
+}
+
+/// tests if every file is exported as `uco-observable:File` with a file
+/// facet and a filesystem object facet, whose timestamps are ISO-8601
+#[test]
+fn files_are_exported() {
+    let document = export(SAMPLE_BODYFILE);
+    assert!(document["@context"]["uco-observable"].is_string());
+
+    let graph = document["@graph"].as_array().unwrap();
+    assert_eq!(graph.len(), 2);
+    for object in graph {
+        assert_eq!(object["@type"], "uco-observable:File");
+    }
+
+    let file_facet = facet(&graph[0], "uco-observable:FileFacet");
+    assert_eq!(file_facet["uco-observable:fileName"], "notepad.exe");
+    assert_eq!(file_facet["uco-observable:sizeInBytes"]["@value"], 42);
+
+    let timestamps = facet(&graph[0], "uco-observable:FileSystemObjectFacet");
+    for (key, expected) in [
+        ("uco-observable:accessedTime", 1609459201),
+        ("uco-observable:modifiedTime", 1609459202),
+        ("uco-observable:observableCreatedTime", 1609459200),
+    ] {
+        assert_eq!(timestamps[key]["@type"], "xsd:dateTime");
+        let value = timestamps[key]["@value"].as_str().unwrap();
+        let datetime = DateTime::parse_from_rfc3339(value).unwrap();
+        assert_eq!(datetime.timestamp(), expected);
+    }
+
+    // unset timestamps are omitted
+    assert!(timestamps
+        .get("uco-observable:metadataChangeTime")
+        .is_none());
+    let timestamps = facet(&graph[1], "uco-observable:FileSystemObjectFacet");
+    assert!(timestamps.get("uco-observable:modifiedTime").is_none());
+}
+
+/// tests if an empty input results in a valid document
+#[test]
+fn empty_input() {
+    let document = export("");
+    assert_eq!(document["@graph"], Value::Array(Vec::new()));
+}
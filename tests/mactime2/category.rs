@@ -0,0 +1,35 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|C:/Windows/notepad.exe|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|C:/Users/jdoe/report.docx|2|r/rrwxrwxrwx|0|0|0|-1|1609459201|-1|-1
+0|C:/Users/jdoe/run.ps1|3|r/rrwxrwxrwx|0|0|0|-1|1609459202|-1|-1
+0|/usr/bin/ls|4|r/rrwxrwxrwx|0|0|0|-1|1609459203|-1|-1
+";
+
+/// tests if `--category` only displays files of the selected categories
+#[test]
+fn category_filter() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name,category")
+        .arg("--category")
+        .arg("executable,script")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "File Name,Category",
+            "\"C:/Windows/notepad.exe\",executable",
+            "\"C:/Users/jdoe/run.ps1\",script",
+        ]
+    );
+}
@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "# bodyfile of a FAT32 volume
+0|/DCIM/IMG_0001.JPG|3|r/rrwxrwxrwx|0|0|1024|1609459200|1609459200|-1|1609459200
+0|/DCIM/IMG_0002.JPG|4|r/rrwxrwxrwx|0|0|1024|1609459200|1609459201|-1|1609459200
+";
+
+/// tests if `check --fs fat` reports an odd modification time
+#[test]
+fn fat_odd_mtime() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("check")
+        .arg("--fs")
+        .arg("fat")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("line 3: '/DCIM/IMG_0002.JPG': Mtime 1609459201"));
+}
+
+/// tests if the same bodyfile is valid for NTFS
+#[test]
+fn ntfs_odd_mtime() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("check")
+        .arg("--fs")
+        .arg("ntfs")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    assert!(result.unwrap().stdout.is_empty());
+}
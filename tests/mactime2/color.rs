@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/tmp/sample.txt|1234|r/rrwxrwxrwx|0|0|42|1609459200|1609459200|-1|-1
+";
+
+fn run_with_color(mode: &str) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--color")
+        .arg(mode)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if `--color never` does not print any ANSI escape sequences
+#[test]
+fn color_never() {
+    let stdout = run_with_color("never");
+    assert!(!stdout.contains('\u{1b}'));
+    assert!(stdout.contains(" ma.. "));
+}
+
+/// tests if `--color always` prints ANSI escape sequences, even if the output is redirected
+#[test]
+fn color_always() {
+    let stdout = run_with_color("always");
+    assert!(stdout.contains('\u{1b}'));
+}
+
+/// tests if `--color auto` does not colorize redirected output
+#[test]
+fn color_auto() {
+    let stdout = run_with_color("auto");
+    assert!(!stdout.contains('\u{1b}'));
+}
+
+/// tests if `--color always` highlights the rows of a file whose mtime is
+/// later than its ctime, and only those
+#[test]
+fn color_timestomped() {
+    const MAGENTA: &str = "\u{1b}[35m";
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--color")
+        .arg("always")
+        .write_stdin(
+            "0|/tmp/regular.txt|1234|r/rrwxrwxrwx|0|0|42|-1|1609459200|1609459300|-1
+0|/tmp/timestomped.txt|1235|r/rrwxrwxrwx|0|0|42|-1|1609459400|1609459300|-1
+",
+        )
+        .ok();
+    assert!(result.is_ok());
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    for line in lines {
+        assert_eq!(line.contains(MAGENTA), line.contains("timestomped.txt"), "{line}");
+    }
+}
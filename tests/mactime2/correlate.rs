@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+fn host(label: &str) -> String {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push(format!("correlate_{label}.bodyfile"));
+    format!("{label}={}", data_path.display())
+}
+
+fn correlate(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("correlate")
+        .arg("--host")
+        .arg(host("dc01"))
+        .arg("--host")
+        .arg(host("ws01"))
+        .arg("--host")
+        .arg(host("ws02"))
+        .args(args)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// the service executable was copied from dc01 to ws01, and all other
+/// files exist on only one host
+#[test]
+fn report_files_on_two_hosts() {
+    assert_eq!(
+        correlate(&[]),
+        "5d41402abc4b2a76b9719d911017c592\t2 hosts\tspread 1h 0m 0s\n\
+         \tdc01\t2021-01-01T00:00:00+00:00\tC:/Windows/Temp/psexesvc.exe\n\
+         \tws01\t2021-01-01T01:00:00+00:00\tC:/Windows/psexesvc.exe\n"
+    );
+}
+
+#[test]
+fn skip_files_outside_of_window() {
+    assert_eq!(correlate(&["--window", "30m"]), "");
+    assert!(!correlate(&["--window", "1h"]).is_empty());
+}
+
+#[test]
+fn require_two_hosts() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("correlate")
+        .arg("--host")
+        .arg(host("dc01"))
+        .assert()
+        .failure();
+}
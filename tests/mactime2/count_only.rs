@@ -0,0 +1,29 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|1609459200|1609459200|1609462800|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+0|c|3|r/rrwxrwxrwx|0|0|0|-1|1609466400|1609470000|1609473600
+";
+
+fn run(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd.arg("-b").arg("-").args(args).write_stdin(SAMPLE_BODYFILE).ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if `--count-only` reports the number of rows of a full run
+#[test]
+fn count_only() {
+    for filter in [vec![], vec!["--from", "2021-01-01T01:00:00"], vec!["--only", "c"]] {
+        let rows = run(&[&["-d"], filter.as_slice()].concat()).lines().count();
+        let counts = run(&[&["-d", "--count-only"], filter.as_slice()].concat());
+        let mut lines = counts.lines();
+        assert_eq!(lines.next(), Some(format!("events: {rows}").as_str()));
+        assert!(lines.next().unwrap().starts_with("files: "));
+        assert_eq!(lines.next(), None);
+    }
+
+    assert_eq!(run(&["--count-only"]), "events: 6\nfiles: 3\n");
+    assert_eq!(run(&["--count-only", "--only", "c"]), "events: 2\nfiles: 2\n");
+}
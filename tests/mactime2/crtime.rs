@@ -0,0 +1,123 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str =
+    "0|/Users/jdoe/Documents/report.pages|1|r/rrw-r--r--|501|20|4096|1609459300|1609459200|1609459200|1577836800
+";
+
+fn timeline(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+/// tests if a unique creation time results in a separate `...b` event
+#[test]
+fn unique_crtime() {
+    assert_eq!(
+        timeline(&[]),
+        vec![
+            "Date,Type,File Name",
+            "2020-01-01T00:00:00+00:00,...b,\"/Users/jdoe/Documents/report.pages\"",
+            "2021-01-01T00:00:00+00:00,m.c.,\"/Users/jdoe/Documents/report.pages\"",
+            "2021-01-01T00:01:40+00:00,.a..,\"/Users/jdoe/Documents/report.pages\"",
+        ]
+    );
+}
+
+/// tests if `--no-crtime` drops the creation time
+#[test]
+fn no_crtime() {
+    assert_eq!(
+        timeline(&["--no-crtime"]),
+        vec![
+            "Date,Type,File Name",
+            "2021-01-01T00:00:00+00:00,m.c.,\"/Users/jdoe/Documents/report.pages\"",
+            "2021-01-01T00:01:40+00:00,.a..,\"/Users/jdoe/Documents/report.pages\"",
+        ]
+    );
+}
+
+const EMPTY_FILE: &str = "0|/tmp/empty|2|r/rrw-r--r--|0|0|0|-1|-1|-1|1609459200";
+const OTHER_FILE: &str = "0|/tmp/other|3|r/rrw-r--r--|0|0|12|1609459300|1609459300|1609459300|-1";
+
+fn events_of(input: &str, args: &[&str], name: &str) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .args(args)
+        .write_stdin(input.to_owned())
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout
+        .lines()
+        .filter(|line| line.ends_with(&format!("\"{name}\"")))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// tests if an empty file whose only timestamp is its crtime results in
+/// exactly one `...b` event, regardless of the order of the input lines
+#[test]
+fn empty_file_with_crtime_only() {
+    let expected = vec!["2021-01-01T00:00:00+00:00,...b,\"/tmp/empty\""];
+    for input in [
+        format!("{EMPTY_FILE}\n"),
+        format!("{EMPTY_FILE}\n{OTHER_FILE}\n"),
+        format!("{OTHER_FILE}\n{EMPTY_FILE}\n"),
+    ] {
+        for args in [
+            &[][..],
+            &["--only", "b"],
+            &["--sort-by", "b"],
+            &["--first-per-file"],
+            &["--last-per-file"],
+        ] {
+            assert_eq!(events_of(&input, args, "/tmp/empty"), expected);
+        }
+    }
+}
+
+/// tests if `--only m` hides an empty file whose only timestamp is its crtime
+#[test]
+fn empty_file_with_crtime_only_hidden() {
+    let input = format!("{EMPTY_FILE}\n{OTHER_FILE}\n");
+    assert!(events_of(&input, &["--only", "m"], "/tmp/empty").is_empty());
+}
+
+/// tests if the json output contains exactly one event of an empty file
+/// whose only timestamp is its crtime
+#[test]
+fn empty_file_with_crtime_only_json() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-j")
+        .write_stdin(format!("{OTHER_FILE}\n{EMPTY_FILE}\n"))
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let events: Vec<_> = stdout
+        .lines()
+        .filter(|l| l.contains("/tmp/empty"))
+        .collect();
+    assert_eq!(events.len(), 1);
+}
@@ -0,0 +1,51 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/tmp/payload.sh (deleted)|1|r/rrwxr-xr-x|0|0|0|-1|1609459200|-1|-1
+0|/tmp/notes.txt|2|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+0|* /tmp/exfil.tar|3|r/rrw-r--r--|0|0|0|-1|1609459202|-1|-1
+";
+
+fn names(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().skip(1).map(String::from).collect()
+}
+
+/// tests if `--only-deleted` displays only entries with a deletion marker
+#[test]
+fn only_deleted() {
+    assert_eq!(
+        names(&["--only-deleted"]),
+        vec!["\"/tmp/payload.sh (deleted)\"", "\"* /tmp/exfil.tar\""]
+    );
+}
+
+/// tests if `--no-deleted` hides entries with a deletion marker
+#[test]
+fn no_deleted() {
+    assert_eq!(names(&["--no-deleted"]), vec!["\"/tmp/notes.txt\""]);
+}
+
+/// tests if both filters cannot be combined
+#[test]
+fn conflicting_filters() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--only-deleted")
+        .arg("--no-deleted")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(3);
+}
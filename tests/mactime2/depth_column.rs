@@ -0,0 +1,53 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+const SAMPLE_BODYFILE: &str = "0|/boot.ini|1|r/rrwxrwxrwx|0|0|42|-1|1609459200|-1|-1
+0|C:\\Users\\jdoe\\AppData\\Local\\Temp\\evil.exe|2|r/rrwxrwxrwx|0|0|42|-1|1609459201|-1|-1
+";
+
+fn mactime2(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--depth-column")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+#[test]
+fn csv_depth() {
+    let stdout = mactime2(&["-d", "--fields", "name"]);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "File Name,Depth",
+            "\"/boot.ini\",1",
+            "\"C:\\Users\\jdoe\\AppData\\Local\\Temp\\evil.exe\",6",
+        ]
+    );
+}
+
+#[test]
+fn txt_depth() {
+    let stdout = mactime2(&[]);
+    let depths: Vec<_> = stdout
+        .lines()
+        .map(|line| line.rsplit(' ').next().unwrap())
+        .collect();
+    assert_eq!(depths, vec!["1", "6"]);
+}
+
+#[test]
+fn json_depth() {
+    let stdout = mactime2(&["-j"]);
+    let depths: Vec<_> = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap()["file"]["depth"].clone())
+        .collect();
+    assert_eq!(depths, vec![1, 6]);
+}
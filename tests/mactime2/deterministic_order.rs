@@ -0,0 +1,53 @@
+use assert_cmd::Command;
+
+const LINES: [&str; 4] = [
+    "0|/tmp/c|3|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1",
+    "0|/tmp/a|2|r/rrw-r--r--|0|0|0|1609459200|1609459200|-1|-1",
+    "0|/tmp/b|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|1609459300",
+    "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1",
+];
+
+fn timeline(lines: &[&str]) -> Vec<u8> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,inode,name")
+        .arg("--deterministic-order")
+        .write_stdin(lines.join("\n"))
+        .ok();
+    assert!(result.is_ok());
+    result.unwrap().stdout
+}
+
+/// tests if events with the same timestamp are ordered by name, inode and
+/// MACB flags
+#[test]
+fn colliding_timestamps() {
+    let stdout = String::from_utf8(timeline(&LINES)).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "Date,Type,Meta,File Name",
+            "2021-01-01T00:00:00+00:00,m...,1,\"/tmp/a\"",
+            "2021-01-01T00:00:00+00:00,ma..,2,\"/tmp/a\"",
+            "2021-01-01T00:00:00+00:00,m...,1,\"/tmp/b\"",
+            "2021-01-01T00:00:00+00:00,m...,3,\"/tmp/c\"",
+            "2021-01-01T00:01:40+00:00,...b,1,\"/tmp/b\"",
+        ]
+    );
+}
+
+/// tests if the same lines result in byte-identical output, regardless of
+/// their order
+#[test]
+fn reproducible_output() {
+    let expected = timeline(&LINES);
+    assert_eq!(timeline(&LINES), expected);
+
+    let mut reversed = LINES;
+    reversed.reverse();
+    assert_eq!(timeline(&reversed), expected);
+}
@@ -0,0 +1,61 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/etc/hosts|2|r/rrw-r--r--|0|0|0|1609459300|1609459200|-1|-1
+0|/etc/shadow|3|r/rrw-r-----|0|0|0|-1|1609466400|-1|-1
+0|C:\\Windows\\notepad.exe|4|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|C:\\Windows\\regedit.exe|5|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|1609459200
+";
+
+fn dot(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("dot")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// tests if every directory is a node, which is labeled with the number of
+/// events in the directory and its subdirectories
+#[test]
+fn directory_nodes() {
+    let lines = dot(&[]);
+    assert_eq!(lines.first().unwrap(), "digraph timeline {");
+    assert_eq!(lines.last().unwrap(), "}");
+    for expected in [
+        r#"    "/" [label="/\n4 events", fontsize=30, fillcolor=9];"#,
+        r#"    "/etc" [label="etc\n4 events", fontsize=30, fillcolor=9];"#,
+        r#"    "C:" [label="C:\n2 events", fontsize=20, fillcolor=5];"#,
+        r#"    "C:\\Windows" [label="Windows\n2 events", fontsize=20, fillcolor=5];"#,
+        r#"    "/" -> "/etc";"#,
+        r#"    "C:" -> "C:\\Windows";"#,
+    ] {
+        assert!(
+            lines.contains(&expected.to_owned()),
+            "missing line: {expected}"
+        );
+    }
+}
+
+/// tests if subdirectories are merged into their ancestors
+#[test]
+fn max_depth() {
+    let lines = dot(&["--max-depth", "1"]);
+    assert!(
+        lines.contains(&r#"    "/" [label="/\n4 events", fontsize=30, fillcolor=9];"#.to_owned())
+    );
+    assert!(
+        lines.contains(&r#"    "C:" [label="C:\n2 events", fontsize=20, fillcolor=5];"#.to_owned())
+    );
+    assert!(!lines.iter().any(|line| line.contains("etc")));
+    assert!(!lines.iter().any(|line| line.contains("->")));
+}
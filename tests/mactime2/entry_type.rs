@@ -0,0 +1,53 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc|1|d/drwxr-xr-x|0|0|4096|-1|1609459200|-1|-1
+0|/etc/passwd|2|r/rrw-r--r--|0|0|42|-1|1609459201|-1|-1
+0|d//var|3||0|0|4096|-1|1609459202|-1|-1
+0|r//var/log/syslog|4||0|0|42|-1|1609459203|-1|-1
+0|/etc/localtime|5|l/lrwxrwxrwx|0|0|27|-1|1609459204|-1|-1
+";
+
+fn names(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().skip(1).map(String::from).collect()
+}
+
+/// tests if `--only-dirs` uses both the mode column and type markers in names
+#[test]
+fn only_dirs() {
+    assert_eq!(names(&["--only-dirs"]), vec!["\"/etc\"", "\"d//var\""]);
+}
+
+/// tests if `--only-files` hides directories and symbolic links
+#[test]
+fn only_files() {
+    assert_eq!(
+        names(&["--only-files"]),
+        vec!["\"/etc/passwd\"", "\"r//var/log/syslog\""]
+    );
+}
+
+/// tests if both filters cannot be combined
+#[test]
+fn conflicting_filters() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--only-dirs")
+        .arg("--only-files")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(3);
+}
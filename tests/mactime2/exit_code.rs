@@ -0,0 +1,59 @@
+use assert_cmd::Command;
+
+const CLEAN_BODYFILE: &str = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/tmp/b|2|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+";
+
+const PARTIAL_BODYFILE: &str = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+this is not a bodyfile line
+0|/tmp/b|2|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+";
+
+/// tests if a bodyfile without errors yields exit code 0
+#[test]
+fn clean_input() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("-d")
+        .write_stdin(CLEAN_BODYFILE)
+        .assert()
+        .code(0);
+}
+
+/// tests if skipped lines yield exit code 2, while the valid lines are
+/// still written
+#[test]
+fn partial_input() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .write_stdin(PARTIAL_BODYFILE)
+        .assert()
+        .code(2);
+
+    let stdout = String::from_utf8(result.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("/tmp/a"));
+    assert!(stdout.contains("/tmp/b"));
+}
+
+/// tests if invalid arguments yield exit code 3
+#[test]
+fn invalid_arguments() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--no-such-option")
+        .write_stdin(CLEAN_BODYFILE)
+        .assert()
+        .code(3);
+}
+
+/// tests if `--help` still yields exit code 0
+#[test]
+fn help() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("--help").assert().code(0);
+}
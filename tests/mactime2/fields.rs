@@ -0,0 +1,63 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/tmp/sample.txt|1234|r/rrwxrwxrwx|0|0|42|-1|1609459200|-1|-1
+";
+
+/// tests if `--fields` restricts and reorders the csv columns
+#[test]
+fn csv_fields() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name,macb,date")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("File Name,Type,Date"));
+    assert_eq!(
+        lines.next(),
+        Some("\"/tmp/sample.txt\",m...,2021-01-01T00:00:00+00:00")
+    );
+    assert_eq!(lines.next(), None);
+}
+
+/// tests if `--fields` restricts and reorders the txt columns
+#[test]
+fn txt_fields() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--fields")
+        .arg("macb,name")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(stdout, "m... /tmp/sample.txt\n");
+}
+
+/// tests if unknown field names are rejected and the valid names are listed
+#[test]
+fn unknown_field() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--fields")
+        .arg("date,foo")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("foo"));
+    assert!(stderr.contains("date, size, macb, mode, uid, gid, inode, name"));
+}
@@ -0,0 +1,37 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/var/log/syslog|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/var/log/syslog|1|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+0|/var/log/syslog|1|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+0|/var/log/syslog|1|r/rrw-r--r--|0|0|0|-1|1609459203|-1|-1
+0|/var/log/syslog|1|r/rrw-r--r--|0|0|0|-1|1609459204|-1|-1
+0|/etc/passwd|2|r/rrw-r--r--|0|0|0|-1|1609459300|-1|-1
+";
+
+/// tests if `--fold-repeats` collapses repeated rows of the same file
+#[test]
+fn fold_repeats() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .arg("--fold-repeats")
+        .arg("10")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Date,Type,File Name",
+            "2021-01-01T00:00:00+00:00,m...,\"/var/log/syslog (repeated 5 times within 4 seconds)\"",
+            "2021-01-01T00:01:40+00:00,m...,\"/etc/passwd\"",
+        ]
+    );
+}
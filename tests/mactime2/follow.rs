@@ -0,0 +1,72 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// tests if lines which are appended to the bodyfile are displayed in the
+/// order in which they have been written, including a line which has been
+/// written in two parts
+#[cfg(unix)]
+#[test]
+fn follow_appended_lines() {
+    let path =
+        std::env::temp_dir().join(format!("mactime2_follow_{}.bodyfile", std::process::id()));
+    fs::write(&path, "0|/tmp/c|3||0|0|0|-1|1609459300|-1|-1\n").unwrap();
+
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin("mactime2"))
+        .arg("-b")
+        .arg(&path)
+        .arg("-d")
+        .arg("--follow")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    let reader = thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let next_line = || rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert!(next_line().contains("\"/tmp/c\""));
+
+    // the events are not sorted, but displayed in the order of the lines
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"0|/tmp/a|1||0|0|0|-1|1609459200|-1|-1\n0|/tmp/b|2||0|0|0|-1|1609459")
+        .unwrap();
+    file.flush().unwrap();
+    assert!(next_line().contains("\"/tmp/a\""));
+
+    thread::sleep(Duration::from_millis(500));
+    file.write_all(b"250|-1|-1\n").unwrap();
+    file.flush().unwrap();
+    assert!(next_line().contains("\"/tmp/b\""));
+
+    let status = Command::new("kill")
+        .arg("-INT")
+        .arg(child.id().to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+    reader.join().unwrap();
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn follow_requires_file() {
+    let mut cmd = assert_cmd::Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--follow")
+        .write_stdin("")
+        .assert()
+        .failure();
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+fn gaps(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push("gaps.bodyfile");
+
+    let result = cmd.arg("-b").arg(data_path).args(args).ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// the events of the fixture are at most 20 minutes apart, except for a
+/// period of 3 hours between 00:30 and 03:30
+#[test]
+fn report_only_large_gaps() {
+    assert_eq!(
+        gaps(&["gaps", "--min", "1h"]),
+        "2021-01-01T00:30:00+00:00\t2021-01-01T03:30:00+00:00\t3h 0m 0s\n"
+    );
+}
+
+#[test]
+fn report_smaller_gaps_with_lower_threshold() {
+    let stdout = gaps(&["gaps", "--min", "900"]);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "2021-01-01T00:30:00+00:00\t2021-01-01T03:30:00+00:00\t3h 0m 0s",
+            "2021-01-01T03:30:00+00:00\t2021-01-01T03:50:00+00:00\t20m 0s",
+        ]
+    );
+}
+
+#[test]
+fn gaps_in_destination_timezone() {
+    assert_eq!(
+        gaps(&["-t", "Europe/Berlin", "gaps", "--min", "1h"]),
+        "2021-01-01T01:30:00+01:00\t2021-01-01T04:30:00+01:00\t3h 0m 0s\n"
+    );
+}
+
+#[test]
+fn gaps_honor_filters() {
+    // without the files of alice, there is only a 15 minute gap left
+    assert_eq!(
+        gaps(&["--where", "uid == 0", "gaps", "--min", "10m"]),
+        "2021-01-01T00:00:00+00:00\t2021-01-01T00:15:00+00:00\t15m 0s\n"
+    );
+    assert_eq!(gaps(&["--where", "uid == 0", "gaps", "--min", "1h"]), "");
+}
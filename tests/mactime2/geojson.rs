@@ -0,0 +1,34 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+const SAMPLE_BODYFILE: &str = "0|/DCIM/IMG_0001.JPG (48.137, 11.575)|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/etc/passwd|2|r/rrw-r--r--|0|0|0|-1|1609459300|-1|-1
+0|/DCIM/IMG_0002.JPG (-33.857,151.215)|3|r/rrw-r--r--|0|0|0|-1|1609459400|-1|-1
+";
+
+/// tests if geotagged names are written as GeoJSON `FeatureCollection`
+#[test]
+fn geojson_feature_collection() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--format")
+        .arg("geojson")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let collection: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(collection["type"], "FeatureCollection");
+
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[0]["geometry"]["coordinates"][0], 11.575);
+    assert_eq!(features[0]["geometry"]["coordinates"][1], 48.137);
+    assert_eq!(features[0]["properties"]["timestamp"], 1609459200);
+    assert_eq!(features[1]["geometry"]["coordinates"][0], 151.215);
+    assert_eq!(features[1]["geometry"]["coordinates"][1], -33.857);
+    assert_eq!(features[1]["properties"]["timestamp"], 1609459400);
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+
+fn create_bodyfiles(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mactime2_{test}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(
+        dir.join("host1.body"),
+        "0|/etc/passwd|1|r/rrw-r--r--|0|0|1024|-1|1609459200|-1|-1\n",
+    )
+    .unwrap();
+
+    // every part may have its own header
+    fs::write(
+        dir.join("host2.body"),
+        "MD5|name|inode|mode|uid|gid|size|atime|mtime|ctime|crtime
+0|/etc/hosts|2|r/rrw-r--r--|0|0|128|-1|1609462800|-1|-1
+",
+    )
+    .unwrap();
+    fs::write(dir.join("notes.txt"), "this is not a bodyfile\n").unwrap();
+    dir
+}
+
+fn mactime2(pattern: &Path) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd.arg("-b").arg(pattern).arg("-d").ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// tests if a glob pattern, which has not been expanded by the shell, reads
+/// all matching files
+#[test]
+fn glob_pattern() {
+    let dir = create_bodyfiles("glob_pattern");
+    let lines = mactime2(&dir.join("*.body"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("/etc/passwd"));
+    assert!(lines[1].contains("/etc/hosts"));
+}
+
+#[test]
+fn brace_expansion() {
+    let dir = create_bodyfiles("brace_expansion");
+    let lines = mactime2(&dir.join("{host2,host1}.body"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("/etc/passwd"));
+    assert!(lines[1].contains("/etc/hosts"));
+}
+
+#[test]
+fn multiple_inputs() {
+    let dir = create_bodyfiles("multiple_inputs");
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg(dir.join("host2.body"))
+        .arg("-b")
+        .arg(dir.join("host1.body"))
+        .arg("-d")
+        .ok();
+    let missing = Command::cargo_bin("mactime2")
+        .unwrap()
+        .arg("-b")
+        .arg(dir.join("host1.body"))
+        .arg("-b")
+        .arg(dir.join("host3.body"))
+        .ok();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(missing.is_err());
+}
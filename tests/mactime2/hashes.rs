@@ -0,0 +1,131 @@
+use assert_cmd::Command;
+
+/// md5 hash of "hello\n"
+const HELLO_MD5: &str = "b1946ac92492d2347c6235b4d2611184";
+
+fn manifest(args: &[&str]) -> Vec<String> {
+    let bodyfile = format!(
+        "{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1
+0|/etc/passwd|2|r/rrw-r--r--|0|0|6|-1|1609459300|-1|-1
+{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|1609459400|-1
+{HELLO_MD5}|/etc/hosts.bak|3|r/rrw-r--r--|0|0|6|-1|1609459500|-1|-1
+"
+    );
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("hashes")
+        .args(args)
+        .write_stdin(bodyfile)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+/// tests if placeholder hashes are skipped and duplicates are printed once
+#[test]
+fn hash_manifest() {
+    assert_eq!(
+        manifest(&[]),
+        vec![
+            format!("{HELLO_MD5}\t/etc/hosts"),
+            format!("{HELLO_MD5}\t/etc/hosts.bak"),
+        ]
+    );
+}
+
+#[test]
+fn md5sum_format() {
+    assert_eq!(
+        manifest(&["--style", "md5sum"]),
+        vec![
+            format!("{HELLO_MD5}  /etc/hosts"),
+            format!("{HELLO_MD5}  /etc/hosts.bak"),
+        ]
+    );
+}
+
+#[test]
+fn csv_format() {
+    assert_eq!(
+        manifest(&["--style", "csv"]),
+        vec![
+            "md5,name".to_owned(),
+            format!("{HELLO_MD5},\"/etc/hosts\""),
+            format!("{HELLO_MD5},\"/etc/hosts.bak\""),
+        ]
+    );
+}
+
+/// tests if hashes are replaced by their HMAC in the manifest
+#[test]
+fn anonymized_manifest() {
+    let bodyfile = format!(
+        "{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1
+0|/etc/passwd|2|r/rrw-r--r--|0|0|6|-1|1609459300|-1|-1
+"
+    );
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--anonymize-hashes")
+        .env("MACTIME2_HASH_KEY", "case 42")
+        .arg("hashes")
+        .write_stdin(bodyfile)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec!["9cf00b56eedaf1aa3af6d0443a1afb7745e5ec78552577ec72a49f195ab6dc93\t/etc/hosts"]
+    );
+}
+
+/// tests if the key of the HMAC is read from a file
+#[test]
+fn anonymized_manifest_with_key_file() {
+    let key_file =
+        std::env::temp_dir().join(format!("mactime2_hash_key_{}", std::process::id()));
+    std::fs::write(&key_file, "case 42\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--anonymize-hashes")
+        .arg("--hash-key-file")
+        .arg(&key_file)
+        .arg("hashes")
+        .env_remove("MACTIME2_HASH_KEY")
+        .write_stdin(format!("{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1\n"))
+        .ok();
+    std::fs::remove_file(&key_file).unwrap();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec!["9cf00b56eedaf1aa3af6d0443a1afb7745e5ec78552577ec72a49f195ab6dc93\t/etc/hosts"]
+    );
+}
+
+/// tests if anonymizing hashes without a key is rejected
+#[test]
+fn anonymized_manifest_without_key() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--anonymize-hashes")
+        .arg("hashes")
+        .env_remove("MACTIME2_HASH_KEY")
+        .write_stdin(format!("{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1\n"))
+        .assert()
+        .failure();
+}
@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+
+const HEADER: &str = "MD5|name|inode|mode_as_string|UID|GID|size|atime|mtime|ctime|crtime\n";
+const SAMPLE_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1609459800|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609460400|-1|1609460400
+";
+
+fn mactime2(input: String) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--max-errors")
+        .arg("1")
+        .write_stdin(input)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if a leading header line is skipped without raising an error
+#[test]
+fn header_is_skipped() {
+    let without_header = mactime2(SAMPLE_BODYFILE.to_owned());
+    let with_header = mactime2(format!("{HEADER}{SAMPLE_BODYFILE}"));
+    assert_eq!(without_header.lines().count(), 2);
+    assert_eq!(with_header, without_header);
+}
+
+/// tests if a header line is only accepted as the first line
+#[test]
+fn header_in_the_middle() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--max-errors")
+        .arg("1")
+        .write_stdin(format!("{SAMPLE_BODYFILE}{HEADER}"))
+        .assert()
+        .failure();
+}
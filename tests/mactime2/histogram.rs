@@ -0,0 +1,48 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1609459800|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609460400|-1|-1
+0|c|3|r/rrwxrwxrwx|0|0|0|1609463100|1609463100|-1|-1
+0|d|4|r/rrwxrwxrwx|0|0|0|-1|-1|-1|1609549200
+";
+
+fn histogram(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("histogram")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+#[test]
+fn histogram_per_hour() {
+    assert_eq!(
+        histogram(&["--bucket", "hour"]),
+        "2021-01-01 00:00\t2\n2021-01-01 01:00\t2\n2021-01-02 01:00\t1\n"
+    );
+}
+
+#[test]
+fn histogram_per_day() {
+    assert_eq!(
+        histogram(&["--bucket", "day"]),
+        "2021-01-01\t4\n2021-01-02\t1\n"
+    );
+}
+
+#[test]
+fn histogram_selected_kinds() {
+    assert_eq!(
+        histogram(&["--bucket", "hour", "--kind", "m"]),
+        "2021-01-01 00:00\t2\n2021-01-01 01:00\t1\n"
+    );
+    assert_eq!(
+        histogram(&["--bucket", "day", "--kind", "a,b"]),
+        "2021-01-01\t1\n2021-01-02\t1\n"
+    );
+}
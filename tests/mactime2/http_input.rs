@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+use crate::es4forensics::mock_server::MockServer;
+
+fn sample_bodyfile() -> PathBuf {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push("sample.bodyfile");
+    data_path
+}
+
+fn mactime2(input: &str) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg(input)
+        .arg("-d")
+        .arg("--timeout")
+        .arg("5")
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if a bodyfile which is read from a URL results in the same output
+/// as the local file
+#[test]
+fn url_equals_local_file() {
+    let bodyfile = sample_bodyfile();
+    let content = std::fs::read_to_string(&bodyfile).unwrap();
+    let server = MockServer::start_with(move |_| (200, content.clone()));
+
+    let local = mactime2(bodyfile.to_str().unwrap());
+    let remote = mactime2(&format!(
+        "http://127.0.0.1:{}/bodyfiles/sample.bodyfile",
+        server.port()
+    ));
+    assert!(!local.is_empty());
+    assert_eq!(local, remote);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/bodyfiles/sample.bodyfile");
+}
+
+#[test]
+fn url_not_found() {
+    let server = MockServer::start_with(|_| (404, String::new()));
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg(format!("http://127.0.0.1:{}/missing", server.port()))
+        .assert()
+        .failure();
+}
@@ -0,0 +1,51 @@
+use assert_cmd::Command;
+
+/// tests if lines with invalid UTF-8 are displayed with replacement characters
+#[test]
+fn invalid_utf8() {
+    let mut input = b"0|/tmp/a|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1\n".to_vec();
+    input.extend_from_slice(b"0|/tmp/b\xff|2|r/rrwxrwxrwx|0|0|0|-1|1609459201|-1|-1\n");
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("-v")
+        .write_stdin(input)
+        .assert()
+        .success();
+
+    let output = result.get_output();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("\"/tmp/b\u{fffd}\""));
+    assert!(stderr.contains("invalid UTF-8 in line 2"));
+}
+
+/// tests if lines with invalid UTF-8 are skipped and counted as errors with
+/// `--strict-utf8`
+#[test]
+fn strict_utf8() {
+    let mut input = b"0|/tmp/a|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1\n".to_vec();
+    input.extend_from_slice(b"0|/tmp/b\xff|2|r/rrwxrwxrwx|0|0|0|-1|1609459201|-1|-1\n");
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("-v")
+        .arg("--strict-utf8")
+        .write_stdin(input)
+        .assert()
+        .code(2);
+
+    let output = result.get_output();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("\"/tmp/a\""));
+    assert!(stderr.contains("invalid UTF-8 in line 2, skipping line"));
+}
@@ -0,0 +1,47 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+/// the first file has two different timestamps, which would result in two
+/// events; the second file has no timestamps at all
+const SAMPLE_BODYFILE: &str = "0|/tmp/a.txt|1|r/rrwxrwxrwx|0|0|42|1609459200|1609459260|-1|-1
+0|/tmp/b.txt|2|r/rrwxrwxrwx|0|0|0|-1|-1|-1|-1
+";
+
+#[test]
+fn one_document_per_file() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--json-per-file")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let documents: Vec<Value> = String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(documents.len(), 2);
+
+    let a = documents
+        .iter()
+        .find(|d| d["name"] == "/tmp/a.txt")
+        .unwrap();
+    assert_eq!(a["size"], 42);
+    assert!(a["timestamps"]["atime"].is_string());
+    assert!(a["timestamps"]["mtime"].is_string());
+    assert!(a["timestamps"]["ctime"].is_null());
+    assert!(a["timestamps"]["crtime"].is_null());
+
+    let b = documents
+        .iter()
+        .find(|d| d["name"] == "/tmp/b.txt")
+        .unwrap();
+    assert!(b["timestamps"]
+        .as_object()
+        .unwrap()
+        .values()
+        .all(Value::is_null));
+}
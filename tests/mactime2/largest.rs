@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+/// tests if `largest` displays the three largest files, and if files of the
+/// same size are ordered by their modification time
+#[test]
+fn top_3() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push("largest.bodyfile");
+
+    let result = cmd
+        .arg("-b")
+        .arg(data_path)
+        .arg("largest")
+        .arg("--top")
+        .arg("3")
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "2147483648\t2021-01-01T00:00:00+00:00\t2021-01-01T00:00:00+00:00\t2021-01-01T00:00:00+00:00\t2021-01-01T00:00:00+00:00\t/home/alice/disk.img",
+            "734003200\t2021-02-01T00:00:00+00:00\t-\t2021-02-01T00:00:00+00:00\t2021-02-01T00:00:00+00:00\t/home/alice/video.mp4",
+            "52428800\t2021-03-01T00:00:00+00:00\t-\t2021-03-01T00:00:00+00:00\t-\t/home/bob/backup.tar",
+        ]
+    );
+}
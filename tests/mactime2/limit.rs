@@ -0,0 +1,72 @@
+use assert_cmd::Command;
+
+/// creates a bodyfile with 100 events, one per minute
+fn sample_bodyfile() -> String {
+    (0..100)
+        .map(|i| {
+            format!(
+                "0|/tmp/file{i:03}|{i}|r/rrw-r--r--|0|0|0|-1|{}|-1|-1\n",
+                1609459200 + i * 60
+            )
+        })
+        .collect()
+}
+
+fn names(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name")
+        .args(args)
+        .write_stdin(sample_bodyfile())
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().skip(1).map(String::from).collect()
+}
+
+/// tests if `--limit` displays only the first events
+#[test]
+fn limit() {
+    assert_eq!(
+        names(&["--limit", "5"]),
+        vec![
+            "\"/tmp/file000\"",
+            "\"/tmp/file001\"",
+            "\"/tmp/file002\"",
+            "\"/tmp/file003\"",
+            "\"/tmp/file004\"",
+        ]
+    );
+}
+
+/// tests if `--limit` is applied after the time range
+#[test]
+fn limit_with_time_range() {
+    assert_eq!(
+        names(&["--from", "2021-01-01T01:00:00", "--limit", "2"]),
+        vec!["\"/tmp/file060\"", "\"/tmp/file061\""]
+    );
+}
+
+/// tests if `--limit` works with the json output as well
+#[test]
+fn limit_json() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-j")
+        .arg("--limit")
+        .arg("5")
+        .write_stdin(sample_bodyfile())
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+}
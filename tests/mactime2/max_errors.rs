@@ -0,0 +1,38 @@
+use assert_cmd::Command;
+
+/// lines which contain pipes, so that they are not rejected before parsing
+fn invalid_lines(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("{i}|this|is|not|a|bodyfile\n"))
+        .collect()
+}
+
+/// tests if `mactime2` aborts early if the input is not a bodyfile
+#[test]
+fn aborts_after_max_errors() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--max-errors")
+        .arg("10")
+        .write_stdin(invalid_lines(1000))
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("aborting after 10 parser errors"));
+}
+
+/// tests if `--max-errors 0` disables the threshold
+#[test]
+fn unlimited_errors() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--max-errors")
+        .arg("0")
+        .write_stdin(invalid_lines(1000))
+        .assert()
+        .code(2);
+}
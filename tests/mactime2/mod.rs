@@ -1,4 +1,63 @@
 mod help;
 mod autocomplete;
 mod is_sorted;
-mod is_stable_sorting;
\ No newline at end of file
+mod is_stable_sorting;
+mod max_errors;
+mod histogram;
+mod fields;
+mod header;
+mod time_range;
+mod summary;
+mod category;
+mod invalid_utf8;
+mod color;
+mod fold_repeats;
+mod check;
+mod multiline_names;
+mod stats;
+mod per_file;
+mod verbosity;
+mod geojson;
+mod verify;
+mod crtime;
+mod tz_from_registry;
+mod cache;
+mod only;
+mod report;
+mod hashes;
+mod newer_than_file;
+mod count_only;
+mod split_by;
+mod null_date;
+mod http_input;
+mod zstd_input;
+mod glob_input;
+mod reconstruct_paths;
+mod sort_by;
+mod exit_code;
+mod largest;
+mod ads;
+mod limit;
+mod deterministic_order;
+mod pretty;
+mod entry_type;
+mod case_insensitive_paths;
+mod gaps;
+mod split_macb;
+mod on_unparseable;
+mod dot;
+mod follow;
+mod correlate;
+mod outside_hours;
+mod sample;
+mod json_per_file;
+mod depth_column;
+mod not_a_bodyfile;
+mod profile;
+mod access_storm;
+mod deleted;
+mod watch;
+mod case_uco;
+mod parse_error_kinds;
+mod session_gap;
+mod output_file;
@@ -0,0 +1,47 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/tmp/a|12|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/tmp/first line
+second line|13|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+0|/tmp/b|14|r/rrw-r--r--|0|0|0|-1|1609459202|-1|-1
+";
+
+/// tests if a name which contains a line break is reassembled
+#[test]
+fn allow_multiline_names() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("inode,name")
+        .arg("--allow-multiline-names")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "Meta,File Name\r\n12,\"/tmp/a\"\r\n13,\"/tmp/first line\nsecond line\"\r\n14,\"/tmp/b\"\r\n"
+    );
+}
+
+/// tests if both halves are rejected without `--allow-multiline-names`
+#[test]
+fn reject_multiline_names() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("inode")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(2);
+
+    let stdout = String::from_utf8(result.get_output().stdout.clone()).unwrap();
+    assert_eq!(stdout, "Meta\r\n12\r\n14\r\n");
+}
@@ -0,0 +1,51 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+const FIRST_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+";
+
+const SECOND_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+0|c|3|r/rrwxrwxrwx|0|0|0|-1|1609466400|-1|-1
+";
+
+fn run(state: &std::path::Path, bodyfile: &str) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--newer-than-file")
+        .arg(state)
+        .write_stdin(bodyfile)
+        .ok();
+    assert!(result.is_ok());
+
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.rsplit(',').next().unwrap().trim_matches('"').to_owned())
+        .collect()
+}
+
+/// tests if a second run over an overlapping input displays only the events
+/// which are newer than the latest event of the first run
+#[test]
+fn newer_than_file() {
+    let state = std::env::temp_dir().join(format!("mactime2_state_{}", std::process::id()));
+    let _ = fs::remove_file(&state);
+
+    assert_eq!(run(&state, FIRST_BODYFILE), vec!["a", "b"]);
+    assert_eq!(fs::read_to_string(&state).unwrap().trim(), "1609462800");
+
+    assert_eq!(run(&state, SECOND_BODYFILE), vec!["c"]);
+    assert_eq!(fs::read_to_string(&state).unwrap().trim(), "1609466400");
+
+    // without new events, the state is not changed
+    assert!(run(&state, SECOND_BODYFILE).is_empty());
+    assert_eq!(fs::read_to_string(&state).unwrap().trim(), "1609466400");
+
+    fs::remove_file(&state).unwrap();
+}
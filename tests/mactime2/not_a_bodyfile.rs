@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+
+const JSON_DOCUMENT: &str = r#"{
+  "files": [
+    {
+      "name": "/tmp/a",
+      "inode": 1,
+      "mtime": 1609459200
+    },
+    {
+      "name": "/tmp/b",
+      "inode": 2,
+      "mtime": 1609459201
+    }
+  ]
+}
+"#;
+
+/// tests if `mactime2` rejects input which is obviously not a bodyfile
+/// with a single error message, instead of one parser error per line
+#[test]
+fn json_document() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .write_stdin(JSON_DOCUMENT.repeat(1000))
+        .assert()
+        .failure();
+
+    let output = result.get_output();
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+    assert_eq!(
+        stderr
+            .matches("input does not appear to be a TSK v3 bodyfile")
+            .count(),
+        1
+    );
+    assert!(!stderr.contains("bodyfile parser error"));
+}
+
+/// tests if short input is rejected as well
+#[test]
+fn short_input() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .write_stdin("this is not a bodyfile\n")
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("input does not appear to be a TSK v3 bodyfile"));
+}
+
+/// tests if comments before the first line are accepted
+#[test]
+fn leading_comments() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("-d")
+        .write_stdin("# comment\n0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1\n")
+        .assert()
+        .success();
+}
@@ -0,0 +1,73 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+/// ctime is not set
+const SAMPLE_BODYFILE: &str = "0|/tmp/sample.txt|1234|r/rrwxrwxrwx|0|0|42|-1|1609459200|-1|-1
+";
+
+fn mactime2(format: &str, args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--format")
+        .arg(format)
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+fn csv_ctime(null_date: &str) -> String {
+    let stdout = mactime2("csv", &["--fields", "name,mtime,ctime", "--null-date", null_date]);
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("File Name,mtime,ctime"));
+    let line = lines.next().unwrap();
+    assert_eq!(lines.next(), None);
+    assert!(line.starts_with("\"/tmp/sample.txt\",2021-01-01T00:00:00+00:00,"));
+    line.rsplit(',').next().unwrap().to_owned()
+}
+
+fn json_ctime(null_date: &str) -> Option<i64> {
+    let stdout = mactime2("json", &["--null-date", null_date]);
+    let mut lines = stdout.lines();
+    let document: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(lines.next(), None);
+    assert_eq!(document["file"]["mtime"].as_i64(), Some(1609459200000));
+    document["file"].get("ctime").map(|ctime| ctime.as_i64().unwrap())
+}
+
+/// tests if an unset timestamp is left out by default
+#[test]
+fn skip() {
+    assert_eq!(csv_ctime("skip"), "");
+    assert_eq!(json_ctime("skip"), None);
+}
+
+/// tests if an unset timestamp can be replaced by the unix epoch
+#[test]
+fn epoch() {
+    assert_eq!(csv_ctime("epoch"), "1970-01-01T00:00:00+00:00");
+    assert_eq!(json_ctime("epoch"), Some(0));
+}
+
+/// tests if an unset timestamp can be replaced by a fixed date
+#[test]
+fn fixed_date() {
+    assert_eq!(csv_ctime("2099-12-31T00:00:00+00:00"), "2099-12-31T00:00:00+00:00");
+    assert_eq!(json_ctime("2099-12-31T00:00:00+00:00"), Some(4102358400000));
+}
+
+/// tests if invalid policies are rejected
+#[test]
+fn invalid_policy() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--null-date")
+        .arg("sometime")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .failure();
+}
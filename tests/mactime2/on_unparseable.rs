@@ -0,0 +1,67 @@
+use assert_cmd::Command;
+
+const BANNER_BODYFILE: &str = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+=== Volume C: ===
+0|/tmp/b|2|r/rrw-r--r--|0|0|0|-1|1609459201|-1|-1
+";
+
+/// runs mactime2 with the specified policy, and returns stdout and stderr
+fn mactime2(policy: &str, exit_code: i32) -> (String, String) {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("-vv")
+        .arg("--on-unparseable")
+        .arg(policy)
+        .write_stdin(BANNER_BODYFILE)
+        .assert()
+        .code(exit_code);
+    let output = result.get_output();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    assert!(stdout.contains("/tmp/a"));
+    assert!(stdout.contains("/tmp/b"));
+    (stdout, String::from_utf8(output.stderr.clone()).unwrap())
+}
+
+/// tests if the banner is reported as an error by default
+#[test]
+fn error() {
+    let (_, stderr) = mactime2("error", 2);
+    assert!(stderr.contains("bodyfile parser error"));
+    assert!(!stderr.contains("skipped"));
+}
+
+/// tests if the banner is skipped, and only the number of skipped lines
+/// is reported
+#[test]
+fn skip() {
+    let (_, stderr) = mactime2("skip", 0);
+    assert!(!stderr.contains("bodyfile parser error"));
+    assert!(stderr.contains("skipped 1 unparseable lines"));
+    assert!(!stderr.contains("=== Volume C: ==="));
+}
+
+/// tests if the banner is skipped, but logged for review
+#[test]
+fn collect() {
+    let (_, stderr) = mactime2("collect", 0);
+    assert!(!stderr.contains("bodyfile parser error"));
+    assert!(stderr.contains("skipped 1 unparseable lines"));
+    assert!(stderr.contains("unparseable line: \"=== Volume C: ===\""));
+}
+
+/// tests if skipped lines do not abort in strict mode
+#[test]
+fn skip_in_strict_mode() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--strict")
+        .arg("--on-unparseable")
+        .arg("skip")
+        .write_stdin(BANNER_BODYFILE)
+        .assert()
+        .code(0);
+}
@@ -0,0 +1,84 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str =
+    "0|/Users/jdoe/Documents/report.pages|1|r/rrw-r--r--|501|20|4096|1609459300|1609459200|1609459200|1577836800
+0|/Users/jdoe/Documents/empty.txt|2|r/rrw-r--r--|501|20|0|-1|-1|-1|-1
+";
+
+fn timeline(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+/// tests if `--only b` displays the creation time only
+#[test]
+fn only_crtime() {
+    assert_eq!(
+        timeline(&["--only", "b"]),
+        vec![
+            "Date,Type,File Name",
+            "2020-01-01T00:00:00+00:00,...b,\"/Users/jdoe/Documents/report.pages\"",
+        ]
+    );
+}
+
+/// tests if `--only c` hides the modification time, which has the same value
+/// as the change time
+#[test]
+fn only_ctime() {
+    assert_eq!(
+        timeline(&["--only", "c"]),
+        vec![
+            "Date,Type,File Name",
+            "2021-01-01T00:00:00+00:00,..c.,\"/Users/jdoe/Documents/report.pages\"",
+        ]
+    );
+}
+
+/// tests if multiple kinds can be selected
+#[test]
+fn only_atime_and_crtime() {
+    assert_eq!(
+        timeline(&["--only", "a,b"]),
+        vec![
+            "Date,Type,File Name",
+            "2020-01-01T00:00:00+00:00,...b,\"/Users/jdoe/Documents/report.pages\"",
+            "2021-01-01T00:01:40+00:00,.a..,\"/Users/jdoe/Documents/report.pages\"",
+        ]
+    );
+}
+
+/// tests if `--only` also restricts the events of the json output
+#[test]
+fn only_json() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--format")
+        .arg("json")
+        .arg("--only")
+        .arg("b")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let timestamps: Vec<_> = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["@timestamp"].clone())
+        .collect();
+    assert_eq!(timestamps, vec![serde_json::json!(1577836800000i64)]);
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+fn data_path(input: &str) -> PathBuf {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push(input);
+    data_path
+}
+
+fn run(args: &[&str], output: Option<&PathBuf>) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b").arg(data_path("sample.bodyfile")).arg("-d");
+    if let Some(output) = output {
+        cmd.arg("--output").arg(output);
+    }
+    let result = cmd.args(args).ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if `--output` writes the timeline and the result of a subcommand
+/// into a file instead of stdout
+#[test]
+fn output_file() {
+    let dir = std::env::temp_dir().join(format!("mactime2_output_file_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("timeline.txt");
+
+    for args in [&[][..], &["--format", "csv"][..], &["stats"][..]] {
+        let expected = run(args, None);
+        assert!(!expected.is_empty());
+        assert!(run(args, Some(&output)).is_empty());
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), expected);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// tests if no output file is created if the input cannot be read
+#[test]
+fn no_output_file_on_error() {
+    let dir = std::env::temp_dir().join(format!("mactime2_no_output_file_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("timeline.txt");
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg(data_path("sample.bodyfile.missing"))
+        .arg("--output")
+        .arg(&output)
+        .ok();
+    assert!(result.is_err());
+    assert!(!output.exists());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
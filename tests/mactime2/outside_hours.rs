@@ -0,0 +1,64 @@
+use assert_cmd::Command;
+
+/// files modified on Monday, 2021-01-04 at 03:00 and 14:00 UTC, and on
+/// Saturday, 2021-01-09 at 14:00 UTC
+const SAMPLE_BODYFILE: &str = "0|/tmp/night.txt|1|r/rrwxrwxrwx|0|0|42|-1|1609729200|-1|-1
+0|/tmp/day.txt|2|r/rrwxrwxrwx|0|0|42|-1|1609768800|-1|-1
+0|/tmp/weekend.txt|3|r/rrwxrwxrwx|0|0|42|-1|1610200800|-1|-1
+";
+
+fn displayed_files(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            line.rsplit(',')
+                .next()
+                .unwrap()
+                .trim_matches('"')
+                .to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn default_business_hours() {
+    assert_eq!(
+        displayed_files(&["--outside-hours"]),
+        vec!["/tmp/night.txt"]
+    );
+}
+
+#[test]
+fn custom_business_hours() {
+    assert_eq!(
+        displayed_files(&["--outside-hours", "02:00-13:00"]),
+        vec!["/tmp/day.txt", "/tmp/weekend.txt"]
+    );
+}
+
+#[test]
+fn weekdays_only() {
+    assert_eq!(
+        displayed_files(&["--outside-hours", "--weekdays-only"]),
+        vec!["/tmp/night.txt", "/tmp/weekend.txt"]
+    );
+}
+
+#[test]
+fn destination_timezone() {
+    // 14:00 UTC is 23:00 in Tokyo, and 03:00 UTC is 12:00
+    assert_eq!(
+        displayed_files(&["--outside-hours", "-t", "Asia/Tokyo"]),
+        vec!["/tmp/day.txt", "/tmp/weekend.txt"]
+    );
+}
@@ -0,0 +1,40 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/etc/shadow|2|r/rrw-------|root|0|0|-1|1609459200|-1|-1
+0|/etc/group|3|r/rrw-r--r--|wheel|0|0|-1|1609459200|-1|-1
+0|/etc/hosts|4|r/rrw-r--r--|0|0|0|yesterday|1609459200|-1|-1
+0|/etc/motd|6|r/rrw-r--r--|0|0|0
+";
+
+fn stderr(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-vv")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(2);
+    String::from_utf8(result.get_output().stderr.clone()).unwrap()
+}
+
+/// tests if the unparseable lines are counted per error kind
+#[test]
+fn errors_per_kind() {
+    let stderr = stderr(&[]);
+    assert!(stderr.contains("parsed 5 lines with 4 errors"));
+    assert!(stderr.contains(
+        "unparseable lines per error kind: IllegalUid: 2, IllegalATime: 1, TooFewColumns: 1"
+    ));
+}
+
+/// tests if `check` reports the same breakdown
+#[test]
+fn check_errors_per_kind() {
+    let stderr = stderr(&["check", "--fs", "ntfs"]);
+    assert!(stderr.contains(
+        "unparseable lines per error kind: IllegalUid: 2, IllegalATime: 1, TooFewColumns: 1"
+    ));
+}
@@ -0,0 +1,48 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|0|1609459300|1609459200|-1|1609459100
+0|/etc/hosts|2|r/rrw-r--r--|0|0|0|-1|1609459250|-1|-1
+";
+
+fn per_file(option: &str) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .arg(option)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+/// tests if `--first-per-file` keeps only the earliest event of every file
+#[test]
+fn first_per_file() {
+    assert_eq!(
+        per_file("--first-per-file"),
+        vec![
+            "Date,Type,File Name",
+            "2020-12-31T23:58:20+00:00,...b,\"/etc/passwd\"",
+            "2021-01-01T00:00:50+00:00,m...,\"/etc/hosts\"",
+        ]
+    );
+}
+
+/// tests if `--last-per-file` keeps only the latest event of every file
+#[test]
+fn last_per_file() {
+    assert_eq!(
+        per_file("--last-per-file"),
+        vec![
+            "Date,Type,File Name",
+            "2021-01-01T00:00:50+00:00,m...,\"/etc/hosts\"",
+            "2021-01-01T00:01:40+00:00,.a..,\"/etc/passwd\"",
+        ]
+    );
+}
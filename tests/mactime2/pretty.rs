@@ -0,0 +1,49 @@
+use assert_cmd::Command;
+
+/// tests if the columns are aligned, even if the values have different
+/// widths, and if long file names are truncated
+#[test]
+fn aligned_columns() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--pretty")
+        .arg("--name-width")
+        .arg("12")
+        .arg("--fields")
+        .arg("date,size,macb,name")
+        .write_stdin(
+            [
+                "0|/a|1|r/rrw-r--r--|0|0|5|-1|1609459200|-1|-1",
+                "0|/tmp/longer/name.txt|2|r/rrw-r--r--|0|0|123456|-1|1609459300|-1|-1",
+            ]
+            .join("\n"),
+        )
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "Date                         Size  Type  File Name",
+            "-------------------------  ------  ----  ------------",
+            "2021-01-01T00:00:00+00:00       5  m...  /a",
+            "2021-01-01T00:01:40+00:00  123456  m...  /tmp/longer…",
+        ]
+    );
+}
+
+/// tests if `--pretty` is rejected for formats other than txt
+#[test]
+fn pretty_requires_txt() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--pretty")
+        .write_stdin("0|/a|1|r/rrw-r--r--|0|0|5|-1|1609459200|-1|-1")
+        .assert()
+        .failure();
+}
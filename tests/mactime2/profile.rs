@@ -0,0 +1,53 @@
+use assert_cmd::Command;
+
+const TWO_USERS_BODYFILE: &str =
+    "0|C:/Users/alice/Desktop/report.docx|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|1609462800
+0|C:/Users/alice/Desktop/notes.txt|2|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+0|C:/Users/alice/Downloads/setup.exe|3|r/rrwxrwxrwx|0|0|0|-1|1609466400|-1|-1
+0|C:/Users/bob/AppData/Local/Temp/a.tmp|4|r/rrwxrwxrwx|0|0|0|-1|1609545600|-1|-1
+0|C:/Users/Public/Desktop/desktop.ini|5|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|/home/bob/.bashrc|6|r/rrw-r--r--|1000|1000|0|-1|1609549200|-1|-1
+";
+
+fn profile(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .args(args)
+        .write_stdin(TWO_USERS_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// tests if the number of events and the time bounds are reported for
+/// every user
+#[test]
+fn profile_by_user() {
+    assert_eq!(
+        profile(&["profile", "--by-user"]),
+        vec![
+            "alice\t4\t2021-01-01T00:00:00+00:00\t2021-01-01T02:00:00+00:00",
+            "\t3\tC:/Users/alice/Desktop",
+            "\t1\tC:/Users/alice/Downloads",
+            "bob\t2\t2021-01-02T00:00:00+00:00\t2021-01-02T01:00:00+00:00",
+            "\t1\t/home/bob",
+            "\t1\tC:/Users/bob/AppData/Local/Temp",
+            "uid 0\t1\t2021-01-01T00:00:00+00:00\t2021-01-01T00:00:00+00:00",
+            "\t1\tC:/Users/Public/Desktop",
+        ]
+    );
+}
+
+/// tests if only the top N directories of every user are displayed
+#[test]
+fn profile_top() {
+    let lines = profile(&["profile", "--by-user", "--top", "1"]);
+    assert!(lines.contains(&"\t3\tC:/Users/alice/Desktop".to_owned()));
+    assert!(!lines.contains(&"\t1\tC:/Users/alice/Downloads".to_owned()));
+}
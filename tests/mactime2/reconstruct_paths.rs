@@ -0,0 +1,37 @@
+use assert_cmd::Command;
+
+const RELATIVE_BODYFILE: &str = "0|5/Users|64-144-1|d/drwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|64/jdoe|70-144-1|d/drwxrwxrwx|0|0|0|-1|1609459300|-1|-1
+0|70/notes.txt|81-128-2|r/rrwxrwxrwx|0|0|42|-1|1609459400|-1|-1
+0|99/orphan.txt|82-128-2|r/rrwxrwxrwx|0|0|42|-1|1609459500|-1|-1
+";
+
+/// tests if names relative to the parent MFT entry are expanded to full
+/// paths, and if unresolvable names are kept
+#[test]
+fn reconstruct_paths() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--reconstruct-paths")
+        .arg("--format")
+        .arg("csv")
+        .arg("--fields")
+        .arg("name")
+        .write_stdin(RELATIVE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "File Name",
+            "\"/Users\"",
+            "\"/Users/jdoe\"",
+            "\"/Users/jdoe/notes.txt\"",
+            "\"99/orphan.txt\"",
+        ]
+    );
+}
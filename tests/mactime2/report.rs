@@ -0,0 +1,36 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/Users/jdoe/Documents/report.pages|1|r/rrw-r--r--|501|20|4096|1609459300|1609459200|1609459200|1577836800
+0|/Users/jdoe/Documents/Übersicht.txt|2|r/rrw-r--r--|501|20|12|-1|1609459400|-1|-1
+0|/tmp/</script><b>|3|r/rrw-r--r--|0|0|0|-1|1609459500|-1|-1
+";
+
+/// tests if the report contains one data row per event, and if file names
+/// cannot break out of the embedded data
+#[test]
+fn html_report() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("report")
+        .arg("--html")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let html = String::from_utf8(result.unwrap().stdout).expect("the report must be valid UTF-8");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    // no external resources
+    assert!(!html.contains("src=") && !html.contains("href="));
+    assert_eq!(html.matches("</script>").count(), 1);
+    assert!(html.contains("Übersicht.txt"));
+
+    let rows: Vec<&str> = html
+        .lines()
+        .filter(|line| line.starts_with('[') || line.starts_with(",["))
+        .collect();
+    // report.pages has three distinct timestamps, the other files one each
+    assert_eq!(rows.len(), 5);
+}
@@ -0,0 +1,48 @@
+use assert_cmd::Command;
+
+fn sample_bodyfile() -> String {
+    (0..100)
+        .map(|idx| {
+            format!(
+                "0|/tmp/file_{idx}|{idx}|r/rrwxrwxrwx|0|0|42|-1|{}|-1|-1\n",
+                1609459200 + idx * 60
+            )
+        })
+        .collect()
+}
+
+fn sampled_lines(args: &[&str]) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .args(args)
+        .write_stdin(sample_bodyfile())
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn fixed_seed_is_deterministic() {
+    let first = sampled_lines(&["--sample", "10", "--seed", "42"]);
+    let second = sampled_lines(&["--sample", "10", "--seed", "42"]);
+    assert_eq!(first.len(), 10);
+    assert_eq!(first, second);
+
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(first, sorted);
+}
+
+#[test]
+fn sample_larger_than_input() {
+    let lines = sampled_lines(&["--sample", "1000", "--seed", "42"]);
+    assert_eq!(lines, sampled_lines(&[]));
+    assert_eq!(lines.len(), 100);
+}
@@ -0,0 +1,51 @@
+use assert_cmd::Command;
+
+/// two events in the morning and two events in the afternoon of 2021-01-01
+const SAMPLE_BODYFILE: &str = "0|/tmp/a|1|r/rrw-r--r--|0|0|0|-1|1609491600|-1|-1
+0|/tmp/b|2|r/rrw-r--r--|0|0|0|-1|1609492200|-1|-1
+0|/tmp/c|3|r/rrw-r--r--|0|0|0|-1|1609513200|-1|-1
+0|/tmp/d|4|r/rrw-r--r--|0|0|0|-1|1609513260|-1|-1
+";
+
+/// tests if an inactivity gap splits the events into two sessions
+#[test]
+fn two_sessions() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("name")
+        .arg("--session-gap")
+        .arg("1h")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    let output = result.unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let names: Vec<_> = stdout.lines().skip(1).collect();
+    assert_eq!(
+        names,
+        vec![
+            "\"/tmp/a (session 1)\"",
+            "\"/tmp/b (session 1)\"",
+            "\"/tmp/c (session 2)\"",
+            "\"/tmp/d (session 2)\"",
+        ]
+    );
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let summaries: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with("session "))
+        .collect();
+    assert_eq!(
+        summaries,
+        vec![
+            "session 1: 2021-01-01T09:00:00+00:00 - 2021-01-01T09:10:00+00:00 (2 events)",
+            "session 2: 2021-01-01T15:00:00+00:00 - 2021-01-01T15:01:00+00:00 (2 events)",
+        ]
+    );
+}
@@ -0,0 +1,37 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/tmp/c.txt|3|r/rrwxrwxrwx|0|0|42|1609459100|1609459100|1609459100|1609459300
+0|/tmp/a.txt|1|r/rrwxrwxrwx|0|0|42|1609459900|1609459900|1609459900|1609459100
+0|/tmp/none.txt|4|r/rrwxrwxrwx|0|0|42|1609459000|1609459000|1609459000|-1
+0|/tmp/b.txt|2|r/rrwxrwxrwx|0|0|42|1609459200|1609459200|1609459200|1609459200
+";
+
+/// tests if `--sort-by crtime` lists every file once in the order of creation,
+/// and skips files without a creation time
+#[test]
+fn sort_by_crtime() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--sort-by")
+        .arg("crtime")
+        .arg("--format")
+        .arg("csv")
+        .arg("--fields")
+        .arg("date,macb,name")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "Date,Type,File Name",
+            "2020-12-31T23:58:20+00:00,...b,\"/tmp/a.txt\"",
+            "2021-01-01T00:00:00+00:00,macb,\"/tmp/b.txt\"",
+            "2021-01-01T00:01:40+00:00,...b,\"/tmp/c.txt\"",
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1613606400|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1613692799|-1|-1
+0|c|3|r/rrwxrwxrwx|0|0|0|-1|1613692800|-1|-1
+0|d|4|r/rrwxrwxrwx|0|0|0|-1|1613700000|-1|-1
+0|e|5|r/rrwxrwxrwx|0|0|0|-1|1613800000|-1|-1
+0|f|6|r/rrwxrwxrwx|0|0|0|-1|1613780000|-1|-1
+";
+
+/// tests if events spanning three days are written into three files
+#[test]
+fn split_by_day() {
+    let dir = std::env::temp_dir().join(format!("mactime2_split_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--split-by")
+        .arg("day")
+        .arg("--split-dir")
+        .arg(&dir)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    assert!(result.unwrap().stdout.is_empty());
+
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            "timeline-2021-02-18.csv",
+            "timeline-2021-02-19.csv",
+            "timeline-2021-02-20.csv"
+        ]
+    );
+
+    let names = |file: &str| -> Vec<String> {
+        fs::read_to_string(dir.join(file))
+            .unwrap()
+            .lines()
+            .map(|line| line.rsplit(',').next().unwrap().trim_matches('"').to_owned())
+            .collect()
+    };
+    assert_eq!(names("timeline-2021-02-18.csv"), vec!["a", "b"]);
+    assert_eq!(names("timeline-2021-02-19.csv"), vec!["c", "d"]);
+    assert_eq!(names("timeline-2021-02-20.csv"), vec!["f", "e"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// tests if `--split-by` is rejected for formats which are not line based
+#[test]
+fn split_by_json() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("-j")
+        .arg("--split-by")
+        .arg("day")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .failure();
+}
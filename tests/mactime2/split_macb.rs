@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+/// mtime and atime are equal, ctime and crtime differ
+const SAMPLE_BODYFILE: &str = "0|/tmp/sample.txt|1234|r/rrwxrwxrwx|0|0|42|1609459200|1609459200|1609462800|1609466400
+";
+
+fn mactime2(format: &str) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("--format")
+        .arg(format)
+        .arg("--split-macb")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if the merged `ma..` event has the columns `accessed` and
+/// `modified` set
+#[test]
+fn csv() {
+    let stdout = mactime2("csv");
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Date,Size,accessed,modified,changed,created,Mode,UID,GID,Meta,File Name",
+            "2021-01-01T00:00:00+00:00,42,true,true,false,false,r/rrwxrwxrwx,0,0,1234,\"/tmp/sample.txt\"",
+            "2021-01-01T01:00:00+00:00,42,false,false,true,false,r/rrwxrwxrwx,0,0,1234,\"/tmp/sample.txt\"",
+            "2021-01-01T02:00:00+00:00,42,false,false,false,true,r/rrwxrwxrwx,0,0,1234,\"/tmp/sample.txt\"",
+        ]
+    );
+}
+
+#[test]
+fn json() {
+    let stdout = mactime2("json");
+    let documents: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(documents.len(), 3);
+
+    let merged = &documents[0];
+    assert_eq!(merged["@timestamp"].as_i64(), Some(1609459200000));
+    assert_eq!(merged["accessed"], Value::Bool(true));
+    assert_eq!(merged["modified"], Value::Bool(true));
+    assert_eq!(merged["changed"], Value::Bool(false));
+    assert_eq!(merged["created"], Value::Bool(false));
+
+    assert_eq!(documents[2]["created"], Value::Bool(true));
+    assert_eq!(documents[2]["accessed"], Value::Bool(false));
+}
+
+#[test]
+fn txt_is_rejected() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--split-macb")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .failure();
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+/// tests if `stats` reports the number of distinct UIDs and the range of
+/// modification times
+#[test]
+fn stats() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push("stats.bodyfile");
+
+    let result = cmd.arg("-b").arg(data_path).arg("stats").ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert!(lines.contains(&"lines\tcount\t5"));
+    assert!(lines.contains(&"uid\tdistinct\t4"));
+    assert!(lines.contains(&"gid\tdistinct\t5"));
+    assert!(lines.contains(&"mtime\tmin\t2020-01-01T00:00:00+00:00"));
+    assert!(lines.contains(&"mtime\tmax\t2021-12-31T23:59:59+00:00"));
+    assert!(lines.contains(&"atime\tunset\t4"));
+}
@@ -0,0 +1,63 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+0|/etc/hosts|2|r/rrw-r--r--|0|0|0|1609459300|1609459200|-1|-1
+0|/etc/shadow|3|r/rrw-r-----|0|0|0|-1|1609466400|-1|-1
+0|C:\\Windows\\notepad.exe|4|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|C:\\Windows\\regedit.exe|5|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|1609459200
+";
+
+fn summary(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if the directories are ranked by their number of events
+#[test]
+fn summary_by_dir() {
+    assert_eq!(
+        summary(&["summary", "--by-dir"]),
+        "4\t/etc\n2\tC:\\Windows\n"
+    );
+}
+
+/// tests if only the top N directories are displayed
+#[test]
+fn summary_top() {
+    assert_eq!(summary(&["summary", "--by-dir", "--top", "1"]), "4\t/etc\n");
+}
+
+/// tests if the summary respects the time range
+#[test]
+fn summary_time_range() {
+    assert_eq!(
+        summary(&["--to", "2021-01-01T00:00:00", "summary", "--by-dir"]),
+        "2\t/etc\n2\tC:\\Windows\n"
+    );
+}
+
+/// tests if the summary counts only the timestamp kinds selected by `--only`
+#[test]
+fn summary_only() {
+    assert_eq!(summary(&["--only", "a", "summary", "--by-dir"]), "1\t/etc\n");
+    assert_eq!(
+        summary(&["--only", "b", "summary", "--by-dir"]),
+        "1\tC:\\Windows\n"
+    );
+}
+
+/// tests if the summary counts only events outside of the business hours
+#[test]
+fn summary_outside_hours() {
+    assert_eq!(
+        summary(&["--outside-hours", "00:00-01:00", "summary", "--by-dir"]),
+        "1\t/etc\n"
+    );
+}
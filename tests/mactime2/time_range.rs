@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|a|1|r/rrwxrwxrwx|0|0|0|-1|1609459200|-1|-1
+0|b|2|r/rrwxrwxrwx|0|0|0|-1|1609462800|-1|-1
+0|c|3|r/rrwxrwxrwx|0|0|0|-1|1609466400|-1|-1
+";
+
+/// tests if `--from` and `--to` restrict the output
+#[test]
+fn time_range() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--from")
+        .arg("2021-01-01T00:30:00")
+        .arg("--to")
+        .arg("2021-01-01T01:00:00")
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.ends_with(",\"b\"\r\n"));
+}
+
+/// tests if an invalid time range is rejected
+#[test]
+fn invalid_time_range() {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    cmd.arg("-b")
+        .arg("-")
+        .arg("--from")
+        .arg("2021-01-02")
+        .arg("--to")
+        .arg("2021-01-01")
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .failure();
+}
@@ -0,0 +1,48 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str =
+    "0|/Windows/System32/config/SYSTEM|1|r/rrw-r--r--|0|0|8192|1609459200|1609459200|1609459200|1609459200
+";
+
+fn timeline(hive: &str) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("-d")
+        .arg("--fields")
+        .arg("date,name")
+        .arg("--tz-from-registry")
+        .arg(hive)
+        .write_stdin(SAMPLE_BODYFILE)
+        .ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout.lines().map(ToOwned::to_owned).collect()
+}
+
+/// tests if the timezone is read from `ControlSet001\Control\TimeZoneInformation`,
+/// which contains `W. Europe Standard Time` with a bias of -60 minutes
+#[test]
+fn detect_timezone() {
+    assert_eq!(
+        timeline("tests/data/mactime2/SYSTEM"),
+        vec![
+            "Date,File Name",
+            "2021-01-01T01:00:00+01:00,\"/Windows/System32/config/SYSTEM\"",
+        ]
+    );
+}
+
+/// tests if UTC is used if the hive contains no timezone information
+#[test]
+fn fallback_to_utc() {
+    assert_eq!(
+        timeline("tests/data/regdump/UsrClass.dat"),
+        vec![
+            "Date,File Name",
+            "2021-01-01T00:00:00+00:00,\"/Windows/System32/config/SYSTEM\"",
+        ]
+    );
+}
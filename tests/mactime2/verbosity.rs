@@ -0,0 +1,31 @@
+use assert_cmd::Command;
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1
+this is not a bodyfile line
+";
+
+fn stderr(args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .args(args)
+        .write_stdin(SAMPLE_BODYFILE)
+        .assert()
+        .code(2);
+    String::from_utf8(result.get_output().stderr.clone()).unwrap()
+}
+
+/// tests if parser errors are not logged by default
+#[test]
+fn quiet_by_default() {
+    assert!(!stderr(&[]).contains("bodyfile parser error"));
+}
+
+/// tests if `-vv` logs parser errors and progress messages
+#[test]
+fn verbose_parser_errors() {
+    let stderr = stderr(&["-vv"]);
+    assert!(stderr.contains("bodyfile parser error"));
+    assert!(stderr.contains("parsed 2 lines with 1 errors"));
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+/// md5 hash of "hello\n"
+const HELLO_MD5: &str = "b1946ac92492d2347c6235b4d2611184";
+
+/// SHA-1 and SHA-256 hashes of "hello\n"
+const HELLO_SHA1: &str = "f572d396fae9206628714fb2ce00f72e94f2258f";
+const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+/// tests if `verify` reports matching, mismatching and missing files, and
+/// skips lines without md5 hash
+#[test]
+fn verify_md5() {
+    let root = std::env::temp_dir().join(format!("mactime2_verify_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("etc")).unwrap();
+    fs::write(root.join("etc/hosts"), "hello\n").unwrap();
+    fs::write(root.join("etc/passwd"), "modified\n").unwrap();
+    fs::write(root.join("etc/group"), "hello\n").unwrap();
+
+    let bodyfile = format!(
+        "{HELLO_MD5}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1
+{HELLO_MD5}|/etc/passwd|2|r/rrw-r--r--|0|0|6|-1|1609459300|-1|-1
+{HELLO_MD5}|/etc/shadow|3|r/rrw-r-----|0|0|6|-1|1609459400|-1|-1
+0|/etc/group|4|r/rrw-r--r--|0|0|6|-1|1609459500|-1|-1
+"
+    );
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("verify")
+        .arg("--root")
+        .arg(&root)
+        .write_stdin(bodyfile)
+        .ok();
+    fs::remove_dir_all(&root).unwrap();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let mut lines: Vec<_> = stdout.lines().collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        vec![
+            "/etc/hosts: OK",
+            "/etc/passwd: FAILED",
+            "/etc/shadow: MISSING"
+        ]
+    );
+}
+
+/// tests if SHA-1 and SHA-256 hashes in the md5 column are verified with
+/// the matching algorithm
+#[test]
+fn verify_sha() {
+    let root = std::env::temp_dir().join(format!("mactime2_verify_sha_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("etc")).unwrap();
+    fs::write(root.join("etc/hosts"), "hello\n").unwrap();
+    fs::write(root.join("etc/passwd"), "hello\n").unwrap();
+    fs::write(root.join("etc/group"), "modified\n").unwrap();
+
+    let bodyfile = format!(
+        "{HELLO_SHA1}|/etc/hosts|1|r/rrw-r--r--|0|0|6|-1|1609459200|-1|-1
+{HELLO_SHA256}|/etc/passwd|2|r/rrw-r--r--|0|0|6|-1|1609459300|-1|-1
+{HELLO_SHA256}|/etc/group|3|r/rrw-r--r--|0|0|6|-1|1609459400|-1|-1
+"
+    );
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-b")
+        .arg("-")
+        .arg("verify")
+        .arg("--root")
+        .arg(&root)
+        .write_stdin(bodyfile)
+        .ok();
+    fs::remove_dir_all(&root).unwrap();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let mut lines: Vec<_> = stdout.lines().collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        vec!["/etc/group: FAILED", "/etc/hosts: OK", "/etc/passwd: OK"]
+    );
+}
@@ -0,0 +1,81 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use assert_cmd::Command;
+
+/// tests if a bodyfile which is dropped into the watched directory is
+/// processed and moved into the processed directory
+#[test]
+fn dropped_file_is_processed() {
+    let dir = std::env::temp_dir().join(format!("mactime2_watch_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let intake = dir.clone();
+    let writer = thread::spawn(move || {
+        // wait until the watcher has created the processed directory
+        let started = Instant::now();
+        while !intake.join("processed").is_dir() {
+            assert!(started.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(100));
+        }
+        fs::write(
+            intake.join("host1.bodyfile"),
+            "0|/tmp/dropped|1|r/rrw-r--r--|0|0|0|-1|1609459200|-1|-1\n",
+        )
+        .unwrap();
+    });
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-d")
+        .arg("watch")
+        .arg(&dir)
+        .arg("--settle-time")
+        .arg("1")
+        .arg("--max-files")
+        .arg("1")
+        .timeout(Duration::from_secs(30))
+        .ok();
+    writer.join().unwrap();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert!(stdout.contains("\"/tmp/dropped\""));
+    assert!(!dir.join("host1.bodyfile").exists());
+    assert!(dir.join("processed").join("host1.bodyfile").is_file());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// tests if a file which cannot be processed is moved into the failed
+/// directory instead of the processed directory
+#[test]
+fn failed_file_is_not_processed() {
+    let dir = std::env::temp_dir().join(format!("mactime2_watch_failed_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("host1.bodyfile"), "this is not a bodyfile\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd
+        .arg("-d")
+        .arg("watch")
+        .arg(&dir)
+        .arg("--settle-time")
+        .arg("0")
+        .arg("--max-files")
+        .arg("1")
+        .timeout(Duration::from_secs(30))
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("unable to process"));
+    assert!(!dir.join("host1.bodyfile").exists());
+    assert!(!dir.join("processed").join("host1.bodyfile").exists());
+    assert!(dir.join("failed").join("host1.bodyfile").is_file());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
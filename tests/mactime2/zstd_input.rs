@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+fn mactime2(input: &str) -> String {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("mactime2");
+    data_path.push(input);
+
+    let mut cmd = Command::cargo_bin("mactime2").unwrap();
+    let result = cmd.arg("-b").arg(data_path).arg("-d").ok();
+    assert!(result.is_ok());
+    String::from_utf8(result.unwrap().stdout).unwrap()
+}
+
+/// tests if a zstd compressed bodyfile results in the same output as the
+/// uncompressed file
+#[test]
+fn zstd_equals_uncompressed_file() {
+    let uncompressed = mactime2("sample.bodyfile");
+    let compressed = mactime2("sample.bodyfile.zst");
+    assert!(!uncompressed.is_empty());
+    assert_eq!(uncompressed, compressed);
+}
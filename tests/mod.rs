@@ -1,2 +1,16 @@
 mod mactime2;
-mod ts2date;
\ No newline at end of file
+mod ts2date;
+mod lnk2bodyfile;
+mod es4forensics;
+mod pf2bodyfile;
+mod bodyfile_merge;
+mod usnjrnl2bodyfile;
+mod regdump;
+mod bodyfile_gen;
+mod browser2bodyfile;
+mod task2bodyfile;
+mod artifact2bodyfile;
+mod bodyfile2xlsx;
+#[cfg(feature = "srum2bodyfile")]
+mod srum2bodyfile;
+mod recyclebin2bodyfile;
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+fn run_times_of(filename: &str) -> Vec<Bodyfile3Line> {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("pf2bodyfile");
+    data_path.push(filename);
+
+    let mut cmd = Command::cargo_bin("pf2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect()
+}
+
+fn assert_run_times(lines: Vec<Bodyfile3Line>) {
+    assert_eq!(lines.len(), 3);
+    for line in lines.iter() {
+        assert_eq!(line.get_name(), "NOTEPAD.EXE-D8414F97 (run count: 3)");
+        assert_eq!(*line.get_mtime(), -1);
+        assert_eq!(*line.get_ctime(), -1);
+        assert_eq!(*line.get_crtime(), -1);
+    }
+
+    let atimes: Vec<_> = lines.iter().map(|l| *l.get_atime()).collect();
+    assert_eq!(atimes, vec![1609459200, 1609462800, 1609466400]);
+}
+
+/// tests if the last run times are read from an uncompressed prefetch file
+#[test]
+fn uncompressed_pf() {
+    assert_run_times(run_times_of("NOTEPAD.EXE-D8414F97.pf"));
+}
+
+/// tests if the last run times are read from a MAM compressed prefetch file
+#[test]
+fn compressed_pf() {
+    assert_run_times(run_times_of("NOTEPAD.EXE-D8414F97.compressed.pf"));
+}
@@ -0,0 +1 @@
+mod sample_index;
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+fn deleted_file(filename: &str) -> Bodyfile3Line {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("recyclebin2bodyfile");
+    data_path.push(filename);
+
+    let mut cmd = Command::cargo_bin("recyclebin2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    lines.into_iter().next().unwrap()
+}
+
+/// tests if a `$I` file of Windows Vista to 8.1 is read
+#[test]
+fn version_1() {
+    let line = deleted_file("$IW5ZQ1A.docx");
+    assert_eq!(
+        line.get_name(),
+        "C:\\Users\\jdoe\\Documents\\report.docx (deleted)"
+    );
+    assert_eq!(*line.get_size(), 12345);
+    assert_eq!(*line.get_ctime(), 1609459200);
+    assert_eq!(*line.get_mtime(), -1);
+    assert_eq!(*line.get_atime(), -1);
+    assert_eq!(*line.get_crtime(), -1);
+}
+
+/// tests if a `$I` file of Windows 10 or later is read
+#[test]
+fn version_2() {
+    let line = deleted_file("$I2K8XQF.txt");
+    assert_eq!(
+        line.get_name(),
+        "C:\\Users\\jdoe\\Desktop\\secret.txt (deleted)"
+    );
+    assert_eq!(*line.get_size(), 42);
+    assert_eq!(*line.get_ctime(), 1609462800);
+}
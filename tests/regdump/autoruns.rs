@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+/// tests if the Run key and the Winlogon values of a SOFTWARE hive are found
+#[test]
+fn autoruns() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("regdump");
+    data_path.push("SOFTWARE");
+
+    let mut cmd = Command::cargo_bin("regdump").unwrap();
+    let result = cmd.arg("--autoruns").arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+
+    let onedrive = &lines[0];
+    assert_eq!(
+        onedrive.get_name(),
+        "Microsoft\\Windows\\CurrentVersion\\Run\\OneDrive = \"C:\\Users\\jdoe\\AppData\\Local\\Microsoft\\OneDrive\\OneDrive.exe\" /background"
+    );
+    assert_eq!(*onedrive.get_ctime(), 1614673800); // 2021-03-02T08:30:00
+    assert_eq!(*onedrive.get_mtime(), -1);
+
+    assert_eq!(
+        lines[1].get_name(),
+        "Microsoft\\Windows\\CurrentVersion\\Run\\Updater = %ProgramData%\\updater.exe -silent"
+    );
+
+    let shell = &lines[2];
+    assert_eq!(
+        shell.get_name(),
+        "Microsoft\\Windows NT\\CurrentVersion\\Winlogon\\Shell = explorer.exe"
+    );
+    assert_eq!(*shell.get_ctime(), 1614790800); // 2021-03-03T17:00:00
+    assert_eq!(
+        lines[3].get_name(),
+        "Microsoft\\Windows NT\\CurrentVersion\\Winlogon\\Userinit = C:\\Windows\\system32\\userinit.exe,"
+    );
+}
@@ -0,0 +1,2 @@
+mod shellbags;
+mod autoruns;
@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+/// tests if the folders stored in the BagMRU key of a UsrClass.dat are found
+#[test]
+fn shellbags() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("regdump");
+    data_path.push("UsrClass.dat");
+
+    let mut cmd = Command::cargo_bin("regdump").unwrap();
+    let result = cmd.arg("--shellbags").arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+
+    let users = &lines[0];
+    assert_eq!(users.get_name(), "My Computer\\C:\\Users");
+    assert_eq!(users.get_inode(), "1234-1");
+
+    let jdoe = &lines[1];
+    assert_eq!(jdoe.get_name(), "My Computer\\C:\\Users\\jdoe");
+    assert_eq!(jdoe.get_inode(), "5678-2");
+    assert_eq!(*jdoe.get_mtime(), 1612351354); // 2021-02-03T11:22:34
+    assert_eq!(*jdoe.get_atime(), 1612422000); // 2021-02-04T07:00:00
+    assert_eq!(*jdoe.get_crtime(), 1590998400); // 2020-06-01T08:00:00
+    assert_eq!(*jdoe.get_ctime(), -1);
+}
@@ -0,0 +1,2 @@
+mod invalid_database;
+mod network_usage;
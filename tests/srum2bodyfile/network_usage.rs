@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+
+/// tests if the entries of the network data usage table of a minimal
+/// `SRUDB.dat` are converted. The application and the user ids are resolved
+/// using the `SruDbIdMapTable`, where the id of the second entry is missing.
+#[test]
+fn network_usage() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("srum2bodyfile");
+    data_path.push("SRUDB.dat");
+
+    let mut cmd = Command::cargo_bin("srum2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "0|SRUM network usage: C:\\Program Files\\Mozilla Firefox\\firefox.exe (user: S-1-5-21-1-2-3-1001, sent: 4096 bytes, received: 1048576 bytes)|0||0|0|0|1609459200|-1|-1|-1",
+            "0|SRUM network usage: #274 (user: S-1-5-21-1-2-3-1001, sent: 512 bytes, received: 0 bytes)|0||0|0|0|1609480800|-1|-1|-1",
+        ]
+    );
+}
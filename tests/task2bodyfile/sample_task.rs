@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+/// tests if a UTF-16 encoded task file is converted into a line which
+/// contains the task path, the action and the registration date
+#[test]
+fn sample_task() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("task2bodyfile");
+
+    let mut cmd = Command::cargo_bin("task2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(
+        lines[0].get_name(),
+        "\\Microsoft\\Windows\\UpdateCheck (C:\\Windows\\System32\\WindowsPowerShell\\v1.0\\powershell.exe -NoProfile -WindowStyle Hidden -File C:\\Users\\Public\\update.ps1)"
+    );
+    // 2021-02-18T10:15:00
+    assert_eq!(*lines[0].get_crtime(), 1613643300);
+    assert_eq!(*lines[0].get_mtime(), -1);
+}
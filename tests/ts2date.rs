@@ -91,4 +91,67 @@ fn ts2date_list2() {
 
     let reader = BufReader::new(Cursor::new(result.unwrap().stdout));
     assert!(reader.lines().map_while(Result::ok).any(|f| f == "Europe/Berlin"));
-}
\ No newline at end of file
+}
+
+const SAMPLE_BODYFILE: &str = "0|/etc/passwd|1|r/rrw-r--r--|0|0|1532|-1|1609459200|1609459300|-1
+0|/tmp/a|b|2|r/rrw-r--r--|0|0|0|0|1693411717|-1|1693411581
+";
+
+#[test]
+fn ts2date_bodyfile_roundtrip() {
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd.arg("--to-date").write_stdin(SAMPLE_BODYFILE).ok();
+    assert!(result.is_ok());
+    let dates = String::from_utf8(result.unwrap().stdout).unwrap();
+    assert_eq!(
+        dates.lines().next().unwrap(),
+        "0|/etc/passwd|1|r/rrw-r--r--|0|0|1532|-|2021-01-01T00:00:00+00:00|2021-01-01T00:01:40+00:00|-"
+    );
+
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd.arg("--to-epoch").write_stdin(dates).ok();
+    assert!(result.is_ok());
+    assert_eq!(
+        SAMPLE_BODYFILE,
+        String::from_utf8(result.unwrap().stdout).unwrap()
+    );
+}
+
+#[test]
+fn ts2date_bodyfile_invalid_date() {
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd
+        .arg("--to-epoch")
+        .write_stdin("0|/etc/passwd|1|r/rrw-r--r--|0|0|1532|-|2021-13-01|-|-\n")
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(result.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("line 1: invalid mtime '2021-13-01'"));
+}
+
+#[test]
+fn ts2date_output_file() {
+    let dir = std::env::temp_dir().join(format!("ts2date_output_file_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd.write_stdin(SAMPLE_TIMELINE).ok();
+    assert!(result.is_ok());
+    let expected = String::from_utf8(result.unwrap().stdout).unwrap();
+
+    let output = dir.join("option.txt");
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd.arg("--output").arg(&output).write_stdin(SAMPLE_TIMELINE).ok();
+    assert!(result.is_ok());
+    assert!(result.unwrap().stdout.is_empty());
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), expected);
+
+    let output = dir.join("positional.txt");
+    let mut cmd = Command::cargo_bin("ts2date").unwrap();
+    let result = cmd.arg("-").arg(&output).write_stdin(SAMPLE_TIMELINE).ok();
+    assert!(result.is_ok());
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), expected);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
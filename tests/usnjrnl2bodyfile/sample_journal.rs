@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use dfir_toolkit::common::bodyfile::Bodyfile3Line;
+
+/// tests if a create and a rename are read from a $UsnJrnl:$J file
+#[test]
+fn create_and_rename() {
+    let mut data_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    data_path.push("tests");
+    data_path.push("data");
+    data_path.push("usnjrnl2bodyfile");
+    data_path.push("UsnJrnl_J.bin");
+
+    let mut cmd = Command::cargo_bin("usnjrnl2bodyfile").unwrap();
+    let result = cmd.arg(&data_path).ok();
+    assert!(result.is_ok());
+
+    let stdout = String::from_utf8(result.unwrap().stdout).unwrap();
+    let lines: Vec<_> = stdout
+        .lines()
+        .map(|line| Bodyfile3Line::try_from(line).unwrap())
+        .collect();
+
+    let events: Vec<_> = lines
+        .iter()
+        .map(|l| (l.get_name().as_str(), *l.get_ctime()))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            ("test.txt (FileCreate)", 1609459200),
+            ("test.txt (FileCreate, Close)", 1609459260),
+            ("test.txt (RenameOldName)", 1609462800),
+            ("renamed.txt (RenameNewName)", 1609462800),
+        ]
+    );
+
+    for line in lines.iter() {
+        assert_eq!(line.get_inode(), "100-3");
+        assert_eq!(*line.get_atime(), -1);
+        assert_eq!(*line.get_mtime(), -1);
+        assert_eq!(*line.get_crtime(), -1);
+    }
+}